@@ -0,0 +1,338 @@
+//! Alert rule evaluation.
+
+use crate::state::{AlertCondition, CrossDirection, Store};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+/// An alert that fired during evaluation.
+#[derive(Debug, Clone)]
+pub struct FiredAlert {
+    /// ID of the rule that fired.
+    pub rule_id: String,
+    /// Human-readable message.
+    pub message: String,
+    /// When the alert fired.
+    pub fired_at: DateTime<Utc>,
+    /// Ring the terminal bell.
+    pub bell: bool,
+    /// Show a desktop notification.
+    pub desktop: bool,
+    /// Shell command to run.
+    pub command: Option<String>,
+    /// Webhook URL to POST to.
+    pub webhook: Option<String>,
+}
+
+/// Evaluate all enabled alert rules against the store.
+///
+/// Returns the alerts whose condition started to hold since the last
+/// evaluation. Rules whose inputs are unavailable (e.g. market not loaded)
+/// keep their previous state.
+pub fn evaluate(store: &mut Store) -> Vec<FiredAlert> {
+    let now = Utc::now();
+
+    let results: Vec<Option<Option<String>>> = store
+        .alerts
+        .rules
+        .iter()
+        .map(|rule| {
+            if rule.enabled {
+                check(&rule.condition, store, now)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let mut fired = Vec::new();
+    for (rule, result) in store.alerts.rules.iter_mut().zip(results) {
+        let Some(outcome) = result else {
+            continue;
+        };
+        match outcome {
+            Some(message) if !rule.active => {
+                rule.active = true;
+                rule.last_fired = Some(now);
+                fired.push(FiredAlert {
+                    rule_id: rule.id.clone(),
+                    message,
+                    fired_at: now,
+                    bell: rule.bell,
+                    desktop: rule.desktop,
+                    command: rule.command.clone(),
+                    webhook: rule.webhook.clone(),
+                });
+            }
+            Some(_) => {}
+            None => rule.active = false,
+        }
+    }
+
+    fired
+}
+
+/// Check a single condition.
+///
+/// Returns `None` if the inputs are unavailable, `Some(None)` if the
+/// condition does not hold, and `Some(Some(message))` if it does.
+fn check(condition: &AlertCondition, store: &Store, now: DateTime<Utc>) -> Option<Option<String>> {
+    let message = match condition {
+        AlertCondition::MidCrosses {
+            token_id,
+            level,
+            direction,
+        } => {
+            let mid = token_mid(store, token_id)?;
+            let holds = match direction {
+                CrossDirection::Above => mid >= *level,
+                CrossDirection::Below => mid <= *level,
+            };
+            holds.then(|| {
+                format!(
+                    "{} mid {:.2}¢ crossed {} {:.2}¢",
+                    token_label(store, token_id),
+                    mid * Decimal::ONE_HUNDRED,
+                    match direction {
+                        CrossDirection::Above => "above",
+                        CrossDirection::Below => "below",
+                    },
+                    *level * Decimal::ONE_HUNDRED
+                )
+            })
+        }
+        AlertCondition::SpreadBelow {
+            market_id,
+            threshold,
+        } => {
+            let market = store.markets.markets.iter().find(|m| &m.id == market_id)?;
+            let outcome = market.outcomes.first()?;
+            let spread = match store.orderbooks.get_book(&outcome.token_id) {
+                Some(book) => book.spread()?,
+                None if outcome.ask > Decimal::ZERO => outcome.spread(),
+                None => return None,
+            };
+            (spread < *threshold).then(|| {
+                format!(
+                    "Spread on '{}' is {:.2}¢ (< {:.2}¢)",
+                    market.question,
+                    spread * Decimal::ONE_HUNDRED,
+                    *threshold * Decimal::ONE_HUNDRED
+                )
+            })
+        }
+        AlertCondition::PositionPnlBelow { token_id, percent } => {
+            let position = store
+                .portfolio
+                .positions
+                .iter()
+                .find(|p| &p.token_id == token_id)?;
+            (position.unrealized_pnl_percent < *percent).then(|| {
+                format!(
+                    "Position '{}' {} PnL {:.1}% (< {}%)",
+                    position.market_question,
+                    position.outcome_name,
+                    position.unrealized_pnl_percent,
+                    percent
+                )
+            })
+        }
+        AlertCondition::MarketEndsWithin {
+            market_id,
+            within_secs,
+        } => {
+            let market = store.markets.markets.iter().find(|m| &m.id == market_id)?;
+            let end_date = market.end_date?;
+            let remaining = end_date.signed_duration_since(now).num_seconds();
+            (remaining >= 0 && remaining <= *within_secs as i64)
+                .then(|| format!("'{}' ends in {}m", market.question, remaining / 60))
+        }
+    };
+    Some(message)
+}
+
+/// Get the mid price for a token, preferring the live order book.
+fn token_mid(store: &Store, token_id: &str) -> Option<Decimal> {
+    if let Some(mid) = store
+        .orderbooks
+        .get_book(token_id)
+        .and_then(|book| book.mid_price())
+    {
+        return Some(mid);
+    }
+
+    store
+        .markets
+        .markets
+        .iter()
+        .flat_map(|m| m.outcomes.iter())
+        .find(|o| o.token_id == token_id && o.ask > Decimal::ZERO)
+        .map(|o| o.mid_price())
+}
+
+/// Get a display label ("question: outcome") for a token.
+fn token_label(store: &Store, token_id: &str) -> String {
+    store
+        .markets
+        .markets
+        .iter()
+        .find_map(|m| {
+            m.outcomes
+                .iter()
+                .find(|o| o.token_id == token_id)
+                .map(|o| format!("'{}' {}", m.question, o.name))
+        })
+        .unwrap_or_else(|| token_id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{AlertRule, Market, MarketStatus, OrderBookDepth, Outcome, PriceLevel};
+    use rust_decimal_macros::dec;
+
+    fn store_with_market(bid: Decimal, ask: Decimal) -> Store {
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut store = Store::new(tx);
+        store.markets.markets = vec![Market {
+            id: "m1".to_string(),
+            question: "Will it rain?".to_string(),
+            description: String::new(),
+            status: MarketStatus::Active,
+            end_date: None,
+            tags: Vec::new(),
+            outcomes: vec![Outcome {
+                token_id: "yes".to_string(),
+                name: "Yes".to_string(),
+                bid,
+                ask,
+                last_price: Decimal::ZERO,
+                price_change_24h: Decimal::ZERO,
+            }],
+            volume: Decimal::ZERO,
+            volume_24h: Decimal::ZERO,
+            liquidity: Decimal::ZERO,
+            image_url: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }];
+        store
+    }
+
+    fn set_quote(store: &mut Store, bid: Decimal, ask: Decimal) {
+        let outcome = &mut store.markets.markets[0].outcomes[0];
+        outcome.bid = bid;
+        outcome.ask = ask;
+    }
+
+    fn mid_above(level: Decimal) -> AlertRule {
+        AlertRule::new(AlertCondition::MidCrosses {
+            token_id: "yes".to_string(),
+            level,
+            direction: CrossDirection::Above,
+        })
+    }
+
+    #[test]
+    fn test_fires_once_while_condition_holds() {
+        let mut store = store_with_market(dec!(0.40), dec!(0.42));
+        store.alerts.add(mid_above(dec!(0.50)));
+
+        // Below the level: nothing fires
+        assert!(evaluate(&mut store).is_empty());
+        assert!(!store.alerts.rules[0].active);
+
+        // Crossing fires once
+        set_quote(&mut store, dec!(0.51), dec!(0.53));
+        let fired = evaluate(&mut store);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].rule_id, store.alerts.rules[0].id);
+        assert!(fired[0].message.contains("crossed above"));
+        assert!(store.alerts.rules[0].active);
+        assert!(store.alerts.rules[0].last_fired.is_some());
+
+        // Holding above the level does not fire again
+        set_quote(&mut store, dec!(0.55), dec!(0.57));
+        assert!(evaluate(&mut store).is_empty());
+        assert!(evaluate(&mut store).is_empty());
+    }
+
+    #[test]
+    fn test_rearms_when_condition_clears() {
+        let mut store = store_with_market(dec!(0.51), dec!(0.53));
+        store.alerts.add(mid_above(dec!(0.50)));
+
+        assert_eq!(evaluate(&mut store).len(), 1);
+
+        // Dropping back below re-arms the rule
+        set_quote(&mut store, dec!(0.45), dec!(0.47));
+        assert!(evaluate(&mut store).is_empty());
+        assert!(!store.alerts.rules[0].active);
+
+        // Crossing again fires again
+        set_quote(&mut store, dec!(0.52), dec!(0.54));
+        assert_eq!(evaluate(&mut store).len(), 1);
+    }
+
+    #[test]
+    fn test_missing_inputs_keep_state() {
+        let mut store = store_with_market(dec!(0.51), dec!(0.53));
+        store.alerts.add(mid_above(dec!(0.50)));
+        assert_eq!(evaluate(&mut store).len(), 1);
+
+        // Market unloaded: the rule neither re-arms nor fires
+        let markets = std::mem::take(&mut store.markets.markets);
+        assert!(evaluate(&mut store).is_empty());
+        assert!(store.alerts.rules[0].active);
+
+        // Back with the condition still holding: still no repeat
+        store.markets.markets = markets;
+        assert!(evaluate(&mut store).is_empty());
+    }
+
+    #[test]
+    fn test_disabled_rules_do_not_fire() {
+        let mut store = store_with_market(dec!(0.51), dec!(0.53));
+        let mut rule = mid_above(dec!(0.50));
+        rule.enabled = false;
+        store.alerts.add(rule);
+
+        assert!(evaluate(&mut store).is_empty());
+        assert!(!store.alerts.rules[0].active);
+    }
+
+    #[test]
+    fn test_live_book_overrides_market_quote() {
+        let mut store = store_with_market(dec!(0.40), dec!(0.42));
+        store.alerts.add(mid_above(dec!(0.50)));
+
+        let mut book = OrderBookDepth::new("m1", "yes");
+        book.bids = vec![PriceLevel::new(dec!(0.55), dec!(100))];
+        book.asks = vec![PriceLevel::new(dec!(0.57), dec!(100))];
+        store.orderbooks.update_book(book);
+
+        assert_eq!(evaluate(&mut store).len(), 1);
+    }
+
+    #[test]
+    fn test_spread_and_end_date_conditions() {
+        let mut store = store_with_market(dec!(0.50), dec!(0.51));
+        store.markets.markets[0].end_date = Some(Utc::now() + chrono::Duration::minutes(30));
+        store
+            .alerts
+            .add(AlertRule::new(AlertCondition::SpreadBelow {
+                market_id: "m1".to_string(),
+                threshold: dec!(0.02),
+            }));
+        store
+            .alerts
+            .add(AlertRule::new(AlertCondition::MarketEndsWithin {
+                market_id: "m1".to_string(),
+                within_secs: 3600,
+            }));
+
+        let fired = evaluate(&mut store);
+        assert_eq!(fired.len(), 2);
+        assert!(fired[0].message.contains("Spread"));
+        assert!(fired[1].message.contains("ends in"));
+    }
+}
@@ -0,0 +1,109 @@
+//! Side effects run when an alert fires.
+
+use super::FiredAlert;
+use std::io::Write;
+use std::process::Stdio;
+use std::sync::LazyLock;
+use tracing::{debug, warn};
+
+/// HTTP client shared by all webhook hooks.
+static WEBHOOK_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+
+/// Ring the terminal bell.
+pub fn ring_bell() {
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
+}
+
+/// Run the desktop, shell command and webhook hooks for a fired alert.
+///
+/// Hooks run in background tasks so a slow command or webhook never blocks
+/// the UI, with their output discarded so it cannot draw over the TUI.
+/// Failures are logged and otherwise ignored.
+pub fn run_hooks(alert: &FiredAlert) {
+    if alert.bell {
+        ring_bell();
+    }
+
+    if alert.desktop {
+        let message = alert.message.clone();
+        tokio::spawn(async move {
+            if let Err(e) = desktop_notification(&message).await {
+                warn!("Desktop notification failed: {}", e);
+            }
+        });
+    }
+
+    if let Some(command) = alert.command.clone() {
+        let alert = alert.clone();
+        tokio::spawn(async move {
+            let result = tokio::process::Command::new("sh")
+                .arg("-c")
+                .arg(&command)
+                .env("CLOBSTER_ALERT_ID", &alert.rule_id)
+                .env("CLOBSTER_ALERT_MESSAGE", &alert.message)
+                .env("CLOBSTER_ALERT_FIRED_AT", alert.fired_at.to_rfc3339())
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .await;
+            match result {
+                Ok(status) if status.success() => debug!("Alert command succeeded: {}", command),
+                Ok(status) => warn!("Alert command '{}' exited with {}", command, status),
+                Err(e) => warn!("Alert command '{}' failed: {}", command, e),
+            }
+        });
+    }
+
+    if let Some(url) = alert.webhook.clone() {
+        let body = serde_json::json!({
+            "rule_id": alert.rule_id,
+            "message": alert.message,
+            "fired_at": alert.fired_at.to_rfc3339(),
+        });
+        tokio::spawn(async move {
+            let result = WEBHOOK_CLIENT
+                .post(&url)
+                .json(&body)
+                .timeout(std::time::Duration::from_secs(10))
+                .send()
+                .await
+                .and_then(|r| r.error_for_status());
+            if let Err(e) = result {
+                warn!("Alert webhook '{}' failed: {}", url, e);
+            }
+        });
+    }
+}
+
+#[cfg(target_os = "macos")]
+async fn desktop_notification(message: &str) -> std::io::Result<()> {
+    let script = format!(
+        "display notification {:?} with title \"Clobster alert\"",
+        message
+    );
+    tokio::process::Command::new("osascript")
+        .arg("-e")
+        .arg(script)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .map(|_| ())
+}
+
+#[cfg(not(target_os = "macos"))]
+async fn desktop_notification(message: &str) -> std::io::Result<()> {
+    tokio::process::Command::new("notify-send")
+        .arg("Clobster alert")
+        .arg(message)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .map(|_| ())
+}
@@ -0,0 +1,12 @@
+//! Price and spread alerts.
+//!
+//! Alert rules are stored in [`AlertState`](crate::state::AlertState) and
+//! evaluated against the [`Store`](crate::state::Store) after every state
+//! update. A rule fires once when its condition starts to hold and re-arms
+//! when the condition clears.
+
+mod evaluator;
+mod hooks;
+
+pub use evaluator::{FiredAlert, evaluate};
+pub use hooks::{ring_bell, run_hooks};
@@ -2,9 +2,14 @@
 
 use crate::config::ApiConfig;
 use crate::error::{Error, Result};
-use crate::state::{Market, Order, OrderBookDepth, OrderRequest, PortfolioState, Position};
-use polymarket_rs::types::{BookParams, ConditionId, OpenOrderParams, Side, TokenId};
+use crate::state::{
+    Fill, Market, MarketState, MarketVolume, Order, OrderBookDepth, OrderRequest, OutcomeQuote,
+    PortfolioState, Position,
+};
+use polymarket_rs::request::END_CURSOR;
+use polymarket_rs::types::{BookParams, ConditionId, OpenOrderParams, Side, TokenId, TradeParams};
 use polymarket_rs::{ClobClient, TradingClient};
+use rust_decimal::Decimal;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -50,12 +55,13 @@ impl Default for ApiClientBuilder {
 /// High-level API client for Polymarket.
 pub struct ApiClient {
     /// Configuration.
-    #[allow(dead_code)]
     config: ApiConfig,
     /// CLOB client for market data.
     clob_client: ClobClient,
     /// Trading client for authenticated endpoints (optional).
     trading_client: Option<TradingClient>,
+    /// HTTP client for the Gamma market data API.
+    http_client: reqwest::Client,
     /// Rate limiter state.
     rate_limiter: Arc<RwLock<RateLimiter>>,
 }
@@ -69,11 +75,19 @@ impl ApiClient {
         // TODO: Initialize trading client with credentials when private key is provided
         let trading_client = None;
 
+        let rate_limit = config.rate_limit.max(1);
+
+        let http_client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(config.timeout_secs.max(1)))
+            .build()
+            .map_err(|e| Error::network(e.to_string()))?;
+
         Ok(Self {
             config,
             clob_client,
             trading_client,
-            rate_limiter: Arc::new(RwLock::new(RateLimiter::new(10))),
+            http_client,
+            rate_limiter: Arc::new(RwLock::new(RateLimiter::new(rate_limit))),
         })
     }
 
@@ -160,6 +174,156 @@ impl ApiClient {
             .collect())
     }
 
+    /// Fetch quotes for a batch of tokens.
+    ///
+    /// Best bid/ask and liquidity come from one bulk order book request and
+    /// last trade prices from one bulk last-trade-price request. The caller is
+    /// responsible for keeping batches to a reasonable size.
+    ///
+    /// Books are used rather than the bulk price, midpoint and spread
+    /// endpoints: polymarket-rs returns those as bare values without their
+    /// token IDs, so results cannot be matched back to tokens, and one book
+    /// request gives both sides of the quote plus the depth used for
+    /// liquidity, where the price endpoints would take three requests.
+    pub async fn fetch_quotes(&self, token_ids: &[String]) -> Result<Vec<OutcomeQuote>> {
+        if token_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let params: Vec<(String, Side)> = token_ids
+            .iter()
+            .map(|token_id| (token_id.clone(), Side::Buy))
+            .collect();
+        let mut quotes: Vec<OutcomeQuote> = self
+            .fetch_orderbooks(&params)
+            .await?
+            .iter()
+            .map(super::DataConverter::convert_quote)
+            .collect();
+
+        self.rate_limit().await?;
+
+        let ids: Vec<TokenId> = token_ids.iter().map(TokenId::new).collect();
+        match self.clob_client.get_last_trade_prices(&ids).await {
+            Ok(response) => {
+                let last_prices = super::DataConverter::convert_last_trade_prices(&response);
+                for quote in &mut quotes {
+                    quote.last_price = last_prices.get(&quote.token_id).copied();
+                }
+            }
+            // Last trade prices are best-effort; the book prices are still useful
+            Err(e) => tracing::debug!("Failed to fetch last trade prices: {}", e),
+        }
+
+        Ok(quotes)
+    }
+
+    /// Fetch live prices and volumes for markets, in priority order.
+    ///
+    /// Quotes are fetched in batches of `price_batch_size` tokens and volumes
+    /// in batches of as many markets. The 24h change needs one price history
+    /// request per token, so it is limited to the first `price_history_limit`
+    /// tokens. Every part is best-effort: failures are logged and the rest is
+    /// still returned.
+    pub async fn fetch_market_data(&self, markets: &[Market]) -> MarketData {
+        let batch_size = self.config.price_batch_size.max(1);
+        let token_ids: Vec<String> = markets
+            .iter()
+            .flat_map(|m| m.outcomes.iter().map(|o| o.token_id.clone()))
+            .collect();
+        let market_ids: Vec<String> = markets.iter().map(|m| m.id.clone()).collect();
+
+        let mut data = MarketData::default();
+
+        for batch in token_ids.chunks(batch_size) {
+            match self.fetch_quotes(batch).await {
+                Ok(quotes) => data.quotes.extend(quotes),
+                Err(e) => tracing::warn!("Failed to fetch quotes: {}", e),
+            }
+        }
+
+        for token_id in token_ids.iter().take(self.config.price_history_limit) {
+            match self.fetch_price_change_24h(token_id).await {
+                Ok(Some(change)) => {
+                    match data.quotes.iter_mut().find(|q| &q.token_id == token_id) {
+                        Some(quote) => quote.price_change_24h = Some(change),
+                        None => data.quotes.push(OutcomeQuote {
+                            token_id: token_id.clone(),
+                            price_change_24h: Some(change),
+                            ..Default::default()
+                        }),
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tracing::debug!("Failed to fetch price history for {}: {}", token_id, e),
+            }
+        }
+
+        for batch in market_ids.chunks(batch_size) {
+            match self.fetch_market_volumes(batch).await {
+                Ok(volumes) => data.volumes.extend(volumes),
+                Err(e) => tracing::warn!("Failed to fetch market volumes: {}", e),
+            }
+        }
+
+        data
+    }
+
+    /// Fetch total and 24h traded volume for a batch of markets.
+    ///
+    /// The CLOB does not report volume, so this asks the Gamma market data
+    /// API at `api.gamma_url` in one request per batch.
+    pub async fn fetch_market_volumes(
+        &self,
+        condition_ids: &[String],
+    ) -> Result<Vec<MarketVolume>> {
+        if condition_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.rate_limit().await?;
+
+        let url = format!("{}/markets", self.config.gamma_url.trim_end_matches('/'));
+        let mut query: Vec<(&str, String)> = condition_ids
+            .iter()
+            .map(|id| ("condition_ids", id.clone()))
+            .collect();
+        query.push(("limit", condition_ids.len().to_string()));
+
+        let response: serde_json::Value = self
+            .http_client
+            .get(&url)
+            .query(&query)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| Error::network(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| Error::network(e.to_string()))?;
+
+        Ok(super::DataConverter::convert_market_volumes(&response))
+    }
+
+    /// Fetch the price change of a token over the last 24 hours.
+    ///
+    /// Returns `None` when there is no price history for the token.
+    pub async fn fetch_price_change_24h(&self, token_id: &str) -> Result<Option<Decimal>> {
+        self.rate_limit().await?;
+
+        let response = self
+            .clob_client
+            .get_prices_history(&TokenId::new(token_id), "1d", None, None, Some(60))
+            .await
+            .map_err(Error::Api)?;
+
+        let first = response.history.iter().min_by_key(|p| p.timestamp);
+        let last = response.history.iter().max_by_key(|p| p.timestamp);
+        Ok(first
+            .zip(last)
+            .map(|(first, last)| last.price - first.price))
+    }
+
     /// Fetch open orders (requires authentication).
     pub async fn fetch_orders(&self) -> Result<Vec<Order>> {
         let trading = self
@@ -181,6 +345,65 @@ impl ApiClient {
             .collect())
     }
 
+    /// Fetch our fills, optionally only those after a unix timestamp (requires authentication).
+    ///
+    /// The endpoint is paginated and polymarket-rs cannot send its
+    /// `next_cursor`, so pages are walked back in time with `before` until the
+    /// endpoint reports the cursor exhausted. Windows overlap by one second and
+    /// fills are de-duplicated by ID, so fills sharing a timestamp with a page
+    /// boundary are not lost.
+    pub async fn fetch_trades(&self, after: Option<u64>) -> Result<Vec<Fill>> {
+        let trading = self
+            .trading_client
+            .as_ref()
+            .ok_or_else(|| Error::auth("Not authenticated"))?;
+
+        let mut fills: Vec<Fill> = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+        let mut before = None;
+
+        loop {
+            self.rate_limit().await?;
+
+            let mut params = TradeParams::new();
+            params.after = after;
+            params.before = before;
+
+            let response = trading.get_trades(params).await.map_err(Error::Api)?;
+            let page = TradePage::from_response(&response);
+
+            let mut added = 0;
+            for fill in page
+                .trades
+                .iter()
+                .flat_map(super::DataConverter::convert_trade)
+            {
+                if seen.insert(fill.id.clone()) {
+                    fills.push(fill);
+                    added += 1;
+                }
+            }
+
+            if !page.has_more || page.trades.is_empty() {
+                break;
+            }
+            let Some(oldest) = fills.iter().map(|f| f.timestamp.timestamp()).min() else {
+                break;
+            };
+            if added == 0 {
+                // A whole page within one second cannot be stepped past by time
+                tracing::warn!(
+                    "Trade history stopped at {}: more than a page of fills in one second",
+                    oldest
+                );
+                break;
+            }
+            before = Some(oldest.max(0) as u64 + 1);
+        }
+
+        Ok(fills)
+    }
+
     /// Fetch positions (requires authentication).
     pub async fn fetch_positions(&self) -> Result<Vec<Position>> {
         let _trading = self
@@ -208,29 +431,25 @@ impl ApiClient {
     }
 
     /// Place an order (requires authentication).
+    ///
+    /// Not supported yet: always fails with [`Error::Unsupported`], whether
+    /// or not the client is authenticated.
     pub async fn place_order(&self, _request: OrderRequest) -> Result<Order> {
-        let _trading = self
-            .trading_client
-            .as_ref()
-            .ok_or_else(|| Error::auth("Not authenticated"))?;
-
-        self.rate_limit().await?;
-
-        // TODO: Implement order placement using TradingClient::create_and_post_order
-        Err(Error::application("Order placement not yet implemented"))
+        // TODO: Check authentication, rate limit and place the order using
+        // TradingClient::create_and_post_order
+        Err(Error::unsupported("order placement is not yet implemented"))
     }
 
     /// Cancel an order (requires authentication).
+    ///
+    /// Not supported yet: always fails with [`Error::Unsupported`], whether
+    /// or not the client is authenticated.
     pub async fn cancel_order(&self, _order_id: &str) -> Result<()> {
-        let _trading = self
-            .trading_client
-            .as_ref()
-            .ok_or_else(|| Error::auth("Not authenticated"))?;
-
-        self.rate_limit().await?;
-
-        // TODO: Implement order cancellation using TradingClient::cancel
-        Err(Error::application("Order cancellation not yet implemented"))
+        // TODO: Check authentication, rate limit and cancel the order using
+        // TradingClient::cancel
+        Err(Error::unsupported(
+            "order cancellation is not yet implemented",
+        ))
     }
 
     /// Apply rate limiting.
@@ -277,3 +496,77 @@ impl RateLimiter {
         Ok(())
     }
 }
+
+/// Live prices and volumes fetched for a set of markets.
+#[derive(Debug, Default)]
+pub struct MarketData {
+    /// Outcome quotes, including the 24h change where fetched.
+    pub quotes: Vec<OutcomeQuote>,
+    /// Market volumes.
+    pub volumes: Vec<MarketVolume>,
+}
+
+impl MarketData {
+    /// Apply the quotes and volumes to loaded markets.
+    pub fn apply(&self, markets: &mut MarketState) {
+        markets.apply_quotes(&self.quotes);
+        markets.apply_volumes(&self.volumes);
+    }
+}
+
+/// One page of the `/data/trades` endpoint.
+struct TradePage<'a> {
+    /// Raw trades on the page.
+    trades: &'a [serde_json::Value],
+    /// Whether the endpoint has more pages.
+    has_more: bool,
+}
+
+impl<'a> TradePage<'a> {
+    /// Split a response into its trades and whether its cursor continues.
+    ///
+    /// The endpoint returns either a bare array or a paginated
+    /// `{ data: [...], next_cursor }` object whose cursor is `LTE=` on the
+    /// last page.
+    fn from_response(response: &'a serde_json::Value) -> Self {
+        match response {
+            serde_json::Value::Array(trades) => Self {
+                trades,
+                has_more: false,
+            },
+            other => Self {
+                trades: other
+                    .get("data")
+                    .and_then(|d| d.as_array())
+                    .map(|d| d.as_slice())
+                    .unwrap_or_default(),
+                has_more: other
+                    .get("next_cursor")
+                    .and_then(|c| c.as_str())
+                    .is_some_and(|c| !c.is_empty() && c != END_CURSOR),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_trade_page_cursor() {
+        let bare = json!([{ "id": "t1" }]);
+        let page = TradePage::from_response(&bare);
+        assert_eq!(page.trades.len(), 1);
+        assert!(!page.has_more);
+
+        let more = json!({ "data": [{ "id": "t1" }, { "id": "t2" }], "next_cursor": "MTAw" });
+        let page = TradePage::from_response(&more);
+        assert_eq!(page.trades.len(), 2);
+        assert!(page.has_more);
+
+        let last = json!({ "data": [], "next_cursor": END_CURSOR });
+        assert!(!TradePage::from_response(&last).has_more);
+    }
+}
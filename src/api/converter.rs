@@ -1,12 +1,14 @@
 //! Data conversion utilities for API responses.
 
 use crate::state::{
-    Market, MarketStatus, Order, OrderBookDepth, OrderSide, OrderStatus, OrderType, Outcome,
-    PriceLevel,
+    Fill, Market, MarketStatus, MarketVolume, Order, OrderBookDepth, OrderSide, OrderStatus,
+    OrderType, Outcome, OutcomeQuote, PriceLevel,
 };
 use chrono::{DateTime, Utc};
 use polymarket_rs::types::Side;
 use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
 
 /// Converts API responses to internal state types.
 pub struct DataConverter;
@@ -23,7 +25,6 @@ impl DataConverter {
                 bid: Decimal::ZERO, // Will be updated from orderbook
                 ask: Decimal::ZERO,
                 last_price: Decimal::ZERO,
-                volume_24h: Decimal::ZERO,
                 price_change_24h: Decimal::ZERO,
             })
             .collect();
@@ -36,8 +37,9 @@ impl DataConverter {
             end_date: market.end_date_iso,
             tags: market.category.map(|c| vec![c]).unwrap_or_default(),
             outcomes,
-            volume: Decimal::ZERO,    // Not directly available
-            liquidity: Decimal::ZERO, // Not directly available
+            volume: Decimal::ZERO,     // Updated from the Gamma API
+            volume_24h: Decimal::ZERO, // Updated from the Gamma API
+            liquidity: Decimal::ZERO,  // Updated from order books
             image_url: Some(market.icon),
             created_at: Utc::now(), // API doesn't provide this
             updated_at: Utc::now(),
@@ -109,18 +111,22 @@ impl DataConverter {
 
     /// Convert a polymarket-rs order book summary to our internal OrderBookDepth type.
     pub fn convert_orderbook(book: polymarket_rs::types::OrderBookSummary) -> OrderBookDepth {
-        let bids = book
+        let mut bids: Vec<PriceLevel> = book
             .bids
             .into_iter()
             .map(|level| PriceLevel::new(level.price, level.size))
             .collect();
 
-        let asks = book
+        let mut asks: Vec<PriceLevel> = book
             .asks
             .into_iter()
             .map(|level| PriceLevel::new(level.price, level.size))
             .collect();
 
+        // The API lists levels worst-first; keep the best price at the front
+        bids.sort_by_key(|l| std::cmp::Reverse(l.price));
+        asks.sort_by_key(|l| l.price);
+
         // API timestamp is in milliseconds, convert to seconds for DateTime
         let timestamp =
             DateTime::from_timestamp_millis(book.timestamp as i64).unwrap_or_else(Utc::now);
@@ -134,4 +140,396 @@ impl DataConverter {
             asks,
         }
     }
+
+    /// Convert a trade from the CLOB `/data/trades` endpoint to our fills.
+    ///
+    /// The endpoint returns untyped JSON describing the taker's order, whose
+    /// ID is `taker_order_id`. When we were the maker, our fills are our own
+    /// entries in `maker_orders` (order ID, asset, price, side and matched
+    /// amount), one fill per order. Returns nothing for malformed entries.
+    pub fn convert_trade(trade: &serde_json::Value) -> Vec<Fill> {
+        let Some(id) = trade.get("id").and_then(|v| v.as_str()) else {
+            return Vec::new();
+        };
+        let market_id = str_field(trade, "market").unwrap_or_default();
+        let timestamp = decimal_field(trade, "match_time")
+            .and_then(|t| i64::try_from(t.trunc().mantissa()).ok())
+            .and_then(|t| DateTime::from_timestamp(t, 0))
+            .unwrap_or_else(Utc::now);
+
+        let fill = |id: String,
+                    order_id: &str,
+                    order: &serde_json::Value,
+                    size_key: &str,
+                    side: Option<OrderSide>| {
+            let price = decimal_field(order, "price")?;
+            let size = decimal_field(order, size_key)?;
+            let fee_rate_bps = decimal_field(order, "fee_rate_bps").unwrap_or_default();
+            Some(Fill {
+                id,
+                order_id: order_id.to_string(),
+                market_id: market_id.to_string(),
+                token_id: str_field(order, "asset_id")?.to_string(),
+                outcome_name: str_field(order, "outcome").unwrap_or_default().to_string(),
+                side: order_side(order).or(side)?,
+                price,
+                size,
+                fee: Self::trade_fee(price, size, fee_rate_bps),
+                timestamp,
+            })
+        };
+
+        let is_maker =
+            str_field(trade, "trader_side").is_some_and(|s| s.eq_ignore_ascii_case("MAKER"));
+        if !is_maker {
+            let order_id = str_field(trade, "taker_order_id").unwrap_or_default();
+            return fill(id.to_string(), order_id, trade, "size", None)
+                .into_iter()
+                .collect();
+        }
+
+        let maker_orders = trade
+            .get("maker_orders")
+            .and_then(|v| v.as_array())
+            .map(|v| v.as_slice())
+            .unwrap_or_default();
+        // Maker orders without a side took the other side of the taker
+        let taker_side = order_side(trade).map(|side| match side {
+            OrderSide::Buy => OrderSide::Sell,
+            OrderSide::Sell => OrderSide::Buy,
+        });
+        // Our orders share the trade's owner; a lone maker order is ours
+        let owner = str_field(trade, "owner");
+        let ours: Vec<&serde_json::Value> = match maker_orders {
+            [only] => vec![only],
+            orders => orders
+                .iter()
+                .filter(|o| owner.is_some() && str_field(o, "owner") == owner)
+                .collect(),
+        };
+
+        ours.into_iter()
+            .filter_map(|order| {
+                let order_id = str_field(order, "order_id").unwrap_or_default();
+                fill(
+                    format!("{}:{}", id, order_id),
+                    order_id,
+                    order,
+                    "matched_amount",
+                    taker_side,
+                )
+            })
+            .collect()
+    }
+
+    /// Fee in USDC on a fill of `size` shares at `price`.
+    ///
+    /// Polymarket charges the fee rate on the cheaper side of the binary
+    /// outcome, `min(price, 1 - price)`, so fees shrink towards the extremes.
+    pub fn trade_fee(price: Decimal, size: Decimal, fee_rate_bps: Decimal) -> Decimal {
+        let basis = price.min(Decimal::ONE - price).max(Decimal::ZERO);
+        basis * size * fee_rate_bps / Decimal::from(10_000)
+    }
+
+    /// Build a quote from an order book: best bid/ask and resting notional.
+    pub fn convert_quote(book: &OrderBookDepth) -> OutcomeQuote {
+        OutcomeQuote {
+            token_id: book.token_id.clone(),
+            bid: book.best_bid_price(),
+            ask: book.best_ask_price(),
+            liquidity: Some(book.total_liquidity(usize::MAX)),
+            ..OutcomeQuote::default()
+        }
+    }
+
+    /// Convert a Gamma `/markets` response to market volumes.
+    ///
+    /// Gamma reports volumes as numbers or strings, under `volumeNum` or
+    /// `volume` for the total and `volume24hr` for the last 24h.
+    pub fn convert_market_volumes(response: &serde_json::Value) -> Vec<MarketVolume> {
+        response
+            .as_array()
+            .map(|markets| markets.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|market| {
+                Some(MarketVolume {
+                    market_id: str_field(market, "conditionId")?.to_string(),
+                    volume: decimal_field(market, "volumeNum")
+                        .or_else(|| decimal_field(market, "volume")),
+                    volume_24h: decimal_field(market, "volume24hr"),
+                })
+            })
+            .collect()
+    }
+
+    /// Parse the bulk last-trade-price response into a map of token ID to price.
+    ///
+    /// Accepts both a list of `{ token_id, price }` objects and a plain
+    /// `{ token_id: price }` map.
+    pub fn convert_last_trade_prices(response: &serde_json::Value) -> HashMap<String, Decimal> {
+        fn decimal(value: &serde_json::Value) -> Option<Decimal> {
+            match value {
+                serde_json::Value::String(s) => Decimal::from_str(s).ok(),
+                serde_json::Value::Number(n) => Decimal::from_str(&n.to_string()).ok(),
+                serde_json::Value::Object(o) => o.get("price").and_then(decimal),
+                _ => None,
+            }
+        }
+
+        match response {
+            serde_json::Value::Array(entries) => entries
+                .iter()
+                .filter_map(|entry| {
+                    let token_id = entry.get("token_id")?.as_str()?;
+                    Some((token_id.to_string(), decimal(entry.get("price")?)?))
+                })
+                .collect(),
+            serde_json::Value::Object(map) => map
+                .iter()
+                .filter_map(|(token_id, price)| Some((token_id.clone(), decimal(price)?)))
+                .collect(),
+            _ => HashMap::new(),
+        }
+    }
+}
+
+/// Read a string field of an untyped JSON object.
+fn str_field<'a>(value: &'a serde_json::Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(|v| v.as_str())
+}
+
+/// Read a decimal field sent either as a string or a number.
+fn decimal_field(value: &serde_json::Value, key: &str) -> Option<Decimal> {
+    value.get(key).and_then(|v| match v {
+        serde_json::Value::String(s) => Decimal::from_str(s).ok(),
+        serde_json::Value::Number(n) => Decimal::from_str(&n.to_string()).ok(),
+        _ => None,
+    })
+}
+
+/// Read the `side` of a trade or maker order.
+fn order_side(value: &serde_json::Value) -> Option<OrderSide> {
+    match str_field(value, "side")?.to_uppercase().as_str() {
+        "BUY" => Some(OrderSide::Buy),
+        "SELL" => Some(OrderSide::Sell),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn trade(trader_side: &str) -> serde_json::Value {
+        json!({
+            "id": "trade-1",
+            "market": "0xmarket",
+            "asset_id": "yes-token",
+            "outcome": "Yes",
+            "side": "BUY",
+            "price": "0.60",
+            "size": "100",
+            "fee_rate_bps": "100",
+            "match_time": "1700000000",
+            "owner": "our-key",
+            "taker_order_id": "0xtaker",
+            "trader_side": trader_side,
+            "maker_orders": [
+                {
+                    "order_id": "0xother",
+                    "owner": "someone-else",
+                    "asset_id": "yes-token",
+                    "outcome": "Yes",
+                    "side": "SELL",
+                    "price": "0.60",
+                    "matched_amount": "70",
+                    "fee_rate_bps": "0"
+                },
+                {
+                    "order_id": "0xours",
+                    "owner": "our-key",
+                    "asset_id": "no-token",
+                    "outcome": "No",
+                    "side": "BUY",
+                    "price": "0.40",
+                    "matched_amount": "30",
+                    "fee_rate_bps": "0"
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn test_convert_taker_trade() {
+        let fills = DataConverter::convert_trade(&trade("TAKER"));
+        assert_eq!(fills.len(), 1);
+
+        let fill = &fills[0];
+        assert_eq!(fill.id, "trade-1");
+        assert_eq!(fill.order_id, "0xtaker");
+        assert_eq!(fill.market_id, "0xmarket");
+        assert_eq!(fill.token_id, "yes-token");
+        assert_eq!(fill.side, OrderSide::Buy);
+        assert_eq!(fill.price, dec!(0.60));
+        assert_eq!(fill.size, dec!(100));
+        assert_eq!(fill.timestamp.timestamp(), 1_700_000_000);
+        // 1% on min(0.60, 0.40) per share
+        assert_eq!(fill.fee, dec!(0.40));
+    }
+
+    #[test]
+    fn test_convert_maker_trade_uses_our_maker_order() {
+        let fills = DataConverter::convert_trade(&trade("MAKER"));
+        assert_eq!(fills.len(), 1);
+
+        let fill = &fills[0];
+        assert_eq!(fill.id, "trade-1:0xours");
+        assert_eq!(fill.order_id, "0xours");
+        assert_eq!(fill.token_id, "no-token");
+        assert_eq!(fill.outcome_name, "No");
+        assert_eq!(fill.side, OrderSide::Buy);
+        assert_eq!(fill.price, dec!(0.40));
+        assert_eq!(fill.size, dec!(30));
+        assert_eq!(fill.fee, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_convert_maker_trade_without_side() {
+        let mut value = trade("MAKER");
+        let orders = value["maker_orders"].as_array_mut().unwrap();
+        orders.remove(0);
+        orders[0].as_object_mut().unwrap().remove("side");
+
+        // A lone maker order is ours and took the other side of the taker
+        let fills = DataConverter::convert_trade(&value);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].side, OrderSide::Sell);
+        assert_eq!(fills[0].size, dec!(30));
+    }
+
+    #[test]
+    fn test_convert_malformed_trade() {
+        assert!(DataConverter::convert_trade(&json!({ "side": "BUY" })).is_empty());
+        assert!(DataConverter::convert_trade(&json!({ "id": "t", "side": "HOLD" })).is_empty());
+    }
+
+    fn book_summary() -> polymarket_rs::types::OrderBookSummary {
+        // The API lists levels worst-first
+        serde_json::from_value(json!({
+            "market": "0xmarket",
+            "asset_id": "yes-token",
+            "hash": "abc",
+            "timestamp": "1700000000000",
+            "bids": [
+                { "price": "0.40", "size": "100" },
+                { "price": "0.45", "size": "50" },
+                { "price": "0.48", "size": "10" }
+            ],
+            "asks": [
+                { "price": "0.60", "size": "100" },
+                { "price": "0.55", "size": "50" },
+                { "price": "0.52", "size": "10" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_convert_orderbook_best_first() {
+        let book = DataConverter::convert_orderbook(book_summary());
+        assert_eq!(book.token_id, "yes-token");
+        assert_eq!(book.timestamp.timestamp(), 1_700_000_000);
+
+        let bids: Vec<Decimal> = book.bids.iter().map(|l| l.price).collect();
+        let asks: Vec<Decimal> = book.asks.iter().map(|l| l.price).collect();
+        assert_eq!(bids, vec![dec!(0.48), dec!(0.45), dec!(0.40)]);
+        assert_eq!(asks, vec![dec!(0.52), dec!(0.55), dec!(0.60)]);
+        assert_eq!(book.best_bid_price(), Some(dec!(0.48)));
+        assert_eq!(book.best_ask_price(), Some(dec!(0.52)));
+    }
+
+    #[test]
+    fn test_convert_quote() {
+        let book = DataConverter::convert_orderbook(book_summary());
+        let quote = DataConverter::convert_quote(&book);
+
+        assert_eq!(quote.token_id, "yes-token");
+        assert_eq!(quote.bid, Some(dec!(0.48)));
+        assert_eq!(quote.ask, Some(dec!(0.52)));
+        // 4.8 + 22.5 + 40 bid notional, 5.2 + 27.5 + 60 ask notional
+        assert_eq!(quote.liquidity, Some(dec!(160)));
+        assert_eq!(quote.last_price, None);
+
+        // An empty book quotes nothing but zero depth
+        let empty = DataConverter::convert_quote(&OrderBookDepth::new("m", "t"));
+        assert_eq!(empty.bid, None);
+        assert_eq!(empty.ask, None);
+        assert_eq!(empty.liquidity, Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_convert_last_trade_prices() {
+        let list = json!([
+            { "token_id": "a", "price": "0.51" },
+            { "token_id": "b", "price": 0.25 },
+            { "token_id": "c" }
+        ]);
+        let prices = DataConverter::convert_last_trade_prices(&list);
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices["a"], dec!(0.51));
+        assert_eq!(prices["b"], dec!(0.25));
+
+        let map = json!({ "a": "0.51", "b": { "price": "0.25", "side": "BUY" }, "c": null });
+        let prices = DataConverter::convert_last_trade_prices(&map);
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices["b"], dec!(0.25));
+
+        assert!(DataConverter::convert_last_trade_prices(&json!("oops")).is_empty());
+    }
+
+    #[test]
+    fn test_convert_market_volumes() {
+        let response = json!([
+            { "conditionId": "m1", "volumeNum": 12345.5, "volume24hr": 678.25 },
+            { "conditionId": "m2", "volume": "99.5" },
+            { "volumeNum": 1 }
+        ]);
+        let volumes = DataConverter::convert_market_volumes(&response);
+        assert_eq!(
+            volumes,
+            vec![
+                MarketVolume {
+                    market_id: "m1".to_string(),
+                    volume: Some(dec!(12345.5)),
+                    volume_24h: Some(dec!(678.25)),
+                },
+                MarketVolume {
+                    market_id: "m2".to_string(),
+                    volume: Some(dec!(99.5)),
+                    volume_24h: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trade_fee_basis() {
+        let bps = dec!(200);
+        assert_eq!(
+            DataConverter::trade_fee(dec!(0.50), dec!(10), bps),
+            dec!(0.10)
+        );
+        // Symmetric around 0.5
+        assert_eq!(
+            DataConverter::trade_fee(dec!(0.90), dec!(10), bps),
+            DataConverter::trade_fee(dec!(0.10), dec!(10), bps)
+        );
+        assert_eq!(
+            DataConverter::trade_fee(dec!(0.90), dec!(10), bps),
+            dec!(0.02)
+        );
+    }
 }
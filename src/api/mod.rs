@@ -6,5 +6,5 @@
 mod client;
 mod converter;
 
-pub use client::{ApiClient, ApiClientBuilder};
+pub use client::{ApiClient, ApiClientBuilder, MarketData};
 pub use converter::DataConverter;
@@ -1,14 +1,25 @@
 //! Main application module.
 //!
 //! This module contains the main `App` struct that coordinates
-//! the event loop, state management, and rendering.
+//! the event loop, state management, and rendering. The same action
+//! handling drives both the TUI and the headless daemon.
 
+use crate::alerts;
 use crate::api::ApiClient;
-use crate::config::Config;
+use crate::config::{Config, ConfigLoader, ConfigWatcher};
 use crate::error::{Error, Result};
 use crate::events::EventHandler;
-use crate::state::{Action, Store};
+use crate::state::{
+    Action, AlertState, DAEMON_STALE_SECS, DaemonStatus, Notification, Order, OrderRequest,
+    SignalEntry, Store, StrategyEntry, StrategySummary, TradeState, View, WatchlistState,
+};
+use crate::strategy::{
+    EngineConfig, Frame, ParameterChange, Recording, StrategyContext, StrategyEngine,
+    StrategyStatus,
+};
 use crate::ui::Ui;
+use chrono::{DateTime, Utc};
+use polymarket_rs::types::Side;
 
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
@@ -17,12 +28,14 @@ use crossterm::{
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
 use std::io::{self, Stdout};
+use std::path::PathBuf;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 /// The main application.
 pub struct App {
-    /// Terminal.
-    terminal: Terminal<CrosstermBackend<Stdout>>,
+    /// Terminal; `None` when running headless.
+    terminal: Option<Terminal<CrosstermBackend<Stdout>>>,
     /// Application store.
     store: Store,
     /// Event handler.
@@ -31,13 +44,22 @@ pub struct App {
     action_rx: mpsc::UnboundedReceiver<Action>,
     /// API client.
     api_client: Option<ApiClient>,
-    /// Configuration.
-    #[allow(dead_code)]
+    /// Configuration with the active profile applied.
     config: Config,
+    /// Configuration as loaded, before profile overrides.
+    base_config: Config,
+    /// Strategy engine.
+    engine: StrategyEngine,
+    /// When the application started.
+    started_at: DateTime<Utc>,
+    /// Watches the config file for changes to apply at runtime.
+    config_watcher: Option<ConfigWatcher>,
+    /// User configuration file that strategy parameters are saved to.
+    config_path: PathBuf,
 }
 
 impl App {
-    /// Create a new application.
+    /// Create a new application attached to the terminal.
     pub async fn new(config: Config) -> Result<Self> {
         // Set up terminal
         enable_raw_mode()?;
@@ -46,14 +68,71 @@ impl App {
         let backend = CrosstermBackend::new(stdout);
         let terminal = Terminal::new(backend)?;
 
+        Self::build(config, Some(terminal)).await
+    }
+
+    /// Create a new application without a terminal, for the headless daemon.
+    pub async fn headless(config: Config) -> Result<Self> {
+        Self::build(config, None).await
+    }
+
+    async fn build(
+        base_config: Config,
+        terminal: Option<Terminal<CrosstermBackend<Stdout>>>,
+    ) -> Result<Self> {
+        let config = base_config.with_active_profile()?;
+
         // Create action channel
         let (action_tx, action_rx) = mpsc::unbounded_channel();
 
         // Create store
-        let store = Store::new(action_tx.clone());
+        let mut store = Store::new(action_tx.clone());
+        store.app.profile = config.profile.clone();
+        store.app.profiles = config.profile_names();
+
+        // Load saved watchlists
+        match WatchlistState::load(None) {
+            Ok(watchlists) => store.watchlists = watchlists,
+            Err(e) => tracing::warn!("Failed to load watchlists: {}", e),
+        }
+
+        // Load saved alert rules
+        match AlertState::load(None) {
+            Ok(alerts) => store.alerts = alerts,
+            Err(e) => tracing::warn!("Failed to load alerts: {}", e),
+        }
+
+        // Load the active profile's fill history
+        match config
+            .trades_path()
+            .and_then(|path| TradeState::load(Some(path), config.portfolio.cost_basis))
+        {
+            Ok(trades) => store.trades = trades,
+            Err(e) => tracing::warn!("Failed to load trade history: {}", e),
+        }
+
+        // Show a headless daemon's status, if one is running
+        match DaemonStatus::load(config.daemon.state_path.clone()) {
+            Ok(status) => store.daemon = status,
+            Err(e) => tracing::warn!("Failed to load daemon status: {}", e),
+        }
+
+        // Register configured strategies
+        let mut engine = StrategyEngine::new(
+            action_tx.clone(),
+            EngineConfig {
+                risk_config: config.strategies.risk.clone(),
+                evaluation_interval_ms: config.strategies.evaluation_interval_ms,
+                state_dir: config.strategy_state_dir(),
+                checkpoint_interval_secs: config.strategies.checkpoint_interval_secs,
+                ..EngineConfig::default()
+            },
+        );
+        Self::register_strategies(&mut engine, &config).await;
 
         // Create event handler
-        let event_handler = EventHandler::new(action_tx);
+        let mut event_handler = EventHandler::new(action_tx);
+        event_handler.set_keybindings(config.keybindings.clone());
 
         // Try to create API client
         let api_client = match ApiClient::new(config.api.clone(), None).await {
@@ -71,12 +150,96 @@ impl App {
             action_rx,
             api_client,
             config,
+            base_config,
+            engine,
+            started_at: Utc::now(),
+            config_watcher: None,
+            config_path: Config::default_path(),
         })
     }
 
-    /// Run the application event loop.
-    pub async fn run(&mut self) -> Result<()> {
-        // Initial connection test
+    /// Register the strategies listed in the configuration.
+    async fn register_strategies(engine: &mut StrategyEngine, config: &Config) {
+        for (name, strategy_config) in &config.strategies.instances {
+            let strategy = match crate::strategy::create(
+                name,
+                strategy_config,
+                &config.strategies.script_limits,
+            ) {
+                Ok(strategy) => strategy,
+                Err(e) => {
+                    tracing::warn!("Cannot create strategy '{}': {}", name, e);
+                    continue;
+                }
+            };
+            if let Err(e) = engine
+                .register_boxed(strategy, strategy_config.clone())
+                .await
+            {
+                tracing::warn!("Failed to register strategy '{}': {}", name, e);
+            }
+        }
+    }
+
+    /// Start every enabled strategy and the engine, returning how many started.
+    fn start_strategies(&mut self) -> Result<usize> {
+        let names: Vec<String> = self
+            .engine
+            .strategies()
+            .iter()
+            .filter(|(_, handle)| handle.config.enabled)
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &names {
+            self.engine.start_strategy(name)?;
+        }
+        self.engine.start();
+        Ok(names.len())
+    }
+
+    /// A live daemon trading the active profile, other than this process.
+    fn strategy_daemon(&self) -> Option<DaemonStatus> {
+        DaemonStatus::load(self.config.daemon.state_path.clone())
+            .ok()
+            .flatten()
+            .filter(|status| {
+                status.pid != std::process::id()
+                    && status.profile == self.config.profile
+                    && status.is_alive(Utc::now(), DAEMON_STALE_SECS)
+            })
+    }
+
+    /// Start strategies in the TUI unless a daemon already runs them.
+    ///
+    /// Two processes running the same strategies would trade twice and
+    /// overwrite each other's checkpoints, so the TUI only displays what it
+    /// can while a daemon is up for its profile.
+    fn start_local_strategies(&mut self) -> Result<()> {
+        if let Some(daemon) = self.strategy_daemon() {
+            tracing::info!(pid = daemon.pid, "Strategies are run by the daemon");
+            self.store
+                .reduce(Action::ShowNotification(Notification::info(format!(
+                    "Strategies are run by the daemon (pid {})",
+                    daemon.pid
+                ))));
+            return Ok(());
+        }
+
+        let started = self.start_strategies()?;
+        tracing::info!(strategies = started, "Strategy engine started");
+        Ok(())
+    }
+
+    /// Reload the configuration whenever one of the loader's files changes.
+    pub fn watch_config(&mut self, loader: ConfigLoader) {
+        if let Some(path) = loader.user_path() {
+            self.config_path = path.clone();
+        }
+        self.config_watcher = Some(ConfigWatcher::new(loader));
+    }
+
+    /// Test the API connection and queue the initial data load.
+    async fn connect(&mut self) -> Result<()> {
         if let Some(client) = &self.api_client {
             match client.test_connection().await {
                 Ok(true) => {
@@ -89,19 +252,55 @@ impl App {
                 }
             }
         }
+        Ok(())
+    }
+
+    /// Run the application event loop.
+    pub async fn run(&mut self) -> Result<()> {
+        if self.terminal.is_none() {
+            return Err(Error::terminal("No terminal attached; use run_headless"));
+        }
+
+        self.connect().await?;
+        self.start_local_strategies()?;
+
+        let mut refresh = tokio::time::interval(Duration::from_secs(
+            self.config.daemon.refresh_interval_secs.max(1),
+        ));
+        let mut evaluate = tokio::time::interval(Duration::from_millis(
+            self.config.strategies.evaluation_interval_ms.max(100),
+        ));
+        // The initial load is already queued
+        refresh.tick().await;
+
+        let shutdown = shutdown_signal();
+        tokio::pin!(shutdown);
 
         // Main event loop
         loop {
+            // Expire toasts whose display time has elapsed
+            self.store.reduce(Action::Tick);
+
             // Update event handler with current state
             self.event_handler.update_store_snapshot(&self.store);
+            crate::crash::record_state(self.store.summary());
+            self.check_config_reload().await;
 
             // Render UI
-            self.terminal.draw(|frame| {
-                Ui::render(frame, &self.store);
-            })?;
+            if let Some(terminal) = &mut self.terminal {
+                terminal.draw(|frame| {
+                    Ui::render(frame, &self.store);
+                })?;
+            }
 
             // Handle events and actions
             tokio::select! {
+                // Ctrl+C arrives as a key press in raw mode, so this is mostly SIGTERM
+                _ = &mut shutdown => {
+                    tracing::info!("Shutdown signal received");
+                    break;
+                }
+
                 // Handle terminal events
                 result = self.event_handler.next() => {
                     if let Some(action) = result? {
@@ -113,6 +312,14 @@ impl App {
                 Some(action) = self.action_rx.recv() => {
                     self.handle_action(action).await?;
                 }
+
+                // Run strategies on fresh data, unless a daemon runs them
+                _ = refresh.tick(), if self.engine.is_running() => {
+                    self.store.dispatch(Action::RefreshAll)?;
+                }
+                _ = evaluate.tick(), if self.engine.is_running() => {
+                    self.evaluate_strategies().await;
+                }
             }
 
             // Check if we should quit
@@ -121,9 +328,447 @@ impl App {
             }
         }
 
+        self.shutdown().await;
+
         Ok(())
     }
 
+    /// Run strategies and data refreshes without a terminal until SIGTERM or Ctrl+C.
+    ///
+    /// Actions go through the same handling as the TUI. A status snapshot is
+    /// written to the daemon state file after every evaluation.
+    pub async fn run_headless(&mut self) -> Result<()> {
+        tracing::info!(pid = std::process::id(), "Daemon starting");
+
+        self.connect().await?;
+        self.store.dispatch(Action::RefreshAll)?;
+
+        let started = self.start_strategies()?;
+        tracing::info!(strategies = started, "Strategy engine started");
+
+        let mut refresh = tokio::time::interval(Duration::from_secs(
+            self.config.daemon.refresh_interval_secs.max(1),
+        ));
+        let mut evaluate = tokio::time::interval(Duration::from_millis(
+            self.config.strategies.evaluation_interval_ms.max(100),
+        ));
+        // The initial load is already queued
+        refresh.tick().await;
+
+        let shutdown = shutdown_signal();
+        tokio::pin!(shutdown);
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => {
+                    tracing::info!("Shutdown signal received");
+                    break;
+                }
+
+                _ = refresh.tick() => {
+                    self.store.dispatch(Action::RefreshAll)?;
+                    self.record_frame(&self.strategy_context());
+                }
+
+                _ = evaluate.tick() => {
+                    self.store.reduce(Action::Tick);
+                    crate::crash::record_state(self.store.summary());
+                    self.check_config_reload().await;
+                    self.evaluate_strategies().await;
+                    self.write_daemon_status(true);
+                }
+
+                Some(action) = self.action_rx.recv() => {
+                    if let Err(e) = self.handle_action(action).await {
+                        tracing::error!(error = %e, "Action failed");
+                    }
+                }
+            }
+
+            if self.store.app.should_quit {
+                break;
+            }
+        }
+
+        self.shutdown().await;
+        self.write_daemon_status(false);
+        tracing::info!("Daemon stopped");
+
+        Ok(())
+    }
+
+    /// Append the latest market data to the recording, if enabled.
+    ///
+    /// The data is what the previous refresh loaded, so frames are one
+    /// refresh interval apart.
+    fn record_frame(&self, ctx: &StrategyContext) {
+        let Some(path) = &self.config.daemon.record_path else {
+            return;
+        };
+        if ctx.markets.is_empty() {
+            return;
+        }
+        if let Err(e) = Recording::append(path, &Frame::from_context(ctx)) {
+            tracing::warn!("Failed to record market data: {}", e);
+        }
+    }
+
+    /// Apply the config file if it changed, notifying what was applied.
+    async fn check_config_reload(&mut self) {
+        let Some(result) = self.config_watcher.as_mut().and_then(|w| w.poll()) else {
+            return;
+        };
+
+        // A profile switched to at runtime stays active across reloads
+        let result = result.and_then(|mut base| {
+            base.profile = self.base_config.profile.clone();
+            let config = base.with_active_profile()?;
+            Ok((base, config))
+        });
+
+        match result {
+            Ok((base, config)) => {
+                self.store.app.profiles = base.profile_names();
+                self.base_config = base;
+                self.apply_config(config).await;
+            }
+            Err(e) => {
+                tracing::warn!("Config reload rejected: {}", e);
+                self.store
+                    .reduce(Action::ShowNotification(Notification::error(format!(
+                        "Config reload rejected: {}",
+                        e
+                    ))));
+            }
+        }
+    }
+
+    /// Apply a reloaded configuration.
+    ///
+    /// UI, theme, key bindings, shutdown, risk limits and the parameters of
+    /// registered strategies take effect immediately. Other sections keep
+    /// their running values until the next start.
+    async fn apply_config(&mut self, mut config: Config) {
+        let changed = self.config.changed_sections(&config);
+        if changed.is_empty() {
+            return;
+        }
+
+        let mut applied = Vec::new();
+        let mut restart = Vec::new();
+        let mut errors = Vec::new();
+
+        for section in &changed {
+            match section.as_str() {
+                "ui" | "theme" | "shutdown" => applied.push(section.clone()),
+                // Profile overrides show up in the sections they change
+                "profile" | "profiles" => {}
+                "keybindings" => {
+                    self.event_handler
+                        .set_keybindings(config.keybindings.clone());
+                    applied.push(section.clone());
+                }
+                "strategies" => {
+                    self.engine.set_risk_config(config.strategies.risk.clone());
+                    for (name, strategy_config) in &config.strategies.instances {
+                        let unchanged =
+                            self.config.strategies.instances.get(name) == Some(strategy_config);
+                        if unchanged {
+                            continue;
+                        }
+                        if !self.engine.strategies().contains_key(name) {
+                            restart.push(format!("strategies.instances.{}", name));
+                        } else if let Err(e) =
+                            self.engine.reconfigure(name, strategy_config.clone()).await
+                        {
+                            errors.push(format!("{}: {}", name, e));
+                        }
+                    }
+                    if config.strategies.evaluation_interval_ms
+                        != self.config.strategies.evaluation_interval_ms
+                    {
+                        restart.push("strategies.evaluation_interval_ms".to_string());
+                    }
+                    if config.strategy_state_dir() != self.config.strategy_state_dir() {
+                        restart.push("strategies.state_dir".to_string());
+                    }
+                    if config.strategies.checkpoint_interval_secs
+                        != self.config.strategies.checkpoint_interval_secs
+                    {
+                        restart.push("strategies.checkpoint_interval_secs".to_string());
+                    }
+                    if config.strategies.script_limits != self.config.strategies.script_limits {
+                        restart.push("strategies.script_limits".to_string());
+                    }
+                    applied.push(section.clone());
+                }
+                _ => restart.push(section.clone()),
+            }
+        }
+
+        // Sections that are only read at startup keep their running values
+        config.api = self.config.api.clone();
+        config.portfolio = self.config.portfolio.clone();
+        config.daemon = self.config.daemon.clone();
+        config.logging = self.config.logging.clone();
+        config.strategies.evaluation_interval_ms = self.config.strategies.evaluation_interval_ms;
+        config.strategies.persist_state = self.config.strategies.persist_state;
+        config.strategies.state_dir = self.config.strategies.state_dir.clone();
+        config.strategies.checkpoint_interval_secs =
+            self.config.strategies.checkpoint_interval_secs;
+        config.strategies.script_limits = self.config.strategies.script_limits.clone();
+        self.config = config;
+        self.load_strategies().await;
+
+        let mut message = format!("Config reloaded: {}", applied.join(", "));
+        if applied.is_empty() {
+            message = "Config reloaded".to_string();
+        }
+        if !restart.is_empty() {
+            message.push_str(&format!(" (restart needed for {})", restart.join(", ")));
+        }
+        tracing::info!(applied = ?applied, restart = ?restart, "Config reloaded");
+
+        let notification = if errors.is_empty() {
+            Notification::success(message)
+        } else {
+            tracing::warn!(errors = ?errors, "Failed to reconfigure strategies");
+            Notification::warning(format!("{}; failed: {}", message, errors.join("; ")))
+        };
+        self.store.reduce(Action::ShowNotification(notification));
+    }
+
+    /// Switch to the next or previous configured profile.
+    async fn cycle_profile(&mut self, delta: i32) {
+        let names = self.base_config.profile_names();
+        if names.is_empty() {
+            self.store
+                .reduce(Action::ShowNotification(Notification::warning(
+                    "No profiles configured",
+                )));
+            return;
+        }
+
+        let len = names.len() as i32;
+        let next = match self
+            .base_config
+            .profile
+            .as_ref()
+            .and_then(|p| names.iter().position(|n| n == p))
+        {
+            Some(i) => (i as i32 + delta).rem_euclid(len),
+            None if delta < 0 => len - 1,
+            None => 0,
+        };
+
+        if let Err(e) = self.switch_profile(names[next as usize].clone()).await {
+            self.store
+                .reduce(Action::SetError(format!("Failed to switch profile: {}", e)));
+        }
+    }
+
+    /// Activate a profile: reconnect with its API settings and replace the
+    /// previous profile's strategies and risk limits.
+    async fn switch_profile(&mut self, name: String) -> Result<()> {
+        let mut base = self.base_config.clone();
+        base.profile = Some(name.clone());
+        let config = base.with_active_profile()?;
+
+        let was_running = self.engine.is_running();
+        self.engine.shutdown().await;
+        let registered: Vec<String> = self.engine.strategies().keys().cloned().collect();
+        for strategy in &registered {
+            if let Err(e) = self.engine.unregister(strategy).await {
+                tracing::warn!(strategy = %strategy, error = %e, "Strategy shutdown failed");
+            }
+        }
+        // Each profile keeps its own strategy state
+        self.engine.set_state_dir(config.strategy_state_dir());
+        self.engine.set_risk_config(config.strategies.risk.clone());
+        Self::register_strategies(&mut self.engine, &config).await;
+
+        self.api_client = match ApiClient::new(config.api.clone(), None).await {
+            Ok(client) => Some(client),
+            Err(e) => {
+                tracing::warn!("Failed to create API client: {}", e);
+                None
+            }
+        };
+        self.base_config = base;
+        self.config = config;
+        if self.terminal.is_some() {
+            self.start_local_strategies()?;
+        } else if was_running {
+            self.start_strategies()?;
+        }
+        self.load_strategies().await;
+
+        tracing::info!(profile = %name, "Switched profile");
+        self.store.reduce(Action::ProfileSwitched(name.clone()));
+        // Fills are fetched after the newest one in the profile's own history
+        match self
+            .config
+            .trades_path()
+            .and_then(|path| TradeState::load(Some(path), self.config.portfolio.cost_basis))
+        {
+            Ok(trades) => self.store.reduce(Action::TradesLoaded(trades.fills)),
+            Err(e) => tracing::warn!("Failed to load trade history: {}", e),
+        }
+        self.store
+            .reduce(Action::ShowNotification(Notification::info(format!(
+                "Switched to profile '{}'",
+                name
+            ))));
+        self.connect().await
+    }
+
+    /// Stop strategies and, if configured, cancel our open orders.
+    ///
+    /// The engine is stopped first so no new signals are generated while
+    /// orders are being cancelled.
+    async fn shutdown(&mut self) {
+        self.engine.stop();
+
+        if self.config.shutdown.cancel_open_orders {
+            let timeout = Duration::from_secs(self.config.shutdown.timeout_secs.max(1));
+            if tokio::time::timeout(timeout, self.cancel_open_orders())
+                .await
+                .is_err()
+            {
+                tracing::warn!("Timed out cancelling open orders");
+            }
+        }
+
+        self.engine.shutdown().await;
+    }
+
+    /// Cancel every open order, fetching the current list first when possible.
+    async fn cancel_open_orders(&mut self) {
+        let Some(client) = self.api_client.as_ref().filter(|c| c.is_authenticated()) else {
+            tracing::debug!("Not authenticated; no orders to cancel");
+            return;
+        };
+
+        let orders = match client.fetch_orders().await {
+            Ok(orders) => orders,
+            Err(e) => {
+                tracing::warn!("Failed to fetch orders, using cached list: {}", e);
+                self.store.orders.orders.clone()
+            }
+        };
+
+        let mut cancelled = 0;
+        for order in orders.iter().filter(|o| o.can_cancel()) {
+            match client.cancel_order(&order.id).await {
+                Ok(()) => cancelled += 1,
+                Err(e) => tracing::warn!(order = %order.id, error = %e, "Failed to cancel order"),
+            }
+        }
+        tracing::info!(cancelled, "Cancelled open orders on shutdown");
+    }
+
+    /// Build a strategy context from the current store.
+    fn strategy_context(&self) -> StrategyContext {
+        StrategyContext::from_state(
+            &self.store.markets.markets,
+            &self.store.portfolio.positions,
+            &self.store.orders.orders,
+            self.store.portfolio.available_usdc(),
+        )
+        .with_order_books(&self.store.orderbooks)
+        .with_price_history(&self.store.markets)
+    }
+
+    /// Evaluate running strategies and execute approved signals.
+    async fn evaluate_strategies(&mut self) {
+        let ctx = self.strategy_context();
+        self.engine
+            .set_watchlists(&self.store.watchlists.watchlists);
+
+        match self.engine.check_brackets(&ctx).await {
+            Ok(exits) if !exits.is_empty() => {
+                tracing::info!(count = exits.len(), "Bracket exits sent");
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!(error = %e, "Bracket exit failed"),
+        }
+
+        for signal in self.engine.evaluate(&ctx).await {
+            tracing::info!(
+                strategy = %signal.strategy_name,
+                market = %signal.market_id,
+                token = %signal.token_id,
+                side = ?signal.side,
+                size = %signal.size,
+                price = ?signal.price,
+                reason = signal.reason.as_deref().unwrap_or(""),
+                "Signal generated"
+            );
+        }
+
+        match self.engine.execute_pending_signals().await {
+            Ok(executed) if !executed.is_empty() => {
+                tracing::info!(count = executed.len(), "Signals executed");
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!(error = %e, "Signal execution failed"),
+        }
+
+        if let Err(e) = self.engine.advance_executions(&ctx) {
+            tracing::error!(error = %e, "Child order failed");
+        }
+        self.load_brackets();
+        self.refresh_signals();
+    }
+
+    /// Write the daemon state file.
+    fn write_daemon_status(&self, running: bool) {
+        let mut strategies: Vec<StrategySummary> = self
+            .engine
+            .strategies()
+            .iter()
+            .map(|(name, handle)| StrategySummary {
+                name: name.clone(),
+                status: if running {
+                    handle.status.to_string()
+                } else {
+                    StrategyStatus::Stopped.to_string()
+                },
+                signals_generated: handle.signals_generated,
+                signals_executed: handle.signals_executed,
+                errors: handle.errors,
+                last_evaluated: handle.last_evaluated,
+            })
+            .collect();
+        strategies.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let status = DaemonStatus {
+            pid: std::process::id(),
+            profile: self.config.profile.clone(),
+            started_at: self.started_at,
+            updated_at: Utc::now(),
+            running,
+            connected: self.store.app.connected,
+            markets: self.store.markets.markets.len(),
+            positions: self.store.portfolio.positions.len(),
+            open_orders: self
+                .store
+                .orders
+                .orders
+                .iter()
+                .filter(|o| o.is_active())
+                .count(),
+            total_value: self.store.portfolio.total_value,
+            pending_signals: self.engine.pending_signals().len(),
+            strategies,
+            last_error: self.store.app.error.clone(),
+        };
+
+        if let Err(e) = status.save(self.config.daemon.state_path.clone()) {
+            tracing::warn!("Failed to write daemon status: {}", e);
+        }
+    }
+
     /// Handle an action.
     async fn handle_action(&mut self, action: Action) -> Result<()> {
         match &action {
@@ -133,24 +778,305 @@ impl App {
             Action::RefreshMarkets | Action::LoadMarkets => {
                 self.refresh_markets().await?;
             }
+            Action::RefreshPrices => {
+                self.refresh_prices().await?;
+            }
             Action::RefreshOrders | Action::LoadOrders => {
                 self.refresh_orders().await?;
             }
             Action::RefreshPortfolio | Action::LoadPortfolio => {
                 self.refresh_portfolio().await?;
             }
+            Action::RefreshTrades | Action::LoadTrades => {
+                self.refresh_trades().await?;
+            }
             Action::RefreshOrderBook(token_id) | Action::LoadOrderBook(token_id) => {
                 self.refresh_orderbook(token_id).await?;
             }
+            Action::LoadOrderBooks(token_ids) => {
+                self.refresh_orderbooks(token_ids).await?;
+            }
+            Action::CycleProfile(delta) => {
+                self.cycle_profile(*delta).await;
+            }
+            Action::SetView(View::Strategies) | Action::LoadStrategies => {
+                self.store.reduce(action);
+                self.load_strategies().await;
+            }
+            Action::SetView(View::Signals) => {
+                self.store.reduce(action);
+                self.load_signals();
+            }
+            Action::SetStrategyParameter {
+                strategy,
+                parameter,
+                value,
+            } => {
+                self.store.reduce(action.clone());
+                self.set_strategy_parameter(strategy, parameter, Some(value))
+                    .await;
+            }
+            Action::ResetStrategyParameter {
+                strategy,
+                parameter,
+            } => {
+                self.set_strategy_parameter(strategy, parameter, None).await;
+            }
+            Action::SaveStrategyParameters(strategy) => {
+                self.save_strategy_parameters(strategy);
+            }
+            Action::PlaceOrder(request) => {
+                self.place_order(request.clone()).await;
+            }
+            Action::CancelOrder(order_id) => {
+                self.cancel_order(order_id).await;
+            }
+            Action::ToggleWatchlistMarket(_)
+            | Action::CreateWatchlist(_)
+            | Action::DeleteWatchlist(_) => {
+                self.store.reduce(action);
+                if let Err(e) = self.store.watchlists.save(None) {
+                    tracing::warn!("Failed to save watchlists: {}", e);
+                }
+            }
+            Action::CreateAlert(_) | Action::ToggleAlert(_) | Action::DeleteAlert(_) => {
+                self.store.reduce(action);
+                if let Err(e) = self.store.alerts.save(None) {
+                    tracing::warn!("Failed to save alerts: {}", e);
+                }
+            }
             _ => {
                 // Let the store handle the action
                 self.store.reduce(action);
             }
         }
 
+        self.check_alerts();
+
         Ok(())
     }
 
+    /// Refresh the strategies view from the engine.
+    async fn load_strategies(&mut self) {
+        let mut names: Vec<String> = self.engine.strategies().keys().cloned().collect();
+        names.sort();
+
+        let mut strategies = Vec::new();
+        for name in names {
+            let Some(handle) = self.engine.strategies().get(&name) else {
+                continue;
+            };
+            let status = handle.status.to_string();
+            let description = handle.strategy.read().await.metadata().description;
+            match self.engine.parameter_fields(&name).await {
+                Ok(parameters) => strategies.push(StrategyEntry {
+                    name,
+                    status,
+                    description,
+                    parameters,
+                }),
+                Err(e) => tracing::warn!(strategy = %name, "Failed to read parameters: {}", e),
+            }
+        }
+
+        let changes = self.engine.parameter_log().iter().cloned().collect();
+        self.store.reduce(Action::StrategiesLoaded {
+            strategies,
+            changes,
+        });
+    }
+
+    /// Refresh the signals view from the engine's signal history.
+    fn load_signals(&mut self) {
+        let entries = self
+            .engine
+            .signal_history()
+            .iter()
+            .rev()
+            .map(|record| SignalEntry {
+                record: record.clone(),
+                lifecycle: record
+                    .client_order_id
+                    .as_deref()
+                    .and_then(|id| self.engine.lifecycle(id))
+                    .cloned(),
+                execution: self.engine.execution(&record.signal.id).cloned(),
+            })
+            .collect();
+        self.store.reduce(Action::SignalsLoaded(entries));
+    }
+
+    /// Show the engine's open brackets next to positions.
+    fn load_brackets(&mut self) {
+        self.store
+            .reduce(Action::BracketsLoaded(self.engine.brackets().to_vec()));
+    }
+
+    /// Refresh the signals view if it is showing.
+    fn refresh_signals(&mut self) {
+        if self.store.app.current_view == View::Signals {
+            self.load_signals();
+        }
+    }
+
+    /// Change a strategy parameter from text typed in the TUI, or restore
+    /// its default with `None`.
+    async fn set_strategy_parameter(
+        &mut self,
+        strategy: &str,
+        parameter: &str,
+        value: Option<&str>,
+    ) {
+        match self.apply_parameter(strategy, parameter, value).await {
+            Ok(change) => {
+                self.store
+                    .reduce(Action::ShowNotification(Notification::success(format!(
+                        "Set {}.{} to {}",
+                        change.strategy, change.parameter, change.new
+                    ))));
+            }
+            Err(e) => {
+                self.store.reduce(Action::SetError(e.to_string()));
+            }
+        }
+        self.load_strategies().await;
+    }
+
+    async fn apply_parameter(
+        &mut self,
+        strategy: &str,
+        parameter: &str,
+        value: Option<&str>,
+    ) -> Result<ParameterChange> {
+        let field = self
+            .engine
+            .parameter_fields(strategy)
+            .await?
+            .into_iter()
+            .find(|f| f.def.name == parameter)
+            .ok_or_else(|| {
+                Error::invalid_input(format!(
+                    "Strategy '{}' has no parameter '{}'",
+                    strategy, parameter
+                ))
+            })?;
+        let value = match value {
+            Some(value) => field.def.parse(value)?,
+            None => field.def.default.clone(),
+        };
+        self.engine.set_parameter(strategy, parameter, value).await
+    }
+
+    /// Write a strategy's current parameters into the user config file.
+    ///
+    /// Parameters go to the active profile's strategies when the profile
+    /// overrides them, and to `[strategies.instances]` otherwise.
+    fn save_strategy_parameters(&mut self, strategy: &str) {
+        let Some(handle) = self.engine.strategies().get(strategy) else {
+            return;
+        };
+        let parameters = handle.config.parameters.clone();
+        let profile = self.base_config.profile.as_deref().filter(|p| {
+            self.base_config
+                .profiles
+                .get(*p)
+                .is_some_and(|p| p.strategies.is_some())
+        });
+
+        match crate::config::save_strategy_parameters(
+            &self.config_path,
+            profile,
+            strategy,
+            &parameters,
+        ) {
+            Ok(()) => {
+                // Already applied, so the reload triggered by the write has nothing to do
+                if let Some(config) = self.config.strategies.instances.get_mut(strategy) {
+                    config.parameters = parameters.clone();
+                }
+                tracing::info!(strategy = %strategy, path = %self.config_path.display(), "Saved strategy parameters");
+                self.store
+                    .reduce(Action::ShowNotification(Notification::success(format!(
+                        "Saved {} parameter(s) of {} to {}",
+                        parameters.len(),
+                        strategy,
+                        self.config_path.display()
+                    ))));
+            }
+            Err(e) => {
+                self.store.reduce(Action::SetError(format!(
+                    "Failed to save {} parameters: {}",
+                    strategy, e
+                )));
+            }
+        }
+    }
+
+    /// Evaluate alert rules against the updated state and fire any that triggered.
+    fn check_alerts(&mut self) {
+        for alert in alerts::evaluate(&mut self.store) {
+            tracing::info!("Alert fired: {}", alert.message);
+            self.store
+                .reduce(Action::ShowNotification(Notification::warning(
+                    alert.message.clone(),
+                )));
+            alerts::run_hooks(&alert);
+        }
+    }
+
+    /// Submit an order through the API.
+    async fn place_order(&mut self, request: OrderRequest) {
+        self.store.reduce(Action::PlaceOrder(request.clone()));
+
+        let client_order_id = request.client_order_id.clone();
+        let result = match &self.api_client {
+            Some(client) => client.place_order(request).await,
+            None => Err(Error::application("No API client available")),
+        };
+        match result {
+            Ok(order) => {
+                tracing::info!(order = %order.id, "Order placed");
+                if let Some(client_order_id) = &client_order_id {
+                    self.engine.order_placed(client_order_id, &order).await;
+                }
+                self.store.reduce(Action::OrderPlaced(order));
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Order placement failed");
+                if let Some(client_order_id) = &client_order_id {
+                    self.engine
+                        .order_rejected(client_order_id, &e.to_string())
+                        .await;
+                }
+                self.store.reduce(Action::SetError(e.to_string()));
+            }
+        }
+        self.refresh_signals();
+    }
+
+    /// Cancel an order through the API.
+    async fn cancel_order(&mut self, order_id: &str) {
+        self.store.reduce(Action::CancelOrder(order_id.to_string()));
+
+        let result = match &self.api_client {
+            Some(client) => client.cancel_order(order_id).await,
+            None => Err(Error::application("No API client available")),
+        };
+        match result {
+            Ok(()) => {
+                tracing::info!(order = %order_id, "Order cancelled");
+                self.engine.order_cancelled(order_id).await;
+                self.refresh_signals();
+                self.store
+                    .reduce(Action::OrderCancelled(order_id.to_string()));
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Order cancellation failed");
+                self.store.reduce(Action::SetError(e.to_string()));
+            }
+        }
+    }
+
     /// Refresh all data.
     async fn refresh_all(&mut self) -> Result<()> {
         self.store.reduce(Action::SetLoading(true));
@@ -159,16 +1085,31 @@ impl App {
         let markets = self.fetch_markets().await;
         let orders = self.fetch_orders().await;
         let portfolio = self.fetch_portfolio().await;
+        let trades = self.fetch_trades().await;
 
         if let Ok(markets) = markets {
             self.store.reduce(Action::MarketsLoaded(markets));
+            self.store.dispatch(Action::RefreshPrices)?;
+            // Keep books fresh for every market running strategies trade
+            if let Err(e) = self.engine.request_order_books(&self.strategy_context()) {
+                tracing::warn!("Failed to request order books: {}", e);
+            }
         }
         if let Ok(orders) = orders {
-            self.store.reduce(Action::OrdersLoaded(orders));
+            self.apply_orders(orders).await;
         }
         if let Ok(portfolio) = portfolio {
             self.store.reduce(Action::PortfolioLoaded(portfolio));
         }
+        if let Ok(trades) = trades {
+            self.apply_trades(trades).await;
+        }
+        if self.terminal.is_some() {
+            match DaemonStatus::load(self.config.daemon.state_path.clone()) {
+                Ok(status) => self.store.reduce(Action::DaemonStatusLoaded(status)),
+                Err(e) => tracing::debug!("Failed to load daemon status: {}", e),
+            }
+        }
 
         self.store.reduce(Action::SetLoading(false));
         Ok(())
@@ -181,6 +1122,8 @@ impl App {
         match self.fetch_markets().await {
             Ok(markets) => {
                 self.store.reduce(Action::MarketsLoaded(markets));
+                // Prices are fetched separately so the list renders first
+                self.store.dispatch(Action::RefreshPrices)?;
             }
             Err(e) => {
                 self.store.reduce(Action::SetError(e.to_string()));
@@ -190,13 +1133,69 @@ impl App {
         Ok(())
     }
 
+    /// Enrich loaded markets with live outcome prices and volumes.
+    ///
+    /// Covers every active market, held and watched markets first so they
+    /// are inside the price history limit.
+    async fn refresh_prices(&mut self) -> Result<()> {
+        let Some(client) = &self.api_client else {
+            return Ok(());
+        };
+
+        let markets: Vec<crate::state::Market> =
+            self.priced_markets().into_iter().cloned().collect();
+        if markets.is_empty() {
+            return Ok(());
+        }
+
+        self.store.reduce(Action::SetLoading(true));
+        let data = client.fetch_market_data(&markets).await;
+        self.store.reduce(Action::QuotesLoaded(data.quotes));
+        self.store.reduce(Action::VolumesLoaded(data.volumes));
+        self.store.reduce(Action::SetLoading(false));
+        Ok(())
+    }
+
+    /// Active markets, held and watched markets first.
+    fn priced_markets(&self) -> Vec<&crate::state::Market> {
+        let markets = &self.store.markets.markets;
+        let held = |id: &str| {
+            self.store
+                .portfolio
+                .positions
+                .iter()
+                .any(|p| p.market_id == id)
+        };
+        let watched = |id: &str| {
+            self.store
+                .watchlists
+                .watchlists
+                .iter()
+                .any(|w| w.contains(id))
+        };
+
+        let mut active: Vec<&crate::state::Market> =
+            markets.iter().filter(|m| m.is_tradeable()).collect();
+        // Stable sort keeps the API order within each group
+        active.sort_by_key(|m| (!held(&m.id), !watched(&m.id)));
+        active
+    }
+
+    /// Store fetched open orders and follow strategy orders through them.
+    async fn apply_orders(&mut self, orders: Vec<Order>) {
+        self.engine.sync_orders(&orders, &self.store.trades).await;
+        self.store.reduce(Action::OrdersLoaded(orders));
+        self.load_brackets();
+        self.refresh_signals();
+    }
+
     /// Refresh orders.
     async fn refresh_orders(&mut self) -> Result<()> {
         self.store.reduce(Action::LoadOrders);
 
         match self.fetch_orders().await {
             Ok(orders) => {
-                self.store.reduce(Action::OrdersLoaded(orders));
+                self.apply_orders(orders).await;
             }
             Err(e) => {
                 self.store.reduce(Action::SetError(e.to_string()));
@@ -222,6 +1221,43 @@ impl App {
         Ok(())
     }
 
+    /// Refresh the fill history.
+    async fn refresh_trades(&mut self) -> Result<()> {
+        self.store.reduce(Action::LoadTrades);
+
+        match self.fetch_trades().await {
+            Ok(trades) => self.apply_trades(trades).await,
+            Err(e) => {
+                self.store.reduce(Action::SetError(e.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Merge fetched fills into the local history and persist it.
+    ///
+    /// Strategy orders that left the book are settled against the new
+    /// history right away rather than at the next order refresh.
+    async fn apply_trades(&mut self, trades: Vec<crate::state::Fill>) {
+        let known = self.store.trades.fills.len();
+        self.store.reduce(Action::TradesLoaded(trades));
+        self.engine
+            .sync_orders(&self.store.orders.orders, &self.store.trades)
+            .await;
+        self.load_brackets();
+        self.refresh_signals();
+
+        if self.store.trades.fills.len() != known
+            && let Err(e) = self
+                .config
+                .trades_path()
+                .and_then(|path| self.store.trades.save(Some(path)))
+        {
+            tracing::warn!("Failed to save trade history: {}", e);
+        }
+    }
+
     /// Refresh order book for a specific token.
     async fn refresh_orderbook(&mut self, token_id: &str) -> Result<()> {
         self.store
@@ -239,6 +1275,35 @@ impl App {
         Ok(())
     }
 
+    /// Refresh order books for many tokens using bulk requests.
+    async fn refresh_orderbooks(&mut self, token_ids: &[String]) -> Result<()> {
+        let Some(client) = &self.api_client else {
+            return Ok(());
+        };
+
+        self.store
+            .reduce(Action::LoadOrderBooks(token_ids.to_vec()));
+
+        for batch in token_ids.chunks(self.config.api.price_batch_size.max(1)) {
+            let params: Vec<(String, Side)> = batch
+                .iter()
+                .map(|token_id| (token_id.clone(), Side::Buy))
+                .collect();
+            match client.fetch_orderbooks(&params).await {
+                Ok(books) => {
+                    for book in books {
+                        self.store.reduce(Action::OrderBookLoaded(book));
+                    }
+                }
+                Err(e) => {
+                    self.store.reduce(Action::OrderBookError(e.to_string()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Fetch markets from the API.
     async fn fetch_markets(&self) -> Result<Vec<crate::state::Market>> {
         if let Some(client) = &self.api_client {
@@ -266,6 +1331,20 @@ impl App {
         }
     }
 
+    /// Fetch fills newer than the local history from the API.
+    async fn fetch_trades(&self) -> Result<Vec<crate::state::Fill>> {
+        if let Some(client) = &self.api_client {
+            let after = self
+                .store
+                .trades
+                .latest_timestamp()
+                .map(|t| t.timestamp().max(0) as u64);
+            client.fetch_trades(after).await
+        } else {
+            Ok(Vec::new()) // Return empty if not authenticated
+        }
+    }
+
     /// Fetch order book for a token from the API.
     async fn fetch_orderbook(&self, token_id: &str) -> Result<crate::state::OrderBookDepth> {
         if let Some(client) = &self.api_client {
@@ -279,12 +1358,40 @@ impl App {
 impl Drop for App {
     fn drop(&mut self) {
         // Restore terminal state
-        let _ = disable_raw_mode();
-        let _ = execute!(
-            self.terminal.backend_mut(),
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        );
-        let _ = self.terminal.show_cursor();
+        if let Some(terminal) = &mut self.terminal {
+            let _ = disable_raw_mode();
+            let _ = execute!(
+                terminal.backend_mut(),
+                LeaveAlternateScreen,
+                DisableMouseCapture
+            );
+            let _ = terminal.show_cursor();
+        }
+    }
+}
+
+/// Resolve when the process receives SIGTERM or Ctrl+C.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = terminate.recv() => {}
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Failed to install SIGTERM handler: {}", e);
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
     }
 }
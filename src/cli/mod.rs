@@ -0,0 +1,911 @@
+//! Headless command-line interface.
+//!
+//! Running `clobster` without a subcommand launches the TUI. Subcommands
+//! reuse the same [`Config`] and [`ApiClient`] to print market data, orders
+//! and positions for scripts and cron jobs.
+//!
+//! Exit codes:
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | Success |
+//! | 1 | Unexpected failure |
+//! | 2 | Invalid arguments or input |
+//! | 3 | Authentication required or failed |
+//! | 4 | API or network failure |
+//! | 5 | Not supported yet, such as placing and cancelling orders |
+
+mod output;
+
+pub use output::{OutputFormat, Table};
+
+use crate::api::{ApiClient, ApiClientBuilder};
+use crate::config::{Config, ConfigLoader};
+use crate::error::{Error, Result};
+use crate::state::{
+    DAEMON_STALE_SECS, DaemonStatus, MarketState, MarketStatus, OrderRequest, OrderSide, OrderType,
+    Position,
+};
+use crate::strategy::{
+    self, Optimizer, OptimizerConfig, Recording, SearchMethod, StrategyConfig, StrategyFactory,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+/// A terminal UI and command-line client for Polymarket.
+#[derive(Debug, Parser)]
+#[command(name = "clobster", version, about)]
+pub struct Cli {
+    /// Path to the user configuration file, replacing the default location.
+    #[arg(long, short, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Configuration profile to use instead of the configured default.
+    #[arg(long, short, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Override a setting, e.g. `--set ui.tick_rate_ms=500`. Repeatable.
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE", value_parser = parse_override)]
+    pub overrides: Vec<(String, String)>,
+
+    /// Output format options.
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Command to run; launches the TUI when omitted.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    /// Build the configuration loader, with command-line flags as the top layer.
+    pub fn config_loader(&self) -> ConfigLoader {
+        let mut loader = ConfigLoader::new().path(self.config.clone());
+        for (key, value) in &self.overrides {
+            loader = loader.set(key, value);
+        }
+        if let Some(profile) = &self.profile {
+            loader = loader.set("profile", profile);
+        }
+        loader
+    }
+}
+
+fn parse_override(arg: &str) -> std::result::Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(format!("expected KEY=VALUE, got '{}'", arg)),
+    }
+}
+
+/// Output format flags shared by all commands.
+#[derive(Debug, Clone, Copy, Args)]
+pub struct OutputArgs {
+    /// Print JSON instead of a table.
+    #[arg(long, global = true, conflicts_with = "csv")]
+    pub json: bool,
+
+    /// Print CSV instead of a table.
+    #[arg(long, global = true)]
+    pub csv: bool,
+}
+
+impl OutputArgs {
+    /// Get the selected output format.
+    pub fn format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else if self.csv {
+            OutputFormat::Csv
+        } else {
+            OutputFormat::Table
+        }
+    }
+}
+
+/// Headless subcommands.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Market queries.
+    #[command(subcommand)]
+    Markets(MarketsCommand),
+    /// Print the order book for a token.
+    Book {
+        /// Token ID of the outcome.
+        token_id: String,
+        /// Number of price levels per side.
+        #[arg(long, short, default_value_t = 10)]
+        depth: usize,
+    },
+    /// List open orders.
+    Orders,
+    /// Place or cancel an order. Not supported yet: the API client cannot
+    /// place or cancel orders, so both exit with code 5.
+    #[command(subcommand)]
+    Order(OrderCommand),
+    /// List positions.
+    Positions {
+        /// Combine balances and positions from every configured profile.
+        #[arg(long)]
+        all_profiles: bool,
+    },
+    /// List configured profiles.
+    Profiles,
+    /// Inspect the configuration.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Launch the TUI, or run strategies without a terminal.
+    Run {
+        /// Run the strategy engine and data refresh as a daemon, logging to file.
+        #[arg(long)]
+        headless: bool,
+    },
+    /// Show the status of a headless daemon.
+    Status,
+    /// Search a strategy's parameters against recorded market data.
+    Optimize(OptimizeArgs),
+}
+
+/// Arguments of `clobster optimize`.
+#[derive(Debug, Args)]
+pub struct OptimizeArgs {
+    /// Configured strategy to optimize: a built-in strategy or a script
+    /// instance. External strategies cannot be optimized.
+    pub strategy: String,
+    /// Recording written by the daemon (`daemon.record_path`).
+    #[arg(long, value_name = "PATH")]
+    pub data: PathBuf,
+    /// Search method.
+    #[arg(long, value_enum, default_value_t = MethodArg::Random)]
+    pub method: MethodArg,
+    /// Parameter sets to try with random or Bayesian search.
+    #[arg(long, default_value_t = 50)]
+    pub trials: usize,
+    /// Values per numeric parameter with grid search.
+    #[arg(long, default_value_t = 5)]
+    pub grid_steps: usize,
+    /// Walk-forward folds; rank by out-of-sample return when set.
+    #[arg(long, default_value_t = 0)]
+    pub folds: usize,
+    /// Backtests to run in parallel (defaults to the number of cores).
+    #[arg(long, short)]
+    pub jobs: Option<usize>,
+    /// Seed for reproducible random and Bayesian search.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Starting balance of each backtest.
+    #[arg(long, default_value = "1000")]
+    pub balance: Decimal,
+    /// Only search this parameter. Repeatable.
+    #[arg(long = "param", value_name = "NAME")]
+    pub parameters: Vec<String>,
+    /// Maximum number of results to print.
+    #[arg(long, short)]
+    pub limit: Option<usize>,
+    /// Write the best configuration as TOML to this file.
+    #[arg(long, value_name = "PATH")]
+    pub export: Option<PathBuf>,
+}
+
+/// Configuration subcommands.
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Print the merged configuration.
+    Show {
+        /// List every setting with the file, environment variable or flag it came from.
+        #[arg(long)]
+        effective: bool,
+    },
+    /// List the configuration files in the order they are applied.
+    Files,
+}
+
+/// Market subcommands.
+#[derive(Debug, Subcommand)]
+pub enum MarketsCommand {
+    /// List markets.
+    List {
+        /// Only markets whose question, description or tags contain this text.
+        #[arg(long, short)]
+        filter: Option<String>,
+        /// Only markets with this status.
+        #[arg(long, value_enum)]
+        status: Option<StatusArg>,
+        /// Maximum number of markets to print.
+        #[arg(long, short)]
+        limit: Option<usize>,
+    },
+}
+
+/// Order subcommands.
+#[derive(Debug, Subcommand)]
+pub enum OrderCommand {
+    /// Place an order (not supported yet, exits with code 5).
+    Place {
+        /// Market condition ID.
+        #[arg(long)]
+        market: String,
+        /// Token ID of the outcome.
+        #[arg(long)]
+        token: String,
+        /// Order side.
+        #[arg(long, value_enum)]
+        side: SideArg,
+        /// Number of shares.
+        #[arg(long)]
+        size: Decimal,
+        /// Limit price; places a market order when omitted.
+        #[arg(long)]
+        price: Option<Decimal>,
+    },
+    /// Cancel an order (not supported yet, exits with code 5).
+    Cancel {
+        /// Order ID.
+        order_id: String,
+    },
+}
+
+/// Market status accepted on the command line.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum StatusArg {
+    Active,
+    Closed,
+    Resolved,
+    Paused,
+}
+
+impl From<StatusArg> for MarketStatus {
+    fn from(status: StatusArg) -> Self {
+        match status {
+            StatusArg::Active => Self::Active,
+            StatusArg::Closed => Self::Closed,
+            StatusArg::Resolved => Self::Resolved,
+            StatusArg::Paused => Self::Paused,
+        }
+    }
+}
+
+/// Optimizer search method accepted on the command line.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum MethodArg {
+    Grid,
+    Random,
+    Bayesian,
+}
+
+impl From<MethodArg> for SearchMethod {
+    fn from(method: MethodArg) -> Self {
+        match method {
+            MethodArg::Grid => Self::Grid,
+            MethodArg::Random => Self::Random,
+            MethodArg::Bayesian => Self::Bayesian,
+        }
+    }
+}
+
+/// Order side accepted on the command line.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SideArg {
+    Buy,
+    Sell,
+}
+
+impl From<SideArg> for OrderSide {
+    fn from(side: SideArg) -> Self {
+        match side {
+            SideArg::Buy => Self::Buy,
+            SideArg::Sell => Self::Sell,
+        }
+    }
+}
+
+/// Run a headless command, printing errors to stderr.
+pub async fn run(command: Command, config: Config, output: OutputArgs) -> ExitCode {
+    match execute(command, config, output.format()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(exit_code(&e))
+        }
+    }
+}
+
+/// Run a `config` subcommand.
+///
+/// Takes the loader rather than a loaded [`Config`] so an invalid
+/// configuration can still be inspected.
+pub fn run_config(command: ConfigCommand, loader: &ConfigLoader, output: OutputArgs) -> ExitCode {
+    match execute_config(command, loader, output.format()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(exit_code(&e))
+        }
+    }
+}
+
+/// Map an error to the process exit code.
+pub fn exit_code(error: &Error) -> u8 {
+    match error {
+        Error::InvalidInput(_) | Error::Config(_) => 2,
+        Error::Auth(_) | Error::Wallet(_) => 3,
+        Error::Api(_) | Error::Network(_) | Error::RateLimited(_) => 4,
+        Error::Unsupported(_) => 5,
+        _ => 1,
+    }
+}
+
+async fn execute(command: Command, config: Config, format: OutputFormat) -> Result<()> {
+    match command {
+        Command::Status => return print_daemon_status(&config, format),
+        Command::Profiles => return list_profiles(&config, format),
+        Command::Optimize(args) => return optimize(args, &config, format).await,
+        Command::Positions { all_profiles: true } => {
+            return list_all_positions(&config, format).await;
+        }
+        Command::Run { .. } => {
+            return Err(Error::invalid_input(
+                "`run` starts the TUI or daemon and is handled by the binary",
+            ));
+        }
+        Command::Config(_) => {
+            return Err(Error::invalid_input(
+                "`config` reads the configuration layers and is handled by `run_config`",
+            ));
+        }
+        _ => {}
+    }
+
+    let config = config.with_active_profile()?;
+    let client = ApiClientBuilder::new().config(config.api).build().await?;
+
+    match command {
+        Command::Markets(MarketsCommand::List {
+            filter,
+            status,
+            limit,
+        }) => list_markets(&client, filter, status, limit, format).await,
+        Command::Book { token_id, depth } => print_book(&client, &token_id, depth, format).await,
+        Command::Orders => list_orders(&client, format).await,
+        Command::Order(OrderCommand::Place {
+            market,
+            token,
+            side,
+            size,
+            price,
+        }) => {
+            let request = OrderRequest {
+                market_id: market,
+                token_id: token,
+                side: side.into(),
+                price,
+                size,
+                order_type: if price.is_some() {
+                    OrderType::Limit
+                } else {
+                    OrderType::Market
+                },
+                client_order_id: None,
+            };
+            validate_order(&request)?;
+            let order = client.place_order(request).await?;
+            output::print(format, &order, || {
+                let mut table = Table::new(&["ID", "Status"]);
+                table.push(vec![order.id.clone(), order.status.to_string()]);
+                table
+            })
+        }
+        Command::Order(OrderCommand::Cancel { order_id }) => {
+            client.cancel_order(&order_id).await?;
+            let result = serde_json::json!({ "id": order_id, "cancelled": true });
+            output::print(format, &result, || {
+                let mut table = Table::new(&["ID", "Status"]);
+                table.push(vec![order_id.clone(), "Cancelled".to_string()]);
+                table
+            })
+        }
+        Command::Positions { .. } => list_positions(&client, format).await,
+        Command::Status
+        | Command::Profiles
+        | Command::Optimize(_)
+        | Command::Run { .. }
+        | Command::Config(_) => {
+            unreachable!("handled above")
+        }
+    }
+}
+
+fn print_daemon_status(config: &Config, format: OutputFormat) -> Result<()> {
+    let status = DaemonStatus::load(config.daemon.state_path.clone())?
+        .ok_or_else(|| Error::application("No daemon status file found"))?;
+
+    output::print(format, &status, || {
+        let alive = status.is_alive(chrono::Utc::now(), DAEMON_STALE_SECS);
+        let mut table = Table::new(&["Name", "Status", "Signals", "Executed", "Errors"]);
+        table.push(vec![
+            format!("daemon (pid {})", status.pid),
+            if alive { "Running" } else { "Not running" }.to_string(),
+            "-".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+        ]);
+        for strategy in &status.strategies {
+            table.push(vec![
+                strategy.name.clone(),
+                strategy.status.clone(),
+                strategy.signals_generated.to_string(),
+                strategy.signals_executed.to_string(),
+                strategy.errors.to_string(),
+            ]);
+        }
+        table
+    })
+}
+
+/// Search strategy parameters against a recording and rank the results.
+///
+/// Parameters outside the search keep the values configured for the
+/// strategy in the active profile.
+async fn optimize(args: OptimizeArgs, config: &Config, format: OutputFormat) -> Result<()> {
+    let name = args.strategy;
+    let config = config.clone().with_active_profile()?;
+    let base = config
+        .strategies
+        .instances
+        .get(&name)
+        .cloned()
+        .unwrap_or_else(|| StrategyConfig {
+            enabled: true,
+            ..Default::default()
+        });
+    // Every trial would launch its own process, or share one socket with
+    // the trials running beside it
+    if base.external.is_some() {
+        return Err(Error::invalid_input(format!(
+            "'{}' is an external strategy, which cannot be optimized; only built-in and script strategies can",
+            name
+        )));
+    }
+    let limits = config.strategies.script_limits.clone();
+    // Fail early on an unknown strategy or a script that does not compile
+    strategy::create(&name, &base, &limits)?;
+    let recording = Recording::load(&args.data)?;
+
+    let mut optimizer_config = OptimizerConfig {
+        method: args.method.into(),
+        trials: args.trials,
+        grid_steps: args.grid_steps,
+        folds: args.folds,
+        seed: args.seed,
+        starting_balance: args.balance,
+        parameters: args.parameters,
+        ..Default::default()
+    };
+    if let Some(jobs) = args.jobs {
+        optimizer_config.parallelism = jobs;
+    }
+    let (factory_name, factory_config) = (name.clone(), base.clone());
+    let factory: StrategyFactory = Arc::new(move || {
+        strategy::create(&factory_name, &factory_config, &limits).expect("strategy created above")
+    });
+    let mut report = Optimizer::new(optimizer_config)
+        .run(factory, &base, &recording)
+        .await?;
+
+    if let Some(path) = &args.export {
+        let best = report
+            .best_config(&base)
+            .ok_or_else(|| Error::application("Optimization produced no results"))?;
+        // `[strategies.instances.<name>]`, ready to paste into a config file
+        let snippet = BTreeMap::from([(
+            "strategies",
+            BTreeMap::from([("instances", BTreeMap::from([(name.as_str(), best)]))]),
+        )]);
+        let content = toml::to_string_pretty(&snippet).map_err(|e| Error::config(e.to_string()))?;
+        std::fs::write(path, content)?;
+    }
+
+    if let Some(limit) = args.limit {
+        report.trials.truncate(limit);
+    }
+    output::print(format, &report, || {
+        let mut table = Table::new(&[
+            "Rank",
+            "Parameters",
+            "In-sample %",
+            "Out-of-sample %",
+            "Trades",
+            "Max DD %",
+        ]);
+        for trial in &report.trials {
+            let parameters: Vec<String> = trial
+                .parameters
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            table.push(vec![
+                trial.rank.to_string(),
+                parameters.join(" "),
+                format!("{:.2}", trial.in_sample.return_pct),
+                trial
+                    .out_of_sample
+                    .as_ref()
+                    .map(|s| format!("{:.2}", s.return_pct))
+                    .unwrap_or_else(|| "-".to_string()),
+                trial.score().trades.to_string(),
+                format!("{:.2}", trial.score().max_drawdown * Decimal::ONE_HUNDRED),
+            ]);
+        }
+        table
+    })
+}
+
+async fn list_markets(
+    client: &ApiClient,
+    filter: Option<String>,
+    status: Option<StatusArg>,
+    limit: Option<usize>,
+    format: OutputFormat,
+) -> Result<()> {
+    let state = MarketState {
+        markets: client.fetch_markets().await?,
+        search_query: filter,
+        status_filter: status.map(Into::into),
+        ..Default::default()
+    };
+
+    let mut listed = MarketState {
+        markets: state.filtered_markets().into_iter().cloned().collect(),
+        ..Default::default()
+    };
+    if let Some(limit) = limit {
+        listed.markets.truncate(limit);
+    }
+
+    // Prices and volumes are not part of the market listing
+    client
+        .fetch_market_data(&listed.markets)
+        .await
+        .apply(&mut listed);
+    let markets = listed.markets;
+
+    output::print(format, &markets, || {
+        let mut table = Table::new(&["ID", "Status", "Yes", "Volume", "Ends", "Question"]);
+        for market in &markets {
+            table.push(vec![
+                market.id.clone(),
+                market.status.to_string(),
+                market
+                    .mid_price(0)
+                    .map(|p| format!("{:.3}", p))
+                    .unwrap_or_default(),
+                format!("{:.0}", market.volume),
+                market
+                    .end_date
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default(),
+                market.question.clone(),
+            ]);
+        }
+        table
+    })
+}
+
+async fn print_book(
+    client: &ApiClient,
+    token_id: &str,
+    depth: usize,
+    format: OutputFormat,
+) -> Result<()> {
+    let mut book = client.fetch_orderbook(token_id).await?;
+    book.bids.truncate(depth);
+    book.asks.truncate(depth);
+
+    output::print(format, &book, || {
+        let mut table = Table::new(&["Side", "Price", "Size", "Value"]);
+        // Asks from worst to best so the spread sits in the middle
+        let asks = book.asks.iter().rev().map(|l| ("ask", l));
+        let bids = book.bids.iter().map(|l| ("bid", l));
+        for (side, level) in asks.chain(bids) {
+            table.push(vec![
+                side.to_string(),
+                format!("{:.3}", level.price),
+                format!("{:.2}", level.size),
+                format!("{:.2}", level.value()),
+            ]);
+        }
+        table
+    })
+}
+
+async fn list_orders(client: &ApiClient, format: OutputFormat) -> Result<()> {
+    let orders = client.fetch_orders().await?;
+
+    output::print(format, &orders, || {
+        let mut table = Table::new(&[
+            "ID", "Market", "Outcome", "Side", "Price", "Size", "Filled", "Status",
+        ]);
+        for order in &orders {
+            table.push(vec![
+                order.id.clone(),
+                order.market_id.clone(),
+                order.outcome_name.clone(),
+                format!("{:?}", order.side),
+                format!("{:.3}", order.price),
+                format!("{:.2}", order.original_size),
+                format!("{:.2}", order.filled_size),
+                order.status.to_string(),
+            ]);
+        }
+        table
+    })
+}
+
+async fn list_positions(client: &ApiClient, format: OutputFormat) -> Result<()> {
+    let positions = client.fetch_positions().await?;
+
+    output::print(format, &positions, || {
+        let mut table = Table::new(&["Market", "Outcome", "Size", "Avg", "Price", "Value", "PnL"]);
+        for position in &positions {
+            table.push(vec![
+                position.market_id.clone(),
+                position.outcome_name.clone(),
+                format!("{:.2}", position.size),
+                format!("{:.3}", position.avg_price),
+                format!("{:.3}", position.current_price),
+                format!("{:.2}", position.market_value),
+                format!("{:.2}", position.unrealized_pnl),
+            ]);
+        }
+        table
+    })
+}
+
+fn execute_config(
+    command: ConfigCommand,
+    loader: &ConfigLoader,
+    format: OutputFormat,
+) -> Result<()> {
+    match command {
+        ConfigCommand::Show { effective: false } => {
+            let config = loader.build()?.config;
+            if format == OutputFormat::Table {
+                let content =
+                    toml::to_string_pretty(&config).map_err(|e| Error::config(e.to_string()))?;
+                output::write(&content)
+            } else {
+                show_effective(loader, format)
+            }
+        }
+        ConfigCommand::Show { effective: true } => show_effective(loader, format),
+        ConfigCommand::Files => {
+            let files: Vec<ConfigFile> = loader
+                .files()
+                .into_iter()
+                .map(|path| ConfigFile {
+                    exists: path.exists(),
+                    path,
+                })
+                .collect();
+            output::print(format, &files, || {
+                let mut table = Table::new(&["Path", "Status"]);
+                for file in &files {
+                    table.push(vec![
+                        file.path.display().to_string(),
+                        if file.exists { "Loaded" } else { "Missing" }.to_string(),
+                    ]);
+                }
+                table
+            })
+        }
+    }
+}
+
+/// Print every setting with its source, flagging invalid ones.
+fn show_effective(loader: &ConfigLoader, format: OutputFormat) -> Result<()> {
+    let effective = loader.build()?;
+    let issues = effective.config.validate();
+
+    output::print(format, &effective.values, || {
+        let mut table = Table::new(&["Key", "Value", "Source", "Problem"]);
+        for value in &effective.values {
+            let problem = issues
+                .iter()
+                .find(|issue| issue.path == value.key)
+                .map(|issue| issue.message.clone())
+                .unwrap_or_default();
+            table.push(vec![
+                value.key.clone(),
+                value.value.clone(),
+                value.source.clone(),
+                problem,
+            ]);
+        }
+        table
+    })
+}
+
+fn list_profiles(config: &Config, format: OutputFormat) -> Result<()> {
+    let profiles: Vec<ProfileSummary> = config
+        .profile_names()
+        .into_iter()
+        .map(|name| {
+            let mut selected = config.clone();
+            selected.profile = Some(name.clone());
+            let resolved = selected.with_active_profile()?;
+            Ok(ProfileSummary {
+                active: config.profile.as_ref() == Some(&name),
+                strategies: resolved.strategies.instances.keys().cloned().collect(),
+                base_url: resolved.api.base_url,
+                credentials_path: resolved.api.credentials_path,
+                name,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    output::print(format, &profiles, || {
+        let mut table = Table::new(&["", "Name", "API", "Credentials", "Strategies"]);
+        for profile in &profiles {
+            table.push(vec![
+                if profile.active { "*" } else { "" }.to_string(),
+                profile.name.clone(),
+                profile.base_url.clone(),
+                profile
+                    .credentials_path
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default(),
+                profile.strategies.join(", "),
+            ]);
+        }
+        table
+    })
+}
+
+/// Balances and positions of every profile, fetched one profile at a time.
+///
+/// A profile that fails (e.g. missing credentials) is reported with its
+/// error instead of aborting the whole listing.
+async fn list_all_positions(config: &Config, format: OutputFormat) -> Result<()> {
+    let names = config.profile_names();
+    if names.is_empty() {
+        return Err(Error::invalid_input("No profiles configured"));
+    }
+
+    let mut portfolios = Vec::new();
+    for name in names {
+        let mut selected = config.clone();
+        selected.profile = Some(name.clone());
+        let result = async {
+            let resolved = selected.with_active_profile()?;
+            let client = ApiClientBuilder::new().config(resolved.api).build().await?;
+            let portfolio = client.fetch_portfolio().await?;
+            let positions = client.fetch_positions().await?;
+            Ok::<_, Error>((portfolio, positions))
+        }
+        .await;
+
+        portfolios.push(match result {
+            Ok((portfolio, positions)) => ProfilePortfolio {
+                profile: name,
+                balance: portfolio.balances.iter().map(|b| b.total).sum(),
+                market_value: positions.iter().map(|p| p.market_value).sum(),
+                unrealized_pnl: positions.iter().map(|p| p.unrealized_pnl).sum(),
+                positions,
+                error: None,
+            },
+            Err(e) => ProfilePortfolio {
+                profile: name,
+                balance: Decimal::ZERO,
+                market_value: Decimal::ZERO,
+                unrealized_pnl: Decimal::ZERO,
+                positions: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        });
+    }
+
+    if portfolios.iter().all(|p| p.error.is_some()) {
+        let errors: Vec<String> = portfolios
+            .iter()
+            .map(|p| format!("{}: {}", p.profile, p.error.as_deref().unwrap_or_default()))
+            .collect();
+        return Err(Error::auth(format!(
+            "No profile could be loaded ({})",
+            errors.join("; ")
+        )));
+    }
+
+    output::print(format, &portfolios, || {
+        let mut table = Table::new(&[
+            "Profile", "Market", "Outcome", "Size", "Price", "Value", "PnL",
+        ]);
+        for portfolio in &portfolios {
+            for position in &portfolio.positions {
+                table.push(vec![
+                    portfolio.profile.clone(),
+                    position.market_id.clone(),
+                    position.outcome_name.clone(),
+                    format!("{:.2}", position.size),
+                    format!("{:.3}", position.current_price),
+                    format!("{:.2}", position.market_value),
+                    format!("{:.2}", position.unrealized_pnl),
+                ]);
+            }
+            let summary = match &portfolio.error {
+                Some(e) => format!("error: {}", e),
+                None => format!("total (cash {:.2})", portfolio.balance),
+            };
+            table.push(vec![
+                portfolio.profile.clone(),
+                summary,
+                String::new(),
+                String::new(),
+                String::new(),
+                format!("{:.2}", portfolio.market_value),
+                format!("{:.2}", portfolio.unrealized_pnl),
+            ]);
+        }
+        let total =
+            |f: fn(&ProfilePortfolio) -> Decimal| -> Decimal { portfolios.iter().map(f).sum() };
+        table.push(vec![
+            "all".to_string(),
+            format!("total (cash {:.2})", total(|p| p.balance)),
+            String::new(),
+            String::new(),
+            String::new(),
+            format!("{:.2}", total(|p| p.market_value)),
+            format!("{:.2}", total(|p| p.unrealized_pnl)),
+        ]);
+        table
+    })
+}
+
+/// A configuration file, as listed by `clobster config files`.
+#[derive(Debug, Serialize)]
+struct ConfigFile {
+    path: PathBuf,
+    exists: bool,
+}
+
+/// A configured profile, as listed by `clobster profiles`.
+#[derive(Debug, Serialize)]
+struct ProfileSummary {
+    name: String,
+    active: bool,
+    base_url: String,
+    credentials_path: Option<PathBuf>,
+    strategies: Vec<String>,
+}
+
+/// One profile's holdings in the aggregated positions view.
+#[derive(Debug, Serialize)]
+struct ProfilePortfolio {
+    profile: String,
+    balance: Decimal,
+    market_value: Decimal,
+    unrealized_pnl: Decimal,
+    positions: Vec<Position>,
+    error: Option<String>,
+}
+
+fn validate_order(request: &OrderRequest) -> Result<()> {
+    if request.size <= Decimal::ZERO {
+        return Err(Error::invalid_input("Order size must be positive"));
+    }
+    if let Some(price) = request.price
+        && (price <= Decimal::ZERO || price >= Decimal::ONE)
+    {
+        return Err(Error::invalid_input(
+            "Limit price must be between 0 and 1 exclusive",
+        ));
+    }
+    Ok(())
+}
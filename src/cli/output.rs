@@ -0,0 +1,141 @@
+//! Output formatting for headless commands.
+
+use serde::Serialize;
+use std::io::Write;
+
+/// How command results are written to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Aligned, human-readable columns.
+    #[default]
+    Table,
+    /// Pretty-printed JSON of the underlying records.
+    Json,
+    /// Comma-separated values with a header row.
+    Csv,
+}
+
+/// Tabular view of command results, used for table and CSV output.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    headers: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Create an empty table with the given column headers.
+    pub fn new(headers: &[&'static str]) -> Self {
+        Self {
+            headers: headers.to_vec(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row; missing cells are rendered empty.
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    /// Render as aligned columns separated by two spaces.
+    pub fn to_text(&self) -> String {
+        let mut widths: Vec<usize> = self.headers.iter().map(|h| h.chars().count()).collect();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate().take(widths.len()) {
+                widths[i] = widths[i].max(cell.chars().count());
+            }
+        }
+
+        let mut out = String::new();
+        let header: Vec<String> = self.headers.iter().map(|h| h.to_string()).collect();
+        for row in std::iter::once(&header).chain(&self.rows) {
+            let line: Vec<String> = widths
+                .iter()
+                .enumerate()
+                .map(|(i, width)| {
+                    let cell = row.get(i).map(String::as_str).unwrap_or("");
+                    format!("{:<width$}", cell, width = width)
+                })
+                .collect();
+            out.push_str(line.join("  ").trim_end());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Render as CSV, quoting cells that need it.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        let header: Vec<String> = self.headers.iter().map(|h| h.to_string()).collect();
+        for row in std::iter::once(&header).chain(&self.rows) {
+            let line: Vec<String> = (0..self.headers.len())
+                .map(|i| csv_escape(row.get(i).map(String::as_str).unwrap_or("")))
+                .collect();
+            out.push_str(&line.join(","));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Print records in the requested format.
+///
+/// `table` is only built for table and CSV output so JSON keeps every field.
+pub fn print<T: Serialize>(
+    format: OutputFormat,
+    records: &T,
+    table: impl FnOnce() -> Table,
+) -> crate::Result<()> {
+    let content = match format {
+        OutputFormat::Json => format!("{}\n", serde_json::to_string_pretty(records)?),
+        OutputFormat::Csv => table().to_csv(),
+        OutputFormat::Table => table().to_text(),
+    };
+    write(&content)
+}
+
+/// Write preformatted text to stdout.
+pub fn write(content: &str) -> crate::Result<()> {
+    match std::io::stdout().lock().write_all(content.as_bytes()) {
+        // The reader went away (e.g. piped into `head`); that is not an error
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+fn csv_escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Table {
+        let mut table = Table::new(&["ID", "Question"]);
+        table.push(vec!["1".into(), "Will it rain, tomorrow?".into()]);
+        table.push(vec!["22".into(), "Say \"yes\"".into()]);
+        table
+    }
+
+    #[test]
+    fn test_text_columns_are_aligned() {
+        let text = sample().to_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "ID  Question");
+        assert_eq!(lines[1], "1   Will it rain, tomorrow?");
+        assert_eq!(lines[2], "22  Say \"yes\"");
+    }
+
+    #[test]
+    fn test_csv_quotes_special_cells() {
+        let csv = sample().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "ID,Question");
+        assert_eq!(lines[1], "1,\"Will it rain, tomorrow?\"");
+        assert_eq!(lines[2], "22,\"Say \"\"yes\"\"\"");
+    }
+}
@@ -4,4 +4,7 @@
 //! multiple widgets for common UI patterns.
 
 // Re-export public UI types for convenience
-pub use crate::ui::{HelpPanel, Layout, MarketList, OrderList, PositionList, StatusBar, TabBar};
+pub use crate::ui::{
+    AlertList, HelpPanel, Layout, LogViewer, MarketList, NotificationLog, OrderList,
+    PortfolioDashboard, PositionList, StatusBar, TabBar, TradeHistory, WatchlistView,
+};
@@ -0,0 +1,371 @@
+//! Layered configuration loading.
+
+use super::Config;
+use ::config::{Environment, File, FileFormat, Map, Source, Value, ValueKind};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Prefix of environment variables that override settings.
+pub const ENV_PREFIX: &str = "CLOBSTER";
+
+/// Separator between nested keys in environment variable names.
+const ENV_SEPARATOR: &str = "__";
+
+/// Name of the project-local configuration file.
+const PROJECT_FILE: &str = "clobster.toml";
+
+const DEFAULT_ORIGIN: &str = "default";
+const ENV_ORIGIN: &str = "environment";
+const COMMAND_LINE_ORIGIN: &str = "command line";
+
+/// Builds a [`Config`] from layered sources.
+///
+/// Later layers override earlier ones, key by key:
+///
+/// 1. built-in defaults
+/// 2. the system file, `/etc/clobster/config.toml`
+/// 3. the user file, [`Config::default_path`] or the `--config` path
+/// 4. `clobster.toml` in the working directory
+/// 5. `CLOBSTER_*` environment variables, with `__` between nested keys
+/// 6. command-line flags
+///
+/// Missing files are skipped, so defaults plus environment variables are a
+/// complete configuration on their own.
+#[derive(Debug, Clone)]
+pub struct ConfigLoader {
+    system_path: Option<PathBuf>,
+    user_path: Option<PathBuf>,
+    project_path: Option<PathBuf>,
+    /// Replaces the process environment when set.
+    env: Option<Map<String, String>>,
+    overrides: BTreeMap<String, String>,
+}
+
+impl Default for ConfigLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigLoader {
+    /// Create a loader reading the standard file locations and environment.
+    pub fn new() -> Self {
+        Self {
+            system_path: super::system_config_path(),
+            user_path: Some(Config::default_path()),
+            project_path: Some(PathBuf::from(PROJECT_FILE)),
+            env: None,
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    /// Read the user layer from `path` instead of the default location.
+    pub fn path(mut self, path: Option<PathBuf>) -> Self {
+        if let Some(path) = path {
+            self.user_path = Some(path);
+        }
+        self
+    }
+
+    /// Override a setting by its dotted key, e.g. `ui.tick_rate_ms`.
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.insert(key.into(), value.into());
+        self
+    }
+
+    /// Get the user configuration file, where settings changed from the
+    /// TUI are written.
+    pub fn user_path(&self) -> Option<&PathBuf> {
+        self.user_path.as_ref()
+    }
+
+    /// Configuration files in the order they are applied, whether or not
+    /// they exist.
+    pub fn files(&self) -> Vec<PathBuf> {
+        [&self.system_path, &self.user_path, &self.project_path]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Merge all layers without validating the result.
+    pub fn build(&self) -> crate::Result<EffectiveConfig> {
+        let defaults = ::config::Config::try_from(&Config::default()).map_err(config_error)?;
+
+        let mut builder =
+            ::config::Config::builder().add_source(Layer::new(DEFAULT_ORIGIN, defaults));
+        for path in self.files() {
+            if path.exists() {
+                let file = File::from(path.clone()).format(FileFormat::Toml);
+                let origin = std::path::absolute(&path).unwrap_or(path);
+                builder = builder.add_source(Layer::new(&origin.display().to_string(), file));
+            }
+        }
+
+        let env = Environment::with_prefix(ENV_PREFIX)
+            .prefix_separator("_")
+            .separator(ENV_SEPARATOR)
+            .try_parsing(true)
+            .source(self.env.clone());
+        // Without a prefix or separator the keys are used as dotted paths
+        let overrides = Environment::default()
+            .try_parsing(true)
+            .source(Some(self.overrides.clone().into_iter().collect()));
+        builder = builder
+            .add_source(Layer::new(ENV_ORIGIN, env))
+            .add_source(Layer::new(COMMAND_LINE_ORIGIN, overrides));
+
+        let merged = builder.build().map_err(config_error)?;
+        let mut values = Vec::new();
+        collect_values(&mut values, "", merged.collect().map_err(config_error)?);
+        values.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let config = merged.try_deserialize().map_err(config_error)?;
+        Ok(EffectiveConfig { config, values })
+    }
+
+    /// Merge all layers and validate the result.
+    ///
+    /// Fails with every invalid setting and the layer it came from.
+    pub fn load(&self) -> crate::Result<Config> {
+        let effective = self.build()?;
+
+        let issues = effective.config.validate();
+        if !issues.is_empty() {
+            let issues: Vec<String> = issues
+                .iter()
+                .map(|issue| match effective.source_of(&issue.path) {
+                    Some(source) => format!("{} (from {})", issue, source),
+                    None => issue.to_string(),
+                })
+                .collect();
+            return Err(crate::Error::config(format!(
+                "{} invalid setting(s): {}",
+                issues.len(),
+                issues.join("; ")
+            )));
+        }
+
+        Ok(effective.config)
+    }
+}
+
+/// A merged configuration and where each of its values came from.
+#[derive(Debug, Clone)]
+pub struct EffectiveConfig {
+    /// The merged configuration.
+    pub config: Config,
+    /// Every set value, sorted by key.
+    pub values: Vec<ConfigValue>,
+}
+
+impl EffectiveConfig {
+    /// Get the source of the value at `key`.
+    pub fn source_of(&self, key: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|v| v.key == key)
+            .map(|v| v.source.as_str())
+    }
+}
+
+/// A single effective setting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigValue {
+    /// Dotted key, e.g. `api.base_url`.
+    pub key: String,
+    /// Value as text.
+    pub value: String,
+    /// Layer the value came from: `default`, a file path, the environment
+    /// variable or `command line`.
+    pub source: String,
+}
+
+/// A source whose values are all attributed to one origin.
+///
+/// The `config` crate records file paths relative to the working directory
+/// and leaves defaults and overrides without an origin, so every layer is
+/// relabelled for `config show --effective`.
+#[derive(Debug)]
+struct Layer {
+    origin: String,
+    source: Box<dyn Source + Send + Sync>,
+}
+
+impl Layer {
+    fn new(origin: &str, source: impl Source + Send + Sync + 'static) -> Self {
+        Self {
+            origin: origin.to_string(),
+            source: Box::new(source),
+        }
+    }
+}
+
+impl Source for Layer {
+    fn clone_into_box(&self) -> Box<dyn Source + Send + Sync> {
+        Box::new(Self {
+            origin: self.origin.clone(),
+            source: self.source.clone_into_box(),
+        })
+    }
+
+    fn collect(&self) -> Result<Map<String, Value>, ::config::ConfigError> {
+        Ok(self
+            .source
+            .collect()?
+            .into_iter()
+            .map(|(key, value)| (key, relabel(value, &self.origin)))
+            .collect())
+    }
+}
+
+fn relabel(value: Value, origin: &String) -> Value {
+    let kind = match value.kind {
+        ValueKind::Table(table) => ValueKind::Table(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, relabel(value, origin)))
+                .collect(),
+        ),
+        ValueKind::Array(array) => {
+            ValueKind::Array(array.into_iter().map(|v| relabel(v, origin)).collect())
+        }
+        kind => kind,
+    };
+    Value::new(Some(origin), kind)
+}
+
+/// Flatten merged values into dotted keys, skipping unset options.
+fn collect_values(values: &mut Vec<ConfigValue>, prefix: &str, table: Map<String, Value>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        let source = match value.origin() {
+            Some(ENV_ORIGIN) => env_var(&key),
+            Some(origin) => origin.to_string(),
+            None => DEFAULT_ORIGIN.to_string(),
+        };
+
+        match value.kind {
+            ValueKind::Nil => {}
+            ValueKind::Table(table) => collect_values(values, &key, table),
+            kind => values.push(ConfigValue {
+                key,
+                value: kind.to_string(),
+                source,
+            }),
+        }
+    }
+}
+
+/// Name of the environment variable that sets `key`.
+fn env_var(key: &str) -> String {
+    format!(
+        "{}_{}",
+        ENV_PREFIX,
+        key.replace('.', ENV_SEPARATOR).to_uppercase()
+    )
+}
+
+fn config_error(e: ::config::ConfigError) -> crate::Error {
+    crate::Error::config(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn loader(user_file: Option<&str>, env: &[(&str, &str)]) -> (ConfigLoader, Option<PathBuf>) {
+        let user_path = user_file.map(|content| {
+            let path =
+                std::env::temp_dir().join(format!("clobster-config-{}.toml", uuid::Uuid::new_v4()));
+            std::fs::write(&path, content).unwrap();
+            path
+        });
+        let loader = ConfigLoader {
+            system_path: None,
+            user_path: user_path.clone(),
+            project_path: None,
+            env: Some(
+                env.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            ),
+            overrides: BTreeMap::new(),
+        };
+        (loader, user_path)
+    }
+
+    #[test]
+    fn test_later_layers_override_earlier_ones() {
+        let (loader, path) = loader(
+            Some("[ui]\ntick_rate_ms = 500\nmarkets_per_page = 20\n"),
+            &[
+                ("CLOBSTER_UI__TICK_RATE_MS", "750"),
+                ("CLOBSTER_API__BASE_URL", "http://localhost:8080"),
+                ("OTHER_UI__TICK_RATE_MS", "1"),
+            ],
+        );
+        let path = path.unwrap();
+        let effective = loader.set("ui.tick_rate_ms", "900").build().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(effective.config.ui.tick_rate_ms, 900);
+        assert_eq!(effective.config.ui.markets_per_page, 20);
+        assert_eq!(effective.config.api.base_url, "http://localhost:8080");
+
+        assert_eq!(effective.source_of("ui.tick_rate_ms"), Some("command line"));
+        assert_eq!(
+            effective.source_of("ui.markets_per_page"),
+            Some(path.display().to_string().as_str())
+        );
+        assert_eq!(
+            effective.source_of("api.base_url"),
+            Some("CLOBSTER_API__BASE_URL")
+        );
+        assert_eq!(effective.source_of("api.timeout_secs"), Some("default"));
+    }
+
+    #[test]
+    fn test_environment_only() {
+        let (loader, _) = loader(
+            None,
+            &[
+                ("CLOBSTER_PROFILE", "bot"),
+                ("CLOBSTER_PROFILES__BOT__BASE_URL", "http://localhost:8080"),
+                ("CLOBSTER_STRATEGIES__RISK__MAX_DAILY_LOSS", "50.5"),
+                (
+                    "CLOBSTER_STRATEGIES__INSTANCES__MOMENTUM__AUTO_EXECUTE",
+                    "true",
+                ),
+                ("CLOBSTER_SHUTDOWN__CANCEL_OPEN_ORDERS", "true"),
+            ],
+        );
+        let config = loader.load().unwrap();
+
+        assert_eq!(config.profile.as_deref(), Some("bot"));
+        assert_eq!(
+            config.with_active_profile().unwrap().api.base_url,
+            "http://localhost:8080"
+        );
+        assert_eq!(
+            config.strategies.risk.max_daily_loss,
+            Some(rust_decimal_macros::dec!(50.5))
+        );
+        assert!(config.strategies.instances["momentum"].auto_execute);
+        assert!(config.shutdown.cancel_open_orders);
+    }
+
+    #[test]
+    fn test_invalid_setting_names_its_source() {
+        let (loader, _) = loader(None, &[("CLOBSTER_UI__TICK_RATE_MS", "0")]);
+        let error = loader.load().unwrap_err().to_string();
+        assert!(error.contains("ui.tick_rate_ms: must be greater than 0"));
+        assert!(error.contains("from CLOBSTER_UI__TICK_RATE_MS"));
+    }
+}
@@ -1,8 +1,19 @@
 //! Configuration management for Clobster.
 
+mod loader;
 mod settings;
+mod validation;
+mod watcher;
+mod writer;
 
-pub use settings::{ApiConfig, Config, KeyBindings, ThemeConfig, UiConfig};
+pub use loader::{ConfigLoader, ConfigValue, ENV_PREFIX, EffectiveConfig};
+pub use settings::{
+    ApiConfig, Config, DaemonConfig, KeyBindings, LogRotation, LoggingConfig, PortfolioConfig,
+    ProfileConfig, ShutdownConfig, StrategySettings, ThemeConfig, UiConfig,
+};
+pub use validation::ConfigIssue;
+pub use watcher::ConfigWatcher;
+pub use writer::save_strategy_parameters;
 
 use crate::error::{Error, Result};
 use directories::ProjectDirs;
@@ -15,6 +26,11 @@ pub fn config_dir() -> Result<PathBuf> {
         .ok_or_else(|| Error::config("Could not determine config directory"))
 }
 
+/// Get the system-wide configuration file path, if the platform has one.
+pub fn system_config_path() -> Option<PathBuf> {
+    cfg!(unix).then(|| PathBuf::from("/etc/clobster/config.toml"))
+}
+
 /// Get the data directory path.
 pub fn data_dir() -> Result<PathBuf> {
     ProjectDirs::from("com", "clobster", "clobster")
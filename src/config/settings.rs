@@ -1,12 +1,16 @@
 //! Configuration settings for Clobster.
 
+use crate::strategy::{RiskConfig, ScriptLimits, StrategyConfig};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /// Main configuration struct.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Config {
+    /// Active profile; `None` uses the top-level settings as they are.
+    pub profile: Option<String>,
     /// API configuration.
     pub api: ApiConfig,
     /// UI configuration.
@@ -15,6 +19,18 @@ pub struct Config {
     pub keybindings: KeyBindings,
     /// Theme configuration.
     pub theme: ThemeConfig,
+    /// Portfolio accounting configuration.
+    pub portfolio: PortfolioConfig,
+    /// Strategy engine configuration.
+    pub strategies: StrategySettings,
+    /// Headless daemon configuration.
+    pub daemon: DaemonConfig,
+    /// Logging configuration.
+    pub logging: LoggingConfig,
+    /// Shutdown behaviour.
+    pub shutdown: ShutdownConfig,
+    /// Named profiles, e.g. one per wallet or environment.
+    pub profiles: BTreeMap<String, ProfileConfig>,
 }
 
 impl Config {
@@ -23,29 +39,82 @@ impl Config {
         Self::load(None)
     }
 
-    /// Load configuration from file.
+    /// Get the default configuration file path.
+    pub fn default_path() -> PathBuf {
+        super::config_dir()
+            .map(|p| p.join("config.toml"))
+            .unwrap_or_else(|_| PathBuf::from("config.toml"))
+    }
+
+    /// Load and validate the layered configuration, reading the user layer
+    /// from `path` instead of the default location when given.
+    ///
+    /// See [`ConfigLoader`](super::ConfigLoader) for the layers and their order.
     pub fn load(path: Option<PathBuf>) -> crate::Result<Self> {
-        let config_path = path.unwrap_or_else(|| {
-            super::config_dir()
-                .map(|p| p.join("config.toml"))
-                .unwrap_or_else(|_| PathBuf::from("config.toml"))
-        });
+        super::ConfigLoader::new().path(path).load()
+    }
+
+    /// Directory strategy state is checkpointed to, or `None` when
+    /// persistence is disabled.
+    pub fn strategy_state_dir(&self) -> Option<PathBuf> {
+        if !self.strategies.persist_state {
+            return None;
+        }
+        match &self.strategies.state_dir {
+            Some(dir) => Some(dir.clone()),
+            None => crate::strategy::StateStore::default_directory(self.profile.as_deref())
+                .inspect_err(|e| tracing::warn!("Strategy state will not be saved: {}", e))
+                .ok(),
+        }
+    }
+
+    /// Fill history file of the active profile.
+    pub fn trades_path(&self) -> crate::Result<PathBuf> {
+        crate::state::TradeState::default_path(self.profile.as_deref())
+    }
 
-        if config_path.exists() {
-            let content = std::fs::read_to_string(&config_path)?;
-            toml::from_str(&content).map_err(|e| crate::Error::config(e.to_string()))
-        } else {
-            Ok(Self::default())
+    /// Names of the configured profiles, sorted.
+    pub fn profile_names(&self) -> Vec<String> {
+        self.profiles.keys().cloned().collect()
+    }
+
+    /// Apply the active profile's overrides on top of the top-level settings.
+    ///
+    /// Returns the configuration unchanged when no profile is active.
+    pub fn with_active_profile(&self) -> crate::Result<Self> {
+        let Some(name) = &self.profile else {
+            return Ok(self.clone());
+        };
+        let profile = self.profiles.get(name).ok_or_else(|| {
+            crate::Error::config(format!(
+                "Unknown profile '{}' (available: {})",
+                name,
+                self.profile_names().join(", ")
+            ))
+        })?;
+
+        let mut config = self.clone();
+        if let Some(base_url) = &profile.base_url {
+            config.api.base_url = base_url.clone();
+        }
+        if let Some(ws_url) = &profile.ws_url {
+            config.api.ws_url = ws_url.clone();
+        }
+        if let Some(credentials_path) = &profile.credentials_path {
+            config.api.credentials_path = Some(credentials_path.clone());
         }
+        if let Some(risk) = &profile.risk {
+            config.strategies.risk = risk.clone();
+        }
+        if let Some(strategies) = &profile.strategies {
+            config.strategies.instances = strategies.clone();
+        }
+        Ok(config)
     }
 
     /// Save configuration to file.
     pub fn save(&self, path: Option<PathBuf>) -> crate::Result<()> {
-        let config_path = path.unwrap_or_else(|| {
-            super::config_dir()
-                .map(|p| p.join("config.toml"))
-                .unwrap_or_else(|_| PathBuf::from("config.toml"))
-        });
+        let config_path = path.unwrap_or_else(Self::default_path);
 
         if let Some(parent) = config_path.parent() {
             std::fs::create_dir_all(parent)?;
@@ -66,6 +135,8 @@ pub struct ApiConfig {
     pub base_url: String,
     /// WebSocket URL.
     pub ws_url: String,
+    /// Gamma market data API base URL, used for market volumes.
+    pub gamma_url: String,
     /// Request timeout in seconds.
     pub timeout_secs: u64,
     /// Maximum retries for failed requests.
@@ -74,6 +145,10 @@ pub struct ApiConfig {
     pub rate_limit: u32,
     /// Path to credentials file (optional).
     pub credentials_path: Option<PathBuf>,
+    /// Number of tokens priced per bulk request.
+    pub price_batch_size: usize,
+    /// Maximum number of tokens whose 24h price history is fetched per refresh.
+    pub price_history_limit: usize,
 }
 
 impl Default for ApiConfig {
@@ -81,10 +156,160 @@ impl Default for ApiConfig {
         Self {
             base_url: "https://clob.polymarket.com".to_string(),
             ws_url: "wss://ws-subscriptions-clob.polymarket.com/ws".to_string(),
+            gamma_url: "https://gamma-api.polymarket.com".to_string(),
             timeout_secs: 30,
             max_retries: 3,
             rate_limit: 10,
             credentials_path: None,
+            price_batch_size: 50,
+            price_history_limit: 50,
+        }
+    }
+}
+
+/// Overrides for one account or environment.
+///
+/// Unset fields fall back to the top-level settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileConfig {
+    /// Polymarket API base URL.
+    pub base_url: Option<String>,
+    /// WebSocket URL.
+    pub ws_url: Option<String>,
+    /// Path to the credentials file for this account.
+    pub credentials_path: Option<PathBuf>,
+    /// Risk limits, replacing `[strategies.risk]`.
+    pub risk: Option<RiskConfig>,
+    /// Strategies to register, replacing `[strategies.instances]`.
+    pub strategies: Option<BTreeMap<String, StrategyConfig>>,
+}
+
+/// Portfolio accounting configuration.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PortfolioConfig {
+    /// Lot accounting method for realized PnL (`fifo` or `average_cost`).
+    pub cost_basis: crate::state::CostBasisMethod,
+}
+
+/// Strategy engine configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StrategySettings {
+    /// How often running strategies are evaluated, in milliseconds.
+    pub evaluation_interval_ms: u64,
+    /// Risk limits applied to every signal.
+    pub risk: RiskConfig,
+    /// Strategies to register, keyed by name: built-in strategies, or
+    /// scripts for instances with a `script` path.
+    pub instances: BTreeMap<String, StrategyConfig>,
+    /// Resource limits of script strategies.
+    pub script_limits: ScriptLimits,
+    /// Save strategy state (positions, inventory) across restarts.
+    pub persist_state: bool,
+    /// Directory for strategy state snapshots (defaults to `strategies` in
+    /// the data directory, with a subdirectory per profile).
+    pub state_dir: Option<PathBuf>,
+    /// How often strategy state is checkpointed while running, in seconds.
+    pub checkpoint_interval_secs: u64,
+}
+
+impl Default for StrategySettings {
+    fn default() -> Self {
+        Self {
+            evaluation_interval_ms: 5000,
+            risk: RiskConfig::default(),
+            instances: BTreeMap::new(),
+            script_limits: ScriptLimits::default(),
+            persist_state: true,
+            state_dir: None,
+            checkpoint_interval_secs: 60,
+        }
+    }
+}
+
+/// Headless daemon configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DaemonConfig {
+    /// Interval between full data refreshes, in seconds. The TUI uses it too
+    /// while it runs strategies.
+    pub refresh_interval_secs: u64,
+    /// Path of the status file (defaults to `daemon.toml` in the data directory).
+    pub state_path: Option<PathBuf>,
+    /// Append market data to this JSON Lines file on every refresh, for
+    /// `clobster optimize`.
+    pub record_path: Option<PathBuf>,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval_secs: 30,
+            state_path: None,
+            record_path: None,
+        }
+    }
+}
+
+/// Shutdown behaviour, for both the TUI and the daemon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShutdownConfig {
+    /// Cancel all of our open orders before exiting.
+    pub cancel_open_orders: bool,
+    /// Maximum time spent cancelling orders, in seconds.
+    pub timeout_secs: u64,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            cancel_open_orders: false,
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// How often log files are rotated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// Start a new file every hour.
+    Hourly,
+    /// Start a new file every day.
+    #[default]
+    Daily,
+    /// Always write to a single file.
+    Never,
+}
+
+/// Logging configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Log level (`error`, `warn`, `info`, `debug`, `trace`) or a full filter
+    /// directive such as `clobster=debug,reqwest=warn`. `RUST_LOG` takes precedence.
+    pub level: String,
+    /// Directory for log files (defaults to `logs` in the data directory).
+    pub directory: Option<PathBuf>,
+    /// How often log files are rotated.
+    pub rotation: LogRotation,
+    /// Maximum number of rotated files to keep.
+    pub max_files: usize,
+    /// Number of recent log lines kept for the in-app log viewer.
+    pub buffer_size: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            directory: None,
+            rotation: LogRotation::default(),
+            max_files: 7,
+            buffer_size: 1000,
         }
     }
 }
@@ -156,12 +381,30 @@ pub struct KeyBindings {
     pub positions: String,
     /// Switch to portfolio view.
     pub portfolio: String,
+    /// Switch to watchlist view.
+    pub watchlist: String,
+    /// Switch to alerts view.
+    pub alerts: String,
+    /// Switch to trade history view.
+    pub trades: String,
+    /// Switch to notification history view.
+    pub notifications: String,
+    /// Switch to the application log view.
+    pub logs: String,
+    /// Switch to the strategy parameter editor.
+    pub strategies: String,
+    /// Dismiss the oldest notification toast.
+    pub dismiss: String,
     /// Open search.
     pub search: String,
     /// Place order.
     pub place_order: String,
     /// Cancel order.
     pub cancel_order: String,
+    /// Add/remove the selected market from the active watchlist.
+    pub toggle_watchlist: String,
+    /// Switch to the next configuration profile.
+    pub switch_profile: String,
 }
 
 impl Default for KeyBindings {
@@ -180,9 +423,18 @@ impl Default for KeyBindings {
             orders: "2".to_string(),
             positions: "3".to_string(),
             portfolio: "4".to_string(),
+            watchlist: "5".to_string(),
+            alerts: "6".to_string(),
+            notifications: "7".to_string(),
+            trades: "8".to_string(),
+            logs: "9".to_string(),
+            strategies: "0".to_string(),
+            dismiss: "Ctrl+d".to_string(),
             search: "/".to_string(),
             place_order: "o".to_string(),
             cancel_order: "x".to_string(),
+            toggle_watchlist: "w".to_string(),
+            switch_profile: "p".to_string(),
         }
     }
 }
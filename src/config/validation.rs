@@ -0,0 +1,595 @@
+//! Configuration validation.
+
+use super::Config;
+use crate::events::InputEvent;
+use crate::strategy::{ExternalConfig, RiskConfig, SizingConfig, SizingMethod, StrategyConfig};
+use ratatui::style::Color;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// A single invalid setting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Dotted path of the setting, e.g. `keybindings.quit`.
+    pub path: String,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+impl ConfigIssue {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Keys handled directly by a view, with the view and what they do.
+const VIEW_KEYS: &[(&str, &str, &str)] = &[
+    ("n", "watchlist", "name a new watchlist"),
+    ("D", "watchlist", "delete the watchlist"),
+    ("n", "alerts", "write a new rule"),
+    (" ", "alerts", "toggle a rule"),
+    ("e", "alerts", "toggle a rule"),
+    ("f", "logs", "filter by target"),
+    ("s", "strategies", "show signal history"),
+    ("e", "strategies", "edit a parameter"),
+    ("d", "strategies", "reset a parameter"),
+];
+
+/// Bindings handled in every view, before the view's own keys.
+const GLOBAL_BINDINGS: &[&str] = &[
+    "quit",
+    "help",
+    "refresh",
+    "markets",
+    "orders",
+    "positions",
+    "portfolio",
+    "watchlist",
+    "alerts",
+    "trades",
+    "notifications",
+    "logs",
+    "strategies",
+    "dismiss",
+    "switch_profile",
+    "up",
+    "down",
+    "search",
+];
+
+/// Bindings a view handles before its own keys.
+fn view_bindings(view: &str) -> &'static [&'static str] {
+    match view {
+        "watchlist" => &["toggle_watchlist", "cancel_order", "left", "right"],
+        "alerts" => &["cancel_order"],
+        "logs" => &["cancel_order", "left", "right"],
+        "strategies" => &["left", "right", "toggle_watchlist", "select"],
+        _ => &[],
+    }
+}
+
+impl Config {
+    /// Check every setting and report all problems found.
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        self.validate_api(&mut issues);
+        self.validate_ui(&mut issues);
+        self.validate_keybindings(&mut issues);
+        self.validate_theme(&mut issues);
+        self.validate_strategies(&mut issues);
+        self.validate_runtime(&mut issues);
+        self.validate_profiles(&mut issues);
+        issues
+    }
+
+    /// Top-level sections whose values differ between `self` and `other`.
+    pub fn changed_sections(&self, other: &Config) -> Vec<String> {
+        let (Ok(serde_json::Value::Object(old)), Ok(serde_json::Value::Object(new))) =
+            (serde_json::to_value(self), serde_json::to_value(other))
+        else {
+            return Vec::new();
+        };
+
+        new.iter()
+            .filter(|(key, value)| old.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    fn validate_api(&self, issues: &mut Vec<ConfigIssue>) {
+        let api = &self.api;
+        validate_urls(issues, "api", Some(&api.base_url), Some(&api.ws_url));
+        if !api.gamma_url.starts_with("http://") && !api.gamma_url.starts_with("https://") {
+            issues.push(ConfigIssue::new("api.gamma_url", "must be an http(s) URL"));
+        }
+        positive(issues, "api.timeout_secs", api.timeout_secs);
+        positive(issues, "api.rate_limit", api.rate_limit as u64);
+        positive(issues, "api.price_batch_size", api.price_batch_size as u64);
+    }
+
+    fn validate_ui(&self, issues: &mut Vec<ConfigIssue>) {
+        positive(issues, "ui.tick_rate_ms", self.ui.tick_rate_ms);
+        positive(
+            issues,
+            "ui.markets_per_page",
+            self.ui.markets_per_page as u64,
+        );
+        positive(issues, "ui.orders_per_page", self.ui.orders_per_page as u64);
+    }
+
+    fn validate_keybindings(&self, issues: &mut Vec<ConfigIssue>) {
+        let Ok(serde_json::Value::Object(bindings)) = serde_json::to_value(&self.keybindings)
+        else {
+            return;
+        };
+
+        // Normalized binding -> first action using it
+        let mut seen: BTreeMap<String, String> = BTreeMap::new();
+        let mut valid = Vec::new();
+        for (action, binding) in &bindings {
+            let Some(binding) = binding.as_str() else {
+                continue;
+            };
+            let path = format!("keybindings.{}", action);
+
+            if let Err(e) = InputEvent::validate_binding(binding) {
+                issues.push(ConfigIssue::new(path, e));
+                continue;
+            }
+
+            let normalized = InputEvent::normalize_binding(binding);
+            match seen.get(&normalized) {
+                Some(other) => issues.push(ConfigIssue::new(
+                    path,
+                    format!("'{}' is already bound to keybindings.{}", binding, other),
+                )),
+                None => {
+                    seen.insert(normalized.clone(), action.clone());
+                    valid.push((action.as_str(), binding, normalized));
+                }
+            }
+        }
+
+        // Bindings are checked before a view's own keys, so they would hide them
+        for (action, binding, normalized) in valid {
+            for (key, view, description) in VIEW_KEYS {
+                if normalized == *key
+                    && (GLOBAL_BINDINGS.contains(&action) || view_bindings(view).contains(&action))
+                {
+                    issues.push(ConfigIssue::new(
+                        format!("keybindings.{}", action),
+                        format!(
+                            "'{}' is the {} view's key to {}",
+                            binding, view, description
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn validate_theme(&self, issues: &mut Vec<ConfigIssue>) {
+        let Ok(serde_json::Value::Object(colors)) = serde_json::to_value(&self.theme) else {
+            return;
+        };
+
+        for (name, value) in &colors {
+            if let Some(color) = value.as_str()
+                && Color::from_str(color).is_err()
+            {
+                issues.push(ConfigIssue::new(
+                    format!("theme.{}", name),
+                    format!("'{}' is not a colour name or #rrggbb value", color),
+                ));
+            }
+        }
+    }
+
+    fn validate_strategies(&self, issues: &mut Vec<ConfigIssue>) {
+        let strategies = &self.strategies;
+        positive(
+            issues,
+            "strategies.evaluation_interval_ms",
+            strategies.evaluation_interval_ms,
+        );
+        positive(
+            issues,
+            "strategies.checkpoint_interval_secs",
+            strategies.checkpoint_interval_secs,
+        );
+        positive(
+            issues,
+            "strategies.script_limits.timeout_ms",
+            strategies.script_limits.timeout_ms,
+        );
+
+        validate_risk(issues, "strategies.risk", &strategies.risk);
+        validate_instances(issues, "strategies.instances", &strategies.instances);
+    }
+
+    fn validate_profiles(&self, issues: &mut Vec<ConfigIssue>) {
+        if let Some(name) = &self.profile
+            && !self.profiles.contains_key(name)
+        {
+            issues.push(ConfigIssue::new(
+                "profile",
+                format!("unknown profile '{}'", name),
+            ));
+        }
+
+        for (name, profile) in &self.profiles {
+            let prefix = format!("profiles.{}", name);
+            validate_urls(
+                issues,
+                &prefix,
+                profile.base_url.as_ref(),
+                profile.ws_url.as_ref(),
+            );
+            if let Some(risk) = &profile.risk {
+                validate_risk(issues, &format!("{}.risk", prefix), risk);
+            }
+            if let Some(instances) = &profile.strategies {
+                validate_instances(issues, &format!("{}.strategies", prefix), instances);
+            }
+        }
+    }
+
+    fn validate_runtime(&self, issues: &mut Vec<ConfigIssue>) {
+        positive(
+            issues,
+            "daemon.refresh_interval_secs",
+            self.daemon.refresh_interval_secs,
+        );
+
+        let level = &self.logging.level;
+        let directive = crate::logging::filter_directive(level);
+        if tracing_subscriber::EnvFilter::try_new(&directive).is_err() {
+            issues.push(ConfigIssue::new(
+                "logging.level",
+                format!("'{}' is not a log level or filter directive", level),
+            ));
+        }
+        positive(issues, "logging.max_files", self.logging.max_files as u64);
+        positive(
+            issues,
+            "logging.buffer_size",
+            self.logging.buffer_size as u64,
+        );
+        positive(issues, "shutdown.timeout_secs", self.shutdown.timeout_secs);
+    }
+}
+
+fn validate_urls(
+    issues: &mut Vec<ConfigIssue>,
+    prefix: &str,
+    base_url: Option<&String>,
+    ws_url: Option<&String>,
+) {
+    if let Some(url) = base_url
+        && !url.starts_with("http://")
+        && !url.starts_with("https://")
+    {
+        issues.push(ConfigIssue::new(
+            format!("{}.base_url", prefix),
+            "must be an http(s) URL",
+        ));
+    }
+    if let Some(url) = ws_url
+        && !url.starts_with("ws://")
+        && !url.starts_with("wss://")
+    {
+        issues.push(ConfigIssue::new(
+            format!("{}.ws_url", prefix),
+            "must be a ws(s) URL",
+        ));
+    }
+}
+
+fn validate_risk(issues: &mut Vec<ConfigIssue>, prefix: &str, risk: &RiskConfig) {
+    for (field, value) in [
+        ("max_position_size", risk.max_position_size),
+        ("min_position_size", risk.min_position_size),
+        ("max_total_exposure", risk.max_total_exposure),
+        ("max_exposure_per_market", risk.max_exposure_per_market),
+        ("max_daily_volume", risk.max_daily_volume),
+        ("max_daily_loss", risk.max_daily_loss),
+        ("min_balance", risk.min_balance),
+    ] {
+        if value.is_some_and(|v| v < Decimal::ZERO) {
+            issues.push(ConfigIssue::new(
+                format!("{}.{}", prefix, field),
+                "must not be negative",
+            ));
+        }
+    }
+    if let (Some(min), Some(max)) = (risk.min_position_size, risk.max_position_size)
+        && min > max
+    {
+        issues.push(ConfigIssue::new(
+            format!("{}.min_position_size", prefix),
+            "must not exceed max_position_size",
+        ));
+    }
+}
+
+fn validate_instances(
+    issues: &mut Vec<ConfigIssue>,
+    prefix: &str,
+    instances: &BTreeMap<String, StrategyConfig>,
+) {
+    for (name, instance) in instances {
+        let path = format!("{}.{}", prefix, name);
+        if let Some(sizing) = &instance.sizing {
+            validate_sizing(issues, &format!("{}.sizing", path), sizing);
+        }
+        match (&instance.script, &instance.external) {
+            (Some(_), Some(_)) => issues.push(ConfigIssue::new(
+                path,
+                "set either `script` or `external`, not both",
+            )),
+            (Some(script), None) if !script.is_file() => issues.push(ConfigIssue::new(
+                format!("{}.script", path),
+                format!("script not found: {}", script.display()),
+            )),
+            (None, Some(external)) => validate_external(issues, &path, external),
+            (None, None) if crate::strategy::builtin(name).is_none() => {
+                issues.push(ConfigIssue::new(
+                    path,
+                    format!(
+                        "unknown strategy (expected one of: {}, or set `script` or `external`)",
+                        crate::strategy::BUILTIN_STRATEGIES.join(", ")
+                    ),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn validate_sizing(issues: &mut Vec<ConfigIssue>, prefix: &str, sizing: &SizingConfig) {
+    let mut amounts = Vec::new();
+    let mut fractions = vec![
+        ("max_balance_fraction", sizing.max_balance_fraction),
+        ("max_book_fraction", sizing.max_book_fraction),
+    ];
+    match &sizing.method {
+        SizingMethod::FixedNotional { notional } | SizingMethod::StrengthScaled { notional } => {
+            amounts.push(("notional", *notional));
+        }
+        SizingMethod::Kelly { fraction } => fractions.push(("fraction", Some(*fraction))),
+        SizingMethod::VolatilityTarget {
+            notional,
+            target_volatility,
+            periods,
+        } => {
+            amounts.push(("notional", *notional));
+            amounts.push(("target_volatility", *target_volatility));
+            if *periods < 2 {
+                issues.push(ConfigIssue::new(
+                    format!("{}.periods", prefix),
+                    "must be at least 2",
+                ));
+            }
+        }
+    }
+    for (field, value) in amounts {
+        if value <= Decimal::ZERO {
+            issues.push(ConfigIssue::new(
+                format!("{}.{}", prefix, field),
+                "must be greater than 0",
+            ));
+        }
+    }
+    for (field, value) in fractions {
+        if value.is_some_and(|v| v <= Decimal::ZERO || v > Decimal::ONE) {
+            issues.push(ConfigIssue::new(
+                format!("{}.{}", prefix, field),
+                "must be greater than 0 and at most 1",
+            ));
+        }
+    }
+}
+
+fn validate_external(issues: &mut Vec<ConfigIssue>, prefix: &str, external: &ExternalConfig) {
+    let path = format!("{}.external", prefix);
+    match (external.command.is_empty(), &external.socket) {
+        (true, None) => issues.push(ConfigIssue::new(&path, "set `command` or `socket`")),
+        (false, Some(_)) => issues.push(ConfigIssue::new(
+            &path,
+            "set either `command` or `socket`, not both",
+        )),
+        _ => {}
+    }
+    positive(issues, &format!("{}.timeout_ms", path), external.timeout_ms);
+}
+
+fn positive(issues: &mut Vec<ConfigIssue>, path: &str, value: u64) {
+    if value == 0 {
+        issues.push(ConfigIssue::new(path, "must be greater than 0"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProfileConfig;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert_eq!(Config::default().validate(), Vec::new());
+    }
+
+    #[test]
+    fn test_reports_every_issue_with_path() {
+        let mut config = Config::default();
+        config.ui.tick_rate_ms = 0;
+        config.keybindings.quit = "Ctrl+".to_string();
+        config.keybindings.refresh = "1".to_string();
+        config.theme.primary = "not-a-colour".to_string();
+        config.logging.level = "loud".to_string();
+
+        let paths: Vec<String> = config.validate().into_iter().map(|i| i.path).collect();
+        assert!(paths.contains(&"ui.tick_rate_ms".to_string()));
+        assert!(paths.contains(&"keybindings.quit".to_string()));
+        assert!(paths.contains(&"theme.primary".to_string()));
+        assert!(paths.contains(&"logging.level".to_string()));
+        // "1" is already used to switch to the markets view
+        assert!(
+            paths.contains(&"keybindings.refresh".to_string())
+                || paths.contains(&"keybindings.markets".to_string())
+        );
+    }
+
+    #[test]
+    fn test_keybindings_are_case_sensitive() {
+        let mut config = Config::default();
+        config.keybindings.left = "H".to_string();
+        config.keybindings.right = "L".to_string();
+        config.keybindings.up = "h".to_string();
+        assert_eq!(config.validate(), Vec::new());
+
+        config.keybindings.down = "Shift+h".to_string();
+        let issues: Vec<String> = config
+            .validate()
+            .into_iter()
+            .map(|i| i.to_string())
+            .collect();
+        // Bindings are checked in alphabetical order of their action
+        assert_eq!(
+            issues,
+            vec!["keybindings.left: 'H' is already bound to keybindings.down"]
+        );
+    }
+
+    #[test]
+    fn test_keybindings_conflicting_with_view_keys() {
+        let mut config = Config::default();
+        // Global bindings hide the watchlist view's keys
+        config.keybindings.refresh = "n".to_string();
+        config.keybindings.quit = "D".to_string();
+        // Handled first in the strategies view
+        config.keybindings.select = "e".to_string();
+        // Only used in views without their own "d"
+        config.keybindings.place_order = "d".to_string();
+
+        let issues: Vec<String> = config
+            .validate()
+            .into_iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            issues,
+            vec![
+                "keybindings.quit: 'D' is the watchlist view's key to delete the watchlist",
+                "keybindings.refresh: 'n' is the watchlist view's key to name a new watchlist",
+                "keybindings.refresh: 'n' is the alerts view's key to write a new rule",
+                "keybindings.select: 'e' is the strategies view's key to edit a parameter",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_profile_overrides() {
+        let mut config = Config::default();
+        config.profiles.insert(
+            "staging".to_string(),
+            ProfileConfig {
+                base_url: Some("http://localhost:8080".to_string()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            config.with_active_profile().unwrap().api.base_url,
+            config.api.base_url
+        );
+
+        config.profile = Some("staging".to_string());
+        let staging = config.with_active_profile().unwrap();
+        assert_eq!(staging.api.base_url, "http://localhost:8080");
+        assert_eq!(staging.api.ws_url, config.api.ws_url);
+
+        config.profile = Some("prod".to_string());
+        assert!(config.with_active_profile().is_err());
+        assert_eq!(config.validate()[0].path, "profile");
+    }
+
+    #[test]
+    fn test_changed_sections() {
+        let old = Config::default();
+        let mut new = old.clone();
+        assert!(old.changed_sections(&new).is_empty());
+
+        new.ui.tick_rate_ms = 500;
+        new.keybindings.quit = "Ctrl+q".to_string();
+        assert_eq!(old.changed_sections(&new), vec!["keybindings", "ui"]);
+    }
+
+    #[test]
+    fn test_external_instances() {
+        let mut config = Config::default();
+        let instance = |external: ExternalConfig| StrategyConfig {
+            external: Some(external),
+            ..Default::default()
+        };
+        config.strategies.instances.insert(
+            "model".to_string(),
+            instance(ExternalConfig {
+                command: vec!["python3".to_string(), "model.py".to_string()],
+                ..Default::default()
+            }),
+        );
+        assert_eq!(config.validate(), Vec::new());
+
+        config.strategies.instances.insert(
+            "remote".to_string(),
+            instance(ExternalConfig {
+                timeout_ms: 0,
+                ..Default::default()
+            }),
+        );
+        let paths: Vec<String> = config.validate().into_iter().map(|i| i.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "strategies.instances.remote.external",
+                "strategies.instances.remote.external.timeout_ms"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sizing() {
+        let mut config = Config::default();
+        config.strategies.instances.insert(
+            "momentum".to_string(),
+            StrategyConfig {
+                sizing: Some(SizingConfig {
+                    method: SizingMethod::Kelly {
+                        fraction: Decimal::new(15, 1),
+                    },
+                    max_balance_fraction: Some(Decimal::new(1, 1)),
+                    max_book_fraction: Some(Decimal::ZERO),
+                }),
+                ..Default::default()
+            },
+        );
+        let paths: Vec<String> = config.validate().into_iter().map(|i| i.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "strategies.instances.momentum.sizing.max_book_fraction",
+                "strategies.instances.momentum.sizing.fraction"
+            ]
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! Configuration file watcher.
+
+use super::{Config, ConfigLoader};
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the config files' modification times are checked.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Watches the configuration files and reloads them when one changes.
+///
+/// Polls each layer's modification time rather than relying on filesystem
+/// events, so editors that save by renaming a temporary file are handled
+/// the same as in-place writes.
+#[derive(Debug)]
+pub struct ConfigWatcher {
+    loader: ConfigLoader,
+    files: Vec<PathBuf>,
+    modified: Vec<Option<SystemTime>>,
+    last_checked: Instant,
+}
+
+impl ConfigWatcher {
+    /// Start watching the files read by `loader`.
+    pub fn new(loader: ConfigLoader) -> Self {
+        let files = loader.files();
+        let modified = files.iter().map(modified).collect();
+        Self {
+            loader,
+            files,
+            modified,
+            last_checked: Instant::now(),
+        }
+    }
+
+    /// Get the watched files, in layer order.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Reload the config if a file was created or changed since the last call.
+    ///
+    /// Returns `None` when nothing changed or the files were checked less
+    /// than a second ago, and the load result otherwise. A deleted file alone
+    /// does not trigger a reload, so the running configuration stays in effect.
+    pub fn poll(&mut self) -> Option<crate::Result<Config>> {
+        if self.last_checked.elapsed() < POLL_INTERVAL {
+            return None;
+        }
+        self.last_checked = Instant::now();
+
+        let modified: Vec<Option<SystemTime>> = self.files.iter().map(modified).collect();
+        let changed = modified
+            .iter()
+            .zip(&self.modified)
+            .any(|(new, old)| new.is_some() && new != old);
+        self.modified = modified;
+        if !changed {
+            return None;
+        }
+
+        Some(self.loader.load())
+    }
+}
+
+fn modified(path: &PathBuf) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
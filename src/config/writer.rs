@@ -0,0 +1,125 @@
+//! Targeted edits to a configuration file.
+//!
+//! Unlike [`Config::save`](super::Config::save), which rewrites the whole
+//! file, these edits change single values and keep the user's comments,
+//! ordering and formatting.
+
+use std::collections::HashMap;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, Table, Value};
+
+/// Write strategy parameters into the configuration file at `path`.
+///
+/// With a profile, the values go to `[profiles.<profile>.strategies.<strategy>.parameters]`,
+/// otherwise to `[strategies.instances.<strategy>.parameters]`. Other
+/// parameters already in the table are kept. The file is created if it does
+/// not exist.
+pub fn save_strategy_parameters(
+    path: &Path,
+    profile: Option<&str>,
+    strategy: &str,
+    parameters: &HashMap<String, serde_json::Value>,
+) -> crate::Result<()> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let mut document: DocumentMut = content
+        .parse()
+        .map_err(|e: toml_edit::TomlError| crate::Error::config(e.to_string()))?;
+
+    let keys = match profile {
+        Some(profile) => vec!["profiles", profile, "strategies", strategy, "parameters"],
+        None => vec!["strategies", "instances", strategy, "parameters"],
+    };
+    let mut table = document.as_table_mut();
+    for key in keys {
+        table = child_table(table, key)?;
+    }
+
+    let mut names: Vec<&String> = parameters.keys().collect();
+    names.sort();
+    for name in names {
+        table[name.as_str()] = Item::Value(toml_value(name, &parameters[name])?);
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, document.to_string())?;
+    Ok(())
+}
+
+/// Get the table at `key`, creating it if needed.
+fn child_table<'a>(table: &'a mut Table, key: &str) -> crate::Result<&'a mut Table> {
+    let item = table.entry(key).or_insert_with(|| {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        Item::Table(table)
+    });
+    if let Some(inline) = item.as_inline_table().cloned() {
+        *item = Item::Table(inline.into_table());
+    }
+    item.as_table_mut()
+        .ok_or_else(|| crate::Error::config(format!("'{}' in the config file is not a table", key)))
+}
+
+fn toml_value(name: &str, value: &serde_json::Value) -> crate::Result<Value> {
+    Ok(match value {
+        serde_json::Value::Bool(v) => Value::from(*v),
+        serde_json::Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(v), _) => Value::from(v),
+            (None, Some(v)) => Value::from(v),
+            (None, None) => Value::from(n.to_string()),
+        },
+        serde_json::Value::String(v) => Value::from(v.as_str()),
+        _ => {
+            return Err(crate::Error::config(format!(
+                "Parameter '{}' cannot be written to the config file",
+                name
+            )));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_strategy_parameters_keeps_comments() {
+        let path =
+            std::env::temp_dir().join(format!("clobster-config-{}.toml", uuid::Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            "# Trading setup\n[strategies.instances.momentum]\nenabled = true # on\n\n[strategies.instances.momentum.parameters]\nmin_volume = 500\n",
+        )
+        .unwrap();
+
+        let parameters: HashMap<String, serde_json::Value> = [
+            ("short_ema_periods".to_string(), serde_json::json!(12)),
+            ("momentum_threshold".to_string(), serde_json::json!(0.08)),
+        ]
+        .into();
+        save_strategy_parameters(&path, None, "momentum", &parameters).unwrap();
+        save_strategy_parameters(&path, Some("bot"), "spread", &parameters).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(content.starts_with("# Trading setup\n"));
+        assert!(content.contains("enabled = true # on"));
+
+        let config: crate::config::Config = toml::from_str(&content).unwrap();
+        let momentum = &config.strategies.instances["momentum"].parameters;
+        assert_eq!(momentum["min_volume"], serde_json::json!(500));
+        assert_eq!(momentum["short_ema_periods"], serde_json::json!(12));
+        assert_eq!(momentum["momentum_threshold"], serde_json::json!(0.08));
+
+        let spread = &config.profiles["bot"].strategies.as_ref().unwrap()["spread"];
+        assert_eq!(
+            spread.parameters["short_ema_periods"],
+            serde_json::json!(12)
+        );
+    }
+}
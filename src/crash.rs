@@ -0,0 +1,232 @@
+//! Panic handling and crash reports.
+//!
+//! Release builds abort on panic, so `Drop` impls never run and the terminal
+//! would be left in raw mode on the alternate screen. The hook installed by
+//! [`install`] restores the terminal itself and writes a crash report with a
+//! [`StoreSummary`] of the last known application state.
+
+use crate::state::StoreSummary;
+use chrono::{DateTime, Utc};
+use crossterm::{
+    cursor::Show,
+    event::DisableMouseCapture,
+    execute,
+    terminal::{LeaveAlternateScreen, disable_raw_mode},
+};
+use serde::Serialize;
+use std::any::Any;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Last store summary recorded by the running application.
+static LAST_STATE: Mutex<Option<StoreSummary>> = Mutex::new(None);
+
+/// Contents of a crash report file.
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReport {
+    /// Application version.
+    pub version: String,
+    /// When the panic occurred.
+    pub timestamp: DateTime<Utc>,
+    /// Name of the panicking thread.
+    pub thread: String,
+    /// Panic message.
+    pub message: String,
+    /// Source location of the panic.
+    pub location: Option<String>,
+    /// Last known application state.
+    pub state: Option<StoreSummary>,
+    /// Captured backtrace.
+    pub backtrace: String,
+}
+
+impl CrashReport {
+    /// Build a report for a panic, with the last recorded state and a
+    /// backtrace of the current thread.
+    pub fn new(message: String, location: Option<String>) -> Self {
+        // The panic may have happened while the state was being recorded
+        let state = LAST_STATE.try_lock().ok().and_then(|s| s.clone());
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp: Utc::now(),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("<unnamed>")
+                .to_string(),
+            message,
+            location,
+            state,
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+        }
+    }
+
+    /// Get the directory crash reports are written to.
+    pub fn directory() -> crate::Result<PathBuf> {
+        crate::config::data_dir().map(|p| p.join("crashes"))
+    }
+
+    /// Write the report to a timestamped file and return its path.
+    pub fn save(&self, directory: PathBuf) -> crate::Result<PathBuf> {
+        std::fs::create_dir_all(&directory)?;
+
+        let path = directory.join(format!(
+            "crash-{}.toml",
+            self.timestamp.format("%Y%m%d-%H%M%S")
+        ));
+        let content =
+            toml::to_string_pretty(self).map_err(|e| crate::Error::config(e.to_string()))?;
+        std::fs::write(&path, content)?;
+        Ok(path)
+    }
+}
+
+/// Install the panic hook.
+///
+/// With `restore_terminal` set the hook leaves raw mode and the alternate
+/// screen before anything is printed, so the panic message stays readable.
+///
+/// The report is written before anything is logged: the panic may have
+/// happened inside logging, with a lock held that logging would wait on.
+pub fn install(restore_terminal: bool) {
+    let previous = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        if restore_terminal {
+            self::restore_terminal();
+        }
+
+        let report = report(info);
+        let saved = CrashReport::directory().and_then(|dir| report.save(dir));
+
+        previous(info);
+
+        match saved {
+            Ok(path) => eprintln!("Crash report written to {}", path.display()),
+            Err(e) => eprintln!("Failed to write crash report: {}", e),
+        }
+
+        // The in-app log buffer skips events while panicking, see `logging`
+        tracing::error!(message = %report.message, location = ?report.location, "Panic");
+    }));
+}
+
+/// Record the latest store summary for inclusion in crash reports.
+pub fn record_state(summary: StoreSummary) {
+    if let Ok(mut state) = LAST_STATE.lock() {
+        *state = Some(summary);
+    }
+}
+
+/// Leave raw mode and the alternate screen, ignoring errors.
+pub fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        std::io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        Show
+    );
+}
+
+fn report(info: &PanicHookInfo<'_>) -> CrashReport {
+    CrashReport::new(
+        panic_message(info.payload()),
+        info.location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+    )
+}
+
+/// Get the message of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn summary() -> StoreSummary {
+        StoreSummary {
+            view: "Markets".to_string(),
+            input_mode: "Normal".to_string(),
+            connected: true,
+            markets: 3,
+            selected_market: Some("0xabc".to_string()),
+            open_orders: 1,
+            positions: 0,
+            total_value: Decimal::ZERO,
+            alerts: 0,
+            fills: 0,
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let payload = std::panic::catch_unwind(|| panic!("static message")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "static message");
+
+        let payload = std::panic::catch_unwind(|| panic!("formatted {}", 42)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "formatted 42");
+
+        let payload = std::panic::catch_unwind(|| std::panic::panic_any(7u8)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "unknown panic payload");
+    }
+
+    #[test]
+    fn test_new_report_uses_recorded_state() {
+        record_state(summary());
+        let report = CrashReport::new("boom".to_string(), None);
+        assert_eq!(report.state, Some(summary()));
+        assert_eq!(report.version, env!("CARGO_PKG_VERSION"));
+        assert!(!report.backtrace.is_empty());
+
+        // A panic while recording the state must not block the report
+        let _held = LAST_STATE.lock().unwrap();
+        let report = CrashReport::new("boom".to_string(), None);
+        assert_eq!(report.state, None);
+    }
+
+    #[test]
+    fn test_report_includes_state() {
+        let report = CrashReport {
+            version: "0.1.0".to_string(),
+            timestamp: Utc::now(),
+            thread: "main".to_string(),
+            message: "index out of bounds".to_string(),
+            location: Some("src/app.rs:1:1".to_string()),
+            state: Some(StoreSummary {
+                view: "Markets".to_string(),
+                input_mode: "Normal".to_string(),
+                connected: true,
+                markets: 3,
+                selected_market: Some("0xabc".to_string()),
+                open_orders: 1,
+                positions: 0,
+                total_value: Decimal::ZERO,
+                alerts: 0,
+                fills: 0,
+                last_error: None,
+            }),
+            backtrace: String::new(),
+        };
+
+        let dir = std::env::temp_dir().join(format!("clobster-crash-{}", uuid::Uuid::new_v4()));
+        let path = report.save(dir.clone()).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(content.contains("message = \"index out of bounds\""));
+        assert!(content.contains("[state]"));
+        assert!(content.contains("selected_market = \"0xabc\""));
+    }
+}
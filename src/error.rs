@@ -49,6 +49,10 @@ pub enum Error {
     #[error("Rate limited: retry after {0} seconds")]
     RateLimited(u64),
 
+    /// Operations this build cannot perform yet
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+
     /// Generic application error
     #[error("{0}")]
     Application(String),
@@ -93,6 +97,11 @@ impl Error {
         Self::Network(msg.into())
     }
 
+    /// Create a new unsupported operation error.
+    pub fn unsupported(msg: impl Into<String>) -> Self {
+        Self::Unsupported(msg.into())
+    }
+
     /// Create a new application error.
     pub fn application(msg: impl Into<String>) -> Self {
         Self::Application(msg.into())
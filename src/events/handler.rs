@@ -27,6 +27,13 @@ struct StoreSnapshot {
     current_view: View,
     selected_order_id: Option<String>,
     selected_order_can_cancel: bool,
+    selected_market_id: Option<String>,
+    selected_watchlist_market_id: Option<String>,
+    active_watchlist_name: Option<String>,
+    selected_alert_id: Option<String>,
+    active_strategy: Option<String>,
+    selected_parameter: Option<String>,
+    input_buffer: String,
 }
 
 impl EventHandler {
@@ -39,6 +46,11 @@ impl EventHandler {
         }
     }
 
+    /// Replace the key bindings.
+    pub fn set_keybindings(&mut self, keybindings: KeyBindings) {
+        self.keybindings = keybindings;
+    }
+
     /// Update the store snapshot for state-aware event handling.
     pub fn update_store_snapshot(&mut self, store: &Store) {
         let selected_order = store.orders.selected_order();
@@ -47,6 +59,16 @@ impl EventHandler {
             current_view: store.app.current_view,
             selected_order_id: selected_order.map(|o| o.id.clone()),
             selected_order_can_cancel: selected_order.map(|o| o.can_cancel()).unwrap_or(false),
+            selected_market_id: store.markets.selected_market().map(|m| m.id.clone()),
+            selected_watchlist_market_id: store.watchlists.selected_market_id().map(str::to_string),
+            active_watchlist_name: store.watchlists.active().map(|w| w.name.clone()),
+            selected_alert_id: store.alerts.selected_rule().map(|r| r.id.clone()),
+            active_strategy: store.strategies.active().map(|s| s.name.clone()),
+            selected_parameter: store
+                .strategies
+                .selected_field()
+                .map(|f| f.def.name.clone()),
+            input_buffer: store.app.input_buffer.clone(),
         });
     }
 
@@ -86,7 +108,7 @@ impl EventHandler {
         // Handle based on current input mode
         match snapshot.input_mode {
             InputMode::Normal => self.handle_normal_mode(key, snapshot),
-            InputMode::Insert => self.handle_insert_mode(key),
+            InputMode::Insert => self.handle_insert_mode(key, snapshot),
             InputMode::Command => self.handle_command_mode(key),
             InputMode::Search => self.handle_search_mode(key),
         }
@@ -130,6 +152,32 @@ impl EventHandler {
         if input.matches(&self.keybindings.portfolio) {
             return Some(Action::SetView(View::Portfolio));
         }
+        if input.matches(&self.keybindings.watchlist) {
+            return Some(Action::SetView(View::Watchlist));
+        }
+        if input.matches(&self.keybindings.alerts) {
+            return Some(Action::SetView(View::Alerts));
+        }
+        if input.matches(&self.keybindings.trades) {
+            return Some(Action::SetView(View::Trades));
+        }
+        if input.matches(&self.keybindings.notifications) {
+            return Some(Action::SetView(View::Notifications));
+        }
+        if input.matches(&self.keybindings.logs) {
+            return Some(Action::SetView(View::Logs));
+        }
+        if input.matches(&self.keybindings.strategies) {
+            return Some(Action::SetView(View::Strategies));
+        }
+
+        if input.matches(&self.keybindings.dismiss) {
+            return Some(Action::DismissNotification);
+        }
+
+        if input.matches(&self.keybindings.switch_profile) {
+            return Some(Action::CycleProfile(1));
+        }
 
         // Navigation
         if input.matches(&self.keybindings.up) || key.code == KeyCode::Up {
@@ -160,14 +208,20 @@ impl EventHandler {
 
         // View-specific actions
         match snapshot.current_view {
-            View::Markets | View::MarketDetail => self.handle_markets_view(key),
+            View::Markets | View::MarketDetail => self.handle_markets_view(key, snapshot),
             View::Orders | View::OrderEntry => self.handle_orders_view(key, snapshot),
             View::Positions | View::Portfolio => self.handle_positions_view(key),
-            View::Settings => None,
+            View::Watchlist => self.handle_watchlist_view(key, snapshot),
+            View::Alerts => self.handle_alerts_view(key, snapshot),
+            View::Notifications => self.handle_notifications_view(key),
+            View::Logs => self.handle_logs_view(key),
+            View::Strategies => self.handle_strategies_view(key, snapshot),
+            View::Signals => self.handle_signals_view(key),
+            View::Trades | View::Settings => None,
         }
     }
 
-    fn handle_markets_view(&self, key: KeyEvent) -> Option<Action> {
+    fn handle_markets_view(&self, key: KeyEvent, snapshot: &StoreSnapshot) -> Option<Action> {
         let input = super::InputEvent::from(key);
 
         if input.matches(&self.keybindings.select) {
@@ -178,9 +232,44 @@ impl EventHandler {
             return Some(Action::SetView(View::OrderEntry));
         }
 
+        if input.matches(&self.keybindings.toggle_watchlist)
+            && let Some(market_id) = &snapshot.selected_market_id
+        {
+            return Some(Action::ToggleWatchlistMarket(market_id.clone()));
+        }
+
         None
     }
 
+    fn handle_watchlist_view(&self, key: KeyEvent, snapshot: &StoreSnapshot) -> Option<Action> {
+        let input = super::InputEvent::from(key);
+
+        if (input.matches(&self.keybindings.toggle_watchlist)
+            || input.matches(&self.keybindings.cancel_order))
+            && let Some(market_id) = &snapshot.selected_watchlist_market_id
+        {
+            return Some(Action::ToggleWatchlistMarket(market_id.clone()));
+        }
+
+        if input.matches(&self.keybindings.left) || key.code == KeyCode::Left {
+            return Some(Action::CycleWatchlist(-1));
+        }
+        if input.matches(&self.keybindings.right) || key.code == KeyCode::Right {
+            return Some(Action::CycleWatchlist(1));
+        }
+
+        match key.code {
+            // Name a new watchlist
+            KeyCode::Char('n') => Some(Action::SetInputMode(InputMode::Insert)),
+            // Delete the active watchlist
+            KeyCode::Char('D') => snapshot
+                .active_watchlist_name
+                .clone()
+                .map(Action::DeleteWatchlist),
+            _ => None,
+        }
+    }
+
     fn handle_orders_view(&self, key: KeyEvent, snapshot: &StoreSnapshot) -> Option<Action> {
         let input = super::InputEvent::from(key);
 
@@ -204,14 +293,128 @@ impl EventHandler {
         None
     }
 
-    fn handle_insert_mode(&self, key: KeyEvent) -> Option<Action> {
+    fn handle_alerts_view(&self, key: KeyEvent, snapshot: &StoreSnapshot) -> Option<Action> {
+        let input = super::InputEvent::from(key);
+
+        if input.matches(&self.keybindings.cancel_order)
+            && let Some(id) = &snapshot.selected_alert_id
+        {
+            return Some(Action::DeleteAlert(id.clone()));
+        }
+
+        match key.code {
+            // Write a new rule
+            KeyCode::Char('n') => Some(Action::SetInputMode(InputMode::Insert)),
+            // Enable/disable the selected rule
+            KeyCode::Char(' ') | KeyCode::Char('e') => {
+                snapshot.selected_alert_id.clone().map(Action::ToggleAlert)
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_notifications_view(&self, key: KeyEvent) -> Option<Action> {
+        let input = super::InputEvent::from(key);
+
+        if input.matches(&self.keybindings.cancel_order) {
+            return Some(Action::ClearNotificationHistory);
+        }
+
+        None
+    }
+
+    fn handle_logs_view(&self, key: KeyEvent) -> Option<Action> {
+        let input = super::InputEvent::from(key);
+
+        if input.matches(&self.keybindings.cancel_order) {
+            return Some(Action::ClearLogs);
+        }
+
+        if input.matches(&self.keybindings.left) || key.code == KeyCode::Left {
+            return Some(Action::CycleLogLevel(-1));
+        }
+        if input.matches(&self.keybindings.right) || key.code == KeyCode::Right {
+            return Some(Action::CycleLogLevel(1));
+        }
+
+        match key.code {
+            // Filter by target
+            KeyCode::Char('f') => Some(Action::SetInputMode(InputMode::Insert)),
+            _ => None,
+        }
+    }
+
+    fn handle_strategies_view(&self, key: KeyEvent, snapshot: &StoreSnapshot) -> Option<Action> {
+        let input = super::InputEvent::from(key);
+
+        if input.matches(&self.keybindings.left) || key.code == KeyCode::Left {
+            return Some(Action::CycleStrategy(-1));
+        }
+        if input.matches(&self.keybindings.right) || key.code == KeyCode::Right {
+            return Some(Action::CycleStrategy(1));
+        }
+
+        // Signal history
+        if key.code == KeyCode::Char('s') {
+            return Some(Action::SetView(View::Signals));
+        }
+
+        let strategy = snapshot.active_strategy.clone()?;
+        if input.matches(&self.keybindings.toggle_watchlist) {
+            return Some(Action::SaveStrategyParameters(strategy));
+        }
+
+        let parameter = snapshot.selected_parameter.clone()?;
+        if input.matches(&self.keybindings.select) {
+            return Some(Action::EditParameter);
+        }
+
+        match key.code {
+            KeyCode::Char('e') => Some(Action::EditParameter),
+            // Restore the default value
+            KeyCode::Char('d') => Some(Action::ResetStrategyParameter {
+                strategy,
+                parameter,
+            }),
+            _ => None,
+        }
+    }
+
+    fn handle_signals_view(&self, key: KeyEvent) -> Option<Action> {
+        let input = super::InputEvent::from(key);
+
+        if input.matches(&self.keybindings.back) {
+            return Some(Action::SetView(View::Strategies));
+        }
+
+        None
+    }
+
+    fn handle_insert_mode(&self, key: KeyEvent, snapshot: &StoreSnapshot) -> Option<Action> {
         match key.code {
             KeyCode::Esc => Some(Action::SetInputMode(InputMode::Normal)),
             KeyCode::Enter => {
-                // Submit the input
-                Some(Action::SetInputMode(InputMode::Normal))
+                // Submit the input; submitting also returns to normal mode
+                let submitted = match snapshot.current_view {
+                    View::Watchlist => Some(Action::CreateWatchlist(snapshot.input_buffer.clone())),
+                    View::Alerts => Some(Action::CreateAlert(snapshot.input_buffer.clone())),
+                    View::Logs => Some(Action::SetLogTargetFilter(snapshot.input_buffer.clone())),
+                    View::Strategies => snapshot
+                        .active_strategy
+                        .clone()
+                        .zip(snapshot.selected_parameter.clone())
+                        .map(|(strategy, parameter)| Action::SetStrategyParameter {
+                            strategy,
+                            parameter,
+                            value: snapshot.input_buffer.clone(),
+                        }),
+                    _ => None,
+                };
+                submitted.or(Some(Action::SetInputMode(InputMode::Normal)))
             }
-            _ => None, // Character input handled separately
+            KeyCode::Backspace => Some(Action::InputBackspace),
+            KeyCode::Char(c) => Some(Action::InputChar(c)),
+            _ => None,
         }
     }
 
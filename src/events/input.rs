@@ -115,59 +115,165 @@ impl InputEvent {
         self.modifiers.shift
     }
 
-    /// Check if this matches a key binding string (e.g., "Ctrl+q", "Enter").
-    pub fn matches(&self, binding: &str) -> bool {
-        let parts: Vec<&str> = binding.split('+').collect();
-        let mut expected_ctrl = false;
-        let mut expected_alt = false;
-        let mut expected_shift = false;
-        let mut expected_key = "";
-
-        for part in parts {
+    /// Check that a key binding string is well formed.
+    ///
+    /// Accepts the same syntax as [`matches`](Self::matches): optional
+    /// `Ctrl`, `Alt` and `Shift` modifiers joined with `+`, followed by one
+    /// key name, function key or single character.
+    pub fn validate_binding(binding: &str) -> Result<(), String> {
+        let mut key = None;
+        for part in binding.split('+') {
             match part.to_lowercase().as_str() {
-                "ctrl" => expected_ctrl = true,
-                "alt" => expected_alt = true,
-                "shift" => expected_shift = true,
-                _ => expected_key = part,
+                "ctrl" | "alt" | "shift" => {}
+                "" => return Err(format!("'{}' has an empty key", binding)),
+                _ if key.is_some() => {
+                    return Err(format!("'{}' has more than one key", binding));
+                }
+                _ => key = Some(part),
             }
         }
 
-        if self.modifiers.ctrl != expected_ctrl
-            || self.modifiers.alt != expected_alt
-            || self.modifiers.shift != expected_shift
-        {
-            return false;
+        let Some(key) = key else {
+            return Err(format!("'{}' has no key", binding));
+        };
+
+        match key.to_lowercase().as_str() {
+            "enter" | "esc" | "escape" | "backspace" | "delete" | "del" | "tab" | "up" | "down"
+            | "left" | "right" | "home" | "end" | "pageup" | "pagedown" => Ok(()),
+            s if s.starts_with('f')
+                && s[1..].parse::<u8>().is_ok_and(|n| (1..=24).contains(&n)) =>
+            {
+                Ok(())
+            }
+            s if s.chars().count() == 1 => Ok(()),
+            _ => Err(format!("'{}' is not a known key", key)),
         }
+    }
 
-        match expected_key.to_lowercase().as_str() {
-            "enter" => self.key == Key::Enter,
-            "esc" | "escape" => self.key == Key::Escape,
-            "backspace" => self.key == Key::Backspace,
-            "delete" | "del" => self.key == Key::Delete,
-            "tab" => self.key == Key::Tab,
-            "up" => self.key == Key::Up,
-            "down" => self.key == Key::Down,
-            "left" => self.key == Key::Left,
-            "right" => self.key == Key::Right,
-            "home" => self.key == Key::Home,
-            "end" => self.key == Key::End,
-            "pageup" => self.key == Key::PageUp,
-            "pagedown" => self.key == Key::PageDown,
-            s if s.starts_with('f') && s.len() <= 3 => {
-                if let Ok(n) = s[1..].parse::<u8>() {
-                    self.key == Key::F(n)
-                } else {
-                    false
-                }
+    /// Canonical form of a binding, for comparing bindings with each other
+    /// and with key presses.
+    ///
+    /// Modifiers and key names are case-insensitive, characters are not:
+    /// `Enter` is `enter`, but `H` and `h` are different keys. Shift on a
+    /// letter is folded into its case, so `Shift+g` is `G`.
+    pub fn normalize_binding(binding: &str) -> String {
+        let mut modifiers = Modifiers::default();
+        let mut key = "";
+        for part in binding.split('+') {
+            match part.to_lowercase().as_str() {
+                "ctrl" => modifiers.ctrl = true,
+                "alt" => modifiers.alt = true,
+                "shift" => modifiers.shift = true,
+                _ => key = part,
             }
-            s if s.len() == 1 => {
-                if let Some(c) = s.chars().next() {
-                    self.key == Key::Char(c) || self.key == Key::Char(c.to_ascii_uppercase())
-                } else {
-                    false
-                }
+        }
+
+        let mut chars = key.chars();
+        let key = match (chars.next(), chars.next()) {
+            (Some(c), None) => return normalized(Key::Char(c), modifiers),
+            _ => key.to_lowercase(),
+        };
+        let key = match key.as_str() {
+            "escape" => "esc".to_string(),
+            "del" => "delete".to_string(),
+            _ => key,
+        };
+        format_binding(modifiers, &key)
+    }
+
+    /// Check if this matches a key binding string (e.g., "Ctrl+q", "Enter").
+    pub fn matches(&self, binding: &str) -> bool {
+        normalized(self.key, self.modifiers) == Self::normalize_binding(binding)
+    }
+}
+
+/// Canonical binding string for a key press, see
+/// [`InputEvent::normalize_binding`].
+fn normalized(key: Key, mut modifiers: Modifiers) -> String {
+    let name = match key {
+        Key::Char(c) => {
+            let c = if modifiers.shift && c.is_lowercase() {
+                c.to_uppercase().next().unwrap_or(c)
+            } else {
+                c
+            };
+            if c.is_uppercase() {
+                modifiers.shift = false;
             }
-            _ => false,
+            c.to_string()
         }
+        Key::Enter => "enter".to_string(),
+        Key::Escape => "esc".to_string(),
+        Key::Backspace => "backspace".to_string(),
+        Key::Delete => "delete".to_string(),
+        Key::Tab => "tab".to_string(),
+        Key::BackTab => "backtab".to_string(),
+        Key::Up => "up".to_string(),
+        Key::Down => "down".to_string(),
+        Key::Left => "left".to_string(),
+        Key::Right => "right".to_string(),
+        Key::Home => "home".to_string(),
+        Key::End => "end".to_string(),
+        Key::PageUp => "pageup".to_string(),
+        Key::PageDown => "pagedown".to_string(),
+        Key::F(n) => format!("f{}", n),
+    };
+    format_binding(modifiers, &name)
+}
+
+fn format_binding(modifiers: Modifiers, key: &str) -> String {
+    let mut binding = String::new();
+    if modifiers.ctrl {
+        binding.push_str("ctrl+");
+    }
+    if modifiers.alt {
+        binding.push_str("alt+");
+    }
+    if modifiers.shift {
+        binding.push_str("shift+");
+    }
+    binding.push_str(key);
+    binding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(key: Key, shift: bool) -> InputEvent {
+        InputEvent::new(
+            key,
+            Modifiers {
+                shift,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_normalize_binding() {
+        assert_eq!(InputEvent::normalize_binding("Ctrl+d"), "ctrl+d");
+        assert_eq!(InputEvent::normalize_binding("Shift+g"), "G");
+        assert_eq!(InputEvent::normalize_binding("G"), "G");
+        assert_eq!(InputEvent::normalize_binding("Escape"), "esc");
+        assert_eq!(InputEvent::normalize_binding("alt+F5"), "alt+f5");
+        assert_ne!(
+            InputEvent::normalize_binding("H"),
+            InputEvent::normalize_binding("h")
+        );
+    }
+
+    #[test]
+    fn test_matches_is_case_sensitive_for_characters() {
+        // Terminals report upper-case letters with or without Shift
+        assert!(press(Key::Char('G'), true).matches("G"));
+        assert!(press(Key::Char('G'), false).matches("G"));
+        assert!(press(Key::Char('G'), true).matches("shift+g"));
+        assert!(!press(Key::Char('g'), false).matches("G"));
+        assert!(!press(Key::Char('G'), true).matches("g"));
+
+        assert!(press(Key::Enter, false).matches("Enter"));
+        assert!(press(Key::Escape, false).matches("esc"));
+        assert!(!press(Key::Tab, true).matches("tab"));
     }
 }
@@ -14,13 +14,21 @@
 //! - **State**: Centralized state management
 //! - **Events**: Input handling and event processing
 //! - **Config**: Configuration management
+//! - **Logging**: Rotating log files and the in-app log buffer
+//! - **Crash**: Panic hook, terminal restoration and crash reports
+//! - **Alerts**: User-defined price and spread alerts
+//! - **CLI**: Headless subcommands for scripting
 
+pub mod alerts;
 pub mod api;
 pub mod app;
+pub mod cli;
 pub mod components;
 pub mod config;
+pub mod crash;
 pub mod error;
 pub mod events;
+pub mod logging;
 pub mod state;
 pub mod strategy;
 pub mod ui;
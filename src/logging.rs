@@ -0,0 +1,310 @@
+//! Logging setup.
+//!
+//! The TUI owns the terminal, so log output never goes to stdout or stderr
+//! while it runs. Events are written to rotating files under the log
+//! directory and also captured in an in-memory [`LogBuffer`] that backs the
+//! in-app log viewer.
+
+use crate::config::{LogRotation, LoggingConfig};
+use crate::error::{Error, Result};
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Default number of entries kept by the global log buffer.
+const DEFAULT_BUFFER_SIZE: usize = 1000;
+
+static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+
+/// A captured log event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// When the event was recorded.
+    pub timestamp: DateTime<Utc>,
+    /// Event level.
+    pub level: Level,
+    /// Module path or explicit target of the event.
+    pub target: String,
+    /// Message followed by any structured fields as `key=value`.
+    pub message: String,
+}
+
+/// Bounded, shareable buffer of recent log entries.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    /// Create a buffer that keeps at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(VecDeque::with_capacity(capacity.min(4096)))),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Append an entry, dropping the oldest one when full.
+    pub fn push(&self, entry: LogEntry) {
+        let mut entries = self.lock();
+        entries.push_back(entry);
+        while entries.len() > self.capacity {
+            entries.pop_front();
+        }
+    }
+
+    /// Copy the current entries, oldest first.
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// Number of entries in the buffer.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Check if the buffer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Remove all entries.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<LogEntry>> {
+        // A panic while holding the lock leaves the deque intact, so keep using it
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUFFER_SIZE)
+    }
+}
+
+/// Get the process-wide log buffer fed by [`init`].
+pub fn buffer() -> LogBuffer {
+    BUFFER.get_or_init(LogBuffer::default).clone()
+}
+
+/// Tracing layer that records events into a [`LogBuffer`].
+///
+/// Events logged while the thread is panicking are skipped: the panic may
+/// have happened with the buffer locked, and nobody reads the buffer after a
+/// panic anyway.
+#[derive(Debug, Clone)]
+pub struct BufferLayer {
+    buffer: LogBuffer,
+}
+
+impl BufferLayer {
+    /// Create a layer writing into `buffer`.
+    pub fn new(buffer: LogBuffer) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if std::thread::panicking() {
+            return;
+        }
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+
+        let metadata = event.metadata();
+        self.buffer.push(LogEntry {
+            timestamp: Utc::now(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+/// Collects the `message` field and formats the others as `key=value`.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.message.is_empty() {
+            self.fields.trim_start().to_string()
+        } else {
+            self.message + self.fields.as_str()
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// Install file and buffer logging.
+///
+/// Files are named `{file_prefix}.YYYY-MM-DD.log` (or hourly) in the
+/// configured directory. The returned guard flushes the file writer and must
+/// be kept alive for the lifetime of the process.
+pub fn init(config: &LoggingConfig, file_prefix: &str) -> Result<WorkerGuard> {
+    let directory = match &config.directory {
+        Some(dir) => dir.clone(),
+        None => crate::config::log_dir()?,
+    };
+    std::fs::create_dir_all(&directory)?;
+
+    let rotation = match config.rotation {
+        LogRotation::Hourly => Rotation::HOURLY,
+        LogRotation::Daily => Rotation::DAILY,
+        LogRotation::Never => Rotation::NEVER,
+    };
+    let appender = RollingFileAppender::builder()
+        .rotation(rotation)
+        .filename_prefix(file_prefix)
+        .filename_suffix("log")
+        .max_log_files(config.max_files.max(1))
+        .build(&directory)
+        .map_err(|e| Error::config(format!("Failed to open log file: {}", e)))?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+
+    let buffer = BUFFER
+        .get_or_init(|| LogBuffer::new(config.buffer_size))
+        .clone();
+
+    tracing_subscriber::registry()
+        .with(env_filter(&config.level))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(writer),
+        )
+        .with(BufferLayer::new(buffer))
+        .try_init()
+        .map_err(|e| Error::application(e.to_string()))?;
+
+    Ok(guard)
+}
+
+/// Install logging to stderr, for headless commands that own stdout.
+pub fn init_stderr(level: &str) {
+    let _ = tracing_subscriber::registry()
+        .with(env_filter(level))
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_target(false)
+                .with_writer(std::io::stderr),
+        )
+        .try_init();
+}
+
+/// Turn a configured level into a filter directive.
+///
+/// A bare level such as `debug` applies to this crate only; anything with a
+/// `=` or `,` is used as a full directive.
+pub fn filter_directive(level: &str) -> String {
+    if level.contains(['=', ',']) {
+        level.to_string()
+    } else {
+        format!("clobster={}", level)
+    }
+}
+
+/// Build the filter from `RUST_LOG`, falling back to the configured level.
+fn env_filter(level: &str) -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(filter_directive(level)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_drops_oldest_when_full() {
+        let buffer = LogBuffer::new(2);
+        for i in 0..3 {
+            buffer.push(LogEntry {
+                timestamp: Utc::now(),
+                level: Level::INFO,
+                target: "clobster".to_string(),
+                message: i.to_string(),
+            });
+        }
+
+        let messages: Vec<String> = buffer.entries().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, vec!["1", "2"]);
+    }
+
+    #[test]
+    fn test_layer_captures_message_and_fields() {
+        let buffer = LogBuffer::new(10);
+        let subscriber = tracing_subscriber::registry().with(BufferLayer::new(buffer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(target: "clobster::api", token = "abc", "Request failed");
+        });
+
+        let entries = buffer.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].level, Level::WARN);
+        assert_eq!(entries[0].target, "clobster::api");
+        assert_eq!(entries[0].message, "Request failed token=abc");
+    }
+
+    #[test]
+    fn test_layer_skips_events_while_panicking() {
+        struct LogOnDrop;
+
+        impl Drop for LogOnDrop {
+            fn drop(&mut self) {
+                tracing::error!("Unwinding");
+            }
+        }
+
+        let buffer = LogBuffer::new(10);
+        let subscriber = tracing_subscriber::registry().with(BufferLayer::new(buffer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let result = std::panic::catch_unwind(|| {
+                // Logging during the unwind must not wait for the held lock
+                let _held = buffer.lock();
+                let _log = LogOnDrop;
+                panic!("boom");
+            });
+            assert!(result.is_err());
+
+            tracing::info!("After");
+        });
+
+        let messages: Vec<String> = buffer.entries().into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, vec!["After"]);
+    }
+}
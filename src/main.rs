@@ -3,26 +3,75 @@
 //! A production-grade terminal user interface for the Polymarket
 //! prediction market platform, built with ratatui and polymarket-rs.
 
-use clobster::{App, Config, Result};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use clap::Parser;
+use clobster::cli::{self, Cli, Command};
+use clobster::config::ConfigLoader;
+use clobster::{App, Config, Result, crash, logging};
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> Result<()> {
-    // Initialize logging
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "clobster=info".into()),
-        )
-        .with(tracing_subscriber::fmt::layer().with_target(false))
-        .init();
-
-    // Load configuration
-    let config = Config::load_or_default()?;
-
-    // Run the application
-    let mut app = App::new(config).await?;
-    app.run().await?;
-
-    Ok(())
+async fn main() -> Result<ExitCode> {
+    let cli = Cli::parse();
+
+    // Layered configuration, with command-line flags applied last
+    let loader = cli.config_loader();
+    if let Some(Command::Config(command)) = cli.command {
+        return Ok(cli::run_config(command, &loader, cli.output));
+    }
+
+    let config = loader.load().and_then(|config| {
+        // Fail early on an unknown profile
+        config.with_active_profile()?;
+        Ok(config)
+    });
+    let config = match config {
+        Ok(config) => config,
+        Err(e) if cli.command.is_some() => {
+            eprintln!("error: {}", e);
+            return Ok(ExitCode::from(cli::exit_code(&e)));
+        }
+        Err(e) => return Err(e),
+    };
+
+    match cli.command {
+        Some(Command::Run { headless: true }) => run_daemon(config, loader).await,
+        None | Some(Command::Run { headless: false }) => {
+            // The TUI owns the terminal, so logs only go to file and the log view
+            let _guard = logging::init(&config.logging, "clobster")?;
+            crash::install(true);
+
+            // Run the application
+            let mut app = App::new(config).await?;
+            app.watch_config(loader);
+            app.run().await?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(command) => {
+            // Headless commands keep stdout for their output and only log warnings
+            logging::init_stderr("warn");
+            Ok(cli::run(command, config, cli.output).await)
+        }
+    }
+}
+
+/// Run the strategy daemon, logging to rotated files.
+async fn run_daemon(config: Config, loader: ConfigLoader) -> Result<ExitCode> {
+    let log_dir = match &config.logging.directory {
+        Some(dir) => dir.clone(),
+        None => clobster::config::log_dir()?,
+    };
+    let _guard = logging::init(&config.logging, "daemon")?;
+    crash::install(false);
+
+    eprintln!("clobster daemon started, logging to {}", log_dir.display());
+
+    let mut app = App::headless(config).await?;
+    app.watch_config(loader);
+    if let Err(e) = app.run_headless().await {
+        tracing::error!(error = %e, "Daemon failed");
+        eprintln!("error: {}", e);
+        return Ok(ExitCode::from(cli::exit_code(&e)));
+    }
+
+    Ok(ExitCode::SUCCESS)
 }
@@ -0,0 +1,339 @@
+//! User-defined alert rules.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Direction in which a price must cross a level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossDirection {
+    Above,
+    Below,
+}
+
+/// Condition that triggers an alert.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AlertCondition {
+    /// Mid price of a token crosses a level.
+    MidCrosses {
+        token_id: String,
+        level: Decimal,
+        direction: CrossDirection,
+    },
+    /// Spread of a market's first outcome drops below a threshold.
+    SpreadBelow {
+        market_id: String,
+        threshold: Decimal,
+    },
+    /// Unrealized PnL of a position drops below a percentage.
+    PositionPnlBelow { token_id: String, percent: Decimal },
+    /// Market ends within the given number of seconds.
+    MarketEndsWithin { market_id: String, within_secs: u64 },
+}
+
+impl fmt::Display for AlertCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MidCrosses {
+                token_id,
+                level,
+                direction,
+            } => {
+                let op = match direction {
+                    CrossDirection::Above => ">",
+                    CrossDirection::Below => "<",
+                };
+                write!(f, "mid {} {} {}", token_id, op, level)
+            }
+            Self::SpreadBelow {
+                market_id,
+                threshold,
+            } => write!(f, "spread {} < {}", market_id, threshold),
+            Self::PositionPnlBelow { token_id, percent } => {
+                write!(f, "pnl {} < {}", token_id, percent)
+            }
+            Self::MarketEndsWithin {
+                market_id,
+                within_secs,
+            } => write!(f, "ends {} {}", market_id, format_duration(*within_secs)),
+        }
+    }
+}
+
+/// Parses the one-line rule syntax used by the alerts view:
+///
+/// - `mid <token_id> > 0.60` / `mid <token_id> < 0.40`
+/// - `spread <market_id> < 0.01`
+/// - `pnl <token_id> < -20` (percent)
+/// - `ends <market_id> 1h` (units: `s`, `m`, `h`, `d`)
+impl FromStr for AlertCondition {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+        let invalid = || crate::Error::invalid_input(format!("Invalid alert rule: '{}'", s));
+
+        match parts.as_slice() {
+            ["mid", token_id, op, level] => {
+                let direction = match *op {
+                    ">" => CrossDirection::Above,
+                    "<" => CrossDirection::Below,
+                    _ => return Err(invalid()),
+                };
+                Ok(Self::MidCrosses {
+                    token_id: token_id.to_string(),
+                    level: parse_decimal(level).ok_or_else(invalid)?,
+                    direction,
+                })
+            }
+            ["spread", market_id, "<", threshold] => Ok(Self::SpreadBelow {
+                market_id: market_id.to_string(),
+                threshold: parse_decimal(threshold).ok_or_else(invalid)?,
+            }),
+            ["pnl", token_id, "<", percent] => Ok(Self::PositionPnlBelow {
+                token_id: token_id.to_string(),
+                percent: parse_decimal(percent.trim_end_matches('%')).ok_or_else(invalid)?,
+            }),
+            ["ends", market_id, within] => Ok(Self::MarketEndsWithin {
+                market_id: market_id.to_string(),
+                within_secs: parse_duration(within).ok_or_else(invalid)?,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+fn parse_decimal(s: &str) -> Option<Decimal> {
+    Decimal::from_str(s).ok()
+}
+
+fn parse_duration(s: &str) -> Option<u64> {
+    let (value, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit())?);
+    let value: u64 = value.parse().ok()?;
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return None,
+    };
+    Some(value * multiplier)
+}
+
+fn format_duration(secs: u64) -> String {
+    if secs.is_multiple_of(86400) {
+        format!("{}d", secs / 86400)
+    } else if secs.is_multiple_of(3600) {
+        format!("{}h", secs / 3600)
+    } else if secs.is_multiple_of(60) {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// A user-defined alert rule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Rule identifier.
+    pub id: String,
+    /// Condition to watch.
+    pub condition: AlertCondition,
+    /// Whether the rule is evaluated.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Ring the terminal bell when fired.
+    #[serde(default = "default_true")]
+    pub bell: bool,
+    /// Show a desktop notification when fired.
+    #[serde(default)]
+    pub desktop: bool,
+    /// Shell command to run when fired.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Webhook URL to POST to when fired.
+    #[serde(default)]
+    pub webhook: Option<String>,
+    /// Whether the condition held at the last evaluation.
+    #[serde(skip)]
+    pub active: bool,
+    /// When the rule last fired.
+    #[serde(skip)]
+    pub last_fired: Option<DateTime<Utc>>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl AlertRule {
+    /// Create a new enabled rule with the terminal bell on.
+    pub fn new(condition: AlertCondition) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            condition,
+            enabled: true,
+            bell: true,
+            desktop: false,
+            command: None,
+            webhook: None,
+            active: false,
+            last_fired: None,
+        }
+    }
+}
+
+/// On-disk representation of all alert rules.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AlertFile {
+    #[serde(default)]
+    rules: Vec<AlertRule>,
+}
+
+/// State for alert rules.
+#[derive(Debug, Default)]
+pub struct AlertState {
+    /// All alert rules.
+    pub rules: Vec<AlertRule>,
+    /// Currently selected rule index.
+    pub selected_index: Option<usize>,
+}
+
+impl AlertState {
+    /// Get the default alert file path.
+    pub fn default_path() -> crate::Result<PathBuf> {
+        crate::config::data_dir().map(|p| p.join("alerts.toml"))
+    }
+
+    /// Load alert rules from file, returning an empty state if the file doesn't exist.
+    pub fn load(path: Option<PathBuf>) -> crate::Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None => Self::default_path()?,
+        };
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let file: AlertFile =
+            toml::from_str(&content).map_err(|e| crate::Error::config(e.to_string()))?;
+
+        Ok(Self {
+            rules: file.rules,
+            selected_index: None,
+        })
+    }
+
+    /// Save alert rules to file.
+    pub fn save(&self, path: Option<PathBuf>) -> crate::Result<()> {
+        let path = match path {
+            Some(path) => path,
+            None => Self::default_path()?,
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = AlertFile {
+            rules: self.rules.clone(),
+        };
+        let content =
+            toml::to_string_pretty(&file).map_err(|e| crate::Error::config(e.to_string()))?;
+        std::fs::write(&path, content)?;
+        Ok(())
+    }
+
+    /// Get the currently selected rule.
+    pub fn selected_rule(&self) -> Option<&AlertRule> {
+        self.selected_index.and_then(|i| self.rules.get(i))
+    }
+
+    /// Add a rule.
+    pub fn add(&mut self, rule: AlertRule) {
+        self.rules.push(rule);
+    }
+
+    /// Enable or disable a rule.
+    pub fn toggle(&mut self, id: &str) {
+        if let Some(rule) = self.rules.iter_mut().find(|r| r.id == id) {
+            rule.enabled = !rule.enabled;
+            rule.active = false;
+        }
+    }
+
+    /// Remove a rule.
+    pub fn remove(&mut self, id: &str) {
+        self.rules.retain(|r| r.id != id);
+        let len = self.rules.len();
+        self.selected_index = match self.selected_index {
+            Some(_) if len == 0 => None,
+            Some(i) => Some(i.min(len - 1)),
+            None => None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_parse_conditions() {
+        assert_eq!(
+            "mid tok > 0.60".parse::<AlertCondition>().unwrap(),
+            AlertCondition::MidCrosses {
+                token_id: "tok".to_string(),
+                level: dec!(0.60),
+                direction: CrossDirection::Above,
+            }
+        );
+        assert_eq!(
+            "spread mkt < 0.01".parse::<AlertCondition>().unwrap(),
+            AlertCondition::SpreadBelow {
+                market_id: "mkt".to_string(),
+                threshold: dec!(0.01),
+            }
+        );
+        assert_eq!(
+            "pnl tok < -20%".parse::<AlertCondition>().unwrap(),
+            AlertCondition::PositionPnlBelow {
+                token_id: "tok".to_string(),
+                percent: dec!(-20),
+            }
+        );
+        assert_eq!(
+            "ends mkt 1h".parse::<AlertCondition>().unwrap(),
+            AlertCondition::MarketEndsWithin {
+                market_id: "mkt".to_string(),
+                within_secs: 3600,
+            }
+        );
+        assert!("mid tok = 0.5".parse::<AlertCondition>().is_err());
+        assert!("ends mkt soon".parse::<AlertCondition>().is_err());
+    }
+
+    #[test]
+    fn test_display_roundtrip() {
+        for rule in [
+            "mid tok < 0.4",
+            "spread mkt < 0.01",
+            "pnl tok < -20",
+            "ends mkt 90m",
+        ] {
+            let condition: AlertCondition = rule.parse().unwrap();
+            assert_eq!(
+                condition.to_string().parse::<AlertCondition>().unwrap(),
+                condition
+            );
+        }
+    }
+}
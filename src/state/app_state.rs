@@ -1,7 +1,5 @@
 //! Application-level state.
 
-use super::Notification;
-
 /// The current view/screen.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum View {
@@ -12,6 +10,13 @@ pub enum View {
     OrderEntry,
     Positions,
     Portfolio,
+    Watchlist,
+    Alerts,
+    Trades,
+    Notifications,
+    Logs,
+    Strategies,
+    Signals,
     Settings,
 }
 
@@ -45,9 +50,7 @@ pub struct AppState {
     pub mode: AppMode,
     /// Whether to show help overlay.
     pub show_help: bool,
-    /// Current notification.
-    pub notification: Option<Notification>,
-    /// Current error message.
+    /// Most recent error message.
     pub error: Option<String>,
     /// Whether the app is loading data.
     pub loading: bool,
@@ -59,6 +62,10 @@ pub struct AppState {
     pub input_buffer: String,
     /// Cursor position in input buffer.
     pub cursor_position: usize,
+    /// Active configuration profile.
+    pub profile: Option<String>,
+    /// Names of all configured profiles.
+    pub profiles: Vec<String>,
 }
 
 impl AppState {
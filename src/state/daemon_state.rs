@@ -0,0 +1,182 @@
+//! Headless daemon status.
+//!
+//! The daemon periodically writes a [`DaemonStatus`] file so that a TUI
+//! instance or a script can inspect what it is doing.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Age in seconds after which a daemon status file is considered stale.
+pub const DAEMON_STALE_SECS: i64 = 120;
+
+/// Summary of one strategy running in the daemon.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrategySummary {
+    /// Strategy name.
+    pub name: String,
+    /// Strategy status (Running, Paused, ...).
+    pub status: String,
+    /// Number of signals generated.
+    pub signals_generated: usize,
+    /// Number of signals executed.
+    pub signals_executed: usize,
+    /// Number of evaluation errors.
+    pub errors: usize,
+    /// Last evaluation timestamp.
+    pub last_evaluated: Option<DateTime<Utc>>,
+}
+
+/// Snapshot of a headless daemon, written to its state file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    /// Process ID of the daemon.
+    pub pid: u32,
+    /// Profile the daemon trades, if any.
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// When the daemon started.
+    pub started_at: DateTime<Utc>,
+    /// When this snapshot was written.
+    pub updated_at: DateTime<Utc>,
+    /// Whether the daemon is still running; `false` after a clean shutdown.
+    pub running: bool,
+    /// Whether the daemon is connected to the API.
+    pub connected: bool,
+    /// Number of markets loaded.
+    pub markets: usize,
+    /// Number of open positions.
+    pub positions: usize,
+    /// Number of active orders.
+    pub open_orders: usize,
+    /// Total portfolio value.
+    pub total_value: Decimal,
+    /// Signals waiting for execution.
+    pub pending_signals: usize,
+    /// Registered strategies.
+    #[serde(default)]
+    pub strategies: Vec<StrategySummary>,
+    /// Most recent error, if any.
+    pub last_error: Option<String>,
+}
+
+impl DaemonStatus {
+    /// Get the default state file path.
+    pub fn default_path() -> crate::Result<PathBuf> {
+        crate::config::data_dir().map(|p| p.join("daemon.toml"))
+    }
+
+    /// Load the daemon status, returning `None` if no daemon has written one.
+    pub fn load(path: Option<PathBuf>) -> crate::Result<Option<Self>> {
+        let path = match path {
+            Some(path) => path,
+            None => Self::default_path()?,
+        };
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| crate::Error::config(e.to_string()))
+    }
+
+    /// Save the daemon status.
+    ///
+    /// Writes to a temporary file first so readers never see a partial file.
+    pub fn save(&self, path: Option<PathBuf>) -> crate::Result<()> {
+        let path = match path {
+            Some(path) => path,
+            None => Self::default_path()?,
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content =
+            toml::to_string_pretty(self).map_err(|e| crate::Error::config(e.to_string()))?;
+        let tmp = path.with_extension("toml.tmp");
+        std::fs::write(&tmp, content)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Check if the daemon is running and has written a snapshot within `max_age_secs`.
+    ///
+    /// A daemon that was killed without a clean shutdown stops updating its
+    /// file, so an old snapshot is treated as not alive.
+    pub fn is_alive(&self, now: DateTime<Utc>, max_age_secs: i64) -> bool {
+        self.running && (now - self.updated_at).num_seconds() <= max_age_secs
+    }
+
+    /// Number of strategies currently running.
+    pub fn running_strategies(&self) -> usize {
+        self.strategies
+            .iter()
+            .filter(|s| s.status.contains("Running"))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn status(now: DateTime<Utc>) -> DaemonStatus {
+        DaemonStatus {
+            pid: 42,
+            profile: None,
+            started_at: now,
+            updated_at: now,
+            running: true,
+            connected: true,
+            markets: 10,
+            positions: 2,
+            open_orders: 1,
+            total_value: dec!(125.50),
+            pending_signals: 0,
+            strategies: vec![StrategySummary {
+                name: "spread".to_string(),
+                status: "▶ Running".to_string(),
+                signals_generated: 3,
+                signals_executed: 1,
+                errors: 0,
+                last_evaluated: Some(now),
+            }],
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let now = Utc::now();
+        let path =
+            std::env::temp_dir().join(format!("clobster-daemon-{}.toml", uuid::Uuid::new_v4()));
+
+        let saved = status(now);
+        saved.save(Some(path.clone())).unwrap();
+        let loaded = DaemonStatus::load(Some(path.clone())).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded, Some(saved));
+        assert_eq!(DaemonStatus::load(Some(path)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_is_alive() {
+        let now = Utc::now();
+        let mut status = status(now - chrono::Duration::seconds(30));
+
+        assert!(status.is_alive(now, 60));
+        assert!(!status.is_alive(now, 10));
+        assert_eq!(status.running_strategies(), 1);
+
+        status.running = false;
+        assert!(!status.is_alive(now, 60));
+    }
+}
@@ -0,0 +1,146 @@
+//! Application log viewer state.
+
+use crate::logging::{LogBuffer, LogEntry};
+use tracing::Level;
+
+/// Level thresholds offered by the viewer, from least to most verbose.
+const LEVELS: [Level; 5] = [
+    Level::ERROR,
+    Level::WARN,
+    Level::INFO,
+    Level::DEBUG,
+    Level::TRACE,
+];
+
+/// State for the in-app log viewer.
+#[derive(Debug, Clone)]
+pub struct LogViewState {
+    /// Captured log entries, shared with the tracing layer.
+    pub buffer: LogBuffer,
+    /// Most verbose level shown; `None` shows everything.
+    pub max_level: Option<Level>,
+    /// Only show entries whose target contains this text.
+    pub target_filter: Option<String>,
+    /// Currently selected entry.
+    pub selected_index: Option<usize>,
+}
+
+impl Default for LogViewState {
+    fn default() -> Self {
+        Self::new(crate::logging::buffer())
+    }
+}
+
+impl LogViewState {
+    /// Create a viewer over the given buffer.
+    pub fn new(buffer: LogBuffer) -> Self {
+        Self {
+            buffer,
+            max_level: None,
+            target_filter: None,
+            selected_index: None,
+        }
+    }
+
+    /// Entries matching the level and target filters, newest first.
+    pub fn filtered_entries(&self) -> Vec<LogEntry> {
+        let target = self.target_filter.as_ref().map(|t| t.to_lowercase());
+        self.buffer
+            .entries()
+            .into_iter()
+            .rev()
+            .filter(|e| self.max_level.is_none_or(|level| e.level <= level))
+            .filter(|e| {
+                target
+                    .as_ref()
+                    .is_none_or(|t| e.target.to_lowercase().contains(t))
+            })
+            .collect()
+    }
+
+    /// Step the level filter through All, ERROR, WARN, INFO, DEBUG and TRACE.
+    pub fn cycle_level(&mut self, delta: i32) {
+        // Index 0 is "All"; 1..=5 map onto LEVELS
+        let slots = LEVELS.len() as i32 + 1;
+        let current = match self.max_level {
+            None => 0,
+            Some(level) => LEVELS.iter().position(|l| *l == level).unwrap_or(0) as i32 + 1,
+        };
+        let next = (current + delta).rem_euclid(slots);
+        self.max_level = match next {
+            0 => None,
+            i => Some(LEVELS[i as usize - 1]),
+        };
+        self.selected_index = None;
+    }
+
+    /// Set the target filter; an empty string clears it.
+    pub fn set_target_filter(&mut self, filter: String) {
+        let filter = filter.trim();
+        self.target_filter = (!filter.is_empty()).then(|| filter.to_string());
+        self.selected_index = None;
+    }
+
+    /// Clear all captured entries.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.selected_index = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn entry(level: Level, target: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: Utc::now(),
+            level,
+            target: target.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    fn state() -> LogViewState {
+        let buffer = LogBuffer::new(10);
+        buffer.push(entry(Level::DEBUG, "clobster::app", "tick"));
+        buffer.push(entry(Level::WARN, "clobster::api::client", "slow"));
+        buffer.push(entry(Level::ERROR, "clobster::strategy", "failed"));
+        LogViewState::new(buffer)
+    }
+
+    #[test]
+    fn test_filters_by_level_and_target() {
+        let mut state = state();
+        let messages = |s: &LogViewState| -> Vec<String> {
+            s.filtered_entries()
+                .into_iter()
+                .map(|e| e.message)
+                .collect()
+        };
+
+        assert_eq!(messages(&state), vec!["failed", "slow", "tick"]);
+
+        state.max_level = Some(Level::WARN);
+        assert_eq!(messages(&state), vec!["failed", "slow"]);
+
+        state.set_target_filter("API".to_string());
+        assert_eq!(messages(&state), vec!["slow"]);
+
+        state.set_target_filter(String::new());
+        assert_eq!(state.target_filter, None);
+    }
+
+    #[test]
+    fn test_cycle_level_wraps() {
+        let mut state = state();
+
+        state.cycle_level(1);
+        assert_eq!(state.max_level, Some(Level::ERROR));
+        state.cycle_level(-1);
+        assert_eq!(state.max_level, None);
+        state.cycle_level(-1);
+        assert_eq!(state.max_level, Some(Level::TRACE));
+    }
+}
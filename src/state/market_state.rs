@@ -1,8 +1,13 @@
 //! Market-related state.
 
+use crate::strategy::PricePoint;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+/// Number of price samples kept per market.
+pub const PRICE_HISTORY_LEN: usize = 200;
 
 /// Market status.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -44,6 +49,9 @@ pub struct Market {
     pub outcomes: Vec<Outcome>,
     /// Total volume traded.
     pub volume: Decimal,
+    /// Volume traded over the last 24h.
+    #[serde(default)]
+    pub volume_24h: Decimal,
     /// Total liquidity.
     pub liquidity: Decimal,
     /// Market image URL.
@@ -96,8 +104,6 @@ pub struct Outcome {
     pub ask: Decimal,
     /// Last traded price.
     pub last_price: Decimal,
-    /// 24h volume.
-    pub volume_24h: Decimal,
     /// Price change in last 24h.
     pub price_change_24h: Decimal,
 }
@@ -124,6 +130,40 @@ impl Outcome {
     }
 }
 
+/// Live pricing for a single outcome token.
+///
+/// Fields left as `None` were not available and keep their previous value
+/// when applied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutcomeQuote {
+    /// Token ID.
+    pub token_id: String,
+    /// Best bid price.
+    pub bid: Option<Decimal>,
+    /// Best ask price.
+    pub ask: Option<Decimal>,
+    /// Last traded price.
+    pub last_price: Option<Decimal>,
+    /// Price change over the last 24h.
+    pub price_change_24h: Option<Decimal>,
+    /// Notional resting on both sides of the book.
+    pub liquidity: Option<Decimal>,
+}
+
+/// Traded volume of a market.
+///
+/// Fields left as `None` were not available and keep their previous value
+/// when applied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketVolume {
+    /// Market (condition) ID.
+    pub market_id: String,
+    /// Total volume traded, in USDC.
+    pub volume: Option<Decimal>,
+    /// Volume traded over the last 24h, in USDC.
+    pub volume_24h: Option<Decimal>,
+}
+
 /// State for market-related data.
 #[derive(Debug, Default)]
 pub struct MarketState {
@@ -141,6 +181,9 @@ pub struct MarketState {
     pub last_updated: Option<DateTime<Utc>>,
     /// Scroll offset for display.
     pub scroll_offset: usize,
+    /// Mid price of each market's first outcome, sampled every time quotes
+    /// are applied, oldest first and indexed by market ID.
+    pub price_history: HashMap<String, VecDeque<PricePoint>>,
 }
 
 impl MarketState {
@@ -181,8 +224,233 @@ impl MarketState {
             .collect()
     }
 
+    /// Update outcome prices from quotes and recompute market liquidity.
+    pub fn apply_quotes(&mut self, quotes: &[OutcomeQuote]) {
+        let quotes: std::collections::HashMap<&str, &OutcomeQuote> =
+            quotes.iter().map(|q| (q.token_id.as_str(), q)).collect();
+
+        for market in &mut self.markets {
+            let mut liquidity = None;
+
+            for outcome in &mut market.outcomes {
+                let Some(quote) = quotes.get(outcome.token_id.as_str()) else {
+                    continue;
+                };
+                if let Some(bid) = quote.bid {
+                    outcome.bid = bid;
+                }
+                if let Some(ask) = quote.ask {
+                    outcome.ask = ask;
+                }
+                if let Some(last_price) = quote.last_price {
+                    outcome.last_price = last_price;
+                }
+                if let Some(change) = quote.price_change_24h {
+                    outcome.price_change_24h = change;
+                }
+                if let Some(depth) = quote.liquidity {
+                    *liquidity.get_or_insert(Decimal::ZERO) += depth;
+                }
+            }
+
+            if let Some(liquidity) = liquidity {
+                market.liquidity = liquidity;
+            }
+        }
+    }
+
+    /// Sample the first outcome's mid price of every quoted market into the
+    /// price history, keeping the last [`PRICE_HISTORY_LEN`] samples.
+    pub fn record_prices(&mut self, timestamp: DateTime<Utc>) {
+        for market in &self.markets {
+            let Some(outcome) = market.outcomes.first() else {
+                continue;
+            };
+            if outcome.bid.is_zero() || outcome.ask.is_zero() {
+                continue;
+            }
+            let history = self.price_history.entry(market.id.clone()).or_default();
+            history.push_back(PricePoint {
+                timestamp,
+                price: outcome.mid_price(),
+                volume: Some(market.volume_24h),
+            });
+            if history.len() > PRICE_HISTORY_LEN {
+                history.pop_front();
+            }
+        }
+    }
+
+    /// Update market volumes.
+    pub fn apply_volumes(&mut self, volumes: &[MarketVolume]) {
+        let volumes: std::collections::HashMap<&str, &MarketVolume> =
+            volumes.iter().map(|v| (v.market_id.as_str(), v)).collect();
+
+        for market in &mut self.markets {
+            let Some(volume) = volumes.get(market.id.as_str()) else {
+                continue;
+            };
+            if let Some(total) = volume.volume {
+                market.volume = total;
+            }
+            if let Some(volume_24h) = volume.volume_24h {
+                market.volume_24h = volume_24h;
+            }
+        }
+    }
+
     /// Get the count of filtered markets.
     pub fn filtered_count(&self) -> usize {
         self.filtered_markets().len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn outcome(token_id: &str) -> Outcome {
+        Outcome {
+            token_id: token_id.to_string(),
+            name: token_id.to_string(),
+            bid: Decimal::ZERO,
+            ask: Decimal::ZERO,
+            last_price: Decimal::ZERO,
+            price_change_24h: Decimal::ZERO,
+        }
+    }
+
+    fn state() -> MarketState {
+        MarketState {
+            markets: vec![Market {
+                id: "m1".to_string(),
+                question: "Will it rain?".to_string(),
+                description: String::new(),
+                status: MarketStatus::Active,
+                end_date: None,
+                tags: Vec::new(),
+                outcomes: vec![outcome("yes"), outcome("no")],
+                volume: Decimal::ZERO,
+                volume_24h: Decimal::ZERO,
+                liquidity: dec!(5),
+                image_url: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }],
+            ..MarketState::default()
+        }
+    }
+
+    #[test]
+    fn test_apply_quotes() {
+        let mut state = state();
+        state.apply_quotes(&[
+            OutcomeQuote {
+                token_id: "yes".to_string(),
+                bid: Some(dec!(0.48)),
+                ask: Some(dec!(0.52)),
+                last_price: Some(dec!(0.50)),
+                liquidity: Some(dec!(100)),
+                ..OutcomeQuote::default()
+            },
+            OutcomeQuote {
+                token_id: "no".to_string(),
+                bid: Some(dec!(0.47)),
+                ask: Some(dec!(0.53)),
+                liquidity: Some(dec!(60)),
+                ..OutcomeQuote::default()
+            },
+            OutcomeQuote {
+                token_id: "unknown".to_string(),
+                bid: Some(dec!(0.10)),
+                ..OutcomeQuote::default()
+            },
+        ]);
+
+        let market = &state.markets[0];
+        let yes = &market.outcomes[0];
+        assert_eq!(yes.bid, dec!(0.48));
+        assert_eq!(yes.ask, dec!(0.52));
+        assert_eq!(yes.mid_price(), dec!(0.50));
+        assert_eq!(yes.last_price, dec!(0.50));
+        // Liquidity is the depth of all outcomes together
+        assert_eq!(market.liquidity, dec!(160));
+    }
+
+    #[test]
+    fn test_apply_partial_quotes_keeps_other_fields() {
+        let mut state = state();
+        state.apply_quotes(&[OutcomeQuote {
+            token_id: "yes".to_string(),
+            bid: Some(dec!(0.48)),
+            ask: Some(dec!(0.52)),
+            ..OutcomeQuote::default()
+        }]);
+
+        // A later 24h change leaves the quote and liquidity alone
+        state.apply_quotes(&[OutcomeQuote {
+            token_id: "yes".to_string(),
+            price_change_24h: Some(dec!(-0.03)),
+            ..OutcomeQuote::default()
+        }]);
+
+        let market = &state.markets[0];
+        assert_eq!(market.outcomes[0].bid, dec!(0.48));
+        assert_eq!(market.outcomes[0].ask, dec!(0.52));
+        assert_eq!(market.outcomes[0].price_change_24h, dec!(-0.03));
+        assert_eq!(market.liquidity, dec!(5));
+    }
+
+    #[test]
+    fn test_record_prices_samples_quoted_markets() {
+        let mut state = state();
+        state.markets.push(Market {
+            id: "m2".to_string(),
+            outcomes: vec![outcome("unquoted")],
+            ..state.markets[0].clone()
+        });
+
+        for (i, bid) in [dec!(0.40), dec!(0.42)].into_iter().enumerate() {
+            state.apply_quotes(&[OutcomeQuote {
+                token_id: "yes".to_string(),
+                bid: Some(bid),
+                ask: Some(bid + dec!(0.02)),
+                ..OutcomeQuote::default()
+            }]);
+            state.record_prices(Utc::now() + chrono::Duration::seconds(i as i64));
+        }
+
+        let prices: Vec<Decimal> = state.price_history["m1"].iter().map(|p| p.price).collect();
+        assert_eq!(prices, vec![dec!(0.41), dec!(0.43)]);
+        // Markets without a quote are not sampled
+        assert!(!state.price_history.contains_key("m2"));
+
+        for _ in 0..PRICE_HISTORY_LEN {
+            state.record_prices(Utc::now());
+        }
+        let history = &state.price_history["m1"];
+        assert_eq!(history.len(), PRICE_HISTORY_LEN);
+        assert_eq!(history.front().unwrap().price, dec!(0.43));
+    }
+
+    #[test]
+    fn test_apply_volumes() {
+        let mut state = state();
+        state.apply_volumes(&[MarketVolume {
+            market_id: "m1".to_string(),
+            volume: Some(dec!(1000)),
+            volume_24h: Some(dec!(250)),
+        }]);
+        assert_eq!(state.markets[0].volume, dec!(1000));
+        assert_eq!(state.markets[0].volume_24h, dec!(250));
+
+        state.apply_volumes(&[MarketVolume {
+            market_id: "m1".to_string(),
+            volume_24h: Some(dec!(300)),
+            ..MarketVolume::default()
+        }]);
+        assert_eq!(state.markets[0].volume, dec!(1000));
+        assert_eq!(state.markets[0].volume_24h, dec!(300));
+    }
+}
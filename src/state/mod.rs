@@ -3,17 +3,35 @@
 //! This module provides centralized state management with a unidirectional
 //! data flow pattern inspired by Redux/Elm architecture.
 
+mod alert_state;
 mod app_state;
+mod daemon_state;
+mod log_state;
 mod market_state;
+mod notification_state;
 mod order_state;
 mod orderbook_state;
 mod portfolio_state;
+mod signal_state;
+mod strategy_state;
+mod trade_state;
+mod watchlist_state;
 
+pub use alert_state::{AlertCondition, AlertRule, AlertState, CrossDirection};
 pub use app_state::{AppMode, AppState, InputMode, View};
-pub use market_state::{Market, MarketState, MarketStatus, Outcome};
-pub use order_state::{Order, OrderState, OrderStatus};
+pub use daemon_state::{DAEMON_STALE_SECS, DaemonStatus, StrategySummary};
+pub use log_state::LogViewState;
+pub use market_state::{Market, MarketState, MarketStatus, MarketVolume, Outcome, OutcomeQuote};
+pub use notification_state::NotificationState;
+pub use order_state::{Order, OrderState, OrderStatus, Quantity, SIZE_DECIMALS};
 pub use orderbook_state::{OrderBookDepth, OrderBookState, OrderBookStats, PriceLevel};
-pub use portfolio_state::{Balance, PortfolioState, Position};
+pub use portfolio_state::{
+    Balance, EquitySnapshot, Exposure, PortfolioState, Position, ResolutionEntry,
+};
+pub use signal_state::{SignalEntry, SignalViewState};
+pub use strategy_state::{StrategyEntry, StrategyViewState};
+pub use trade_state::{CostBasisMethod, Fill, TokenPnl, TradeState, TradeSummary, compute_pnl};
+pub use watchlist_state::{DEFAULT_WATCHLIST, Watchlist, WatchlistState};
 
 use crate::error::Result;
 use tokio::sync::mpsc;
@@ -29,6 +47,8 @@ pub enum Action {
     // Market actions
     LoadMarkets,
     MarketsLoaded(Vec<Market>),
+    QuotesLoaded(Vec<OutcomeQuote>),
+    VolumesLoaded(Vec<MarketVolume>),
     SelectMarket(usize),
     SearchMarkets(String),
     FilterMarkets(MarketStatus),
@@ -48,9 +68,15 @@ pub enum Action {
     PortfolioLoaded(PortfolioState),
     LoadPositions,
     PositionsLoaded(Vec<Position>),
+    BracketsLoaded(Vec<crate::strategy::Bracket>),
+
+    // Trade history actions
+    LoadTrades,
+    TradesLoaded(Vec<Fill>),
 
     // Order book actions
-    LoadOrderBook(String), // token_id
+    LoadOrderBook(String),       // token_id
+    LoadOrderBooks(Vec<String>), // token_ids
     OrderBookLoaded(OrderBookDepth),
     OrderBookError(String),  // error message for orderbook loading
     SelectOrderBook(String), // token_id
@@ -58,6 +84,17 @@ pub enum Action {
     ClearAllOrderBooks,
     SetOrderBookDepth(usize), // display depth
 
+    // Watchlist actions
+    ToggleWatchlistMarket(String), // market_id
+    CreateWatchlist(String),       // name
+    DeleteWatchlist(String),       // name
+    CycleWatchlist(i32),
+
+    // Alert actions
+    CreateAlert(String), // rule text, e.g. "mid <token_id> > 0.60"
+    ToggleAlert(String), // rule id
+    DeleteAlert(String), // rule id
+
     // UI actions
     ScrollUp,
     ScrollDown,
@@ -68,12 +105,47 @@ pub enum Action {
     ToggleHelp,
     ShowNotification(Notification),
     DismissNotification,
+    ClearNotificationHistory,
+    Tick,
+
+    // Strategy parameter editor
+    LoadStrategies,
+    StrategiesLoaded {
+        strategies: Vec<StrategyEntry>,
+        changes: Vec<crate::strategy::ParameterChange>,
+    },
+    CycleStrategy(i32),
+    EditParameter,
+    SetStrategyParameter {
+        strategy: String,
+        parameter: String,
+        value: String,
+    },
+    ResetStrategyParameter {
+        strategy: String,
+        parameter: String,
+    },
+    SaveStrategyParameters(String), // strategy name
+
+    // Signal history
+    SignalsLoaded(Vec<SignalEntry>),
+
+    // Log viewer
+    CycleLogLevel(i32),
+    SetLogTargetFilter(String),
+    ClearLogs,
+
+    // Text input
+    InputChar(char),
+    InputBackspace,
 
     // Data refresh
     RefreshAll,
     RefreshMarkets,
+    RefreshPrices,
     RefreshOrders,
     RefreshPortfolio,
+    RefreshTrades,
     RefreshOrderBook(String), // token_id
 
     // Error handling
@@ -84,6 +156,13 @@ pub enum Action {
     SetConnected(bool),
     SetLoading(bool),
 
+    // Headless daemon
+    DaemonStatusLoaded(Option<DaemonStatus>),
+
+    // Profiles
+    CycleProfile(i32),
+    ProfileSwitched(String),
+
     // Quit
     Quit,
 }
@@ -96,8 +175,11 @@ pub struct OrderRequest {
     pub side: OrderSide,
     /// Price for limit orders. None for market orders.
     pub price: Option<rust_decimal::Decimal>,
+    /// Number of shares.
     pub size: rust_decimal::Decimal,
     pub order_type: OrderType,
+    /// Local ID tying the order to the signal it was placed for.
+    pub client_order_id: Option<String>,
 }
 
 /// Order side (buy/sell).
@@ -120,6 +202,7 @@ pub struct Notification {
     pub message: String,
     pub level: NotificationLevel,
     pub duration_secs: u64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
 /// Notification severity level.
@@ -137,6 +220,7 @@ impl Notification {
             message: message.into(),
             level: NotificationLevel::Info,
             duration_secs: 3,
+            created_at: chrono::Utc::now(),
         }
     }
 
@@ -145,6 +229,7 @@ impl Notification {
             message: message.into(),
             level: NotificationLevel::Success,
             duration_secs: 3,
+            created_at: chrono::Utc::now(),
         }
     }
 
@@ -153,6 +238,7 @@ impl Notification {
             message: message.into(),
             level: NotificationLevel::Warning,
             duration_secs: 5,
+            created_at: chrono::Utc::now(),
         }
     }
 
@@ -161,8 +247,52 @@ impl Notification {
             message: message.into(),
             level: NotificationLevel::Error,
             duration_secs: 10,
+            created_at: chrono::Utc::now(),
         }
     }
+
+    /// Check if the notification's display duration has elapsed.
+    pub fn is_expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        now.signed_duration_since(self.created_at).num_seconds() >= self.duration_secs as i64
+    }
+}
+
+impl std::fmt::Display for NotificationLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "Info"),
+            Self::Success => write!(f, "Success"),
+            Self::Warning => write!(f, "Warning"),
+            Self::Error => write!(f, "Error"),
+        }
+    }
+}
+
+/// Compact, serializable overview of the store, used in crash reports.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct StoreSummary {
+    /// Current view.
+    pub view: String,
+    /// Current input mode.
+    pub input_mode: String,
+    /// Whether the API connection is up.
+    pub connected: bool,
+    /// Number of markets loaded.
+    pub markets: usize,
+    /// Selected market ID.
+    pub selected_market: Option<String>,
+    /// Number of open orders.
+    pub open_orders: usize,
+    /// Number of positions.
+    pub positions: usize,
+    /// Total portfolio value.
+    pub total_value: rust_decimal::Decimal,
+    /// Number of alert rules.
+    pub alerts: usize,
+    /// Number of recorded fills.
+    pub fills: usize,
+    /// Most recent error, if any.
+    pub last_error: Option<String>,
 }
 
 /// The global state store.
@@ -178,6 +308,22 @@ pub struct Store {
     pub orderbooks: OrderBookState,
     /// Portfolio state.
     pub portfolio: PortfolioState,
+    /// Watchlist state.
+    pub watchlists: WatchlistState,
+    /// Alert rules.
+    pub alerts: AlertState,
+    /// Fill history and realized PnL.
+    pub trades: TradeState,
+    /// Notification toasts and history.
+    pub notifications: NotificationState,
+    /// Status of a headless daemon, if one has written its state file.
+    pub daemon: Option<DaemonStatus>,
+    /// Application log viewer.
+    pub logs: LogViewState,
+    /// Strategy parameter editor.
+    pub strategies: StrategyViewState,
+    /// Executed strategy signals.
+    pub signals: SignalViewState,
     /// Action sender for dispatching actions.
     action_tx: mpsc::UnboundedSender<Action>,
 }
@@ -191,10 +337,35 @@ impl Store {
             orders: OrderState::default(),
             orderbooks: OrderBookState::default(),
             portfolio: PortfolioState::default(),
+            watchlists: WatchlistState::default(),
+            alerts: AlertState::default(),
+            trades: TradeState::default(),
+            notifications: NotificationState::default(),
+            daemon: None,
+            logs: LogViewState::default(),
+            strategies: StrategyViewState::default(),
+            signals: SignalViewState::default(),
             action_tx,
         }
     }
 
+    /// Summarize the store for diagnostics.
+    pub fn summary(&self) -> StoreSummary {
+        StoreSummary {
+            view: format!("{:?}", self.app.current_view),
+            input_mode: format!("{:?}", self.app.input_mode),
+            connected: self.app.connected,
+            markets: self.markets.markets.len(),
+            selected_market: self.markets.selected_market().map(|m| m.id.clone()),
+            open_orders: self.orders.open_orders().len(),
+            positions: self.portfolio.positions.len(),
+            total_value: self.portfolio.total_value,
+            alerts: self.alerts.rules.len(),
+            fills: self.trades.fills.len(),
+            last_error: self.app.error.clone(),
+        }
+    }
+
     /// Dispatch an action to the store.
     pub fn dispatch(&self, action: Action) -> Result<()> {
         self.action_tx
@@ -207,7 +378,12 @@ impl Store {
         match action {
             // Navigation
             Action::SetView(view) => self.app.current_view = view,
-            Action::SetInputMode(mode) => self.app.input_mode = mode,
+            Action::SetInputMode(mode) => {
+                if mode != self.app.input_mode {
+                    self.app.clear_input();
+                }
+                self.app.input_mode = mode;
+            }
             Action::SetAppMode(mode) => self.app.mode = mode,
 
             // Market actions
@@ -217,6 +393,13 @@ impl Store {
                 self.markets.loading = false;
                 self.markets.last_updated = Some(chrono::Utc::now());
             }
+            Action::QuotesLoaded(quotes) => {
+                let now = chrono::Utc::now();
+                self.markets.apply_quotes(&quotes);
+                self.markets.record_prices(now);
+                self.markets.last_updated = Some(now);
+            }
+            Action::VolumesLoaded(volumes) => self.markets.apply_volumes(&volumes),
             Action::SelectMarket(index) => {
                 if index < self.markets.markets.len() {
                     self.markets.selected_index = Some(index);
@@ -259,17 +442,70 @@ impl Store {
             // Portfolio actions
             Action::LoadPortfolio => self.portfolio.loading = true,
             Action::PortfolioLoaded(portfolio) => {
-                self.portfolio = portfolio;
+                let equity_history = std::mem::take(&mut self.portfolio.equity_history);
+                let brackets = std::mem::take(&mut self.portfolio.brackets);
+                self.portfolio = PortfolioState {
+                    equity_history,
+                    brackets,
+                    ..portfolio
+                };
                 self.portfolio.loading = false;
+                self.sync_realized_pnl();
+                self.record_equity();
+            }
+
+            // Strategy parameter editor
+            Action::StrategiesLoaded {
+                strategies,
+                changes,
+            } => self.strategies.load(strategies, changes),
+            Action::CycleStrategy(delta) => self.strategies.cycle(delta),
+            Action::EditParameter => {
+                // Start from the current value
+                if let Some(field) = self.strategies.selected_field() {
+                    let current = field.current.to_string();
+                    self.reduce(Action::SetInputMode(InputMode::Insert));
+                    self.app.cursor_position = current.len();
+                    self.app.input_buffer = current;
+                }
+            }
+            Action::SetStrategyParameter { .. } => {
+                self.reduce(Action::SetInputMode(InputMode::Normal));
             }
+            Action::LoadStrategies
+            | Action::ResetStrategyParameter { .. }
+            | Action::SaveStrategyParameters(_) => {}
+
+            // Signal history
+            Action::SignalsLoaded(entries) => self.signals.load(entries),
+
+            // Log viewer
+            Action::CycleLogLevel(delta) => self.logs.cycle_level(delta),
+            Action::SetLogTargetFilter(filter) => {
+                self.logs.set_target_filter(filter);
+                self.reduce(Action::SetInputMode(InputMode::Normal));
+            }
+            Action::ClearLogs => self.logs.clear(),
             Action::LoadPositions => self.portfolio.loading = true,
+            Action::BracketsLoaded(brackets) => self.portfolio.brackets = brackets,
             Action::PositionsLoaded(positions) => {
                 self.portfolio.positions = positions;
                 self.portfolio.loading = false;
+                self.sync_realized_pnl();
+                self.record_equity();
+            }
+
+            // Trade history actions
+            Action::LoadTrades => self.trades.loading = true,
+            Action::TradesLoaded(fills) => {
+                self.trades.merge(fills);
+                self.trades.loading = false;
+                self.trades.last_updated = Some(chrono::Utc::now());
+                self.sync_realized_pnl();
             }
 
             // Order book actions
-            Action::LoadOrderBook(_) => self.orderbooks.loading = true,
+            Action::LoadOrderBook(_) | Action::LoadOrderBooks(_) => self.orderbooks.loading = true,
             Action::OrderBookLoaded(book) => {
                 self.orderbooks.update_book(book);
                 self.orderbooks.loading = false;
@@ -294,6 +530,28 @@ impl Store {
                 }
             }
 
+            // Watchlist actions
+            Action::ToggleWatchlistMarket(market_id) => {
+                self.watchlists.toggle(&market_id);
+            }
+            Action::CreateWatchlist(name) => {
+                self.watchlists.create(&name);
+                self.reduce(Action::SetInputMode(InputMode::Normal));
+            }
+            Action::DeleteWatchlist(name) => self.watchlists.delete(&name),
+            Action::CycleWatchlist(delta) => self.watchlists.cycle(delta),
+
+            // Alert actions
+            Action::CreateAlert(text) => {
+                match text.parse::<AlertCondition>() {
+                    Ok(condition) => self.alerts.add(AlertRule::new(condition)),
+                    Err(e) => self.reduce(Action::SetError(e.to_string())),
+                }
+                self.reduce(Action::SetInputMode(InputMode::Normal));
+            }
+            Action::ToggleAlert(id) => self.alerts.toggle(&id),
+            Action::DeleteAlert(id) => self.alerts.remove(&id),
+
             // UI actions
             Action::ScrollUp => self.scroll(-1),
             Action::ScrollDown => self.scroll(1),
@@ -303,23 +561,37 @@ impl Store {
             Action::GoToBottom => self.go_to_bottom(),
             Action::ToggleHelp => self.app.show_help = !self.app.show_help,
             Action::ShowNotification(notification) => {
-                self.app.notification = Some(notification);
+                self.notifications.push(notification);
             }
             Action::DismissNotification => {
-                self.app.notification = None;
+                self.notifications.dismiss();
+            }
+            Action::ClearNotificationHistory => {
+                self.notifications.clear_history();
+            }
+            Action::Tick => {
+                self.notifications.expire(chrono::Utc::now());
+                self.record_equity();
             }
 
+            // Text input
+            Action::InputChar(c) => self.app.push_char(c),
+            Action::InputBackspace => self.app.pop_char(),
+
             // Data refresh
             Action::RefreshAll
             | Action::RefreshMarkets
+            | Action::RefreshPrices
             | Action::RefreshOrders
             | Action::RefreshPortfolio
+            | Action::RefreshTrades
             | Action::RefreshOrderBook(_) => {
                 self.app.loading = true;
             }
 
             // Error handling
             Action::SetError(error) => {
+                self.notifications.push(Notification::error(error.clone()));
                 self.app.error = Some(error);
                 self.app.loading = false;
             }
@@ -335,6 +607,25 @@ impl Store {
                 self.app.loading = loading;
             }
 
+            // Headless daemon
+            Action::DaemonStatusLoaded(status) => {
+                self.daemon = status;
+            }
+
+            // Profiles
+            Action::CycleProfile(_) => {}
+            Action::ProfileSwitched(name) => {
+                // Account data belongs to the previous profile
+                self.markets = MarketState::default();
+                self.orders = OrderState::default();
+                self.orderbooks = OrderBookState::default();
+                self.portfolio = PortfolioState::default();
+                self.trades.clear();
+                self.app.connected = false;
+                self.app.error = None;
+                self.app.profile = Some(name);
+            }
+
             // Quit
             Action::Quit => {
                 self.app.should_quit = true;
@@ -342,6 +633,20 @@ impl Store {
         }
     }
 
+    /// Apply realized PnL from the local fill history to positions.
+    fn sync_realized_pnl(&mut self) {
+        self.trades
+            .apply_to_positions(&mut self.portfolio.positions);
+        self.portfolio.calculate_totals();
+    }
+
+    /// Take a periodic equity snapshot once portfolio data is available.
+    fn record_equity(&mut self) {
+        if !self.portfolio.positions.is_empty() || !self.portfolio.balances.is_empty() {
+            self.portfolio.record_equity(chrono::Utc::now());
+        }
+    }
+
     fn scroll(&mut self, delta: i32) {
         match self.app.current_view {
             View::Markets => {
@@ -362,6 +667,48 @@ impl Store {
                 let max_index = self.portfolio.positions.len().saturating_sub(1);
                 self.portfolio.selected_position = Some(new_index.min(max_index));
             }
+            View::Watchlist => {
+                let current = self.watchlists.selected_index.unwrap_or(0) as i32;
+                let new_index = (current + delta).max(0) as usize;
+                let max_index = self.watchlists.active_len().saturating_sub(1);
+                self.watchlists.selected_index = Some(new_index.min(max_index));
+            }
+            View::Alerts => {
+                let current = self.alerts.selected_index.unwrap_or(0) as i32;
+                let new_index = (current + delta).max(0) as usize;
+                let max_index = self.alerts.rules.len().saturating_sub(1);
+                self.alerts.selected_index = Some(new_index.min(max_index));
+            }
+            View::Trades => {
+                let current = self.trades.selected_index.unwrap_or(0) as i32;
+                let new_index = (current + delta).max(0) as usize;
+                let max_index = self.trades.fills.len().saturating_sub(1);
+                self.trades.selected_index = Some(new_index.min(max_index));
+            }
+            View::Notifications => {
+                let current = self.notifications.selected_index.unwrap_or(0) as i32;
+                let new_index = (current + delta).max(0) as usize;
+                let max_index = self.notifications.history.len().saturating_sub(1);
+                self.notifications.selected_index = Some(new_index.min(max_index));
+            }
+            View::Logs => {
+                let current = self.logs.selected_index.unwrap_or(0) as i32;
+                let new_index = (current + delta).max(0) as usize;
+                let max_index = self.logs.filtered_entries().len().saturating_sub(1);
+                self.logs.selected_index = Some(new_index.min(max_index));
+            }
+            View::Strategies => {
+                let current = self.strategies.selected_index.unwrap_or(0) as i32;
+                let new_index = (current + delta).max(0) as usize;
+                let max_index = self.strategies.active_len().saturating_sub(1);
+                self.strategies.selected_index = Some(new_index.min(max_index));
+            }
+            View::Signals => {
+                let current = self.signals.selected_index.unwrap_or(0) as i32;
+                let new_index = (current + delta).max(0) as usize;
+                let max_index = self.signals.entries.len().saturating_sub(1);
+                self.signals.selected_index = Some(new_index.min(max_index));
+            }
             _ => {}
         }
     }
@@ -371,6 +718,13 @@ impl Store {
             View::Markets => self.markets.selected_index = Some(0),
             View::Orders => self.orders.selected_index = Some(0),
             View::Positions => self.portfolio.selected_position = Some(0),
+            View::Watchlist => self.watchlists.selected_index = Some(0),
+            View::Alerts => self.alerts.selected_index = Some(0),
+            View::Trades => self.trades.selected_index = Some(0),
+            View::Notifications => self.notifications.selected_index = Some(0),
+            View::Logs => self.logs.selected_index = Some(0),
+            View::Strategies => self.strategies.selected_index = Some(0),
+            View::Signals => self.signals.selected_index = Some(0),
             _ => {}
         }
     }
@@ -389,6 +743,34 @@ impl Store {
                 let max = self.portfolio.positions.len().saturating_sub(1);
                 self.portfolio.selected_position = Some(max);
             }
+            View::Watchlist => {
+                let max = self.watchlists.active_len().saturating_sub(1);
+                self.watchlists.selected_index = Some(max);
+            }
+            View::Alerts => {
+                let max = self.alerts.rules.len().saturating_sub(1);
+                self.alerts.selected_index = Some(max);
+            }
+            View::Trades => {
+                let max = self.trades.fills.len().saturating_sub(1);
+                self.trades.selected_index = Some(max);
+            }
+            View::Notifications => {
+                let max = self.notifications.history.len().saturating_sub(1);
+                self.notifications.selected_index = Some(max);
+            }
+            View::Logs => {
+                let max = self.logs.filtered_entries().len().saturating_sub(1);
+                self.logs.selected_index = Some(max);
+            }
+            View::Strategies => {
+                let max = self.strategies.active_len().saturating_sub(1);
+                self.strategies.selected_index = Some(max);
+            }
+            View::Signals => {
+                let max = self.signals.entries.len().saturating_sub(1);
+                self.signals.selected_index = Some(max);
+            }
             _ => {}
         }
     }
@@ -0,0 +1,117 @@
+//! Notification queue and history.
+
+use super::{Notification, NotificationLevel};
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+
+/// Maximum number of toasts shown at once.
+const MAX_ACTIVE: usize = 5;
+
+/// Maximum number of notifications kept in the history log.
+const MAX_HISTORY: usize = 500;
+
+/// State for on-screen toasts and the notification history log.
+#[derive(Debug, Default)]
+pub struct NotificationState {
+    /// Toasts currently on screen, oldest first.
+    pub active: VecDeque<Notification>,
+    /// Every notification and error raised, oldest first.
+    pub history: VecDeque<Notification>,
+    /// Currently selected history entry.
+    pub selected_index: Option<usize>,
+}
+
+impl NotificationState {
+    /// Show a notification and record it in the history.
+    ///
+    /// When more than [`MAX_ACTIVE`] toasts are on screen the oldest
+    /// non-error toast is dropped first, so errors are never crowded out
+    /// by routine messages.
+    pub fn push(&mut self, notification: Notification) {
+        self.history.push_back(notification.clone());
+        if self.history.len() > MAX_HISTORY {
+            self.history.pop_front();
+            self.selected_index = self.selected_index.map(|i| i.saturating_sub(1));
+        }
+
+        self.active.push_back(notification);
+        while self.active.len() > MAX_ACTIVE {
+            let evict = self
+                .active
+                .iter()
+                .position(|n| n.level != NotificationLevel::Error)
+                .unwrap_or(0);
+            self.active.remove(evict);
+        }
+    }
+
+    /// Dismiss the oldest toast on screen.
+    pub fn dismiss(&mut self) {
+        self.active.pop_front();
+    }
+
+    /// Remove toasts whose display duration has elapsed.
+    pub fn expire(&mut self, now: DateTime<Utc>) {
+        self.active.retain(|n| !n.is_expired(now));
+    }
+
+    /// Clear the history log.
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.selected_index = None;
+    }
+
+    /// Count history entries at the given level.
+    pub fn count(&self, level: NotificationLevel) -> usize {
+        self.history.iter().filter(|n| n.level == level).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_stacks_and_records_history() {
+        let mut state = NotificationState::default();
+        state.push(Notification::info("one"));
+        state.push(Notification::error("two"));
+
+        assert_eq!(state.active.len(), 2);
+        assert_eq!(state.history.len(), 2);
+
+        state.dismiss();
+        assert_eq!(state.active.len(), 1);
+        assert_eq!(state.active[0].message, "two");
+        assert_eq!(state.history.len(), 2);
+    }
+
+    #[test]
+    fn test_overflow_evicts_non_errors_first() {
+        let mut state = NotificationState::default();
+        state.push(Notification::error("order failed"));
+        for i in 0..MAX_ACTIVE {
+            state.push(Notification::info(format!("info {}", i)));
+        }
+
+        assert_eq!(state.active.len(), MAX_ACTIVE);
+        assert_eq!(state.active[0].message, "order failed");
+    }
+
+    #[test]
+    fn test_expire_uses_duration() {
+        let mut state = NotificationState::default();
+        let info = Notification::info("short");
+        let created_at = info.created_at;
+        state.push(info);
+        state.push(Notification::error("long"));
+
+        state.expire(created_at + chrono::Duration::seconds(5));
+        assert_eq!(state.active.len(), 1);
+        assert_eq!(state.active[0].message, "long");
+
+        state.expire(created_at + chrono::Duration::seconds(11));
+        assert!(state.active.is_empty());
+        assert_eq!(state.history.len(), 2);
+    }
+}
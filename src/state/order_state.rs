@@ -1,5 +1,6 @@
 //! Order-related state.
 
+use super::{OrderBookDepth, OrderSide};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -111,6 +112,163 @@ impl Order {
     }
 }
 
+/// Decimal places order sizes in shares are rounded down to.
+pub const SIZE_DECIMALS: u32 = 2;
+
+/// Amount of an outcome token to trade.
+///
+/// Orders on the exchange are sized in shares, each paying 1 USDC if its
+/// outcome resolves true. A notional is the USDC spent (buys) or received
+/// (sells), and is converted to shares at a price before an order is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", from = "QuantityRepr")]
+pub enum Quantity {
+    /// Number of shares.
+    Shares(Decimal),
+    /// Amount of USDC.
+    Notional(Decimal),
+}
+
+impl Quantity {
+    /// The amount, in shares or USDC.
+    pub fn amount(&self) -> Decimal {
+        match *self {
+            Self::Shares(amount) | Self::Notional(amount) => amount,
+        }
+    }
+
+    /// Check if the amount is zero.
+    pub fn is_zero(&self) -> bool {
+        self.amount().is_zero()
+    }
+
+    /// Shares at `price`, or `None` for a notional without a positive
+    /// price to convert at.
+    pub fn shares_at(&self, price: Decimal) -> Option<Decimal> {
+        match *self {
+            Self::Shares(shares) => Some(shares),
+            Self::Notional(_) if price <= Decimal::ZERO => None,
+            Self::Notional(notional) => Some(notional / price),
+        }
+    }
+
+    /// USDC value at `price`.
+    pub fn notional_at(&self, price: Decimal) -> Decimal {
+        match *self {
+            Self::Shares(shares) => shares * price,
+            Self::Notional(notional) => notional,
+        }
+    }
+
+    /// Shares an order on `side` gets from `book`, converting a notional at
+    /// the volume-weighted price of the levels it takes.
+    ///
+    /// Returns `None` for a notional when that side of the book is empty.
+    /// A notional larger than the book is converted at the average price of
+    /// the whole side.
+    pub fn shares_in(&self, book: &OrderBookDepth, side: OrderSide) -> Option<Decimal> {
+        let notional = match *self {
+            Self::Shares(shares) => return Some(shares),
+            Self::Notional(notional) => notional,
+        };
+        let levels = match side {
+            OrderSide::Buy => &book.asks,
+            OrderSide::Sell => &book.bids,
+        };
+
+        let mut remaining = notional;
+        let mut shares = Decimal::ZERO;
+        let mut value = Decimal::ZERO;
+        for level in levels.iter().filter(|l| l.price > Decimal::ZERO) {
+            let take = (remaining / level.price).min(level.size);
+            shares += take;
+            value += take * level.price;
+            remaining -= take * level.price;
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+        }
+
+        if value.is_zero() {
+            return None;
+        }
+        Some(shares + remaining.max(Decimal::ZERO) * shares / value)
+    }
+
+    /// USDC value of an order on `side` filled from `book`, valuing shares
+    /// at the volume-weighted price of the levels they take.
+    ///
+    /// Returns `None` for shares when that side of the book is empty.
+    /// Shares beyond the book are valued at the average price of the whole
+    /// side.
+    pub fn notional_in(&self, book: &OrderBookDepth, side: OrderSide) -> Option<Decimal> {
+        let shares = match *self {
+            Self::Shares(shares) => shares,
+            Self::Notional(notional) => return Some(notional),
+        };
+        let levels = match side {
+            OrderSide::Buy => &book.asks,
+            OrderSide::Sell => &book.bids,
+        };
+
+        let mut remaining = shares;
+        let mut value = Decimal::ZERO;
+        for level in levels {
+            let take = remaining.min(level.size);
+            value += take * level.price;
+            remaining -= take;
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+        }
+
+        let taken = shares - remaining;
+        if taken.is_zero() {
+            return None;
+        }
+        Some(value + remaining * value / taken)
+    }
+}
+
+impl std::fmt::Display for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shares(shares) => match f.precision() {
+                Some(precision) => write!(f, "{:.*} sh", precision, shares),
+                None => write!(f, "{} sh", shares),
+            },
+            Self::Notional(notional) => write!(f, "${:.2}", notional),
+        }
+    }
+}
+
+/// Accepted forms of a [`Quantity`]: tagged, or a bare amount of shares as
+/// recorded before sizes had units.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum QuantityRepr {
+    Tagged(TaggedQuantity),
+    Shares(Decimal),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TaggedQuantity {
+    Shares(Decimal),
+    Notional(Decimal),
+}
+
+impl From<QuantityRepr> for Quantity {
+    fn from(repr: QuantityRepr) -> Self {
+        match repr {
+            QuantityRepr::Tagged(TaggedQuantity::Shares(shares)) | QuantityRepr::Shares(shares) => {
+                Self::Shares(shares)
+            }
+            QuantityRepr::Tagged(TaggedQuantity::Notional(notional)) => Self::Notional(notional),
+        }
+    }
+}
+
 /// State for order-related data.
 #[derive(Debug, Default)]
 pub struct OrderState {
@@ -139,6 +297,15 @@ impl OrderState {
         self.orders.iter().filter(|o| o.is_active()).collect()
     }
 
+    /// Capital locked in open buy orders.
+    pub fn locked_capital(&self) -> Decimal {
+        self.orders
+            .iter()
+            .filter(|o| o.is_active() && o.side == super::OrderSide::Buy)
+            .map(|o| o.price * o.remaining_size)
+            .sum()
+    }
+
     /// Get filled orders.
     pub fn filled_orders(&self) -> Vec<&Order> {
         self.orders
@@ -166,3 +333,50 @@ impl OrderState {
         self.open_orders().len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::PriceLevel;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_quantity_conversions() {
+        assert_eq!(
+            Quantity::Notional(dec!(5)).shares_at(dec!(0.25)),
+            Some(dec!(20))
+        );
+        assert_eq!(Quantity::Notional(dec!(5)).shares_at(Decimal::ZERO), None);
+        assert_eq!(Quantity::Shares(dec!(20)).notional_at(dec!(0.25)), dec!(5));
+
+        let mut book = OrderBookDepth::new("m1", "yes");
+        book.asks = vec![
+            PriceLevel::new(dec!(0.40), dec!(10)),
+            PriceLevel::new(dec!(0.50), dec!(10)),
+        ];
+        // $4 takes the first level, the next $2 buys 4 more at 0.50
+        assert_eq!(
+            Quantity::Notional(dec!(6)).shares_in(&book, OrderSide::Buy),
+            Some(dec!(14))
+        );
+        assert_eq!(
+            Quantity::Shares(dec!(14)).notional_in(&book, OrderSide::Buy),
+            Some(dec!(6))
+        );
+        assert_eq!(
+            Quantity::Notional(dec!(6)).shares_in(&book, OrderSide::Sell),
+            None
+        );
+    }
+
+    #[test]
+    fn test_quantity_serde() {
+        let json = serde_json::to_string(&Quantity::Notional(dec!(5))).unwrap();
+        assert_eq!(json, r#"{"notional":"5"}"#);
+        let parsed: Quantity = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, Quantity::Notional(dec!(5)));
+        // Sizes recorded before they had units are shares
+        let legacy: Quantity = serde_json::from_str(r#""12.5""#).unwrap();
+        assert_eq!(legacy, Quantity::Shares(dec!(12.5)));
+    }
+}
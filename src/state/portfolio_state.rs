@@ -1,8 +1,17 @@
 //! Portfolio and position state.
 
-use chrono::{DateTime, Utc};
+use super::Market;
+use crate::strategy::Bracket;
+use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// Minimum time between equity snapshots.
+const SNAPSHOT_INTERVAL_SECS: i64 = 60;
+
+/// Maximum number of equity snapshots kept (24 hours at one per minute).
+const MAX_SNAPSHOTS: usize = 1440;
 
 /// A balance entry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -98,6 +107,43 @@ pub struct PortfolioState {
     pub last_updated: Option<DateTime<Utc>>,
     /// Scroll offset for display.
     pub scroll_offset: usize,
+    /// Total equity over time, oldest first.
+    pub equity_history: VecDeque<EquitySnapshot>,
+    /// Stop-loss and take-profit brackets watched by the strategy engine.
+    pub brackets: Vec<Bracket>,
+}
+
+/// Total portfolio equity at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquitySnapshot {
+    /// Snapshot time.
+    pub timestamp: DateTime<Utc>,
+    /// Total portfolio value.
+    pub equity: Decimal,
+}
+
+/// Market value of positions grouped by a key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exposure {
+    /// Market question, tag or outcome name.
+    pub label: String,
+    /// Current market value.
+    pub value: Decimal,
+    /// Share of total position value, in percent.
+    pub percent: Decimal,
+}
+
+/// A held market and when it resolves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolutionEntry {
+    /// Market ID.
+    pub market_id: String,
+    /// Market question.
+    pub question: String,
+    /// Market end date.
+    pub end_date: DateTime<Utc>,
+    /// Current market value of our positions in the market.
+    pub value: Decimal,
 }
 
 impl PortfolioState {
@@ -106,6 +152,11 @@ impl PortfolioState {
         self.selected_position.and_then(|i| self.positions.get(i))
     }
 
+    /// Get the oldest bracket protecting a token.
+    pub fn bracket_for(&self, token_id: &str) -> Option<&Bracket> {
+        self.brackets.iter().find(|b| b.token_id == token_id)
+    }
+
     /// Get available USDC balance.
     pub fn available_usdc(&self) -> Decimal {
         self.balances
@@ -126,6 +177,147 @@ impl PortfolioState {
         self.total_value = positions_value + balances_value;
     }
 
+    /// Record an equity snapshot unless one was taken within the snapshot interval.
+    pub fn record_equity(&mut self, now: DateTime<Utc>) {
+        if let Some(last) = self.equity_history.back()
+            && now - last.timestamp < Duration::seconds(SNAPSHOT_INTERVAL_SECS)
+        {
+            return;
+        }
+
+        self.equity_history.push_back(EquitySnapshot {
+            timestamp: now,
+            equity: self.total_value,
+        });
+        if self.equity_history.len() > MAX_SNAPSHOTS {
+            self.equity_history.pop_front();
+        }
+    }
+
+    /// Exposure per market, largest first.
+    pub fn exposure_by_market(&self) -> Vec<Exposure> {
+        self.exposure_by(|p| vec![p.market_question.clone()])
+    }
+
+    /// Exposure per market tag, largest first. Untagged markets are grouped as "Other".
+    pub fn exposure_by_tag(&self, markets: &[Market]) -> Vec<Exposure> {
+        self.exposure_by(|p| {
+            markets
+                .iter()
+                .find(|m| m.id == p.market_id)
+                .map(|m| m.tags.clone())
+                .filter(|tags| !tags.is_empty())
+                .unwrap_or_else(|| vec!["Other".to_string()])
+        })
+    }
+
+    /// Exposure per outcome name (e.g. "Yes" vs "No"), largest first.
+    pub fn exposure_by_outcome(&self) -> Vec<Exposure> {
+        self.exposure_by(|p| vec![p.outcome_name.clone()])
+    }
+
+    /// Group position values by the keys returned for each position.
+    ///
+    /// A position with several keys (e.g. tags) counts towards each of them, so
+    /// percentages are relative to total position value and may sum above 100.
+    fn exposure_by(&self, keys: impl Fn(&Position) -> Vec<String>) -> Vec<Exposure> {
+        let total: Decimal = self.positions.iter().map(|p| p.market_value).sum();
+        let mut groups: HashMap<String, Decimal> = HashMap::new();
+
+        for position in &self.positions {
+            for key in keys(position) {
+                *groups.entry(key).or_default() += position.market_value;
+            }
+        }
+
+        let mut exposures: Vec<Exposure> = groups
+            .into_iter()
+            .map(|(label, value)| Exposure {
+                label,
+                value,
+                percent: if total.is_zero() {
+                    Decimal::ZERO
+                } else {
+                    value / total * Decimal::ONE_HUNDRED
+                },
+            })
+            .collect();
+        exposures.sort_by(|a, b| b.value.cmp(&a.value).then(a.label.cmp(&b.label)));
+        exposures
+    }
+
+    /// Loss from current value if every market resolves against us.
+    ///
+    /// Each outcome share pays 1 on resolution. Within a market the worst case
+    /// is the outcome we hold least of winning; markets are assumed binary
+    /// when they are not loaded.
+    pub fn worst_case_loss(&self, markets: &[Market]) -> Decimal {
+        let mut by_market: BTreeMap<&str, Vec<&Position>> = BTreeMap::new();
+        for position in &self.positions {
+            by_market
+                .entry(position.market_id.as_str())
+                .or_default()
+                .push(position);
+        }
+
+        by_market
+            .into_iter()
+            .map(|(market_id, positions)| {
+                let value: Decimal = positions.iter().map(|p| p.market_value).sum();
+                let outcome_count = markets
+                    .iter()
+                    .find(|m| m.id == market_id)
+                    .map(|m| m.outcomes.len())
+                    .unwrap_or(2);
+
+                let held: HashMap<&str, Decimal> = positions
+                    .iter()
+                    .map(|p| (p.token_id.as_str(), p.size))
+                    .collect();
+                let worst_payout = if held.len() < outcome_count {
+                    Decimal::ZERO
+                } else {
+                    held.values().copied().min().unwrap_or_default()
+                };
+
+                (value - worst_payout).max(Decimal::ZERO)
+            })
+            .sum()
+    }
+
+    /// Markets we hold positions in, ordered by end date. Markets without a
+    /// known end date are omitted.
+    pub fn resolution_calendar(&self, markets: &[Market]) -> Vec<ResolutionEntry> {
+        let mut entries: Vec<ResolutionEntry> = Vec::new();
+
+        for position in &self.positions {
+            if let Some(entry) = entries
+                .iter_mut()
+                .find(|e| e.market_id == position.market_id)
+            {
+                entry.value += position.market_value;
+                continue;
+            }
+
+            let Some(market) = markets.iter().find(|m| m.id == position.market_id) else {
+                continue;
+            };
+            let Some(end_date) = market.end_date else {
+                continue;
+            };
+
+            entries.push(ResolutionEntry {
+                market_id: market.id.clone(),
+                question: market.question.clone(),
+                end_date,
+                value: position.market_value,
+            });
+        }
+
+        entries.sort_by_key(|e| e.end_date);
+        entries
+    }
+
     /// Get profitable positions.
     pub fn profitable_positions(&self) -> Vec<&Position> {
         self.positions
@@ -142,3 +334,64 @@ impl PortfolioState {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn position(market_id: &str, token_id: &str, outcome: &str, size: Decimal) -> Position {
+        let mut position = Position {
+            market_id: market_id.to_string(),
+            market_question: format!("Question {}", market_id),
+            token_id: token_id.to_string(),
+            outcome_name: outcome.to_string(),
+            size,
+            avg_price: dec!(0.50),
+            current_price: dec!(0.50),
+            unrealized_pnl: Decimal::ZERO,
+            unrealized_pnl_percent: Decimal::ZERO,
+            realized_pnl: Decimal::ZERO,
+            cost_basis: Decimal::ZERO,
+            market_value: Decimal::ZERO,
+        };
+        position.calculate_pnl();
+        position
+    }
+
+    #[test]
+    fn test_exposure_and_worst_case() {
+        let portfolio = PortfolioState {
+            positions: vec![
+                position("a", "a-yes", "Yes", dec!(300)),
+                position("b", "b-yes", "Yes", dec!(60)),
+                position("b", "b-no", "No", dec!(40)),
+            ],
+            ..PortfolioState::default()
+        };
+
+        let by_market = portfolio.exposure_by_market();
+        assert_eq!(by_market[0].label, "Question a");
+        assert_eq!(by_market[0].percent, dec!(75));
+
+        let by_outcome = portfolio.exposure_by_outcome();
+        assert_eq!(by_outcome[0].label, "Yes");
+        assert_eq!(by_outcome[0].value, dec!(180));
+
+        // Market a loses its full 150; market b pays at least 40 of its 50
+        assert_eq!(portfolio.worst_case_loss(&[]), dec!(160));
+    }
+
+    #[test]
+    fn test_record_equity_is_throttled() {
+        let mut portfolio = PortfolioState::default();
+        let now = Utc::now();
+
+        portfolio.record_equity(now);
+        portfolio.record_equity(now + Duration::seconds(10));
+        assert_eq!(portfolio.equity_history.len(), 1);
+
+        portfolio.record_equity(now + Duration::seconds(SNAPSHOT_INTERVAL_SECS));
+        assert_eq!(portfolio.equity_history.len(), 2);
+    }
+}
@@ -0,0 +1,86 @@
+//! Signal history view state.
+
+use crate::strategy::{OrderLifecycle, ParentOrder, SignalRecord};
+
+/// An executed signal and the order placed for it.
+#[derive(Debug, Clone)]
+pub struct SignalEntry {
+    /// The signal as recorded by the engine.
+    pub record: SignalRecord,
+    /// The order placed for it, while still tracked.
+    pub lifecycle: Option<OrderLifecycle>,
+    /// The parent order working it, if it requested an execution algorithm.
+    pub execution: Option<ParentOrder>,
+}
+
+/// State for the signals view.
+#[derive(Debug, Clone, Default)]
+pub struct SignalViewState {
+    /// Executed signals, newest first.
+    pub entries: Vec<SignalEntry>,
+    /// Currently selected signal.
+    pub selected_index: Option<usize>,
+}
+
+impl SignalViewState {
+    /// Replace the signal list, keeping the selected signal where it still
+    /// exists.
+    pub fn load(&mut self, entries: Vec<SignalEntry>) {
+        let selected = self.selected().map(|e| e.record.signal.id.clone());
+        self.entries = entries;
+        self.selected_index = match selected {
+            _ if self.entries.is_empty() => None,
+            Some(id) => Some(
+                self.entries
+                    .iter()
+                    .position(|e| e.record.signal.id == id)
+                    .unwrap_or(0),
+            ),
+            None => Some(0),
+        };
+    }
+
+    /// Get the selected signal.
+    pub fn selected(&self) -> Option<&SignalEntry> {
+        self.entries.get(self.selected_index?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Quantity;
+    use crate::strategy::Signal;
+    use rust_decimal::Decimal;
+
+    fn entry(signal: &Signal) -> SignalEntry {
+        SignalEntry {
+            record: SignalRecord {
+                signal: signal.clone(),
+                executed: true,
+                executed_at: None,
+                client_order_id: None,
+                result: None,
+            },
+            lifecycle: None,
+            execution: None,
+        }
+    }
+
+    #[test]
+    fn test_load_follows_selected_signal() {
+        let first = Signal::buy("m", "t", Quantity::Shares(Decimal::ONE));
+        let second = Signal::sell("m", "t", Quantity::Shares(Decimal::ONE));
+        let mut state = SignalViewState::default();
+
+        state.load(vec![entry(&first)]);
+        assert_eq!(state.selected_index, Some(0));
+
+        // A newer signal is listed first; the selection stays on the old one
+        state.load(vec![entry(&second), entry(&first)]);
+        assert_eq!(state.selected().unwrap().record.signal.id, first.id);
+
+        state.load(Vec::new());
+        assert!(state.selected().is_none());
+    }
+}
@@ -0,0 +1,150 @@
+//! Strategy parameter editor state.
+
+use crate::strategy::{ParameterChange, ParameterField};
+
+/// A registered strategy and its tunable parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyEntry {
+    /// Strategy name.
+    pub name: String,
+    /// Strategy status (Running, Paused, ...).
+    pub status: String,
+    /// Human-readable description.
+    pub description: String,
+    /// Parameters with their current values, sorted by name.
+    pub parameters: Vec<ParameterField>,
+}
+
+/// State for the strategies view.
+#[derive(Debug, Clone, Default)]
+pub struct StrategyViewState {
+    /// Registered strategies, sorted by name.
+    pub strategies: Vec<StrategyEntry>,
+    /// Index of the strategy being edited.
+    pub active_index: usize,
+    /// Currently selected parameter.
+    pub selected_index: Option<usize>,
+    /// Runtime parameter changes, oldest first.
+    pub changes: Vec<ParameterChange>,
+}
+
+impl StrategyViewState {
+    /// Replace the strategy list, keeping the active strategy and the
+    /// selected parameter where they still exist.
+    pub fn load(&mut self, strategies: Vec<StrategyEntry>, changes: Vec<ParameterChange>) {
+        let active = self.active().map(|s| s.name.clone());
+        self.strategies = strategies;
+        self.changes = changes;
+        self.active_index = active
+            .and_then(|name| self.strategies.iter().position(|s| s.name == name))
+            .unwrap_or(0);
+        self.clamp_selection();
+    }
+
+    /// Get the strategy being edited.
+    pub fn active(&self) -> Option<&StrategyEntry> {
+        self.strategies.get(self.active_index)
+    }
+
+    /// Get the selected parameter of the active strategy.
+    pub fn selected_field(&self) -> Option<&ParameterField> {
+        self.active()?.parameters.get(self.selected_index?)
+    }
+
+    /// Number of parameters of the active strategy.
+    pub fn active_len(&self) -> usize {
+        self.active().map(|s| s.parameters.len()).unwrap_or(0)
+    }
+
+    /// Switch to the next or previous strategy, wrapping around.
+    pub fn cycle(&mut self, delta: i32) {
+        if self.strategies.is_empty() {
+            return;
+        }
+        let len = self.strategies.len() as i32;
+        self.active_index = (self.active_index as i32 + delta).rem_euclid(len) as usize;
+        self.selected_index = None;
+    }
+
+    /// Changes made to the active strategy, newest first.
+    pub fn active_changes(&self) -> Vec<&ParameterChange> {
+        let Some(active) = self.active() else {
+            return Vec::new();
+        };
+        self.changes
+            .iter()
+            .rev()
+            .filter(|c| c.strategy == active.name)
+            .collect()
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.active_len();
+        self.selected_index = match self.selected_index {
+            _ if len == 0 => None,
+            Some(i) => Some(i.min(len - 1)),
+            None => None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::{ParameterDef, ParameterType, ParameterValue};
+
+    fn entry(name: &str, parameters: &[&str]) -> StrategyEntry {
+        StrategyEntry {
+            name: name.to_string(),
+            status: "Running".to_string(),
+            description: String::new(),
+            parameters: parameters
+                .iter()
+                .map(|p| ParameterField {
+                    def: ParameterDef {
+                        name: p.to_string(),
+                        description: String::new(),
+                        param_type: ParameterType::Integer,
+                        default: ParameterValue::Integer(1),
+                        min: None,
+                        max: None,
+                        allowed_values: None,
+                    },
+                    current: ParameterValue::Integer(1),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_cycle_wraps_and_clears_selection() {
+        let mut state = StrategyViewState::default();
+        state.load(vec![entry("a", &["x"]), entry("b", &["y"])], Vec::new());
+        state.selected_index = Some(0);
+
+        state.cycle(-1);
+        assert_eq!(state.active().unwrap().name, "b");
+        assert_eq!(state.selected_index, None);
+        state.cycle(1);
+        assert_eq!(state.active().unwrap().name, "a");
+    }
+
+    #[test]
+    fn test_load_keeps_active_strategy_and_clamps_selection() {
+        let mut state = StrategyViewState::default();
+        state.load(
+            vec![entry("a", &["x"]), entry("b", &["x", "y", "z"])],
+            Vec::new(),
+        );
+        state.cycle(1);
+        state.selected_index = Some(2);
+
+        state.load(vec![entry("b", &["x"]), entry("c", &[])], Vec::new());
+        assert_eq!(state.active_index, 0);
+        assert_eq!(state.selected_field().unwrap().def.name, "x");
+
+        state.load(vec![entry("c", &[])], Vec::new());
+        assert_eq!(state.active().unwrap().name, "c");
+        assert_eq!(state.selected_index, None);
+    }
+}
@@ -0,0 +1,463 @@
+//! Fill history and realized PnL accounting.
+
+use super::{OrderSide, Position};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+
+/// A single fill (matched trade) of one of our orders.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fill {
+    /// Trade ID.
+    pub id: String,
+    /// ID of our order that was filled, empty if unknown.
+    #[serde(default)]
+    pub order_id: String,
+    /// Market (condition) ID.
+    pub market_id: String,
+    /// Token ID.
+    pub token_id: String,
+    /// Outcome name (e.g., "Yes", "No").
+    pub outcome_name: String,
+    /// Side of our order.
+    pub side: OrderSide,
+    /// Fill price.
+    pub price: Decimal,
+    /// Filled size in shares.
+    pub size: Decimal,
+    /// Fee paid, in USDC.
+    pub fee: Decimal,
+    /// Match time.
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Fill {
+    /// Notional value of the fill, excluding fees.
+    pub fn notional(&self) -> Decimal {
+        self.price * self.size
+    }
+}
+
+/// Lot accounting method used to compute realized PnL.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostBasisMethod {
+    /// Sells close the oldest open lots first.
+    #[default]
+    Fifo,
+    /// All buys are pooled into a single lot at the average cost.
+    AverageCost,
+}
+
+impl std::fmt::Display for CostBasisMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fifo => write!(f, "FIFO"),
+            Self::AverageCost => write!(f, "Average cost"),
+        }
+    }
+}
+
+/// Open lot of shares. `cost` includes the buy fee.
+#[derive(Debug, Clone, Copy)]
+struct Lot {
+    size: Decimal,
+    cost: Decimal,
+}
+
+/// Accounting result for a single token.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenPnl {
+    /// Token ID.
+    pub token_id: String,
+    /// Market ID.
+    pub market_id: String,
+    /// Outcome name.
+    pub outcome_name: String,
+    /// Shares still held according to the fill history.
+    pub size: Decimal,
+    /// Cost basis of the shares still held, including buy fees.
+    pub cost_basis: Decimal,
+    /// Realized PnL, net of fees.
+    pub realized_pnl: Decimal,
+    /// Total fees paid.
+    pub fees: Decimal,
+}
+
+impl TokenPnl {
+    /// Average cost per share still held.
+    pub fn avg_price(&self) -> Decimal {
+        if self.size.is_zero() {
+            Decimal::ZERO
+        } else {
+            self.cost_basis / self.size
+        }
+    }
+
+    /// Unrealized PnL of the shares still held at the given mark price.
+    pub fn unrealized_pnl(&self, mark: Decimal) -> Decimal {
+        self.size * mark - self.cost_basis
+    }
+}
+
+/// Aggregated trading activity for a market or a day.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TradeSummary {
+    /// Market ID or date (`YYYY-MM-DD`, UTC).
+    pub key: String,
+    /// Number of fills.
+    pub trades: usize,
+    /// Traded notional.
+    pub volume: Decimal,
+    /// Fees paid.
+    pub fees: Decimal,
+    /// Realized PnL, net of fees.
+    pub realized_pnl: Decimal,
+}
+
+/// Replay fills in time order and compute per-token PnL.
+///
+/// Returns the per-token results and the realized PnL of each fill (zero for
+/// buys), aligned with `fills`. Sells beyond the shares bought in the history
+/// have no known cost basis and only their matched portion is realized.
+pub fn compute_pnl(
+    fills: &[Fill],
+    method: CostBasisMethod,
+) -> (HashMap<String, TokenPnl>, Vec<Decimal>) {
+    let mut lots: HashMap<&str, VecDeque<Lot>> = HashMap::new();
+    let mut tokens: HashMap<String, TokenPnl> = HashMap::new();
+    let mut realized = Vec::with_capacity(fills.len());
+
+    for fill in fills {
+        let token = tokens
+            .entry(fill.token_id.clone())
+            .or_insert_with(|| TokenPnl {
+                token_id: fill.token_id.clone(),
+                market_id: fill.market_id.clone(),
+                outcome_name: fill.outcome_name.clone(),
+                ..TokenPnl::default()
+            });
+        token.fees += fill.fee;
+
+        let open = lots.entry(fill.token_id.as_str()).or_default();
+        let fill_pnl = match fill.side {
+            OrderSide::Buy => {
+                let lot = Lot {
+                    size: fill.size,
+                    cost: fill.notional() + fill.fee,
+                };
+                match (method, open.front_mut()) {
+                    (CostBasisMethod::AverageCost, Some(pooled)) => {
+                        pooled.size += lot.size;
+                        pooled.cost += lot.cost;
+                    }
+                    _ => open.push_back(lot),
+                }
+                Decimal::ZERO
+            }
+            OrderSide::Sell => {
+                let mut remaining = fill.size;
+                let mut basis = Decimal::ZERO;
+                while remaining > Decimal::ZERO {
+                    let Some(lot) = open.front_mut() else {
+                        break;
+                    };
+                    let take = remaining.min(lot.size);
+                    let portion = lot.cost * take / lot.size;
+                    lot.size -= take;
+                    lot.cost -= portion;
+                    basis += portion;
+                    remaining -= take;
+                    if lot.size.is_zero() {
+                        open.pop_front();
+                    }
+                }
+                let matched = fill.size - remaining;
+                matched * fill.price - fill.fee - basis
+            }
+        };
+
+        token.realized_pnl += fill_pnl;
+        token.size = open.iter().map(|l| l.size).sum();
+        token.cost_basis = open.iter().map(|l| l.cost).sum();
+        realized.push(fill_pnl);
+    }
+
+    (tokens, realized)
+}
+
+/// On-disk representation of the fill history.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TradeFile {
+    #[serde(default)]
+    fills: Vec<Fill>,
+}
+
+/// State for our fill history and the PnL derived from it.
+#[derive(Debug, Default)]
+pub struct TradeState {
+    /// All known fills, oldest first.
+    pub fills: Vec<Fill>,
+    /// Lot accounting method.
+    pub method: CostBasisMethod,
+    /// Per-token PnL computed from `fills`.
+    pub pnl: HashMap<String, TokenPnl>,
+    /// Realized PnL of each fill, aligned with `fills`.
+    pub fill_pnl: Vec<Decimal>,
+    /// Currently selected fill index (newest first, as displayed).
+    pub selected_index: Option<usize>,
+    /// Whether trades are loading.
+    pub loading: bool,
+    /// Last update timestamp.
+    pub last_updated: Option<DateTime<Utc>>,
+}
+
+impl TradeState {
+    /// Get the default trade history file path, with one file per profile.
+    pub fn default_path(profile: Option<&str>) -> crate::Result<PathBuf> {
+        let directory = crate::config::data_dir()?;
+        Ok(match profile {
+            Some(profile) => directory.join("trades").join(format!("{}.toml", profile)),
+            None => directory.join("trades.toml"),
+        })
+    }
+
+    /// Load the fill history from file, returning an empty state if the file doesn't exist.
+    pub fn load(path: Option<PathBuf>, method: CostBasisMethod) -> crate::Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None => Self::default_path(None)?,
+        };
+
+        let mut state = Self {
+            method,
+            ..Self::default()
+        };
+
+        if !path.exists() {
+            return Ok(state);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let file: TradeFile =
+            toml::from_str(&content).map_err(|e| crate::Error::config(e.to_string()))?;
+
+        state.merge(file.fills);
+        Ok(state)
+    }
+
+    /// Save the fill history to file.
+    pub fn save(&self, path: Option<PathBuf>) -> crate::Result<()> {
+        let path = match path {
+            Some(path) => path,
+            None => Self::default_path(None)?,
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = TradeFile {
+            fills: self.fills.clone(),
+        };
+        let content =
+            toml::to_string_pretty(&file).map_err(|e| crate::Error::config(e.to_string()))?;
+        std::fs::write(&path, content)?;
+        Ok(())
+    }
+
+    /// Merge newly fetched fills, skipping ones already known, and recompute PnL.
+    ///
+    /// Returns the number of new fills.
+    pub fn merge(&mut self, fills: Vec<Fill>) -> usize {
+        let mut known: HashSet<String> = self.fills.iter().map(|f| f.id.clone()).collect();
+        let before = self.fills.len();
+
+        for fill in fills {
+            if known.insert(fill.id.clone()) {
+                self.fills.push(fill);
+            }
+        }
+
+        self.fills.sort_by_key(|f| f.timestamp);
+        self.recompute();
+        self.fills.len() - before
+    }
+
+    /// Forget the fill history, keeping the accounting method.
+    pub fn clear(&mut self) {
+        *self = Self {
+            method: self.method,
+            ..Self::default()
+        };
+    }
+
+    /// Change the accounting method and recompute PnL.
+    pub fn set_method(&mut self, method: CostBasisMethod) {
+        self.method = method;
+        self.recompute();
+    }
+
+    /// Recompute per-token and per-fill PnL from the fill history.
+    pub fn recompute(&mut self) {
+        let (pnl, fill_pnl) = compute_pnl(&self.fills, self.method);
+        self.pnl = pnl;
+        self.fill_pnl = fill_pnl;
+    }
+
+    /// Fills of one of our orders, oldest first.
+    pub fn order_fills<'a>(&'a self, order_id: &'a str) -> impl Iterator<Item = &'a Fill> + 'a {
+        self.fills
+            .iter()
+            .filter(move |f| !order_id.is_empty() && f.order_id == order_id)
+    }
+
+    /// Timestamp of the newest known fill, used to fetch only newer ones.
+    pub fn latest_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.fills.last().map(|f| f.timestamp)
+    }
+
+    /// Total realized PnL across all tokens.
+    pub fn total_realized_pnl(&self) -> Decimal {
+        self.pnl.values().map(|p| p.realized_pnl).sum()
+    }
+
+    /// Total fees paid.
+    pub fn total_fees(&self) -> Decimal {
+        self.fills.iter().map(|f| f.fee).sum()
+    }
+
+    /// Summaries per market, sorted by market ID.
+    pub fn market_summaries(&self) -> Vec<TradeSummary> {
+        self.summarize(|fill| fill.market_id.clone())
+    }
+
+    /// Summaries per UTC day, newest first.
+    pub fn daily_summaries(&self) -> Vec<TradeSummary> {
+        let mut days = self.summarize(|fill| fill.timestamp.date_naive().to_string());
+        days.reverse();
+        days
+    }
+
+    fn summarize(&self, key: impl Fn(&Fill) -> String) -> Vec<TradeSummary> {
+        let mut groups: BTreeMap<String, TradeSummary> = BTreeMap::new();
+
+        for (fill, pnl) in self.fills.iter().zip(&self.fill_pnl) {
+            let key = key(fill);
+            let summary = groups.entry(key.clone()).or_insert_with(|| TradeSummary {
+                key,
+                ..TradeSummary::default()
+            });
+            summary.trades += 1;
+            summary.volume += fill.notional();
+            summary.fees += fill.fee;
+            summary.realized_pnl += *pnl;
+        }
+
+        groups.into_values().collect()
+    }
+
+    /// Overwrite the realized PnL of positions with the locally computed values.
+    pub fn apply_to_positions(&self, positions: &mut [Position]) {
+        for position in positions {
+            if let Some(pnl) = self.pnl.get(&position.token_id) {
+                position.realized_pnl = pnl.realized_pnl;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn fill(id: &str, side: OrderSide, price: Decimal, size: Decimal, fee: Decimal) -> Fill {
+        Fill {
+            id: id.to_string(),
+            order_id: String::new(),
+            market_id: "mkt".to_string(),
+            token_id: "tok".to_string(),
+            outcome_name: "Yes".to_string(),
+            side,
+            price,
+            size,
+            fee,
+            timestamp: DateTime::from_timestamp(1_700_000_000 + id.len() as i64, 0).unwrap(),
+        }
+    }
+
+    fn history() -> Vec<Fill> {
+        vec![
+            fill("a", OrderSide::Buy, dec!(0.40), dec!(100), dec!(0)),
+            fill("bb", OrderSide::Buy, dec!(0.60), dec!(100), dec!(0)),
+            fill("ccc", OrderSide::Sell, dec!(0.70), dec!(100), dec!(1)),
+        ]
+    }
+
+    #[test]
+    fn test_fifo_closes_oldest_lot() {
+        let (tokens, realized) = compute_pnl(&history(), CostBasisMethod::Fifo);
+        let tok = &tokens["tok"];
+
+        // Sold 100 @ 0.70 against the 0.40 lot, minus a 1 USDC fee
+        assert_eq!(realized, vec![dec!(0), dec!(0), dec!(29)]);
+        assert_eq!(tok.realized_pnl, dec!(29));
+        assert_eq!(tok.size, dec!(100));
+        assert_eq!(tok.avg_price(), dec!(0.60));
+        assert_eq!(tok.unrealized_pnl(dec!(0.65)), dec!(5));
+    }
+
+    #[test]
+    fn test_average_cost_pools_lots() {
+        let (tokens, _) = compute_pnl(&history(), CostBasisMethod::AverageCost);
+        let tok = &tokens["tok"];
+
+        assert_eq!(tok.realized_pnl, dec!(19));
+        assert_eq!(tok.size, dec!(100));
+        assert_eq!(tok.avg_price(), dec!(0.50));
+    }
+
+    #[test]
+    fn test_merge_dedups_and_summarizes() {
+        let mut state = TradeState::default();
+        assert_eq!(state.merge(history()), 3);
+        assert_eq!(state.merge(history()), 0);
+
+        let markets = state.market_summaries();
+        assert_eq!(markets.len(), 1);
+        assert_eq!(markets[0].trades, 3);
+        assert_eq!(markets[0].volume, dec!(170));
+        assert_eq!(markets[0].fees, dec!(1));
+        assert_eq!(markets[0].realized_pnl, dec!(29));
+        assert_eq!(state.daily_summaries().len(), 1);
+    }
+
+    #[test]
+    fn test_history_is_kept_per_profile() {
+        let main = TradeState::default_path(Some("main")).unwrap();
+        let bot = TradeState::default_path(Some("bot")).unwrap();
+        assert_ne!(main, bot);
+        assert_ne!(main, TradeState::default_path(None).unwrap());
+
+        let path =
+            std::env::temp_dir().join(format!("clobster-trades-{}.toml", uuid::Uuid::new_v4()));
+        let mut state = TradeState::load(Some(path.clone()), CostBasisMethod::AverageCost).unwrap();
+        state.merge(history());
+        state.save(Some(path.clone())).unwrap();
+
+        let loaded = TradeState::load(Some(path.clone()), CostBasisMethod::AverageCost).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.fills.len(), 3);
+        assert_eq!(loaded.pnl["tok"].realized_pnl, dec!(19));
+
+        // Switching profile starts from an empty history
+        state.selected_index = Some(1);
+        state.clear();
+        assert!(state.fills.is_empty() && state.pnl.is_empty() && state.fill_pnl.is_empty());
+        assert_eq!(state.selected_index, None);
+        assert_eq!(state.method, CostBasisMethod::AverageCost);
+    }
+}
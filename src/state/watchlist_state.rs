@@ -0,0 +1,287 @@
+//! Watchlist state.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Name of the watchlist created when none exist.
+pub const DEFAULT_WATCHLIST: &str = "Favourites";
+
+/// A named list of pinned markets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Watchlist {
+    /// Watchlist name.
+    pub name: String,
+    /// Market condition IDs, in the order they were added.
+    #[serde(default)]
+    pub market_ids: Vec<String>,
+}
+
+impl Watchlist {
+    /// Create a new empty watchlist.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            market_ids: Vec::new(),
+        }
+    }
+
+    /// Check if a market is in this watchlist.
+    pub fn contains(&self, market_id: &str) -> bool {
+        self.market_ids.iter().any(|id| id == market_id)
+    }
+
+    /// Add a market if not already present.
+    pub fn add(&mut self, market_id: impl Into<String>) {
+        let market_id = market_id.into();
+        if !self.contains(&market_id) {
+            self.market_ids.push(market_id);
+        }
+    }
+
+    /// Remove a market.
+    pub fn remove(&mut self, market_id: &str) {
+        self.market_ids.retain(|id| id != market_id);
+    }
+
+    /// Add the market if absent, otherwise remove it.
+    ///
+    /// Returns `true` if the market is in the watchlist afterwards.
+    pub fn toggle(&mut self, market_id: &str) -> bool {
+        if self.contains(market_id) {
+            self.remove(market_id);
+            false
+        } else {
+            self.add(market_id);
+            true
+        }
+    }
+}
+
+/// On-disk representation of all watchlists.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct WatchlistFile {
+    #[serde(default)]
+    watchlists: Vec<Watchlist>,
+}
+
+/// State for watchlists.
+#[derive(Debug)]
+pub struct WatchlistState {
+    /// All watchlists.
+    pub watchlists: Vec<Watchlist>,
+    /// Index of the active watchlist.
+    pub active_index: usize,
+    /// Currently selected entry in the active watchlist.
+    pub selected_index: Option<usize>,
+}
+
+impl Default for WatchlistState {
+    fn default() -> Self {
+        Self {
+            watchlists: vec![Watchlist::new(DEFAULT_WATCHLIST)],
+            active_index: 0,
+            selected_index: None,
+        }
+    }
+}
+
+impl WatchlistState {
+    /// Get the default watchlist file path.
+    pub fn default_path() -> crate::Result<PathBuf> {
+        crate::config::data_dir().map(|p| p.join("watchlists.toml"))
+    }
+
+    /// Load watchlists from file, returning the default state if the file doesn't exist.
+    pub fn load(path: Option<PathBuf>) -> crate::Result<Self> {
+        let path = match path {
+            Some(path) => path,
+            None => Self::default_path()?,
+        };
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let file: WatchlistFile =
+            toml::from_str(&content).map_err(|e| crate::Error::config(e.to_string()))?;
+
+        let mut state = Self {
+            watchlists: file.watchlists,
+            ..Self::default()
+        };
+        if state.watchlists.is_empty() {
+            state.watchlists.push(Watchlist::new(DEFAULT_WATCHLIST));
+        }
+        Ok(state)
+    }
+
+    /// Save watchlists to file.
+    pub fn save(&self, path: Option<PathBuf>) -> crate::Result<()> {
+        let path = match path {
+            Some(path) => path,
+            None => Self::default_path()?,
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = WatchlistFile {
+            watchlists: self.watchlists.clone(),
+        };
+        let content =
+            toml::to_string_pretty(&file).map_err(|e| crate::Error::config(e.to_string()))?;
+        std::fs::write(&path, content)?;
+        Ok(())
+    }
+
+    /// Get the active watchlist.
+    pub fn active(&self) -> Option<&Watchlist> {
+        self.watchlists.get(self.active_index)
+    }
+
+    /// Get the active watchlist mutably.
+    pub fn active_mut(&mut self) -> Option<&mut Watchlist> {
+        self.watchlists.get_mut(self.active_index)
+    }
+
+    /// Get a watchlist by name.
+    pub fn get(&self, name: &str) -> Option<&Watchlist> {
+        self.watchlists.iter().find(|w| w.name == name)
+    }
+
+    /// Check if a market is in the active watchlist.
+    pub fn is_watched(&self, market_id: &str) -> bool {
+        self.active().is_some_and(|w| w.contains(market_id))
+    }
+
+    /// Get the market ID of the selected entry in the active watchlist.
+    pub fn selected_market_id(&self) -> Option<&str> {
+        let index = self.selected_index?;
+        self.active()
+            .and_then(|w| w.market_ids.get(index))
+            .map(String::as_str)
+    }
+
+    /// Toggle a market in the active watchlist.
+    pub fn toggle(&mut self, market_id: &str) -> bool {
+        let added = match self.active_mut() {
+            Some(watchlist) => watchlist.toggle(market_id),
+            None => return false,
+        };
+        self.clamp_selection();
+        added
+    }
+
+    /// Create a new watchlist and make it active.
+    ///
+    /// Does nothing if the name is empty. If a watchlist with the same name
+    /// already exists, it becomes active instead.
+    pub fn create(&mut self, name: &str) {
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+
+        match self.watchlists.iter().position(|w| w.name == name) {
+            Some(index) => self.active_index = index,
+            None => {
+                self.watchlists.push(Watchlist::new(name));
+                self.active_index = self.watchlists.len() - 1;
+            }
+        }
+        self.selected_index = None;
+    }
+
+    /// Delete a watchlist by name.
+    ///
+    /// The last remaining watchlist cannot be deleted.
+    pub fn delete(&mut self, name: &str) {
+        if self.watchlists.len() <= 1 {
+            return;
+        }
+        self.watchlists.retain(|w| w.name != name);
+        self.active_index = self.active_index.min(self.watchlists.len() - 1);
+        self.selected_index = None;
+    }
+
+    /// Cycle the active watchlist by `delta` positions, wrapping around.
+    pub fn cycle(&mut self, delta: i32) {
+        let len = self.watchlists.len() as i32;
+        if len == 0 {
+            return;
+        }
+        self.active_index = (self.active_index as i32 + delta).rem_euclid(len) as usize;
+        self.selected_index = None;
+    }
+
+    /// Number of entries in the active watchlist.
+    pub fn active_len(&self) -> usize {
+        self.active().map(|w| w.market_ids.len()).unwrap_or(0)
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.active_len();
+        self.selected_index = match self.selected_index {
+            Some(_) if len == 0 => None,
+            Some(i) => Some(i.min(len - 1)),
+            None => None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_adds_and_removes() {
+        let mut state = WatchlistState::default();
+        assert!(state.toggle("market_1"));
+        assert!(state.is_watched("market_1"));
+        assert!(!state.toggle("market_1"));
+        assert!(!state.is_watched("market_1"));
+    }
+
+    #[test]
+    fn test_create_and_cycle() {
+        let mut state = WatchlistState::default();
+        state.create("Elections");
+        assert_eq!(state.active().unwrap().name, "Elections");
+
+        state.cycle(1);
+        assert_eq!(state.active().unwrap().name, DEFAULT_WATCHLIST);
+        state.cycle(-1);
+        assert_eq!(state.active().unwrap().name, "Elections");
+
+        // Creating an existing name re-activates it rather than duplicating.
+        state.create(DEFAULT_WATCHLIST);
+        assert_eq!(state.watchlists.len(), 2);
+        assert_eq!(state.active_index, 0);
+    }
+
+    #[test]
+    fn test_delete_keeps_last_watchlist() {
+        let mut state = WatchlistState::default();
+        state.delete(DEFAULT_WATCHLIST);
+        assert_eq!(state.watchlists.len(), 1);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("clobster-watchlists-{}.toml", uuid::Uuid::new_v4()));
+
+        let mut state = WatchlistState::default();
+        state.toggle("market_1");
+        state.create("Sports");
+        state.toggle("market_2");
+        state.save(Some(path.clone())).unwrap();
+
+        let loaded = WatchlistState::load(Some(path.clone())).unwrap();
+        assert_eq!(loaded.watchlists, state.watchlists);
+
+        let _ = std::fs::remove_file(path);
+    }
+}
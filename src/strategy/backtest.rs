@@ -0,0 +1,494 @@
+//! Offline replay of recorded market data.
+//!
+//! A recording is a JSON Lines file with one [`Frame`] per line, appended by
+//! the daemon when `daemon.record_path` is set. [`Backtest`] feeds the frames
+//! to a strategy in order and simulates its fills.
+
+use super::context::PricePoint;
+use super::{
+    BracketBook, MarketSnapshot, PositionSnapshot, SignalAction, Strategy, StrategyContext,
+};
+use crate::state::{MarketStatus, OrderSide};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+/// Market data at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Frame {
+    /// When the data was captured.
+    pub timestamp: DateTime<Utc>,
+    /// Markets, sorted by condition ID.
+    pub markets: Vec<RecordedMarket>,
+}
+
+impl Frame {
+    /// Capture the markets of a strategy context.
+    pub fn from_context(ctx: &StrategyContext) -> Self {
+        let mut markets: Vec<RecordedMarket> = ctx
+            .markets
+            .values()
+            .map(|m| RecordedMarket {
+                condition_id: m.condition_id.clone(),
+                question: m.question.clone(),
+                status: m.status,
+                token_ids: m.token_ids.clone(),
+                token_names: m.token_names.clone(),
+                token_prices: m.token_prices.clone(),
+                volume_24h: m.volume_24h,
+                liquidity: m.liquidity,
+                spread: m.spread,
+            })
+            .collect();
+        markets.sort_by(|a, b| a.condition_id.cmp(&b.condition_id));
+        Self {
+            timestamp: ctx.timestamp,
+            markets,
+        }
+    }
+}
+
+/// A recorded market snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMarket {
+    /// Market condition ID.
+    pub condition_id: String,
+    /// Market question/title.
+    #[serde(default)]
+    pub question: String,
+    /// Market status.
+    #[serde(default)]
+    pub status: MarketStatus,
+    /// Token IDs for outcomes.
+    pub token_ids: Vec<String>,
+    /// Token names/labels.
+    #[serde(default)]
+    pub token_names: Vec<String>,
+    /// Token prices, in outcome order.
+    pub token_prices: Vec<Decimal>,
+    /// 24h volume.
+    #[serde(default)]
+    pub volume_24h: Decimal,
+    /// Total liquidity.
+    #[serde(default)]
+    pub liquidity: Decimal,
+    /// Spread of the first outcome.
+    #[serde(default)]
+    pub spread: Option<Decimal>,
+}
+
+impl RecordedMarket {
+    fn snapshot(&self) -> MarketSnapshot {
+        MarketSnapshot {
+            condition_id: self.condition_id.clone(),
+            question: self.question.clone(),
+            status: self.status,
+            token_ids: self.token_ids.clone(),
+            token_names: self.token_names.clone(),
+            token_prices: self.token_prices.clone(),
+            volume_24h: self.volume_24h,
+            liquidity: self.liquidity,
+            spread: self.spread,
+            end_date: None,
+        }
+    }
+
+    fn price_of(&self, token_id: &str) -> Option<Decimal> {
+        let index = self.token_ids.iter().position(|t| t == token_id)?;
+        self.token_prices.get(index).copied()
+    }
+}
+
+/// Recorded market data, oldest frame first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+    /// The recorded frames.
+    pub frames: Vec<Frame>,
+}
+
+impl Recording {
+    /// Load a JSON Lines recording, skipping blank lines.
+    pub fn load(path: &Path) -> crate::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let mut frames = Vec::new();
+        for (number, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let frame = serde_json::from_str(line).map_err(|e| {
+                crate::Error::invalid_input(format!(
+                    "{} line {}: {}",
+                    path.display(),
+                    number + 1,
+                    e
+                ))
+            })?;
+            frames.push(frame);
+        }
+        frames.sort_by_key(|f: &Frame| f.timestamp);
+        Ok(Self { frames })
+    }
+
+    /// Append a frame to the recording at `path`, creating it if needed.
+    pub fn append(path: &Path, frame: &Frame) -> crate::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", serde_json::to_string(frame)?)?;
+        Ok(())
+    }
+
+    /// Number of frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if there are no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Replays recorded frames through a strategy.
+///
+/// The fill model is deliberately simple: every signal whose limit price is
+/// marketable fills in full at the frame's price, with no fees, slippage or
+/// risk limits. Buys are capped by the cash available and sells by the
+/// position held. `size` is a number of shares. Orders never rest, so
+/// cancel signals have nothing to cancel and amend signals are treated as new
+/// orders; an order's ID is its signal's ID. Stop-loss and take-profit
+/// levels on entries are watched as [`BracketBook`] brackets, as the engine
+/// does live, and exit at the price of the frame they are crossed in.
+/// Execution algorithms are ignored: every order is sent whole.
+#[derive(Debug, Clone)]
+pub struct Backtest {
+    /// Cash at the start of the replay.
+    pub starting_balance: Decimal,
+}
+
+impl Backtest {
+    /// Create a backtest starting with `starting_balance` in cash.
+    pub fn new(starting_balance: Decimal) -> Self {
+        Self { starting_balance }
+    }
+
+    /// Replay `frames` through an initialized strategy.
+    pub fn run(&self, strategy: &mut dyn Strategy, frames: &[Frame]) -> BacktestResult {
+        self.run_from(strategy, frames, 0)
+    }
+
+    /// Replay `frames[start..]`, using the earlier frames only to build up
+    /// price history so indicators are warm when trading begins.
+    pub fn run_from(
+        &self,
+        strategy: &mut dyn Strategy,
+        frames: &[Frame],
+        start: usize,
+    ) -> BacktestResult {
+        let mut ctx = StrategyContext::new();
+        let mut cash = self.starting_balance;
+        // token ID -> (market ID, shares, average price)
+        let mut holdings: HashMap<String, (String, Decimal, Decimal)> = HashMap::new();
+        let mut prices: HashMap<String, Decimal> = HashMap::new();
+        let mut trades = 0;
+        let mut peak = self.starting_balance;
+        let mut max_drawdown = Decimal::ZERO;
+        let mut equity = self.starting_balance;
+        let mut brackets = BracketBook::new();
+
+        for (index, frame) in frames.iter().enumerate() {
+            ctx.timestamp = frame.timestamp;
+            ctx.markets.clear();
+            for market in &frame.markets {
+                ctx.markets
+                    .insert(market.condition_id.clone(), market.snapshot());
+                for (token_id, price) in market.token_ids.iter().zip(&market.token_prices) {
+                    prices.insert(token_id.clone(), *price);
+                }
+                if let Some(price) = market.token_prices.first() {
+                    ctx.price_history
+                        .entry(market.condition_id.clone())
+                        .or_default()
+                        .push(PricePoint {
+                            timestamp: frame.timestamp,
+                            price: *price,
+                            volume: Some(market.volume_24h),
+                        });
+                }
+            }
+            if index < start {
+                continue;
+            }
+            update_positions(&mut ctx, &holdings, &prices, cash);
+
+            let exits: Vec<_> = brackets
+                .check(&ctx)
+                .into_iter()
+                .map(|(id, signal)| (Some(id), signal))
+                .collect();
+            let entries = strategy.evaluate(&ctx).into_iter().map(|s| (None, s));
+
+            for (bracket_id, signal) in exits.into_iter().chain(entries) {
+                if matches!(signal.action, SignalAction::Cancel { .. }) {
+                    continue;
+                }
+                match &bracket_id {
+                    Some(id) => brackets.exit_submitted(id, Some(signal.id.clone())),
+                    None => {
+                        brackets.arm(&signal, &signal.id);
+                    }
+                }
+                let Some(price) = frame
+                    .markets
+                    .iter()
+                    .find(|m| m.condition_id == signal.market_id)
+                    .and_then(|m| m.price_of(&signal.token_id))
+                else {
+                    brackets.ended(&signal.id, true);
+                    strategy.on_signal_executed(&signal, false);
+                    continue;
+                };
+                let marketable = match (signal.side, signal.price) {
+                    (OrderSide::Buy, Some(limit)) => limit >= price,
+                    (OrderSide::Sell, Some(limit)) => limit <= price,
+                    (_, None) => true,
+                };
+
+                // Notionals are converted at the frame's price
+                let wanted = signal.size.shares_at(price).unwrap_or_default();
+                let size = match signal.side {
+                    OrderSide::Buy if !price.is_zero() => wanted.min(cash / price),
+                    OrderSide::Buy => Decimal::ZERO,
+                    OrderSide::Sell => holdings
+                        .get(&signal.token_id)
+                        .map(|(_, held, _)| wanted.min(*held))
+                        .unwrap_or(Decimal::ZERO),
+                };
+                if !marketable || size <= Decimal::ZERO {
+                    brackets.ended(&signal.id, true);
+                    strategy.on_signal_executed(&signal, false);
+                    continue;
+                }
+
+                let holding = holdings
+                    .entry(signal.token_id.clone())
+                    .or_insert_with(|| (signal.market_id.clone(), Decimal::ZERO, Decimal::ZERO));
+                match signal.side {
+                    OrderSide::Buy => {
+                        holding.2 = (holding.1 * holding.2 + size * price) / (holding.1 + size);
+                        holding.1 += size;
+                        cash -= size * price;
+                    }
+                    OrderSide::Sell => {
+                        holding.1 -= size;
+                        cash += size * price;
+                    }
+                }
+                if holding.1.is_zero() {
+                    holdings.remove(&signal.token_id);
+                }
+                trades += 1;
+                // Orders never rest, so whatever did not fill now never will
+                brackets.filled(&signal.id, price, size);
+                brackets.ended(&signal.id, false);
+
+                strategy.on_signal_executed(&signal, true);
+                strategy.on_order_placed(&signal.id, &signal.id);
+                strategy.on_order_filled(&signal.id, price, size);
+                update_positions(&mut ctx, &holdings, &prices, cash);
+            }
+
+            equity = cash
+                + holdings
+                    .iter()
+                    .map(|(token, (_, size, avg))| *size * prices.get(token).unwrap_or(avg))
+                    .sum::<Decimal>();
+            peak = peak.max(equity);
+            if !peak.is_zero() {
+                max_drawdown = max_drawdown.max((peak - equity) / peak);
+            }
+        }
+
+        let pnl = equity - self.starting_balance;
+        BacktestResult {
+            final_equity: equity,
+            pnl,
+            return_pct: if self.starting_balance.is_zero() {
+                Decimal::ZERO
+            } else {
+                pnl / self.starting_balance * Decimal::ONE_HUNDRED
+            },
+            trades,
+            max_drawdown,
+        }
+    }
+}
+
+/// Rebuild the context's positions and balances from the simulated holdings.
+fn update_positions(
+    ctx: &mut StrategyContext,
+    holdings: &HashMap<String, (String, Decimal, Decimal)>,
+    prices: &HashMap<String, Decimal>,
+    cash: Decimal,
+) {
+    ctx.positions = holdings
+        .iter()
+        .map(|(token_id, (market_id, size, avg_price))| {
+            let current_price = prices.get(token_id).copied().unwrap_or(*avg_price);
+            let cost = *size * *avg_price;
+            let unrealized_pnl = *size * current_price - cost;
+            let snapshot = PositionSnapshot {
+                market_id: market_id.clone(),
+                token_id: token_id.clone(),
+                size: *size,
+                avg_price: *avg_price,
+                current_price,
+                current_value: *size * current_price,
+                unrealized_pnl,
+                pnl_percent: if cost.is_zero() {
+                    Decimal::ZERO
+                } else {
+                    unrealized_pnl / cost * Decimal::ONE_HUNDRED
+                },
+            };
+            (token_id.clone(), snapshot)
+        })
+        .collect();
+    ctx.available_balance = cash;
+    ctx.total_value = cash + ctx.total_exposure();
+}
+
+/// Outcome of a backtest.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BacktestResult {
+    /// Cash plus the value of open positions at the last frame.
+    pub final_equity: Decimal,
+    /// Profit or loss over the replay.
+    pub pnl: Decimal,
+    /// Profit or loss as a percentage of the starting balance.
+    pub return_pct: Decimal,
+    /// Number of filled signals.
+    pub trades: usize,
+    /// Largest fall from a previous equity peak, as a fraction.
+    pub max_drawdown: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Quantity;
+    use crate::strategy::Signal;
+    use rust_decimal_macros::dec;
+
+    /// Buys below 0.40 and sells above 0.60.
+    #[derive(Debug)]
+    struct Threshold;
+
+    impl Strategy for Threshold {
+        fn name(&self) -> &str {
+            "threshold"
+        }
+
+        fn evaluate(&mut self, ctx: &StrategyContext) -> Vec<Signal> {
+            let market = &ctx.markets["m"];
+            let price = market.token_prices[0];
+            let held = ctx.get_position("yes").map(|p| p.size);
+            match held {
+                None if price < dec!(0.40) => {
+                    vec![Signal::buy("m", "yes", Quantity::Shares(dec!(100)))]
+                }
+                Some(size) if price > dec!(0.60) => {
+                    vec![Signal::sell("m", "yes", Quantity::Shares(size))]
+                }
+                _ => vec![],
+            }
+        }
+    }
+
+    fn frames(prices: &[Decimal]) -> Vec<Frame> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, price)| Frame {
+                timestamp: DateTime::from_timestamp(i as i64 * 60, 0).unwrap(),
+                markets: vec![RecordedMarket {
+                    condition_id: "m".to_string(),
+                    question: String::new(),
+                    status: MarketStatus::Active,
+                    token_ids: vec!["yes".to_string(), "no".to_string()],
+                    token_names: vec![],
+                    token_prices: vec![*price, Decimal::ONE - price],
+                    volume_24h: Decimal::ZERO,
+                    liquidity: Decimal::ZERO,
+                    spread: None,
+                }],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_replay_fills_signals_at_frame_prices() {
+        let frames = frames(&[dec!(0.50), dec!(0.30), dec!(0.20), dec!(0.70), dec!(0.50)]);
+        let result = Backtest::new(dec!(100)).run(&mut Threshold, &frames);
+
+        // Buy 100 at 0.30, fall to 0.20, sell 100 at 0.70
+        assert_eq!(result.trades, 2);
+        assert_eq!(result.pnl, dec!(40));
+        assert_eq!(result.return_pct, dec!(40));
+        assert_eq!(result.final_equity, dec!(140));
+        assert_eq!(result.max_drawdown.round_dp(4), dec!(0.1000));
+    }
+
+    #[test]
+    fn test_replay_exits_brackets() {
+        /// Buys below 0.40 with a stop at 0.25 and never sells itself.
+        #[derive(Debug)]
+        struct Stopped;
+
+        impl Strategy for Stopped {
+            fn name(&self) -> &str {
+                "stopped"
+            }
+
+            fn evaluate(&mut self, ctx: &StrategyContext) -> Vec<Signal> {
+                let price = ctx.markets["m"].token_prices[0];
+                if ctx.get_position("yes").is_none() && price < dec!(0.40) {
+                    vec![
+                        Signal::buy("m", "yes", Quantity::Shares(dec!(100)))
+                            .with_stop_loss(dec!(0.25)),
+                    ]
+                } else {
+                    vec![]
+                }
+            }
+        }
+
+        let frames = frames(&[dec!(0.50), dec!(0.30), dec!(0.20), dec!(0.50)]);
+        let result = Backtest::new(dec!(100)).run(&mut Stopped, &frames);
+
+        // Buy 100 at 0.30, stopped out at 0.20
+        assert_eq!(result.trades, 2);
+        assert_eq!(result.pnl, dec!(-10));
+    }
+
+    #[test]
+    fn test_recording_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("clobster-recording-{}.jsonl", uuid::Uuid::new_v4()));
+        let frames = frames(&[dec!(0.50), dec!(0.30)]);
+        for frame in frames.iter().rev() {
+            Recording::append(&path, frame).unwrap();
+        }
+
+        let recording = Recording::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(recording.frames, frames);
+    }
+}
@@ -0,0 +1,490 @@
+//! Client-side stop-loss and take-profit brackets.
+//!
+//! An entry signal carrying [`SignalMetadata::stop_loss`] or
+//! [`SignalMetadata::take_profit`] arms a [`Bracket`] when it is dispatched.
+//! Fills of the entry order grow the protected size; the engine then checks
+//! every bracket against the live book and, when a level is crossed, sends a
+//! market exit for the protected size. Both legs are watched locally, so the
+//! leg that triggers first disarms the other (one-cancels-other) without any
+//! resting order to cancel on the exchange.
+//!
+//! [`SignalMetadata::stop_loss`]: super::signal::SignalMetadata::stop_loss
+//! [`SignalMetadata::take_profit`]: super::signal::SignalMetadata::take_profit
+
+use super::{Signal, SignalAction, SignalStrength, SignalType, StrategyContext};
+use crate::state::{OrderSide, OrderType, Quantity};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// Exit level of a bracket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BracketLeg {
+    /// Exit at a loss.
+    StopLoss,
+    /// Exit at a profit.
+    TakeProfit,
+}
+
+impl std::fmt::Display for BracketLeg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StopLoss => write!(f, "Stop loss"),
+            Self::TakeProfit => write!(f, "Take profit"),
+        }
+    }
+}
+
+/// Where a bracket is in its life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BracketStatus {
+    /// Entry order not filled yet.
+    Pending,
+    /// Watching both levels.
+    Active,
+    /// A leg triggered and its exit order is working.
+    Exiting(BracketLeg),
+}
+
+impl std::fmt::Display for BracketStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "Pending"),
+            Self::Active => write!(f, "Active"),
+            Self::Exiting(leg) => write!(f, "{} exit", leg),
+        }
+    }
+}
+
+/// Protective exits for the position opened by one entry signal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bracket {
+    /// ID of the entry signal.
+    pub id: String,
+    /// Strategy that generated the entry.
+    pub strategy: String,
+    /// Market condition ID.
+    pub market_id: String,
+    /// Token held.
+    pub token_id: String,
+    /// Side of the entry order.
+    pub side: OrderSide,
+    /// Client order ID of the entry order.
+    pub entry_client_order_id: String,
+    /// Average entry fill price.
+    pub entry_price: Option<Decimal>,
+    /// Size filled by the entry and not yet exited.
+    pub size: Decimal,
+    /// Stop-loss price.
+    pub stop_loss: Option<Decimal>,
+    /// Take-profit price.
+    pub take_profit: Option<Decimal>,
+    /// Current status.
+    pub status: BracketStatus,
+    /// Client order ID of the working exit order.
+    #[serde(default)]
+    pub exit_client_order_id: Option<String>,
+    /// When the bracket was armed.
+    pub created_at: DateTime<Utc>,
+}
+
+impl Bracket {
+    /// Side of the exit order.
+    pub fn exit_side(&self) -> OrderSide {
+        match self.side {
+            OrderSide::Buy => OrderSide::Sell,
+            OrderSide::Sell => OrderSide::Buy,
+        }
+    }
+
+    /// Leg crossed at `price`, the price the exit would trade at. The stop
+    /// wins if both levels are crossed.
+    pub fn triggered(&self, price: Decimal) -> Option<BracketLeg> {
+        let (stop_hit, take_hit) = match self.side {
+            OrderSide::Buy => (
+                self.stop_loss.is_some_and(|stop| price <= stop),
+                self.take_profit.is_some_and(|take| price >= take),
+            ),
+            OrderSide::Sell => (
+                self.stop_loss.is_some_and(|stop| price >= stop),
+                self.take_profit.is_some_and(|take| price <= take),
+            ),
+        };
+        if stop_hit {
+            Some(BracketLeg::StopLoss)
+        } else if take_hit {
+            Some(BracketLeg::TakeProfit)
+        } else {
+            None
+        }
+    }
+
+    /// Market exit for the protected size.
+    fn exit_signal(&self, leg: BracketLeg, price: Decimal) -> Signal {
+        let signal = match self.exit_side() {
+            OrderSide::Buy => {
+                Signal::buy(&self.market_id, &self.token_id, Quantity::Shares(self.size))
+            }
+            OrderSide::Sell => {
+                Signal::sell(&self.market_id, &self.token_id, Quantity::Shares(self.size))
+            }
+        };
+        let (signal_type, strength, level) = match leg {
+            BracketLeg::StopLoss => (
+                SignalType::StopLoss,
+                SignalStrength::VeryStrong,
+                self.stop_loss,
+            ),
+            BracketLeg::TakeProfit => (
+                SignalType::TakeProfit,
+                SignalStrength::Strong,
+                self.take_profit,
+            ),
+        };
+        signal
+            .with_strategy(&self.strategy)
+            .with_type(signal_type)
+            .with_strength(strength)
+            .with_order_type(OrderType::Market)
+            .with_price(price)
+            .with_reason(format!(
+                "{} at {:.4} (level {:.4}, entry {})",
+                leg,
+                price,
+                level.unwrap_or_default(),
+                self.entry_price
+                    .map(|p| format!("{:.4}", p))
+                    .unwrap_or_else(|| "-".to_string())
+            ))
+    }
+}
+
+/// Open brackets, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct BracketBook {
+    brackets: Vec<Bracket>,
+}
+
+impl BracketBook {
+    /// Create an empty book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restore brackets saved by a previous run.
+    ///
+    /// Orders are not tracked across restarts, so brackets whose entry never
+    /// filled are dropped and exits that were working are watched again.
+    pub fn restore(brackets: Vec<Bracket>) -> Self {
+        let brackets = brackets
+            .into_iter()
+            .filter_map(|mut bracket| {
+                if bracket.size <= Decimal::ZERO {
+                    warn!(
+                        "Dropping bracket of unfilled entry {} ({})",
+                        bracket.id, bracket.strategy
+                    );
+                    return None;
+                }
+                bracket.status = BracketStatus::Active;
+                bracket.exit_client_order_id = None;
+                Some(bracket)
+            })
+            .collect();
+        Self { brackets }
+    }
+
+    /// Open brackets, oldest first.
+    pub fn brackets(&self) -> &[Bracket] {
+        &self.brackets
+    }
+
+    /// Arm a bracket for an entry order placed with client order ID
+    /// `client_order_id`. Returns whether the signal has exit levels.
+    pub fn arm(&mut self, signal: &Signal, client_order_id: &str) -> bool {
+        let metadata = &signal.metadata;
+        if metadata.stop_loss.is_none() && metadata.take_profit.is_none() {
+            return false;
+        }
+        if matches!(signal.action, SignalAction::Cancel { .. }) {
+            return false;
+        }
+        self.brackets.push(Bracket {
+            id: signal.id.clone(),
+            strategy: signal.strategy_name.clone(),
+            market_id: signal.market_id.clone(),
+            token_id: signal.token_id.clone(),
+            side: signal.side,
+            entry_client_order_id: client_order_id.to_string(),
+            entry_price: None,
+            size: Decimal::ZERO,
+            stop_loss: metadata.stop_loss,
+            take_profit: metadata.take_profit,
+            status: BracketStatus::Pending,
+            exit_client_order_id: None,
+            created_at: Utc::now(),
+        });
+        true
+    }
+
+    /// Apply a fill of the order with client order ID `client_order_id`,
+    /// returning whether a bracket changed.
+    ///
+    /// Entry fills add to the protected size; exit fills take from it and
+    /// close the bracket once nothing is left.
+    pub fn filled(&mut self, client_order_id: &str, price: Decimal, size: Decimal) -> bool {
+        if let Some(bracket) = self
+            .brackets
+            .iter_mut()
+            .find(|b| b.entry_client_order_id == client_order_id)
+        {
+            let notional = bracket.entry_price.unwrap_or_default() * bracket.size;
+            bracket.size += size;
+            bracket.entry_price = Some((notional + price * size) / bracket.size);
+            if bracket.status == BracketStatus::Pending {
+                bracket.status = BracketStatus::Active;
+            }
+            return true;
+        }
+
+        let Some(index) = self.exit_index(client_order_id) else {
+            return false;
+        };
+        let bracket = &mut self.brackets[index];
+        bracket.size -= size;
+        if bracket.size <= Decimal::ZERO {
+            let bracket = self.brackets.remove(index);
+            info!(
+                "Bracket of {} ({}) closed at {}",
+                bracket.id, bracket.strategy, price
+            );
+        }
+        true
+    }
+
+    /// Handle the order with client order ID `client_order_id` ending
+    /// without filling completely, returning whether a bracket changed.
+    ///
+    /// An entry that never filled drops its bracket. An exit that was
+    /// cancelled or expired re-arms the bracket for what is still held; one
+    /// that was `rejected` closes it, since the position is most likely gone.
+    pub fn ended(&mut self, client_order_id: &str, rejected: bool) -> bool {
+        if let Some(index) = self
+            .brackets
+            .iter()
+            .position(|b| b.entry_client_order_id == client_order_id)
+        {
+            if self.brackets[index].status == BracketStatus::Pending {
+                self.brackets.remove(index);
+            }
+            return true;
+        }
+
+        let Some(index) = self.exit_index(client_order_id) else {
+            return false;
+        };
+        if rejected {
+            let bracket = self.brackets.remove(index);
+            warn!(
+                "Exit of bracket {} ({}) was rejected, no longer protecting {}",
+                bracket.id, bracket.strategy, bracket.token_id
+            );
+        } else {
+            let bracket = &mut self.brackets[index];
+            bracket.status = BracketStatus::Active;
+            bracket.exit_client_order_id = None;
+        }
+        true
+    }
+
+    /// Check active brackets against the market, returning the exit signal
+    /// of every bracket with a crossed level as `(bracket ID, signal)`.
+    ///
+    /// Long positions are marked at the best bid and short positions at the
+    /// best ask, falling back to the last token price when there is no book.
+    /// Triggered brackets stop watching until [`exit_submitted`] or
+    /// [`ended`] is called for their exit.
+    ///
+    /// [`exit_submitted`]: Self::exit_submitted
+    /// [`ended`]: Self::ended
+    pub fn check(&mut self, ctx: &StrategyContext) -> Vec<(String, Signal)> {
+        let mut exits = Vec::new();
+        for bracket in &mut self.brackets {
+            if bracket.status != BracketStatus::Active {
+                continue;
+            }
+            let Some(price) = exit_price(ctx, bracket) else {
+                continue;
+            };
+            let Some(leg) = bracket.triggered(price) else {
+                continue;
+            };
+            bracket.status = BracketStatus::Exiting(leg);
+            exits.push((bracket.id.clone(), bracket.exit_signal(leg, price)));
+        }
+        exits
+    }
+
+    /// Record the exit order sent for a triggered bracket. Without a client
+    /// order ID the exit cannot be followed and the bracket is closed.
+    pub fn exit_submitted(&mut self, bracket_id: &str, client_order_id: Option<String>) {
+        let Some(index) = self.brackets.iter().position(|b| b.id == bracket_id) else {
+            return;
+        };
+        match client_order_id {
+            Some(id) => self.brackets[index].exit_client_order_id = Some(id),
+            None => {
+                self.brackets.remove(index);
+            }
+        }
+    }
+
+    fn exit_index(&self, client_order_id: &str) -> Option<usize> {
+        self.brackets
+            .iter()
+            .position(|b| b.exit_client_order_id.as_deref() == Some(client_order_id))
+    }
+}
+
+/// Price an exit of `bracket` would trade at now.
+fn exit_price(ctx: &StrategyContext, bracket: &Bracket) -> Option<Decimal> {
+    let book = match bracket.exit_side() {
+        OrderSide::Sell => ctx.best_bid(&bracket.token_id),
+        OrderSide::Buy => ctx.best_ask(&bracket.token_id),
+    };
+    book.or_else(|| {
+        let market = ctx.get_market(&bracket.market_id)?;
+        let index = market
+            .token_ids
+            .iter()
+            .position(|t| *t == bracket.token_id)?;
+        market.token_prices.get(index).copied()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MarketStatus;
+    use crate::strategy::MarketSnapshot;
+    use rust_decimal_macros::dec;
+
+    fn context(price: Decimal) -> StrategyContext {
+        let mut ctx = StrategyContext::new();
+        ctx.markets.insert(
+            "m1".to_string(),
+            MarketSnapshot {
+                condition_id: "m1".to_string(),
+                question: "Test?".to_string(),
+                status: MarketStatus::Active,
+                token_ids: vec!["yes".to_string(), "no".to_string()],
+                token_names: vec!["Yes".to_string(), "No".to_string()],
+                token_prices: vec![price, Decimal::ONE - price],
+                volume_24h: Decimal::ZERO,
+                liquidity: Decimal::ZERO,
+                spread: None,
+                end_date: None,
+            },
+        );
+        ctx
+    }
+
+    fn entry() -> Signal {
+        Signal::buy("m1", "yes", Quantity::Shares(dec!(10)))
+            .with_strategy("momentum")
+            .with_price(dec!(0.50))
+            .with_stop_loss(dec!(0.45))
+            .with_take_profit(dec!(0.60))
+    }
+
+    #[test]
+    fn test_arms_on_entry_fill_and_triggers_one_leg() {
+        let mut book = BracketBook::new();
+        assert!(!book.arm(
+            &Signal::buy("m1", "yes", Quantity::Shares(dec!(10))),
+            "plain"
+        ));
+        assert!(book.arm(&entry(), "entry"));
+
+        // Nothing to protect until the entry fills
+        assert!(book.check(&context(dec!(0.40))).is_empty());
+        assert!(book.filled("entry", dec!(0.50), dec!(4)));
+        assert!(book.filled("entry", dec!(0.50), dec!(6)));
+        assert_eq!(book.brackets()[0].status, BracketStatus::Active);
+        assert_eq!(book.brackets()[0].size, dec!(10));
+
+        assert!(book.check(&context(dec!(0.55))).is_empty());
+        let exits = book.check(&context(dec!(0.62)));
+        assert_eq!(exits.len(), 1);
+        let (id, exit) = &exits[0];
+        assert_eq!(exit.side, OrderSide::Sell);
+        assert_eq!(exit.signal_type, SignalType::TakeProfit);
+        assert_eq!(exit.order_type, OrderType::Market);
+        assert_eq!(exit.size, Quantity::Shares(dec!(10)));
+        assert_eq!(exit.strategy_name, "momentum");
+
+        // The stop leg is disarmed while the exit works
+        book.exit_submitted(id, Some("exit".to_string()));
+        assert!(book.check(&context(dec!(0.40))).is_empty());
+        assert_eq!(
+            book.brackets()[0].status,
+            BracketStatus::Exiting(BracketLeg::TakeProfit)
+        );
+
+        assert!(book.filled("exit", dec!(0.62), dec!(4)));
+        assert_eq!(book.brackets()[0].size, dec!(6));
+        assert!(book.filled("exit", dec!(0.62), dec!(6)));
+        assert!(book.brackets().is_empty());
+    }
+
+    #[test]
+    fn test_short_brackets_and_unfilled_orders() {
+        let short = Signal::sell("m1", "yes", Quantity::Shares(dec!(5)))
+            .with_price(dec!(0.50))
+            .with_stop_loss(dec!(0.55))
+            .with_take_profit(dec!(0.40));
+        let mut book = BracketBook::new();
+        book.arm(&short, "short");
+        book.filled("short", dec!(0.50), dec!(5));
+        let bracket = &book.brackets()[0];
+        assert_eq!(bracket.exit_side(), OrderSide::Buy);
+        assert_eq!(bracket.triggered(dec!(0.56)), Some(BracketLeg::StopLoss));
+        assert_eq!(bracket.triggered(dec!(0.39)), Some(BracketLeg::TakeProfit));
+        assert_eq!(bracket.triggered(dec!(0.50)), None);
+
+        // An entry that never fills drops its bracket
+        let mut book = BracketBook::new();
+        book.arm(&entry(), "entry");
+        assert!(book.ended("entry", false));
+        assert!(book.brackets().is_empty());
+
+        // A cancelled exit re-arms; a rejected one closes
+        book.arm(&entry(), "entry");
+        book.filled("entry", dec!(0.50), dec!(10));
+        let (id, _) = book.check(&context(dec!(0.44))).remove(0);
+        book.exit_submitted(&id, Some("exit".to_string()));
+        assert!(book.ended("exit", false));
+        let (id, exit) = book.check(&context(dec!(0.44))).remove(0);
+        assert_eq!(exit.signal_type, SignalType::StopLoss);
+        book.exit_submitted(&id, Some("exit-2".to_string()));
+        assert!(book.ended("exit-2", true));
+        assert!(book.brackets().is_empty());
+    }
+
+    #[test]
+    fn test_restore_drops_unfilled_and_rearms_exits() {
+        let mut book = BracketBook::new();
+        book.arm(&entry(), "a");
+        book.arm(&entry(), "b");
+        book.filled("b", dec!(0.50), dec!(10));
+        let (id, _) = book.check(&context(dec!(0.44))).remove(0);
+        book.exit_submitted(&id, Some("exit".to_string()));
+
+        let saved = serde_json::to_string(book.brackets()).unwrap();
+        let restored = BracketBook::restore(serde_json::from_str(&saved).unwrap());
+        assert_eq!(restored.brackets().len(), 1);
+        assert_eq!(restored.brackets()[0].status, BracketStatus::Active);
+        assert_eq!(restored.brackets()[0].exit_client_order_id, None);
+    }
+}
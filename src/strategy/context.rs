@@ -1,15 +1,19 @@
 //! Strategy context - market data and state provided to strategies.
 
-use crate::state::{Market, MarketStatus, Order, OrderStatus, Position};
+use crate::state::{
+    Market, MarketState, MarketStatus, Order, OrderBookDepth, OrderBookState, OrderBookStats,
+    OrderStatus, Position,
+};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use serde::Serialize;
 use std::collections::HashMap;
 
 /// Context provided to strategies during evaluation.
 ///
 /// Contains snapshots of current market data, positions, orders,
 /// and account state that strategies can use to make decisions.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct StrategyContext {
     /// Current timestamp.
     pub timestamp: DateTime<Utc>,
@@ -25,6 +29,8 @@ pub struct StrategyContext {
     pub total_value: Decimal,
     /// Price history for markets (condition_id -> prices).
     pub price_history: HashMap<String, Vec<PricePoint>>,
+    /// Order book snapshots indexed by token ID.
+    pub order_books: HashMap<String, OrderBookDepth>,
 }
 
 impl StrategyContext {
@@ -38,6 +44,7 @@ impl StrategyContext {
             available_balance: Decimal::ZERO,
             total_value: Decimal::ZERO,
             price_history: HashMap::new(),
+            order_books: HashMap::new(),
         }
     }
 
@@ -165,6 +172,48 @@ impl StrategyContext {
 
         Some((current - past) / past)
     }
+
+    /// Get the order book snapshot for a token.
+    pub fn get_book(&self, token_id: &str) -> Option<&OrderBookDepth> {
+        self.order_books.get(token_id)
+    }
+
+    /// Get order book statistics for a token, limited to `depth` levels per side.
+    pub fn book_stats(&self, token_id: &str, depth: usize) -> Option<OrderBookStats> {
+        self.get_book(token_id)
+            .map(|book| OrderBookStats::from_orderbook(book, depth))
+    }
+
+    /// Get the best bid price for a token from its order book.
+    pub fn best_bid(&self, token_id: &str) -> Option<Decimal> {
+        self.get_book(token_id)?.best_bid_price()
+    }
+
+    /// Get the best ask price for a token from its order book.
+    pub fn best_ask(&self, token_id: &str) -> Option<Decimal> {
+        self.get_book(token_id)?.best_ask_price()
+    }
+
+    /// Get the total liquidity (value) within `depth` levels of a token's book.
+    pub fn liquidity(&self, token_id: &str, depth: usize) -> Option<Decimal> {
+        self.get_book(token_id)
+            .map(|book| book.total_liquidity(depth))
+    }
+
+    /// Get the order book imbalance (-1 to 1) within `depth` levels.
+    pub fn imbalance(&self, token_id: &str, depth: usize) -> Option<Decimal> {
+        self.get_book(token_id)?.imbalance(depth)
+    }
+
+    /// Get the volume-weighted price to buy `size` shares of a token.
+    pub fn vwap_buy(&self, token_id: &str, size: Decimal) -> Option<Decimal> {
+        self.get_book(token_id)?.vwap_buy(size)
+    }
+
+    /// Get the volume-weighted price to sell `size` shares of a token.
+    pub fn vwap_sell(&self, token_id: &str, size: Decimal) -> Option<Decimal> {
+        self.get_book(token_id)?.vwap_sell(size)
+    }
 }
 
 impl Default for StrategyContext {
@@ -208,10 +257,46 @@ impl StrategyContext {
 
         ctx
     }
+
+    /// Attach order book snapshots for the context's markets.
+    ///
+    /// Only books for tokens of known markets are copied. A market's
+    /// `spread` is taken from the book of its first outcome when available.
+    pub fn with_order_books(mut self, books: &OrderBookState) -> Self {
+        for market in self.markets.values_mut() {
+            for token_id in &market.token_ids {
+                if let Some(book) = books.get_book(token_id) {
+                    self.order_books.insert(token_id.clone(), book.clone());
+                }
+            }
+
+            if let Some(spread) = market
+                .token_ids
+                .first()
+                .and_then(|id| books.get_book(id))
+                .and_then(|book| book.spread())
+            {
+                market.spread = Some(spread);
+            }
+        }
+
+        self
+    }
+
+    /// Add the sampled price history of every market in the context.
+    pub fn with_price_history(mut self, markets: &MarketState) -> Self {
+        for id in self.markets.keys() {
+            if let Some(history) = markets.price_history.get(id) {
+                self.price_history
+                    .insert(id.clone(), history.iter().cloned().collect());
+            }
+        }
+        self
+    }
 }
 
 /// Snapshot of market state for strategy evaluation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct MarketSnapshot {
     /// Market condition ID.
     pub condition_id: String,
@@ -241,6 +326,12 @@ impl MarketSnapshot {
         let token_prices: Vec<Decimal> = market.outcomes.iter().map(|o| o.mid_price()).collect();
         let token_ids: Vec<String> = market.outcomes.iter().map(|o| o.token_id.clone()).collect();
         let token_names: Vec<String> = market.outcomes.iter().map(|o| o.name.clone()).collect();
+        // Quoted spread of the first outcome, if both sides are known
+        let spread = market
+            .outcomes
+            .first()
+            .filter(|o| !o.bid.is_zero() && !o.ask.is_zero())
+            .map(|o| o.spread());
 
         Self {
             condition_id: market.id.clone(),
@@ -249,9 +340,9 @@ impl MarketSnapshot {
             token_ids,
             token_names,
             token_prices,
-            volume_24h: market.volume,
+            volume_24h: market.volume_24h,
             liquidity: market.liquidity,
-            spread: None,
+            spread,
             end_date: market.end_date,
         }
     }
@@ -278,7 +369,7 @@ impl MarketSnapshot {
 }
 
 /// Snapshot of a position.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PositionSnapshot {
     /// Market condition ID.
     pub market_id: String,
@@ -320,7 +411,7 @@ impl PositionSnapshot {
 }
 
 /// Snapshot of an order.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct OrderSnapshot {
     /// Order ID.
     pub order_id: String,
@@ -380,7 +471,7 @@ impl OrderSnapshot {
 }
 
 /// A price point in history.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PricePoint {
     /// Timestamp.
     pub timestamp: DateTime<Utc>,
@@ -389,3 +480,114 @@ pub struct PricePoint {
     /// Volume at this point.
     pub volume: Option<Decimal>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{MarketStatus, OrderBookDepth, PriceLevel};
+    use rust_decimal_macros::dec;
+
+    fn snapshot(id: &str, tokens: &[&str]) -> MarketSnapshot {
+        MarketSnapshot {
+            condition_id: id.to_string(),
+            question: format!("{}?", id),
+            status: MarketStatus::Active,
+            token_ids: tokens.iter().map(|t| t.to_string()).collect(),
+            token_names: tokens.iter().map(|t| t.to_string()).collect(),
+            token_prices: vec![dec!(0.50); tokens.len()],
+            volume_24h: Decimal::ZERO,
+            liquidity: Decimal::ZERO,
+            spread: None,
+            end_date: None,
+        }
+    }
+
+    fn book(token_id: &str) -> OrderBookDepth {
+        let mut book = OrderBookDepth::new("m1", token_id);
+        book.bids = vec![
+            PriceLevel::new(dec!(0.50), dec!(100)),
+            PriceLevel::new(dec!(0.49), dec!(200)),
+            PriceLevel::new(dec!(0.48), dec!(150)),
+        ];
+        book.asks = vec![
+            PriceLevel::new(dec!(0.52), dec!(80)),
+            PriceLevel::new(dec!(0.53), dec!(120)),
+            PriceLevel::new(dec!(0.54), dec!(100)),
+        ];
+        book
+    }
+
+    fn context() -> StrategyContext {
+        let mut ctx = StrategyContext::new();
+        ctx.markets
+            .insert("m1".to_string(), snapshot("m1", &["yes", "no"]));
+
+        let mut books = OrderBookState::new();
+        books.update_book(book("yes"));
+        // Not part of any known market
+        books.update_book(book("stray"));
+
+        ctx.with_order_books(&books)
+    }
+
+    #[test]
+    fn test_with_order_books_copies_known_tokens() {
+        let ctx = context();
+
+        assert!(ctx.get_book("yes").is_some());
+        assert!(ctx.get_book("no").is_none());
+        assert!(ctx.get_book("stray").is_none());
+        // The market spread comes from its first outcome's book
+        assert_eq!(ctx.get_market("m1").unwrap().spread, Some(dec!(0.02)));
+    }
+
+    #[test]
+    fn test_book_prices_and_stats() {
+        let ctx = context();
+
+        assert_eq!(ctx.best_bid("yes"), Some(dec!(0.50)));
+        assert_eq!(ctx.best_ask("yes"), Some(dec!(0.52)));
+        assert_eq!(ctx.best_bid("no"), None);
+
+        let stats = ctx.book_stats("yes", 2).unwrap();
+        assert_eq!(stats.mid_price, Some(dec!(0.51)));
+        assert_eq!(stats.spread, Some(dec!(0.02)));
+        // 0.50*100 + 0.49*200 within two levels
+        assert_eq!(stats.bid_liquidity, dec!(148));
+        // 0.52*80 + 0.53*120
+        assert_eq!(stats.ask_liquidity, dec!(105.2));
+    }
+
+    #[test]
+    fn test_book_liquidity_is_depth_limited() {
+        let ctx = context();
+
+        assert_eq!(ctx.liquidity("yes", 1), Some(dec!(91.6)));
+        assert_eq!(ctx.liquidity("yes", 3), Some(dec!(379.2)));
+        assert_eq!(ctx.liquidity("no", 3), None);
+    }
+
+    #[test]
+    fn test_book_imbalance() {
+        let ctx = context();
+
+        // (100 - 80) / (100 + 80) at the top of the book
+        assert_eq!(ctx.imbalance("yes", 1).unwrap().round_dp(4), dec!(0.1111));
+        // (450 - 300) / (450 + 300) over all levels
+        assert_eq!(ctx.imbalance("yes", 3), Some(dec!(0.2)));
+        assert_eq!(ctx.imbalance("no", 3), None);
+    }
+
+    #[test]
+    fn test_book_vwap() {
+        let ctx = context();
+
+        // Within the best level
+        assert_eq!(ctx.vwap_buy("yes", dec!(50)), Some(dec!(0.52)));
+        // 80 at 0.52 and 20 at 0.53
+        assert_eq!(ctx.vwap_buy("yes", dec!(100)), Some(dec!(0.522)));
+        // 100 at 0.50 and 100 at 0.49
+        assert_eq!(ctx.vwap_sell("yes", dec!(200)), Some(dec!(0.495)));
+        assert_eq!(ctx.vwap_buy("no", dec!(10)), None);
+    }
+}
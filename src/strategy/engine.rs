@@ -1,14 +1,25 @@
 //! Strategy engine - manages strategy lifecycle and execution.
 
-use super::{RiskGuard, Signal, Strategy, StrategyConfig, StrategyContext};
+use super::{
+    Bracket, BracketBook, ExecutionBook, ExecutionStep, LifecycleTracker, LifecycleUpdate,
+    OrderLifecycle, OrderStage, ParameterDef, ParameterValue, ParentOrder, RiskGuard, Signal,
+    SignalAction, Sizer, StateStore, Strategy, StrategyConfig, StrategyContext,
+};
 use crate::error::Result;
-use crate::state::{Action, OrderRequest, OrderType};
+use crate::state::{
+    Action, Order, OrderRequest, OrderType, Quantity, SIZE_DECIMALS, TradeState, Watchlist,
+};
 use chrono::{DateTime, Utc};
+use rust_decimal::RoundingStrategy;
 use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::{RwLock, mpsc};
 use tracing::{debug, error, info, warn};
 
+/// Maximum number of parameter changes kept in the change log.
+const MAX_PARAMETER_LOG: usize = 100;
+
 /// Manages multiple strategies and their execution.
 pub struct StrategyEngine {
     /// Registered strategies.
@@ -21,31 +32,91 @@ pub struct StrategyEngine {
     pending_signals: Vec<Signal>,
     /// Signal history (VecDeque for O(1) front removal).
     signal_history: VecDeque<SignalRecord>,
+    /// Orders placed for executed signals.
+    lifecycles: LifecycleTracker,
+    /// Stop-loss and take-profit exits of filled entries.
+    brackets: BracketBook,
+    /// Signals worked by execution algorithms.
+    executions: ExecutionBook,
+    /// Parameter changes made at runtime, oldest first.
+    parameter_log: VecDeque<ParameterChange>,
     /// Engine configuration.
     config: EngineConfig,
+    /// Watchlist markets by watchlist name.
+    watchlists: HashMap<String, Vec<String>>,
+    /// Where strategy state is checkpointed, if anywhere.
+    state_store: Option<StateStore>,
+    /// When state was last checkpointed.
+    last_checkpoint: Option<DateTime<Utc>>,
     /// Is the engine running.
     running: bool,
+    /// Whether the engine was started since the state directory was set.
+    /// Until then the saved state may belong to another process, such as a
+    /// daemon running the same profile, and is left alone.
+    started: bool,
 }
 
 impl StrategyEngine {
     /// Create a new strategy engine.
     pub fn new(action_tx: mpsc::UnboundedSender<Action>, config: EngineConfig) -> Self {
-        Self {
+        let mut engine = Self {
             strategies: HashMap::new(),
             risk_guard: RiskGuard::new(config.risk_config.clone()),
             action_tx,
             pending_signals: Vec::new(),
             signal_history: VecDeque::new(),
+            lifecycles: LifecycleTracker::new(config.max_signal_history),
+            brackets: BracketBook::new(),
+            executions: ExecutionBook::new(config.max_signal_history),
+            parameter_log: VecDeque::new(),
+            state_store: config.state_dir.clone().map(StateStore::new),
+            last_checkpoint: None,
             config,
+            watchlists: HashMap::new(),
             running: false,
+            started: false,
+        };
+        engine.restore_brackets();
+        engine
+    }
+
+    /// Checkpoint strategy state to `directory` from now on, or stop
+    /// persisting it with `None`.
+    ///
+    /// Only affects strategies registered afterwards and future checkpoints.
+    /// Brackets saved in the new directory are restored unless brackets are
+    /// already open.
+    pub fn set_state_dir(&mut self, directory: Option<PathBuf>) {
+        self.state_store = directory.clone().map(StateStore::new);
+        self.config.state_dir = directory;
+        self.started = self.running;
+        if self.brackets.brackets().is_empty() {
+            self.restore_brackets();
         }
     }
 
+    /// Replace the watchlists available to strategies as market universes.
+    pub fn set_watchlists(&mut self, watchlists: &[Watchlist]) {
+        self.watchlists = watchlists
+            .iter()
+            .map(|w| (w.name.clone(), w.market_ids.clone()))
+            .collect();
+    }
+
     /// Register a strategy with the engine.
     pub async fn register<S: Strategy + 'static>(
         &mut self,
         strategy: S,
         config: StrategyConfig,
+    ) -> Result<()> {
+        self.register_boxed(Box::new(strategy), config).await
+    }
+
+    /// Register an already boxed strategy, e.g. one created by name.
+    pub async fn register_boxed(
+        &mut self,
+        strategy: Box<dyn Strategy>,
+        config: StrategyConfig,
     ) -> Result<()> {
         let name = strategy.name().to_string();
 
@@ -57,7 +128,8 @@ impl StrategyEngine {
         }
 
         let handle = StrategyHandle {
-            strategy: Arc::new(RwLock::new(Box::new(strategy))),
+            strategy: Arc::new(RwLock::new(strategy)),
+            sizer: config.sizing.as_ref().map(Sizer::from_config),
             config,
             status: StrategyStatus::Stopped,
             last_evaluated: None,
@@ -66,10 +138,11 @@ impl StrategyEngine {
             errors: 0,
         };
 
-        // Initialize the strategy
+        // Initialize the strategy, then restore its state from the last session
         {
             let mut strategy = handle.strategy.write().await;
             strategy.initialize(&handle.config).await?;
+            self.restore_state(&name, strategy.as_mut());
         }
 
         info!("Registered strategy: {}", name);
@@ -78,10 +151,13 @@ impl StrategyEngine {
         Ok(())
     }
 
-    /// Unregister a strategy.
+    /// Unregister a strategy, checkpointing its state first.
     pub async fn unregister(&mut self, name: &str) -> Result<()> {
         if let Some(handle) = self.strategies.remove(name) {
             let mut strategy = handle.strategy.write().await;
+            if handle.last_evaluated.is_some() {
+                self.save_state(name, strategy.as_ref());
+            }
             strategy.shutdown().await?;
             info!("Unregistered strategy: {}", name);
         }
@@ -133,15 +209,169 @@ impl StrategyEngine {
     /// Start the engine (enables evaluation loop).
     pub fn start(&mut self) {
         self.running = true;
+        self.started = true;
         info!("Strategy engine started");
     }
 
+    /// Check if the engine is running.
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
     /// Stop the engine.
     pub fn stop(&mut self) {
         self.running = false;
         info!("Strategy engine stopped");
     }
 
+    /// Stop the engine, checkpoint strategy state and give every registered
+    /// strategy a chance to clean up.
+    ///
+    /// Strategies stay registered so their statistics can still be reported.
+    pub async fn shutdown(&mut self) {
+        self.running = false;
+        self.checkpoint().await;
+
+        for (name, handle) in &mut self.strategies {
+            handle.status = StrategyStatus::Stopped;
+            let mut strategy = handle.strategy.write().await;
+            if let Err(e) = strategy.shutdown().await {
+                warn!("Strategy '{}' failed to shut down: {}", name, e);
+            }
+        }
+    }
+
+    /// Write the state snapshot of every strategy evaluated in this process,
+    /// returning how many were written.
+    ///
+    /// Strategies that never ran still hold the state they were restored
+    /// with, which may since have been updated by another process.
+    pub async fn checkpoint(&mut self) -> usize {
+        let Some(store) = &self.state_store else {
+            return 0;
+        };
+
+        let mut saved = 0;
+        for (name, handle) in &self.strategies {
+            if handle.last_evaluated.is_none() {
+                continue;
+            }
+            let strategy = handle.strategy.read().await;
+            if Self::save_to(store, name, strategy.as_ref()) {
+                saved += 1;
+            }
+        }
+        self.save_brackets();
+        self.last_checkpoint = Some(Utc::now());
+        if saved > 0 {
+            debug!("Checkpointed {} strategy state(s)", saved);
+        }
+        saved
+    }
+
+    /// Checkpoint if the configured interval has passed since the last one.
+    pub async fn checkpoint_if_due(&mut self) {
+        let due = self.last_checkpoint.is_none_or(|last| {
+            Utc::now().signed_duration_since(last).num_seconds()
+                >= self.config.checkpoint_interval_secs as i64
+        });
+        if due {
+            self.checkpoint().await;
+        }
+    }
+
+    fn save_state(&self, name: &str, strategy: &dyn Strategy) {
+        if let Some(store) = &self.state_store {
+            Self::save_to(store, name, strategy);
+        }
+    }
+
+    /// Save one snapshot, returning whether anything was written.
+    fn save_to(store: &StateStore, name: &str, strategy: &dyn Strategy) -> bool {
+        let result = strategy
+            .state()
+            .and_then(|state| state.map(|s| store.save(name, &s)).transpose());
+        match result {
+            Ok(saved) => saved.is_some(),
+            Err(e) => {
+                warn!("Failed to checkpoint strategy '{}': {}", name, e);
+                false
+            }
+        }
+    }
+
+    fn save_brackets(&self) {
+        if self.started
+            && let Some(store) = &self.state_store
+            && let Err(e) = store.save_brackets(self.brackets.brackets())
+        {
+            warn!("Failed to save brackets: {}", e);
+        }
+    }
+
+    fn restore_brackets(&mut self) {
+        let Some(store) = &self.state_store else {
+            return;
+        };
+        match store.load_brackets() {
+            Ok(brackets) if brackets.is_empty() => {}
+            Ok(brackets) => {
+                self.brackets = BracketBook::restore(brackets);
+                info!("Restored {} bracket(s)", self.brackets.brackets().len());
+            }
+            Err(e) => warn!("Failed to read brackets: {}", e),
+        }
+    }
+
+    /// Restore a strategy's snapshot, migrating it from an older version if
+    /// needed.
+    ///
+    /// A snapshot that cannot be used is archived next to the others and
+    /// the strategy starts without it.
+    fn restore_state(&self, name: &str, strategy: &mut dyn Strategy) {
+        let Some(store) = &self.state_store else {
+            return;
+        };
+        let state = match store.load(name) {
+            Ok(Some(state)) => state,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("Failed to read state of strategy '{}': {}", name, e);
+                return;
+            }
+        };
+
+        let version = state.version;
+        let current = strategy.state_version();
+        let result = if version == current {
+            strategy.restore_state(state)
+        } else if version < current {
+            strategy
+                .migrate_state(state)
+                .and_then(|state| strategy.restore_state(state))
+        } else {
+            Err(crate::Error::invalid_input(format!(
+                "State version {} is newer than supported version {}",
+                version, current
+            )))
+        };
+
+        match result {
+            Ok(()) if version == current => info!("Restored state of strategy '{}'", name),
+            Ok(()) => info!(
+                "Restored state of strategy '{}' (migrated from version {} to {})",
+                name, version, current
+            ),
+            Err(e) => {
+                warn!("Discarding state of strategy '{}': {}", name, e);
+                match store.archive(name, version) {
+                    Ok(path) => warn!("Previous state kept at {}", path.display()),
+                    Err(e) => warn!("Failed to archive state of strategy '{}': {}", name, e),
+                }
+            }
+        }
+    }
+
     /// Evaluate all running strategies against current context.
     pub async fn evaluate(&mut self, ctx: &StrategyContext) -> Vec<Signal> {
         if !self.running {
@@ -181,6 +411,20 @@ impl StrategyEngine {
                 for signal in &mut signals {
                     signal.strategy_name = name.clone();
                 }
+                signals.retain_mut(|signal| {
+                    let sized = match &handle.sizer {
+                        Some(sizer) => sizer.apply(signal, &filtered_ctx),
+                        None => Ok(()),
+                    };
+                    match sized.and_then(|()| resolve_shares(signal, &filtered_ctx)) {
+                        Ok(()) => true,
+                        Err(reason) => {
+                            warn!("Dropped signal {} from '{}': {}", signal.id, name, reason);
+                            strategy.on_signal_executed(signal, false);
+                            false
+                        }
+                    }
+                });
                 Ok::<Vec<Signal>, crate::Error>(signals)
             };
 
@@ -206,19 +450,29 @@ impl StrategyEngine {
         }
 
         // Apply risk checks to signals
-        let approved_signals = self.apply_risk_checks(all_signals, ctx);
+        let approved_signals = self.apply_risk_checks(all_signals, ctx).await;
 
         // Store signals for potential execution
         self.pending_signals.extend(approved_signals.clone());
 
+        self.checkpoint_if_due().await;
+
         approved_signals
     }
 
     fn filter_context(&self, ctx: &StrategyContext, config: &StrategyConfig) -> StrategyContext {
         let mut filtered = ctx.clone();
 
-        // Filter markets if include/exclude lists are specified
-        if !config.include_markets.is_empty() {
+        // Filter markets if include/exclude lists or a watchlist are specified
+        if let Some(name) = &config.watchlist {
+            let watched = self.watchlists.get(name);
+            if watched.is_none() {
+                debug!("Watchlist '{}' not found, strategy universe is empty", name);
+            }
+            filtered.markets.retain(|id, _| {
+                config.include_markets.contains(id) || watched.is_some_and(|w| w.contains(id))
+            });
+        } else if !config.include_markets.is_empty() {
             filtered
                 .markets
                 .retain(|id, _| config.include_markets.contains(id));
@@ -233,10 +487,48 @@ impl StrategyEngine {
         filtered
     }
 
-    fn apply_risk_checks(&self, signals: Vec<Signal>, ctx: &StrategyContext) -> Vec<Signal> {
+    /// Token IDs whose order books running strategies need.
+    ///
+    /// Covers every outcome of every market in each running strategy's
+    /// filtered universe, deduplicated.
+    pub fn book_subscriptions(&self, ctx: &StrategyContext) -> Vec<String> {
+        let mut token_ids: Vec<String> = self
+            .strategies
+            .values()
+            .filter(|handle| handle.status == StrategyStatus::Running && handle.config.enabled)
+            .flat_map(|handle| {
+                self.filter_context(ctx, &handle.config)
+                    .markets
+                    .into_values()
+                    .flat_map(|m| m.token_ids)
+            })
+            .collect();
+        token_ids.sort();
+        token_ids.dedup();
+        token_ids
+    }
+
+    /// Request order books for all markets in running strategies' universes.
+    pub fn request_order_books(&self, ctx: &StrategyContext) -> Result<()> {
+        let token_ids = self.book_subscriptions(ctx);
+        if token_ids.is_empty() {
+            return Ok(());
+        }
+
+        self.action_tx
+            .send(Action::LoadOrderBooks(token_ids))
+            .map_err(|e| crate::Error::channel(e.to_string()))
+    }
+
+    async fn apply_risk_checks(&self, signals: Vec<Signal>, ctx: &StrategyContext) -> Vec<Signal> {
         let mut approved = Vec::new();
 
         for signal in signals {
+            // Cancelling only ever reduces exposure
+            if matches!(signal.action, SignalAction::Cancel { .. }) {
+                approved.push(signal);
+                continue;
+            }
             match self.risk_guard.check_signal(&signal, ctx) {
                 Ok(()) => approved.push(signal),
                 Err(violation) => {
@@ -244,6 +536,10 @@ impl StrategyEngine {
                         "Signal rejected by risk guard: {} - {:?}",
                         signal.id, violation
                     );
+                    if let Some(handle) = self.strategies.get(&signal.strategy_name) {
+                        let mut strategy = handle.strategy.write().await;
+                        strategy.on_signal_executed(&signal, false);
+                    }
                 }
             }
         }
@@ -274,29 +570,174 @@ impl StrategyEngine {
                 }
             }
 
-            // Convert signal to order request
-            let order_request = self.signal_to_order(&signal)?;
+            // Dispatch the order actions and record execution
+            let client_order_id = self.dispatch_signal(&signal)?;
+            self.record_signal(&signal, client_order_id);
+            executed.push(signal.id.clone());
+
+            // Notify strategy and update execution count
+            if let Some(handle) = self.strategies.get_mut(&signal.strategy_name) {
+                handle.signals_executed += 1;
+                let mut strategy = handle.strategy.write().await;
+                strategy.on_signal_executed(&signal, true);
+            }
+        }
+
+        Ok(executed)
+    }
+
+    /// Send the order actions for a signal: cancel the order it cancels or
+    /// amends, then place its order unless it only cancels.
+    ///
+    /// Returns the client order ID of the order placed, which is tracked.
+    /// Signals with an execution algorithm place no order here; their
+    /// children are sent by [`advance_executions`](Self::advance_executions).
+    fn dispatch_signal(&mut self, signal: &Signal) -> Result<Option<String>> {
+        let order_request = match signal.action {
+            SignalAction::Cancel { .. } => None,
+            _ => Some(self.signal_to_order(signal)?),
+        };
 
-            // Dispatch order action
+        if let Some(order_id) = signal.action.cancels() {
             self.action_tx
-                .send(Action::PlaceOrder(order_request))
+                .send(Action::CancelOrder(order_id.to_string()))
                 .map_err(|e| crate::Error::channel(e.to_string()))?;
+        }
+
+        let Some(order_request) = order_request else {
+            return Ok(None);
+        };
+        if !signal.execution.is_immediate() {
+            self.executions.start(signal, order_request.size);
+            if self.brackets.arm(signal, &signal.id) {
+                self.save_brackets();
+            }
+            return Ok(None);
+        }
+        self.lifecycles.submit(signal, &order_request);
+        if let Some(client_order_id) = &order_request.client_order_id
+            && self.brackets.arm(signal, client_order_id)
+        {
+            self.save_brackets();
+        }
+        self.action_tx
+            .send(Action::PlaceOrder(order_request.clone()))
+            .map_err(|e| crate::Error::channel(e.to_string()))?;
+        Ok(order_request.client_order_id)
+    }
+
+    /// Check open brackets against the market and send the exit of every
+    /// bracket whose stop-loss or take-profit level was crossed, returning
+    /// the exit signal IDs.
+    ///
+    /// Exits are sent whether or not their strategy auto-executes and bypass
+    /// the risk guard, since they only reduce exposure. An entry order still
+    /// working when its bracket triggers is cancelled, and an entry worked by
+    /// an execution algorithm is stopped with its working children cancelled.
+    pub async fn check_brackets(&mut self, ctx: &StrategyContext) -> Result<Vec<String>> {
+        let exits = self.brackets.check(ctx);
+        if exits.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut executed = Vec::new();
+        for (bracket_id, signal) in exits {
+            info!("{}", signal);
+            let entry = self
+                .brackets
+                .brackets()
+                .iter()
+                .find(|b| b.id == bracket_id)
+                .map(|b| b.entry_client_order_id.clone());
+            if let Some(entry) = entry {
+                let children = self.executions.stop(&entry);
+                self.cancel_children(&children)?;
+                if let Some(order_id) = self.working_order_id(&entry) {
+                    self.action_tx
+                        .send(Action::CancelOrder(order_id))
+                        .map_err(|e| crate::Error::channel(e.to_string()))?;
+                }
+            }
 
-            // Record execution
-            self.record_signal(&signal, true);
+            let client_order_id = self.dispatch_signal(&signal)?;
+            self.brackets
+                .exit_submitted(&bracket_id, client_order_id.clone());
+            self.record_signal(&signal, client_order_id);
             executed.push(signal.id.clone());
 
-            // Notify strategy and update execution count
             if let Some(handle) = self.strategies.get_mut(&signal.strategy_name) {
                 handle.signals_executed += 1;
                 let mut strategy = handle.strategy.write().await;
                 strategy.on_signal_executed(&signal, true);
             }
         }
+        self.save_brackets();
 
         Ok(executed)
     }
 
+    /// Send the child orders that are due for signals worked by execution
+    /// algorithms and cancel the children they no longer want working,
+    /// returning how many children were sent.
+    pub fn advance_executions(&mut self, ctx: &StrategyContext) -> Result<usize> {
+        let ExecutionStep {
+            children,
+            cancels,
+            finished,
+        } = self.executions.advance(ctx, Utc::now());
+        self.cancel_children(&cancels)?;
+        for child in &children {
+            if let Some(parent) = self.executions.get(&child.parent_id) {
+                self.lifecycles.submit(&parent.signal, &child.request);
+            }
+            self.action_tx
+                .send(Action::PlaceOrder(child.request.clone()))
+                .map_err(|e| crate::Error::channel(e.to_string()))?;
+        }
+        for parent_id in finished {
+            self.refresh_execution(&parent_id);
+            // An entry that never filled leaves nothing to protect
+            if self.brackets.ended(&parent_id, false) {
+                self.save_brackets();
+            }
+        }
+        Ok(children.len())
+    }
+
+    /// Cancel working child orders of execution algorithms. Children the
+    /// exchange has not acknowledged yet are cancelled on a later advance.
+    fn cancel_children(&mut self, client_order_ids: &[String]) -> Result<()> {
+        for client_order_id in client_order_ids {
+            let Some(order_id) = self.working_order_id(client_order_id) else {
+                continue;
+            };
+            self.action_tx
+                .send(Action::CancelOrder(order_id))
+                .map_err(|e| crate::Error::channel(e.to_string()))?;
+            self.executions.cancel_sent(client_order_id);
+        }
+        Ok(())
+    }
+
+    /// Exchange order ID of a tracked order that is still working.
+    fn working_order_id(&self, client_order_id: &str) -> Option<String> {
+        self.lifecycles
+            .get(client_order_id)
+            .filter(|l| !l.stage.is_terminal())
+            .and_then(|l| l.order_id())
+            .map(str::to_string)
+    }
+
+    /// Get the parent order of a signal worked by an execution algorithm.
+    pub fn execution(&self, signal_id: &str) -> Option<&ParentOrder> {
+        self.executions.get(signal_id)
+    }
+
+    /// Open stop-loss and take-profit brackets, oldest first.
+    pub fn brackets(&self) -> &[Bracket] {
+        self.brackets.brackets()
+    }
+
     fn signal_to_order(&self, signal: &Signal) -> Result<OrderRequest> {
         // For limit orders, price is required
         if signal.order_type == OrderType::Limit && signal.price.is_none() {
@@ -305,21 +746,29 @@ impl StrategyEngine {
             ));
         }
 
+        let Quantity::Shares(size) = signal.size else {
+            return Err(crate::Error::invalid_input(
+                "Signal size must be converted to shares before placing an order",
+            ));
+        };
+
         Ok(OrderRequest {
             market_id: signal.market_id.clone(),
             token_id: signal.token_id.clone(),
             side: signal.side,
             price: signal.price,
-            size: signal.size,
+            size,
             order_type: signal.order_type,
+            client_order_id: Some(uuid::Uuid::new_v4().to_string()),
         })
     }
 
-    fn record_signal(&mut self, signal: &Signal, executed: bool) {
+    fn record_signal(&mut self, signal: &Signal, client_order_id: Option<String>) {
         self.signal_history.push_back(SignalRecord {
             signal: signal.clone(),
-            executed,
-            executed_at: if executed { Some(Utc::now()) } else { None },
+            executed: true,
+            executed_at: Some(Utc::now()),
+            client_order_id,
             result: None,
         });
 
@@ -339,6 +788,158 @@ impl StrategyEngine {
         &self.signal_history
     }
 
+    /// Get the order placed for an executed signal, by client order ID.
+    pub fn lifecycle(&self, client_order_id: &str) -> Option<&OrderLifecycle> {
+        self.lifecycles.get(client_order_id)
+    }
+
+    /// Record that the exchange accepted an order placed for a signal.
+    ///
+    /// Orders without a tracked client order ID, e.g. manual ones, are
+    /// ignored.
+    pub async fn order_placed(&mut self, client_order_id: &str, order: &Order) {
+        let updates = self.lifecycles.placed(client_order_id, order);
+        self.refresh_result(client_order_id);
+        if let Some(lifecycle) = self.lifecycles.get(client_order_id)
+            && let Some(handle) = self.strategies.get(&lifecycle.strategy)
+        {
+            let mut strategy = handle.strategy.write().await;
+            strategy.on_order_placed(&lifecycle.signal_id, &order.id);
+        }
+        self.apply_lifecycle_updates(updates).await;
+    }
+
+    /// Record that an order placed for a signal was refused, telling the
+    /// signal's strategy unless the order was a child its parent may resend.
+    pub async fn order_rejected(&mut self, client_order_id: &str, reason: &str) {
+        self.lifecycles.rejected(client_order_id, reason);
+        self.refresh_result(client_order_id);
+        if let Some(parent_id) = self.executions.child_ended(client_order_id) {
+            self.refresh_execution(&parent_id);
+        } else if let Some(signal) = self
+            .signal_history
+            .iter()
+            .rev()
+            .find(|r| r.client_order_id.as_deref() == Some(client_order_id))
+            .map(|r| r.signal.clone())
+            && let Some(handle) = self.strategies.get(&signal.strategy_name)
+        {
+            let mut strategy = handle.strategy.write().await;
+            strategy.on_signal_executed(&signal, false);
+        }
+        if self.brackets.ended(client_order_id, true) {
+            self.save_brackets();
+        }
+    }
+
+    /// Record that an order was cancelled on request.
+    pub async fn order_cancelled(&mut self, order_id: &str) {
+        let updates = self.lifecycles.cancelled(order_id);
+        self.apply_lifecycle_updates(updates).await;
+    }
+
+    /// Update tracked orders from the exchange's open orders and our fill
+    /// history, notifying strategies of fills and cancellations.
+    pub async fn sync_orders(&mut self, open_orders: &[Order], trades: &TradeState) {
+        let updates = self.lifecycles.sync(open_orders, trades);
+        self.apply_lifecycle_updates(updates).await;
+    }
+
+    async fn apply_lifecycle_updates(&mut self, updates: Vec<LifecycleUpdate>) {
+        for update in updates {
+            let client_order_id = update.client_order_id().to_string();
+            self.refresh_result(&client_order_id);
+            let bracket_changed = match &update {
+                LifecycleUpdate::Filled { price, size, .. } => {
+                    // Children fill their parent's bracket
+                    let entry = match self
+                        .executions
+                        .child_filled(&client_order_id, *price, *size)
+                    {
+                        Some(parent_id) => {
+                            self.refresh_execution(&parent_id);
+                            parent_id
+                        }
+                        None => client_order_id.clone(),
+                    };
+                    self.brackets.filled(&entry, *price, *size)
+                }
+                LifecycleUpdate::Cancelled { .. } => {
+                    if let Some(parent_id) = self.executions.child_ended(&client_order_id) {
+                        self.refresh_execution(&parent_id);
+                    }
+                    let rejected = self
+                        .lifecycles
+                        .get(&client_order_id)
+                        .is_some_and(|l| l.stage == OrderStage::Rejected);
+                    self.brackets.ended(&client_order_id, rejected)
+                }
+            };
+            if bracket_changed {
+                self.save_brackets();
+            }
+
+            let Some(lifecycle) = self.lifecycles.get(&client_order_id) else {
+                continue;
+            };
+            let Some(handle) = self.strategies.get(&lifecycle.strategy) else {
+                continue;
+            };
+            let mut strategy = handle.strategy.write().await;
+            match update {
+                LifecycleUpdate::Filled {
+                    order_id,
+                    price,
+                    size,
+                    ..
+                } => {
+                    debug!(
+                        "Order {} of '{}' filled {} at {}",
+                        order_id, lifecycle.strategy, size, price
+                    );
+                    strategy.on_order_filled(&order_id, price, size);
+                }
+                LifecycleUpdate::Cancelled { order_id, .. } => {
+                    debug!(
+                        "Order {} of '{}' ended unfilled",
+                        order_id, lifecycle.strategy
+                    );
+                    strategy.on_order_cancelled(&order_id);
+                }
+            }
+        }
+    }
+
+    /// Copy an order's outcome into its signal record.
+    fn refresh_result(&mut self, client_order_id: &str) {
+        let Some(lifecycle) = self.lifecycles.get(client_order_id) else {
+            return;
+        };
+        if let Some(record) = self
+            .signal_history
+            .iter_mut()
+            .rev()
+            .find(|r| r.client_order_id.as_deref() == Some(client_order_id))
+        {
+            record.result = lifecycle.result();
+        }
+    }
+
+    /// Copy a parent order's outcome into its signal record.
+    fn refresh_execution(&mut self, parent_id: &str) {
+        let Some(parent) = self.executions.get(parent_id) else {
+            return;
+        };
+        if let Some(record) = self
+            .signal_history
+            .iter_mut()
+            .rev()
+            .find(|r| r.signal.id == parent_id)
+        {
+            record.result = parent.result();
+        }
+    }
+
     /// Get strategy handles.
     pub fn strategies(&self) -> &HashMap<String, StrategyHandle> {
         &self.strategies
@@ -349,9 +950,21 @@ impl StrategyEngine {
         self.strategies.get_mut(name)
     }
 
+    /// Size a strategy's entries with a custom sizer instead of the one in
+    /// its configuration.
+    pub fn set_sizer(&mut self, name: &str, sizer: Sizer) -> Result<()> {
+        let handle = self
+            .strategies
+            .get_mut(name)
+            .ok_or_else(|| crate::Error::invalid_input(format!("Strategy '{}' not found", name)))?;
+        handle.sizer = Some(sizer);
+        Ok(())
+    }
+
     /// Update strategy configuration.
     pub fn update_config(&mut self, name: &str, config: StrategyConfig) -> Result<()> {
         if let Some(handle) = self.strategies.get_mut(name) {
+            handle.sizer = config.sizing.as_ref().map(Sizer::from_config);
             handle.config = config;
             Ok(())
         } else {
@@ -362,6 +975,121 @@ impl StrategyEngine {
         }
     }
 
+    /// Replace a strategy's configuration and re-run its initialization so
+    /// parameter changes take effect.
+    pub async fn reconfigure(&mut self, name: &str, config: StrategyConfig) -> Result<()> {
+        let handle = self
+            .strategies
+            .get_mut(name)
+            .ok_or_else(|| crate::Error::invalid_input(format!("Strategy '{}' not found", name)))?;
+
+        handle.strategy.write().await.initialize(&config).await?;
+        handle.sizer = config.sizing.as_ref().map(Sizer::from_config);
+        handle.config = config;
+        info!("Reconfigured strategy: {}", name);
+        Ok(())
+    }
+
+    /// Get a strategy's tunable parameters with their current values,
+    /// sorted by name.
+    ///
+    /// The current value is the one in the strategy's configuration, or the
+    /// default when it is unset or invalid.
+    pub async fn parameter_fields(&self, name: &str) -> Result<Vec<ParameterField>> {
+        let handle = self
+            .strategies
+            .get(name)
+            .ok_or_else(|| crate::Error::invalid_input(format!("Strategy '{}' not found", name)))?;
+
+        let mut fields: Vec<ParameterField> = handle
+            .strategy
+            .read()
+            .await
+            .parameters()
+            .into_values()
+            .map(|def| {
+                let current = handle
+                    .config
+                    .parameters
+                    .get(&def.name)
+                    .and_then(|v| def.from_json(v).ok())
+                    .unwrap_or_else(|| def.default.clone());
+                ParameterField { def, current }
+            })
+            .collect();
+        fields.sort_by(|a, b| a.def.name.cmp(&b.def.name));
+        Ok(fields)
+    }
+
+    /// Validate a parameter change against its definition and apply it to a
+    /// running strategy.
+    ///
+    /// The new value is also stored in the strategy's configuration, so it
+    /// survives [`reconfigure`](Self::reconfigure), and recorded in the
+    /// change log.
+    pub async fn set_parameter(
+        &mut self,
+        name: &str,
+        parameter: &str,
+        value: ParameterValue,
+    ) -> Result<ParameterChange> {
+        let field = self
+            .parameter_fields(name)
+            .await?
+            .into_iter()
+            .find(|f| f.def.name == parameter)
+            .ok_or_else(|| {
+                crate::Error::invalid_input(format!(
+                    "Strategy '{}' has no parameter '{}'",
+                    name, parameter
+                ))
+            })?;
+        let value = field.def.validate(value)?;
+
+        let handle = self
+            .strategies
+            .get_mut(name)
+            .ok_or_else(|| crate::Error::invalid_input(format!("Strategy '{}' not found", name)))?;
+        handle
+            .strategy
+            .write()
+            .await
+            .set_parameter(parameter, value.clone())?;
+        handle
+            .config
+            .parameters
+            .insert(parameter.to_string(), value.to_json());
+
+        let change = ParameterChange {
+            strategy: name.to_string(),
+            parameter: parameter.to_string(),
+            old: field.current,
+            new: value,
+            changed_at: Utc::now(),
+        };
+        info!(
+            "Set {}.{} from {} to {}",
+            name, change.parameter, change.old, change.new
+        );
+
+        self.parameter_log.push_back(change.clone());
+        while self.parameter_log.len() > MAX_PARAMETER_LOG {
+            self.parameter_log.pop_front();
+        }
+        Ok(change)
+    }
+
+    /// Get the runtime parameter changes, oldest first.
+    pub fn parameter_log(&self) -> &VecDeque<ParameterChange> {
+        &self.parameter_log
+    }
+
+    /// Replace the risk limits applied to every signal.
+    pub fn set_risk_config(&mut self, risk_config: super::RiskConfig) {
+        self.risk_guard = RiskGuard::new(risk_config.clone());
+        self.config.risk_config = risk_config;
+    }
+
     /// Notify strategies of a market update.
     pub async fn on_market_update(&mut self, ctx: &StrategyContext) {
         for handle in self.strategies.values() {
@@ -405,13 +1133,8 @@ impl StrategyEngine {
             .cloned()
             .ok_or_else(|| crate::Error::invalid_input("Signal not found"))?;
 
-        let order_request = self.signal_to_order(&signal)?;
-
-        self.action_tx
-            .send(Action::PlaceOrder(order_request))
-            .map_err(|e| crate::Error::channel(e.to_string()))?;
-
-        self.record_signal(&signal, true);
+        let client_order_id = self.dispatch_signal(&signal)?;
+        self.record_signal(&signal, client_order_id);
         self.pending_signals.retain(|s| s.id != signal_id);
 
         if let Some(handle) = self.strategies.get_mut(&signal.strategy_name) {
@@ -424,12 +1147,32 @@ impl StrategyEngine {
     }
 }
 
+/// Convert the size of a signal given as a notional to shares, keeping the
+/// notional in its metadata.
+fn resolve_shares(signal: &mut Signal, ctx: &StrategyContext) -> std::result::Result<(), String> {
+    let Quantity::Notional(notional) = signal.size else {
+        return Ok(());
+    };
+    if matches!(signal.action, SignalAction::Cancel { .. }) {
+        return Ok(());
+    }
+    let shares = signal
+        .shares(ctx)
+        .ok_or("no price to convert its notional to shares")?
+        .round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero);
+    signal.metadata.notional = Some(notional);
+    signal.size = Quantity::Shares(shares);
+    Ok(())
+}
+
 /// Handle to a registered strategy.
 pub struct StrategyHandle {
     /// The strategy instance.
     pub strategy: Arc<RwLock<Box<dyn Strategy>>>,
     /// Strategy configuration.
     pub config: StrategyConfig,
+    /// Sizer applied to the strategy's entries, if any.
+    pub sizer: Option<Sizer>,
     /// Current status.
     pub status: StrategyStatus,
     /// Last evaluation timestamp.
@@ -475,12 +1218,38 @@ pub struct SignalRecord {
     pub executed: bool,
     /// When it was executed.
     pub executed_at: Option<DateTime<Utc>>,
+    /// Client order ID of the order placed for it.
+    pub client_order_id: Option<String>,
     /// Execution result.
     pub result: Option<SignalResult>,
 }
 
+/// A strategy parameter and its current value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterField {
+    /// The parameter's definition.
+    pub def: ParameterDef,
+    /// Value in effect.
+    pub current: ParameterValue,
+}
+
+/// A parameter changed at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterChange {
+    /// Strategy name.
+    pub strategy: String,
+    /// Parameter name.
+    pub parameter: String,
+    /// Value before the change.
+    pub old: ParameterValue,
+    /// Value after the change.
+    pub new: ParameterValue,
+    /// When the change was made.
+    pub changed_at: DateTime<Utc>,
+}
+
 /// Result of signal execution.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum SignalResult {
     /// Order was placed successfully.
     OrderPlaced { order_id: String },
@@ -506,6 +1275,10 @@ pub struct EngineConfig {
     pub max_signal_history: usize,
     /// Evaluation interval in milliseconds.
     pub evaluation_interval_ms: u64,
+    /// Directory for strategy state snapshots; `None` disables persistence.
+    pub state_dir: Option<PathBuf>,
+    /// Minimum time between state checkpoints while running, in seconds.
+    pub checkpoint_interval_secs: u64,
 }
 
 impl Default for EngineConfig {
@@ -515,6 +1288,210 @@ impl Default for EngineConfig {
             max_strategy_errors: 5,
             max_signal_history: 1000,
             evaluation_interval_ms: 1000,
+            state_dir: None,
+            checkpoint_interval_secs: 60,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{MarketStatus, OrderSide, OrderStatus};
+    use crate::strategy::{
+        ExecutionAlgo, MarketSnapshot, ParentStatus, RiskConfig,
+        strategies::{MomentumStrategy, SpreadStrategy},
+    };
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn snapshot(id: &str) -> MarketSnapshot {
+        MarketSnapshot {
+            condition_id: id.to_string(),
+            question: format!("{}?", id),
+            status: MarketStatus::Active,
+            token_ids: vec![format!("{}-yes", id), format!("{}-no", id)],
+            token_names: vec!["Yes".to_string(), "No".to_string()],
+            token_prices: vec![Decimal::new(5, 1); 2],
+            volume_24h: Decimal::ZERO,
+            liquidity: Decimal::ZERO,
+            spread: None,
+            end_date: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_order_books_for_running_universes() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut engine = StrategyEngine::new(tx, EngineConfig::default());
+        let config = StrategyConfig {
+            enabled: true,
+            include_markets: vec!["m1".to_string()],
+            ..StrategyConfig::default()
+        };
+        engine
+            .register(MomentumStrategy::new(), config)
+            .await
+            .unwrap();
+
+        let mut ctx = StrategyContext::new();
+        for id in ["m1", "m2"] {
+            ctx.markets.insert(id.to_string(), snapshot(id));
+        }
+
+        // Nothing is requested until the strategy runs
+        assert!(engine.book_subscriptions(&ctx).is_empty());
+        engine.request_order_books(&ctx).unwrap();
+        assert!(rx.try_recv().is_err());
+
+        engine.start_strategy("momentum").unwrap();
+        assert_eq!(engine.book_subscriptions(&ctx), vec!["m1-no", "m1-yes"]);
+
+        engine.request_order_books(&ctx).unwrap();
+        match rx.try_recv() {
+            Ok(Action::LoadOrderBooks(token_ids)) => {
+                assert_eq!(token_ids, vec!["m1-no", "m1-yes"])
+            }
+            other => panic!("expected LoadOrderBooks, got {:?}", other),
         }
     }
+
+    #[tokio::test]
+    async fn test_checkpoint_only_saves_strategies_that_ran() {
+        let dir = std::env::temp_dir().join(format!("clobster-engine-{}", uuid::Uuid::new_v4()));
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut engine = StrategyEngine::new(
+            tx,
+            EngineConfig {
+                state_dir: Some(dir.clone()),
+                ..EngineConfig::default()
+            },
+        );
+        let config = StrategyConfig {
+            enabled: true,
+            ..StrategyConfig::default()
+        };
+        engine
+            .register(MomentumStrategy::new(), config)
+            .await
+            .unwrap();
+
+        // Another process owns the state while this engine has not run
+        engine.shutdown().await;
+        assert!(!dir.exists());
+
+        engine.start();
+        engine.start_strategy("momentum").unwrap();
+        let mut ctx = StrategyContext::new();
+        ctx.markets.insert("m1".to_string(), snapshot("m1"));
+        engine.evaluate(&ctx).await;
+
+        assert_eq!(engine.checkpoint().await, 1);
+        assert!(dir.join("momentum.json").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_risk_rejected_quote_is_reissued() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let mut engine = StrategyEngine::new(tx, EngineConfig::default());
+        engine.set_risk_config(RiskConfig {
+            enabled: false,
+            ..RiskConfig::default()
+        });
+        let config = StrategyConfig {
+            enabled: true,
+            ..StrategyConfig::default()
+        };
+        engine
+            .register(SpreadStrategy::new(), config)
+            .await
+            .unwrap();
+        engine.start();
+        engine.start_strategy("spread").unwrap();
+
+        let mut ctx = StrategyContext::new();
+        let mut market = snapshot("m1");
+        market.liquidity = dec!(5000);
+        market.spread = Some(dec!(0.04));
+        ctx.markets.insert("m1".to_string(), market);
+        assert!(engine.evaluate(&ctx).await.is_empty());
+
+        // The strategy was told, so it quotes again instead of waiting on
+        // orders that were never placed
+        engine.set_risk_config(RiskConfig::default());
+        let quotes = engine.evaluate(&ctx).await;
+        assert_eq!(quotes.len(), 2);
+        assert!(quotes.iter().all(|q| q.action == SignalAction::Place));
+    }
+
+    #[tokio::test]
+    async fn test_bracket_trigger_stops_algorithm_entry() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut engine = StrategyEngine::new(tx, EngineConfig::default());
+        let entry = Signal::buy("m1", "m1-yes", Quantity::Shares(dec!(100)))
+            .with_price(dec!(0.50))
+            .with_stop_loss(dec!(0.40))
+            .with_execution(ExecutionAlgo::Iceberg {
+                clip_size: dec!(40),
+                timeout_secs: 60,
+            });
+        engine.dispatch_signal(&entry).unwrap();
+
+        let mut ctx = StrategyContext::new();
+        ctx.markets.insert("m1".to_string(), snapshot("m1"));
+        assert_eq!(engine.advance_executions(&ctx).unwrap(), 1);
+        let child = match rx.try_recv() {
+            Ok(Action::PlaceOrder(request)) => request,
+            other => panic!("expected PlaceOrder, got {:?}", other),
+        };
+
+        // The clip rests with part of it filled, arming the bracket
+        let now = Utc::now();
+        let order = Order {
+            id: "o1".to_string(),
+            market_id: "m1".to_string(),
+            market_question: String::new(),
+            token_id: "m1-yes".to_string(),
+            outcome_name: "Yes".to_string(),
+            side: OrderSide::Buy,
+            order_type: OrderType::Limit,
+            price: dec!(0.50),
+            original_size: child.size,
+            remaining_size: child.size - dec!(10),
+            filled_size: dec!(10),
+            status: OrderStatus::PartiallyFilled,
+            created_at: now,
+            updated_at: now,
+            expires_at: None,
+        };
+        engine
+            .order_placed(child.client_order_id.as_deref().unwrap(), &order)
+            .await;
+        assert_eq!(engine.brackets()[0].size, dec!(10));
+
+        // The stop cancels the working clip before the exit is sent
+        ctx.markets.get_mut("m1").unwrap().token_prices[0] = dec!(0.35);
+        assert_eq!(engine.check_brackets(&ctx).await.unwrap().len(), 1);
+        match rx.try_recv() {
+            Ok(Action::CancelOrder(order_id)) => assert_eq!(order_id, "o1"),
+            other => panic!("expected CancelOrder, got {:?}", other),
+        }
+        match rx.try_recv() {
+            Ok(Action::PlaceOrder(exit)) => {
+                assert_eq!(exit.side, OrderSide::Sell);
+                assert_eq!(exit.size, dec!(10));
+            }
+            other => panic!("expected PlaceOrder, got {:?}", other),
+        }
+
+        // No more clips are sent, and the parent expires once the cancel lands
+        engine.order_cancelled("o1").await;
+        assert_eq!(engine.advance_executions(&ctx).unwrap(), 0);
+        assert!(rx.try_recv().is_err());
+        assert_eq!(
+            engine.execution(&entry.id).unwrap().status,
+            ParentStatus::Expired
+        );
+    }
 }
@@ -0,0 +1,879 @@
+//! Execution algorithms.
+//!
+//! A signal normally becomes one order of its full size. A signal that
+//! requests an [`ExecutionAlgo`] instead becomes a [`ParentOrder`] worked by
+//! the engine as a series of smaller child orders, so large orders do not
+//! walk thin books. Each child is tracked like any other order, and its
+//! fills count towards the parent.
+
+use super::{Signal, SignalResult, StrategyContext};
+use crate::state::{OrderRequest, OrderSide, OrderType, SIZE_DECIMALS};
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// How the order of a signal is worked.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "algo", rename_all = "snake_case")]
+pub enum ExecutionAlgo {
+    /// One order of the full size.
+    #[default]
+    Immediate,
+    /// Equal slices sent at even intervals over `duration_secs`. A slice
+    /// still working when the next one is due is cancelled and its unfilled
+    /// size folded into the later slices.
+    Twap { duration_secs: u64, slices: u32 },
+    /// One child of at most `clip_size` on the book at a time, until filled
+    /// or `timeout_secs` have passed, when the working clip is cancelled.
+    Iceberg {
+        clip_size: Decimal,
+        timeout_secs: u64,
+    },
+    /// Children sized to what the book offers within `max_slippage` (a price
+    /// difference, e.g. `0.02` for two cents) of the best price, one at a
+    /// time, until filled or `timeout_secs` have passed.
+    SlippageCapped {
+        max_slippage: Decimal,
+        timeout_secs: u64,
+    },
+}
+
+impl ExecutionAlgo {
+    /// Whether the signal is sent as a single order.
+    pub fn is_immediate(&self) -> bool {
+        matches!(self, Self::Immediate)
+    }
+
+    /// How long the parent order may be worked.
+    fn duration(&self) -> Duration {
+        let secs = match self {
+            Self::Immediate => 0,
+            Self::Twap { duration_secs, .. } => *duration_secs,
+            Self::Iceberg { timeout_secs, .. } | Self::SlippageCapped { timeout_secs, .. } => {
+                *timeout_secs
+            }
+        };
+        Duration::seconds(secs as i64)
+    }
+}
+
+impl std::fmt::Display for ExecutionAlgo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Immediate => write!(f, "Immediate"),
+            Self::Twap {
+                duration_secs,
+                slices,
+            } => write!(f, "TWAP {} slices over {}s", slices, duration_secs),
+            Self::Iceberg { clip_size, .. } => write!(f, "Iceberg, clips of {}", clip_size),
+            Self::SlippageCapped { max_slippage, .. } => write!(
+                f,
+                "Slippage capped at {:.2}¢",
+                max_slippage * Decimal::ONE_HUNDRED
+            ),
+        }
+    }
+}
+
+/// Where a parent order is in its life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParentStatus {
+    /// Children are still being sent or worked.
+    Working,
+    /// Completely filled.
+    Completed,
+    /// Ran out of time or slices before filling completely.
+    Expired,
+}
+
+impl std::fmt::Display for ParentStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Working => write!(f, "Working"),
+            Self::Completed => write!(f, "Completed"),
+            Self::Expired => write!(f, "Expired"),
+        }
+    }
+}
+
+/// An order sent for part of a parent order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChildOrder {
+    /// Client order ID of the child.
+    pub client_order_id: String,
+    /// Requested size.
+    pub size: Decimal,
+    /// Limit price, `None` for market orders.
+    pub price: Option<Decimal>,
+    /// Filled size.
+    pub filled: Decimal,
+    /// Value of the fills, at their prices.
+    pub filled_value: Decimal,
+    /// Whether the child can no longer fill.
+    pub done: bool,
+    /// Whether a cancel of the child was sent.
+    pub cancel_sent: bool,
+    /// When the child was sent.
+    pub sent_at: DateTime<Utc>,
+}
+
+impl ChildOrder {
+    /// Size still working on the book.
+    fn working(&self) -> Decimal {
+        if self.done {
+            Decimal::ZERO
+        } else {
+            self.size - self.filled
+        }
+    }
+}
+
+/// A signal's order, worked as child orders by an execution algorithm.
+#[derive(Debug, Clone)]
+pub struct ParentOrder {
+    /// Signal the order was requested by.
+    pub signal: Signal,
+    /// Shares to fill.
+    pub size: Decimal,
+    /// Children sent, oldest first.
+    pub children: Vec<ChildOrder>,
+    /// Current status.
+    pub status: ParentStatus,
+    /// When work started.
+    pub started_at: DateTime<Utc>,
+    /// When the order completed or expired.
+    pub ended_at: Option<DateTime<Utc>>,
+    /// Whether work was stopped early, e.g. by the order's bracket. A
+    /// stopped order sends no more children and expires once its working
+    /// children are cancelled.
+    pub stopped: bool,
+}
+
+impl ParentOrder {
+    /// ID of the parent, which is its signal's ID.
+    pub fn id(&self) -> &str {
+        &self.signal.id
+    }
+
+    /// Execution algorithm working the order.
+    pub fn algo(&self) -> &ExecutionAlgo {
+        &self.signal.execution
+    }
+
+    /// Total filled size.
+    pub fn filled_size(&self) -> Decimal {
+        self.children.iter().map(|c| c.filled).sum()
+    }
+
+    /// Size-weighted average fill price of the children.
+    pub fn average_price(&self) -> Option<Decimal> {
+        let size = self.filled_size();
+        if size.is_zero() {
+            return None;
+        }
+        let value: Decimal = self.children.iter().map(|c| c.filled_value).sum();
+        Some(value / size)
+    }
+
+    /// Filled share of the order, from 0 to 1.
+    pub fn progress(&self) -> Decimal {
+        if self.size.is_zero() {
+            return Decimal::ONE;
+        }
+        (self.filled_size() / self.size).min(Decimal::ONE)
+    }
+
+    /// Outcome of the signal so far, as recorded in the signal history.
+    pub fn result(&self) -> Option<SignalResult> {
+        let filled_price = self.average_price().unwrap_or_default();
+        match self.status {
+            ParentStatus::Working if self.filled_size().is_zero() => None,
+            ParentStatus::Working => Some(SignalResult::OrderPlaced {
+                order_id: self.id().to_string(),
+            }),
+            ParentStatus::Completed => Some(SignalResult::Filled {
+                order_id: self.id().to_string(),
+                filled_price,
+            }),
+            ParentStatus::Expired if self.filled_size().is_zero() => Some(SignalResult::Cancelled),
+            ParentStatus::Expired => Some(SignalResult::Filled {
+                order_id: self.id().to_string(),
+                filled_price,
+            }),
+        }
+    }
+
+    /// Size neither filled nor working on the book.
+    fn unsent(&self) -> Decimal {
+        let working: Decimal = self.children.iter().map(|c| c.working()).sum();
+        (self.size - self.filled_size() - working).max(Decimal::ZERO)
+    }
+
+    fn has_working_child(&self) -> bool {
+        self.children.iter().any(|c| !c.done)
+    }
+
+    /// Size and limit price of the next child due at `now`, if any. Sizes
+    /// are rounded down to [`SIZE_DECIMALS`] and a child that rounds to
+    /// nothing is not sent.
+    fn next_child(
+        &self,
+        ctx: &StrategyContext,
+        now: DateTime<Utc>,
+    ) -> Option<(Decimal, Option<Decimal>)> {
+        let (size, price) = self.next_child_unrounded(ctx, now)?;
+        let size = size.round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero);
+        (size > Decimal::ZERO).then_some((size, price))
+    }
+
+    fn next_child_unrounded(
+        &self,
+        ctx: &StrategyContext,
+        now: DateTime<Utc>,
+    ) -> Option<(Decimal, Option<Decimal>)> {
+        let unsent = self.unsent();
+        if unsent <= Decimal::ZERO {
+            return None;
+        }
+        let price = match self.signal.order_type {
+            OrderType::Limit => self.signal.price,
+            OrderType::Market => None,
+        };
+        match self.algo() {
+            ExecutionAlgo::Immediate => Some((unsent, price)),
+            ExecutionAlgo::Twap { slices, .. } => {
+                let sent = self.children.len() as u32;
+                // A late slice is cancelled first, so its rest is resent here
+                if sent >= *slices || self.has_working_child() {
+                    return None;
+                }
+                if now < self.slice_due(sent)? {
+                    return None;
+                }
+                // The last slice takes whatever rounding left over
+                let remaining = slices - sent;
+                if remaining == 1 {
+                    return Some((unsent, price));
+                }
+                Some((unsent / Decimal::from(remaining), price))
+            }
+            ExecutionAlgo::Iceberg { clip_size, .. } => {
+                if self.has_working_child() {
+                    return None;
+                }
+                Some((unsent.min(*clip_size), price))
+            }
+            ExecutionAlgo::SlippageCapped { max_slippage, .. } => {
+                if self.has_working_child() {
+                    return None;
+                }
+                let (size, worst) =
+                    capped_size(ctx, &self.signal, unsent.min(self.size), *max_slippage)?;
+                // Never trade through the signal's own limit
+                let worst = match (price, self.signal.side) {
+                    (Some(limit), OrderSide::Buy) => worst.min(limit),
+                    (Some(limit), OrderSide::Sell) => worst.max(limit),
+                    (None, _) => worst,
+                };
+                Some((size, Some(worst)))
+            }
+        }
+    }
+
+    /// When TWAP slice `index` is due. Slice `slices` is the end of the
+    /// duration.
+    fn slice_due(&self, index: u32) -> Option<DateTime<Utc>> {
+        let ExecutionAlgo::Twap {
+            duration_secs,
+            slices,
+        } = self.algo()
+        else {
+            return None;
+        };
+        let interval = *duration_secs as i64 * 1000 / i64::from(*slices);
+        Some(self.started_at + Duration::milliseconds(interval * i64::from(index)))
+    }
+
+    /// Whether the order can send no more children.
+    fn exhausted(&self, now: DateTime<Utc>) -> bool {
+        if self.stopped {
+            return true;
+        }
+        match self.algo() {
+            ExecutionAlgo::Twap { slices, .. } => self.children.len() as u32 >= *slices,
+            _ => now >= self.started_at + self.algo().duration(),
+        }
+    }
+
+    /// Client order IDs of working children due to be cancelled at `now`
+    /// that have no cancel sent yet.
+    ///
+    /// Every working child is cancelled once the order is stopped or its
+    /// duration or timeout has passed, and a TWAP slice also once the next
+    /// slice is due.
+    fn stale_children(&self, now: DateTime<Utc>) -> Vec<String> {
+        let timed_out = self.stopped || now >= self.started_at + self.algo().duration();
+        self.children
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.done && !c.cancel_sent)
+            .filter(|(index, _)| {
+                timed_out
+                    || self
+                        .slice_due(*index as u32 + 1)
+                        .is_some_and(|next| now >= next)
+            })
+            .map(|(_, c)| c.client_order_id.clone())
+            .collect()
+    }
+
+    fn end(&mut self, status: ParentStatus, now: DateTime<Utc>) {
+        self.status = status;
+        self.ended_at = Some(now);
+    }
+}
+
+/// Largest size up to `size` the book offers within `max_slippage` of its
+/// best price, with the worst price reached.
+///
+/// The average price of the size is held within the cap, checked with the
+/// book's slippage estimate; the worst price is used as the child's limit.
+fn capped_size(
+    ctx: &StrategyContext,
+    signal: &Signal,
+    size: Decimal,
+    max_slippage: Decimal,
+) -> Option<(Decimal, Decimal)> {
+    let book = ctx.get_book(&signal.token_id)?;
+    let (levels, best) = match signal.side {
+        OrderSide::Buy => (book.cumulative_asks(), book.best_ask_price()?),
+        OrderSide::Sell => (book.cumulative_bids(), book.best_bid_price()?),
+    };
+    if best.is_zero() {
+        return None;
+    }
+    let max_pct = max_slippage / best * Decimal::ONE_HUNDRED;
+
+    let mut capped = None;
+    for (price, cumulative) in levels {
+        let candidate = cumulative.min(size);
+        let slippage = match signal.side {
+            OrderSide::Buy => book.slippage_buy(candidate)?,
+            OrderSide::Sell => book.slippage_sell(candidate)?,
+        };
+        if slippage > max_pct {
+            break;
+        }
+        capped = Some((candidate, price));
+        if candidate >= size {
+            break;
+        }
+    }
+    capped
+}
+
+/// What to do for the parent orders at one [`ExecutionBook::advance`].
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionStep {
+    /// Child orders to send.
+    pub children: Vec<ChildRequest>,
+    /// Client order IDs of working children to cancel.
+    pub cancels: Vec<String>,
+    /// IDs of parents that finished.
+    pub finished: Vec<String>,
+}
+
+/// A child order to send for a parent.
+#[derive(Debug, Clone)]
+pub struct ChildRequest {
+    /// Parent order ID.
+    pub parent_id: String,
+    /// Order to send.
+    pub request: OrderRequest,
+}
+
+/// Parent orders being worked, and the most recent finished ones.
+#[derive(Debug, Clone)]
+pub struct ExecutionBook {
+    /// Parent orders, oldest first.
+    parents: VecDeque<ParentOrder>,
+    /// Maximum number of parents kept.
+    capacity: usize,
+}
+
+impl ExecutionBook {
+    /// Create a book keeping up to `capacity` parents. The oldest finished
+    /// parents are forgotten first.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            parents: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Start working the order of `signal` for `size` shares with its
+    /// execution algorithm.
+    pub fn start(&mut self, signal: &Signal, size: Decimal) {
+        self.parents.push_back(ParentOrder {
+            signal: signal.clone(),
+            size,
+            children: Vec::new(),
+            status: ParentStatus::Working,
+            started_at: Utc::now(),
+            ended_at: None,
+            stopped: false,
+        });
+
+        while self.parents.len() > self.capacity {
+            let oldest = self
+                .parents
+                .iter()
+                .position(|p| p.status != ParentStatus::Working)
+                .unwrap_or(0);
+            self.parents.remove(oldest);
+        }
+    }
+
+    /// Get a parent order by ID.
+    pub fn get(&self, parent_id: &str) -> Option<&ParentOrder> {
+        self.parents.iter().find(|p| p.id() == parent_id)
+    }
+
+    /// Parent orders, oldest first.
+    pub fn parents(&self) -> impl Iterator<Item = &ParentOrder> {
+        self.parents.iter()
+    }
+
+    /// Work the parent orders at `now`, returning the children to send, the
+    /// working children to cancel and the IDs of parents that finished.
+    ///
+    /// Children to cancel are returned until [`cancel_sent`](Self::cancel_sent)
+    /// is called for them.
+    pub fn advance(&mut self, ctx: &StrategyContext, now: DateTime<Utc>) -> ExecutionStep {
+        let mut step = ExecutionStep::default();
+        for parent in &mut self.parents {
+            if parent.status != ParentStatus::Working {
+                continue;
+            }
+            step.cancels.extend(parent.stale_children(now));
+            if !parent.exhausted(now)
+                && let Some((size, price)) = parent.next_child(ctx, now)
+                && size > Decimal::ZERO
+            {
+                let client_order_id = uuid::Uuid::new_v4().to_string();
+                let order_type = if price.is_some() {
+                    OrderType::Limit
+                } else {
+                    OrderType::Market
+                };
+                parent.children.push(ChildOrder {
+                    client_order_id: client_order_id.clone(),
+                    size,
+                    price,
+                    filled: Decimal::ZERO,
+                    filled_value: Decimal::ZERO,
+                    done: false,
+                    cancel_sent: false,
+                    sent_at: now,
+                });
+                step.children.push(ChildRequest {
+                    parent_id: parent.id().to_string(),
+                    request: OrderRequest {
+                        market_id: parent.signal.market_id.clone(),
+                        token_id: parent.signal.token_id.clone(),
+                        side: parent.signal.side,
+                        price,
+                        size,
+                        order_type,
+                        client_order_id: Some(client_order_id),
+                    },
+                });
+            } else if parent.exhausted(now) && !parent.has_working_child() {
+                parent.end(ParentStatus::Expired, now);
+                step.finished.push(parent.id().to_string());
+            }
+        }
+        step
+    }
+
+    /// Record that a cancel was sent for a working child.
+    pub fn cancel_sent(&mut self, client_order_id: &str) {
+        if let Some(child) = self.parent_of(client_order_id).and_then(|p| {
+            p.children
+                .iter_mut()
+                .find(|c| c.client_order_id == client_order_id)
+        }) {
+            child.cancel_sent = true;
+        }
+    }
+
+    /// Stop working a parent order, returning the client order IDs of its
+    /// working children to cancel. The parent expires once they have ended.
+    pub fn stop(&mut self, parent_id: &str) -> Vec<String> {
+        let Some(parent) = self
+            .parents
+            .iter_mut()
+            .find(|p| p.id() == parent_id && p.status == ParentStatus::Working)
+        else {
+            return Vec::new();
+        };
+        parent.stopped = true;
+        parent.stale_children(Utc::now())
+    }
+
+    /// Apply a fill of a child order, returning its parent's ID.
+    pub fn child_filled(
+        &mut self,
+        client_order_id: &str,
+        price: Decimal,
+        size: Decimal,
+    ) -> Option<String> {
+        let parent = self.parent_of(client_order_id)?;
+        let child = parent
+            .children
+            .iter_mut()
+            .find(|c| c.client_order_id == client_order_id)?;
+        child.filled += size;
+        child.filled_value += price * size;
+        if child.filled >= child.size {
+            child.done = true;
+        }
+        if parent.filled_size() >= parent.size {
+            parent.end(ParentStatus::Completed, Utc::now());
+        }
+        Some(parent.id().to_string())
+    }
+
+    /// Handle a child order ending without filling completely, returning its
+    /// parent's ID. The unfilled size is sent again by later children.
+    pub fn child_ended(&mut self, client_order_id: &str) -> Option<String> {
+        let parent = self.parent_of(client_order_id)?;
+        if let Some(child) = parent
+            .children
+            .iter_mut()
+            .find(|c| c.client_order_id == client_order_id)
+        {
+            child.done = true;
+        }
+        Some(parent.id().to_string())
+    }
+
+    fn parent_of(&mut self, client_order_id: &str) -> Option<&mut ParentOrder> {
+        self.parents.iter_mut().find(|p| {
+            p.children
+                .iter()
+                .any(|c| c.client_order_id == client_order_id)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{OrderBookDepth, PriceLevel, Quantity};
+    use rust_decimal_macros::dec;
+
+    fn parent(book: &mut ExecutionBook, algo: ExecutionAlgo) -> ParentOrder {
+        let signal = Signal::buy("m1", "yes", Quantity::Shares(dec!(100))).with_price(dec!(0.50));
+        start(book, signal.with_execution(algo))
+    }
+
+    fn start(book: &mut ExecutionBook, signal: Signal) -> ParentOrder {
+        book.start(&signal, dec!(100));
+        book.get(&signal.id).unwrap().clone()
+    }
+
+    fn slippage_capped() -> ExecutionAlgo {
+        ExecutionAlgo::SlippageCapped {
+            max_slippage: dec!(0.01),
+            timeout_secs: 60,
+        }
+    }
+
+    #[test]
+    fn test_twap_rounds_slices_and_sends_leftover_last() {
+        let mut book = ExecutionBook::new(10);
+        let ctx = StrategyContext::new();
+        let parent = parent(
+            &mut book,
+            ExecutionAlgo::Twap {
+                duration_secs: 60,
+                slices: 3,
+            },
+        );
+        let start = parent.started_at;
+
+        let mut sizes = Vec::new();
+        for secs in [0, 20, 40] {
+            let children = book.advance(&ctx, start + Duration::seconds(secs)).children;
+            assert_eq!(children.len(), 1);
+            let id = children[0].request.client_order_id.clone().unwrap();
+            book.child_filled(&id, dec!(0.50), children[0].request.size);
+            sizes.push(children[0].request.size);
+        }
+        assert_eq!(sizes, vec![dec!(33.33), dec!(33.33), dec!(33.34)]);
+        assert_eq!(
+            book.get(parent.id()).unwrap().status,
+            ParentStatus::Completed
+        );
+    }
+
+    #[test]
+    fn test_twap_slices_over_duration() {
+        let mut book = ExecutionBook::new(10);
+        let ctx = StrategyContext::new();
+        let parent = parent(
+            &mut book,
+            ExecutionAlgo::Twap {
+                duration_secs: 60,
+                slices: 4,
+            },
+        );
+        let start = parent.started_at;
+
+        let children = book.advance(&ctx, start).children;
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].request.size, dec!(25));
+        assert_eq!(children[0].request.price, Some(dec!(0.50)));
+
+        // Not due again until the next interval
+        assert!(
+            book.advance(&ctx, start + Duration::seconds(10))
+                .children
+                .is_empty()
+        );
+
+        // A slice still working at the next boundary is cancelled, and the
+        // next slice waits for the cancel before taking its rest
+        let first = children[0].request.client_order_id.clone().unwrap();
+        book.child_filled(&first, dec!(0.50), dec!(10));
+        let step = book.advance(&ctx, start + Duration::seconds(15));
+        assert!(step.children.is_empty());
+        assert_eq!(step.cancels, vec![first.clone()]);
+        book.cancel_sent(&first);
+        assert!(
+            book.advance(&ctx, start + Duration::seconds(16))
+                .cancels
+                .is_empty()
+        );
+        book.child_ended(&first);
+        let children = book.advance(&ctx, start + Duration::seconds(17)).children;
+        assert_eq!(children[0].request.size, dec!(30));
+        for child in &children {
+            let id = child.request.client_order_id.as_deref().unwrap();
+            book.child_filled(id, dec!(0.50), dec!(30));
+        }
+
+        let children = book.advance(&ctx, start + Duration::seconds(30)).children;
+        assert_eq!(children[0].request.size, dec!(30));
+        for child in &children {
+            let id = child.request.client_order_id.as_deref().unwrap();
+            book.child_filled(id, dec!(0.50), dec!(30));
+        }
+        let children = book.advance(&ctx, start + Duration::seconds(45)).children;
+        assert_eq!(children[0].request.size, dec!(30));
+        let last = children[0].request.client_order_id.clone().unwrap();
+
+        let working = book.get(parent.id()).unwrap();
+        assert_eq!(working.status, ParentStatus::Working);
+        assert_eq!(working.filled_size(), dec!(70));
+
+        // The last slice is cancelled at the end of the duration, and the
+        // parent expires once the cancel is confirmed
+        let step = book.advance(&ctx, start + Duration::seconds(60));
+        assert_eq!(step.cancels, vec![last.clone()]);
+        assert!(step.finished.is_empty());
+        book.cancel_sent(&last);
+        book.child_ended(&last);
+        let step = book.advance(&ctx, start + Duration::seconds(61));
+        assert_eq!(step.finished, vec![parent.id().to_string()]);
+        assert_eq!(book.get(parent.id()).unwrap().status, ParentStatus::Expired);
+    }
+
+    #[test]
+    fn test_resting_clip_is_cancelled_at_timeout() {
+        let mut book = ExecutionBook::new(10);
+        let ctx = StrategyContext::new();
+        let parent = parent(
+            &mut book,
+            ExecutionAlgo::Iceberg {
+                clip_size: dec!(40),
+                timeout_secs: 60,
+            },
+        );
+        let start = parent.started_at;
+        let children = book.advance(&ctx, start).children;
+        let clip = children[0].request.client_order_id.clone().unwrap();
+
+        // Resting below the market: nothing happens until the timeout
+        assert!(
+            book.advance(&ctx, start + Duration::seconds(30))
+                .cancels
+                .is_empty()
+        );
+        let step = book.advance(&ctx, start + Duration::seconds(60));
+        assert_eq!(step.cancels, vec![clip.clone()]);
+        assert!(step.children.is_empty());
+        assert!(step.finished.is_empty());
+
+        book.cancel_sent(&clip);
+        book.child_ended(&clip);
+        let step = book.advance(&ctx, start + Duration::seconds(61));
+        assert_eq!(step.finished, vec![parent.id().to_string()]);
+        assert_eq!(
+            book.get(parent.id()).unwrap().result(),
+            Some(SignalResult::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_stop_cancels_working_children() {
+        let mut book = ExecutionBook::new(10);
+        let ctx = StrategyContext::new();
+        let parent = parent(
+            &mut book,
+            ExecutionAlgo::Iceberg {
+                clip_size: dec!(40),
+                timeout_secs: 60,
+            },
+        );
+        let children = book.advance(&ctx, parent.started_at).children;
+        let clip = children[0].request.client_order_id.clone().unwrap();
+        book.child_filled(&clip, dec!(0.50), dec!(10));
+
+        assert_eq!(book.stop(parent.id()), vec![clip.clone()]);
+        book.cancel_sent(&clip);
+        book.child_ended(&clip);
+
+        // No further clips, and the parent expires with what it filled
+        let step = book.advance(&ctx, parent.started_at);
+        assert!(step.children.is_empty());
+        assert_eq!(step.finished, vec![parent.id().to_string()]);
+        assert_eq!(book.get(parent.id()).unwrap().filled_size(), dec!(10));
+    }
+
+    #[test]
+    fn test_iceberg_shows_one_clip_at_a_time() {
+        let mut book = ExecutionBook::new(10);
+        let ctx = StrategyContext::new();
+        let parent = parent(
+            &mut book,
+            ExecutionAlgo::Iceberg {
+                clip_size: dec!(40),
+                timeout_secs: 60,
+            },
+        );
+        let now = parent.started_at;
+
+        let children = book.advance(&ctx, now).children;
+        assert_eq!(children[0].request.size, dec!(40));
+        assert!(book.advance(&ctx, now).children.is_empty());
+
+        let id = children[0].request.client_order_id.clone().unwrap();
+        assert_eq!(
+            book.child_filled(&id, dec!(0.50), dec!(40)),
+            Some(parent.id().to_string())
+        );
+        let children = book.advance(&ctx, now).children;
+        let id = children[0].request.client_order_id.clone().unwrap();
+        book.child_filled(&id, dec!(0.50), dec!(40));
+        let children = book.advance(&ctx, now).children;
+        assert_eq!(children[0].request.size, dec!(20));
+        let id = children[0].request.client_order_id.clone().unwrap();
+        book.child_filled(&id, dec!(0.50), dec!(20));
+
+        let parent = book.get(parent.id()).unwrap();
+        assert_eq!(parent.status, ParentStatus::Completed);
+        assert_eq!(parent.progress(), Decimal::ONE);
+        assert_eq!(
+            parent.result(),
+            Some(SignalResult::Filled {
+                order_id: parent.id().to_string(),
+                filled_price: dec!(0.50),
+            })
+        );
+
+        // Expires once the timeout passes with nothing working
+        let mut book = ExecutionBook::new(10);
+        let parent = self::parent(
+            &mut book,
+            ExecutionAlgo::Iceberg {
+                clip_size: dec!(40),
+                timeout_secs: 60,
+            },
+        );
+        let finished = book
+            .advance(&ctx, parent.started_at + Duration::seconds(61))
+            .finished;
+        assert_eq!(finished, vec![parent.id().to_string()]);
+        assert_eq!(
+            book.get(parent.id()).unwrap().result(),
+            Some(SignalResult::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_slippage_cap_sizes_children_from_the_book() {
+        let mut ctx = StrategyContext::new();
+        let mut depth = OrderBookDepth::new("m1", "yes");
+        depth.asks = vec![
+            PriceLevel::new(dec!(0.50), dec!(30)),
+            PriceLevel::new(dec!(0.51), dec!(30)),
+            PriceLevel::new(dec!(0.60), dec!(100)),
+        ];
+        ctx.order_books.insert("yes".to_string(), depth);
+
+        let mut book = ExecutionBook::new(10);
+        let signal = Signal::buy("m1", "yes", Quantity::Shares(dec!(100))).with_price(dec!(0.55));
+        let parent = start(&mut book, signal.with_execution(slippage_capped()));
+
+        // The third level would push the average price more than a cent up
+        let children = book.advance(&ctx, parent.started_at).children;
+        assert_eq!(children[0].request.size, dec!(60));
+        assert_eq!(children[0].request.price, Some(dec!(0.51)));
+        assert_eq!(children[0].request.order_type, OrderType::Limit);
+
+        // No book, no child
+        let mut book = ExecutionBook::new(10);
+        let parent = self::parent(&mut book, slippage_capped());
+        let empty = StrategyContext::new();
+        assert!(book.advance(&empty, parent.started_at).children.is_empty());
+    }
+
+    #[test]
+    fn test_slippage_cap_keeps_the_signal_limit() {
+        let mut ctx = StrategyContext::new();
+        let mut depth = OrderBookDepth::new("m1", "yes");
+        depth.asks = vec![
+            PriceLevel::new(dec!(0.50), dec!(30)),
+            PriceLevel::new(dec!(0.51), dec!(30)),
+        ];
+        depth.bids = vec![
+            PriceLevel::new(dec!(0.48), dec!(30)),
+            PriceLevel::new(dec!(0.47), dec!(30)),
+        ];
+        ctx.order_books.insert("yes".to_string(), depth);
+
+        // A buy limited at 0.50 does not pay the 0.51 level
+        let mut book = ExecutionBook::new(10);
+        let parent = parent(&mut book, slippage_capped());
+        let children = book.advance(&ctx, parent.started_at).children;
+        assert_eq!(children[0].request.price, Some(dec!(0.50)));
+
+        // A sell limited at 0.48 does not take the 0.47 level
+        let mut book = ExecutionBook::new(10);
+        let signal = Signal::sell("m1", "yes", Quantity::Shares(dec!(100))).with_price(dec!(0.48));
+        let parent = start(&mut book, signal.with_execution(slippage_capped()));
+        let children = book.advance(&ctx, parent.started_at).children;
+        assert_eq!(children[0].request.price, Some(dec!(0.48)));
+
+        // Market orders take the capped price
+        let mut book = ExecutionBook::new(10);
+        let signal = Signal::sell("m1", "yes", Quantity::Shares(dec!(100)))
+            .with_order_type(OrderType::Market);
+        let parent = start(&mut book, signal.with_execution(slippage_capped()));
+        let children = book.advance(&ctx, parent.started_at).children;
+        assert_eq!(children[0].request.price, Some(dec!(0.47)));
+    }
+}
@@ -0,0 +1,609 @@
+//! Order lifecycle tracking.
+//!
+//! Follows every order placed for a signal from the request to its terminal
+//! state. Orders are tied to their signal by a client order ID assigned when
+//! the signal is converted to an order request, and to the exchange order
+//! once it is placed.
+
+use super::{Signal, SignalResult};
+use crate::state::{Order, OrderRequest, OrderStatus, TradeState};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::VecDeque;
+
+/// Where an order placed for a signal is in its life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStage {
+    /// Request sent, not yet acknowledged by the exchange.
+    Submitted,
+    /// Resting on the book.
+    Open,
+    /// Partly filled and still resting.
+    PartiallyFilled,
+    /// Completely filled.
+    Filled,
+    /// Cancelled before it was completely filled.
+    Cancelled,
+    /// Expired before it was completely filled.
+    Expired,
+    /// Refused before reaching the book.
+    Rejected,
+}
+
+impl OrderStage {
+    /// Whether the order can no longer change.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Filled | Self::Cancelled | Self::Expired | Self::Rejected
+        )
+    }
+}
+
+impl std::fmt::Display for OrderStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Submitted => write!(f, "Submitted"),
+            Self::Open => write!(f, "Open"),
+            Self::PartiallyFilled => write!(f, "Partial"),
+            Self::Filled => write!(f, "Filled"),
+            Self::Cancelled => write!(f, "Cancelled"),
+            Self::Expired => write!(f, "Expired"),
+            Self::Rejected => write!(f, "Rejected"),
+        }
+    }
+}
+
+/// Part of an order that was filled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderFill {
+    /// Fill price.
+    pub price: Decimal,
+    /// Filled size.
+    pub size: Decimal,
+    /// When the fill was noticed.
+    pub at: DateTime<Utc>,
+}
+
+/// Something that happened to a tracked order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LifecycleEvent {
+    /// When it happened.
+    pub at: DateTime<Utc>,
+    /// Stage after the event.
+    pub stage: OrderStage,
+    /// What happened.
+    pub detail: String,
+}
+
+/// An order placed for a signal, from request to terminal state.
+#[derive(Debug, Clone)]
+pub struct OrderLifecycle {
+    /// Client order ID attached to the request.
+    pub client_order_id: String,
+    /// Signal the order was placed for.
+    pub signal_id: String,
+    /// Strategy that generated the signal.
+    pub strategy: String,
+    /// Order request sent.
+    pub request: OrderRequest,
+    /// Latest exchange view of the order, once placed.
+    pub order: Option<Order>,
+    /// Current stage.
+    pub stage: OrderStage,
+    /// Fills, oldest first.
+    pub fills: Vec<OrderFill>,
+    /// History, oldest first.
+    pub events: Vec<LifecycleEvent>,
+    /// When the placed order was first missing from the open orders, while
+    /// the fill history cannot yet tell whether it filled.
+    pub missing_since: Option<DateTime<Utc>>,
+}
+
+impl OrderLifecycle {
+    /// Exchange order ID, once placed.
+    pub fn order_id(&self) -> Option<&str> {
+        self.order.as_ref().map(|o| o.id.as_str())
+    }
+
+    /// Total filled size.
+    pub fn filled_size(&self) -> Decimal {
+        self.fills.iter().map(|f| f.size).sum()
+    }
+
+    /// Size-weighted average fill price.
+    pub fn average_price(&self) -> Option<Decimal> {
+        let size = self.filled_size();
+        if size.is_zero() {
+            return None;
+        }
+        let notional: Decimal = self.fills.iter().map(|f| f.price * f.size).sum();
+        Some(notional / size)
+    }
+
+    /// Outcome of the signal so far, as recorded in the signal history.
+    pub fn result(&self) -> Option<SignalResult> {
+        let order_id = self.order_id().unwrap_or_default().to_string();
+        match self.stage {
+            OrderStage::Submitted => None,
+            OrderStage::Open | OrderStage::PartiallyFilled => {
+                Some(SignalResult::OrderPlaced { order_id })
+            }
+            OrderStage::Filled => Some(SignalResult::Filled {
+                order_id,
+                filled_price: self.average_price().unwrap_or_default(),
+            }),
+            OrderStage::Cancelled | OrderStage::Expired => Some(SignalResult::Cancelled),
+            OrderStage::Rejected => Some(SignalResult::Rejected {
+                reason: self
+                    .events
+                    .last()
+                    .map(|e| e.detail.clone())
+                    .unwrap_or_default(),
+            }),
+        }
+    }
+
+    fn transition(&mut self, stage: OrderStage, detail: impl Into<String>) {
+        self.stage = stage;
+        self.events.push(LifecycleEvent {
+            at: Utc::now(),
+            stage,
+            detail: detail.into(),
+        });
+    }
+
+    /// Record a fill of `size` at `price`, returning the update to report.
+    fn fill(&mut self, price: Decimal, size: Decimal) -> LifecycleUpdate {
+        self.fills.push(OrderFill {
+            price,
+            size,
+            at: Utc::now(),
+        });
+        let stage = if self.filled_size() >= self.request.size {
+            OrderStage::Filled
+        } else {
+            OrderStage::PartiallyFilled
+        };
+        self.transition(stage, format!("Filled {} at {}", size, price));
+        LifecycleUpdate::Filled {
+            client_order_id: self.client_order_id.clone(),
+            order_id: self.order_id().unwrap_or_default().to_string(),
+            price,
+            size,
+        }
+    }
+
+    /// End the order without further fills, returning the update to report.
+    fn close(&mut self, stage: OrderStage, detail: impl Into<String>) -> LifecycleUpdate {
+        self.transition(stage, detail);
+        LifecycleUpdate::Cancelled {
+            client_order_id: self.client_order_id.clone(),
+            order_id: self.order_id().unwrap_or_default().to_string(),
+        }
+    }
+
+    /// Bring the lifecycle in line with the exchange's view of the order.
+    fn sync(&mut self, order: &Order) -> Vec<LifecycleUpdate> {
+        self.order = Some(order.clone());
+        self.missing_since = None;
+        let mut updates = Vec::new();
+        let filled = order.filled_size - self.filled_size();
+        if filled > Decimal::ZERO {
+            updates.push(self.fill(order.price, filled));
+        }
+        let stage = match order.status {
+            OrderStatus::Pending | OrderStatus::Open => OrderStage::Open,
+            OrderStatus::PartiallyFilled => OrderStage::PartiallyFilled,
+            OrderStatus::Filled => OrderStage::Filled,
+            OrderStatus::Cancelled => OrderStage::Cancelled,
+            OrderStatus::Expired => OrderStage::Expired,
+            OrderStatus::Failed => OrderStage::Rejected,
+        };
+        if stage != self.stage {
+            match stage {
+                OrderStage::Cancelled | OrderStage::Expired => {
+                    updates.push(self.close(stage, format!("Order {}", order.status)));
+                }
+                OrderStage::Rejected => {
+                    updates.push(self.close(stage, "Order failed on the exchange"));
+                }
+                _ => self.transition(stage, format!("Order {}", order.status)),
+            }
+        }
+        updates
+    }
+}
+
+/// Change to a tracked order that its strategy should hear about.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LifecycleUpdate {
+    /// Part of the order was filled.
+    Filled {
+        client_order_id: String,
+        order_id: String,
+        price: Decimal,
+        size: Decimal,
+    },
+    /// The order ended without filling completely.
+    Cancelled {
+        client_order_id: String,
+        order_id: String,
+    },
+}
+
+impl LifecycleUpdate {
+    /// Client order ID of the order concerned.
+    pub fn client_order_id(&self) -> &str {
+        match self {
+            Self::Filled {
+                client_order_id, ..
+            }
+            | Self::Cancelled {
+                client_order_id, ..
+            } => client_order_id,
+        }
+    }
+}
+
+/// Tracks the orders placed for signals.
+#[derive(Debug, Clone)]
+pub struct LifecycleTracker {
+    /// Tracked orders, oldest first.
+    lifecycles: VecDeque<OrderLifecycle>,
+    /// Maximum number of orders kept.
+    capacity: usize,
+}
+
+impl LifecycleTracker {
+    /// Create a tracker keeping up to `capacity` orders. The oldest finished
+    /// orders are forgotten first.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lifecycles: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Start tracking the order requested for `signal`. The request must
+    /// carry a client order ID.
+    pub fn submit(&mut self, signal: &Signal, request: &OrderRequest) {
+        let Some(client_order_id) = request.client_order_id.clone() else {
+            return;
+        };
+        let mut lifecycle = OrderLifecycle {
+            client_order_id,
+            signal_id: signal.id.clone(),
+            strategy: signal.strategy_name.clone(),
+            request: request.clone(),
+            order: None,
+            stage: OrderStage::Submitted,
+            fills: Vec::new(),
+            events: Vec::new(),
+            missing_since: None,
+        };
+        lifecycle.transition(OrderStage::Submitted, "Order requested");
+        self.lifecycles.push_back(lifecycle);
+
+        while self.lifecycles.len() > self.capacity {
+            let oldest = self
+                .lifecycles
+                .iter()
+                .position(|l| l.stage.is_terminal())
+                .unwrap_or(0);
+            self.lifecycles.remove(oldest);
+        }
+    }
+
+    /// Record that the exchange accepted the order.
+    pub fn placed(&mut self, client_order_id: &str, order: &Order) -> Vec<LifecycleUpdate> {
+        let Some(lifecycle) = self.get_mut(client_order_id) else {
+            return Vec::new();
+        };
+        lifecycle.order = Some(order.clone());
+        lifecycle.transition(OrderStage::Open, format!("Placed as {}", order.id));
+        lifecycle.sync(order)
+    }
+
+    /// Record that the order was refused.
+    pub fn rejected(&mut self, client_order_id: &str, reason: &str) {
+        if let Some(lifecycle) = self.get_mut(client_order_id) {
+            lifecycle.transition(OrderStage::Rejected, reason);
+        }
+    }
+
+    /// Record that an order was cancelled on request.
+    pub fn cancelled(&mut self, order_id: &str) -> Vec<LifecycleUpdate> {
+        self.lifecycles
+            .iter_mut()
+            .filter(|l| l.order_id() == Some(order_id) && !l.stage.is_terminal())
+            .map(|l| l.close(OrderStage::Cancelled, "Cancelled"))
+            .collect()
+    }
+
+    /// Update tracked orders from the exchange's open orders and our fill
+    /// history.
+    ///
+    /// A placed order that is no longer listed has left the book without a
+    /// cancellation from us, filled or not. It is filled for the size its
+    /// fills in `trades` cover. If that leaves it unfilled, it is marked
+    /// expired once its expiry has passed, and cancelled once trades fetched
+    /// after it went missing show nothing more; until then it stays as it is.
+    pub fn sync(&mut self, open_orders: &[Order], trades: &TradeState) -> Vec<LifecycleUpdate> {
+        let now = Utc::now();
+        let mut updates = Vec::new();
+        for lifecycle in &mut self.lifecycles {
+            if lifecycle.stage.is_terminal() {
+                continue;
+            }
+            let Some(last_seen) = lifecycle.order.clone() else {
+                continue;
+            };
+            if let Some(order) = open_orders.iter().find(|o| o.id == last_seen.id) {
+                updates.extend(lifecycle.sync(order));
+                continue;
+            }
+
+            let missing_since = *lifecycle.missing_since.get_or_insert(now);
+            let (size, value) = trades
+                .order_fills(&last_seen.id)
+                .fold((Decimal::ZERO, Decimal::ZERO), |(size, value), f| {
+                    (size + f.size, value + f.notional())
+                });
+            let filled = size.min(lifecycle.request.size) - lifecycle.filled_size();
+            if filled > Decimal::ZERO {
+                updates.push(lifecycle.fill(value / size, filled));
+            }
+            if lifecycle.stage.is_terminal() {
+                continue;
+            }
+            if last_seen.expires_at.is_some_and(|t| t <= now) {
+                updates.push(lifecycle.close(OrderStage::Expired, "Expired"));
+            } else if trades.last_updated.is_some_and(|t| t > missing_since) {
+                updates.push(lifecycle.close(OrderStage::Cancelled, "Left the book unfilled"));
+            }
+        }
+        updates
+    }
+
+    /// Get a tracked order by client order ID.
+    pub fn get(&self, client_order_id: &str) -> Option<&OrderLifecycle> {
+        self.lifecycles
+            .iter()
+            .find(|l| l.client_order_id == client_order_id)
+    }
+
+    /// Tracked orders, oldest first.
+    pub fn lifecycles(&self) -> &VecDeque<OrderLifecycle> {
+        &self.lifecycles
+    }
+
+    fn get_mut(&mut self, client_order_id: &str) -> Option<&mut OrderLifecycle> {
+        self.lifecycles
+            .iter_mut()
+            .find(|l| l.client_order_id == client_order_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Fill, OrderSide, OrderType, Quantity};
+    use rust_decimal_macros::dec;
+
+    fn submitted(tracker: &mut LifecycleTracker) -> (Signal, OrderRequest) {
+        let mut signal = Signal::buy("m", "t", Quantity::Shares(dec!(10))).with_price(dec!(0.4));
+        signal.strategy_name = "s".to_string();
+        let request = OrderRequest {
+            market_id: "m".to_string(),
+            token_id: "t".to_string(),
+            side: OrderSide::Buy,
+            price: Some(dec!(0.4)),
+            size: dec!(10),
+            order_type: OrderType::Limit,
+            client_order_id: Some(format!("c-{}", signal.id)),
+        };
+        tracker.submit(&signal, &request);
+        (signal, request)
+    }
+
+    fn order(filled: Decimal, status: OrderStatus) -> Order {
+        let now = Utc::now();
+        Order {
+            id: "o1".to_string(),
+            market_id: "m".to_string(),
+            market_question: String::new(),
+            token_id: "t".to_string(),
+            outcome_name: "Yes".to_string(),
+            side: OrderSide::Buy,
+            order_type: OrderType::Limit,
+            price: dec!(0.4),
+            original_size: dec!(10),
+            remaining_size: dec!(10) - filled,
+            filled_size: filled,
+            status,
+            created_at: now,
+            updated_at: now,
+            expires_at: None,
+        }
+    }
+
+    /// Fill history holding fills of order `o1` at 0.4, fetched now.
+    fn trades(sizes: &[Decimal]) -> TradeState {
+        let mut trades = TradeState::default();
+        trades.merge(
+            sizes
+                .iter()
+                .enumerate()
+                .map(|(i, size)| Fill {
+                    id: format!("t{}", i),
+                    order_id: "o1".to_string(),
+                    market_id: "m".to_string(),
+                    token_id: "t".to_string(),
+                    outcome_name: "Yes".to_string(),
+                    side: OrderSide::Buy,
+                    price: dec!(0.4),
+                    size: *size,
+                    fee: Decimal::ZERO,
+                    timestamp: Utc::now(),
+                })
+                .collect(),
+        );
+        trades.last_updated = Some(Utc::now());
+        trades
+    }
+
+    #[test]
+    fn test_partial_then_full_fill() {
+        let mut tracker = LifecycleTracker::new(10);
+        let (signal, request) = submitted(&mut tracker);
+        let id = request.client_order_id.unwrap();
+        assert_eq!(tracker.get(&id).unwrap().result(), None);
+
+        let updates = tracker.placed(&id, &order(Decimal::ZERO, OrderStatus::Open));
+        assert!(updates.is_empty());
+        let lifecycle = tracker.get(&id).unwrap();
+        assert_eq!(lifecycle.signal_id, signal.id);
+        assert_eq!(lifecycle.order_id(), Some("o1"));
+        assert_eq!(
+            lifecycle.result(),
+            Some(SignalResult::OrderPlaced {
+                order_id: "o1".to_string()
+            })
+        );
+
+        let none = TradeState::default();
+        let updates = tracker.sync(&[order(dec!(4), OrderStatus::PartiallyFilled)], &none);
+        assert_eq!(
+            updates,
+            vec![LifecycleUpdate::Filled {
+                client_order_id: id.clone(),
+                order_id: "o1".to_string(),
+                price: dec!(0.4),
+                size: dec!(4),
+            }]
+        );
+        assert_eq!(tracker.get(&id).unwrap().stage, OrderStage::PartiallyFilled);
+
+        // Seen again unchanged: nothing new to report
+        assert!(
+            tracker
+                .sync(&[order(dec!(4), OrderStatus::PartiallyFilled)], &none)
+                .is_empty()
+        );
+
+        // Gone from the open orders, filled according to the fill history
+        let filled = trades(&[dec!(4), dec!(6)]);
+        let updates = tracker.sync(&[], &filled);
+        assert_eq!(updates.len(), 1);
+        let lifecycle = tracker.get(&id).unwrap();
+        assert_eq!(lifecycle.stage, OrderStage::Filled);
+        assert_eq!(lifecycle.filled_size(), dec!(10));
+        assert_eq!(
+            lifecycle.result(),
+            Some(SignalResult::Filled {
+                order_id: "o1".to_string(),
+                filled_price: dec!(0.4),
+            })
+        );
+
+        // Finished orders are left alone
+        assert!(tracker.sync(&[], &filled).is_empty());
+    }
+
+    #[test]
+    fn test_order_leaving_the_book_unfilled() {
+        let mut tracker = LifecycleTracker::new(10);
+        let (_, request) = submitted(&mut tracker);
+        let id = request.client_order_id.unwrap();
+        tracker.placed(&id, &order(Decimal::ZERO, OrderStatus::Open));
+
+        // Trades fetched before the order went missing tell nothing
+        let stale = trades(&[]);
+        assert!(tracker.sync(&[], &stale).is_empty());
+        assert_eq!(tracker.get(&id).unwrap().stage, OrderStage::Open);
+
+        // Later trades with only part of the order filled settle it
+        let later = trades(&[dec!(3)]);
+        let updates = tracker.sync(&[], &later);
+        assert_eq!(
+            updates,
+            vec![
+                LifecycleUpdate::Filled {
+                    client_order_id: id.clone(),
+                    order_id: "o1".to_string(),
+                    price: dec!(0.4),
+                    size: dec!(3),
+                },
+                LifecycleUpdate::Cancelled {
+                    client_order_id: id.clone(),
+                    order_id: "o1".to_string(),
+                },
+            ]
+        );
+        let lifecycle = tracker.get(&id).unwrap();
+        assert_eq!(lifecycle.stage, OrderStage::Cancelled);
+        assert_eq!(lifecycle.filled_size(), dec!(3));
+
+        // With no fill at all, nothing is filled
+        let (_, request) = submitted(&mut tracker);
+        let id = request.client_order_id.unwrap();
+        let mut other = order(Decimal::ZERO, OrderStatus::Open);
+        other.id = "o2".to_string();
+        tracker.placed(&id, &other);
+        assert!(tracker.sync(&[], &stale).is_empty());
+        let updates = tracker.sync(&[], &trades(&[dec!(3)]));
+        assert_eq!(
+            updates,
+            vec![LifecycleUpdate::Cancelled {
+                client_order_id: id.clone(),
+                order_id: "o2".to_string(),
+            }]
+        );
+        assert_eq!(
+            tracker.get(&id).unwrap().result(),
+            Some(SignalResult::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_cancel_and_reject() {
+        let mut tracker = LifecycleTracker::new(10);
+        let (_, request) = submitted(&mut tracker);
+        let cancelled = request.client_order_id.unwrap();
+        tracker.placed(&cancelled, &order(Decimal::ZERO, OrderStatus::Open));
+
+        let updates = tracker.cancelled("o1");
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].client_order_id(), cancelled);
+        assert!(tracker.cancelled("o1").is_empty());
+        assert_eq!(
+            tracker.get(&cancelled).unwrap().result(),
+            Some(SignalResult::Cancelled)
+        );
+
+        let (_, request) = submitted(&mut tracker);
+        let rejected = request.client_order_id.unwrap();
+        tracker.rejected(&rejected, "insufficient balance");
+        assert_eq!(
+            tracker.get(&rejected).unwrap().result(),
+            Some(SignalResult::Rejected {
+                reason: "insufficient balance".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_capacity_evicts_finished_orders_first() {
+        let mut tracker = LifecycleTracker::new(2);
+        let (_, first) = submitted(&mut tracker);
+        let (_, second) = submitted(&mut tracker);
+        let second = second.client_order_id.unwrap();
+        tracker.rejected(&second, "rejected");
+
+        submitted(&mut tracker);
+        assert_eq!(tracker.lifecycles().len(), 2);
+        assert!(tracker.get(&first.client_order_id.unwrap()).is_some());
+        assert!(tracker.get(&second).is_none());
+    }
+}
@@ -10,10 +10,16 @@
 //! - [`StrategyContext`]: Market data and state provided to strategies
 //! - [`Signal`]: Trading signals generated by strategies
 //! - [`StrategyEngine`]: Manages strategy lifecycle and execution
+//! - [`LifecycleTracker`]: Follows orders placed for signals to their fills
+//! - [`BracketBook`]: Stop-loss and take-profit exits watched by the engine
+//! - [`ExecutionBook`]: Parent orders worked as child orders by an [`ExecutionAlgo`]
+//! - [`Sizer`]: Sizes entries with a [`PositionSizer`] before risk checks
+//! - [`Optimizer`]: Searches strategy parameters against recorded data
 //!
 //! # Example
 //!
 //! ```rust,ignore
+//! use clobster::state::Quantity;
 //! use clobster::strategy::{Strategy, StrategyContext, Signal, StrategyConfig};
 //!
 //! struct MyStrategy {
@@ -28,7 +34,8 @@
 //!         for market in ctx.markets() {
 //!             if let Some(outcome) = market.outcomes.first() {
 //!                 if outcome.price < self.threshold {
-//!                     signals.push(Signal::buy(market.id.clone(), outcome.token_id.clone(), 0.10));
+//!                     let size = Quantity::Notional(dec!(10));
+//!                     signals.push(Signal::buy(market.id.clone(), outcome.token_id.clone(), size));
 //!                 }
 //!             }
 //!         }
@@ -37,18 +44,46 @@
 //! }
 //! ```
 
+mod backtest;
+mod bracket;
 mod context;
 mod engine;
+mod execution;
+mod lifecycle;
+mod optimizer;
+mod persistence;
 mod risk;
 mod signal;
+mod sizing;
 mod strategies;
 mod traits;
 
-pub use context::{MarketSnapshot, OrderSnapshot, PositionSnapshot, StrategyContext};
-pub use engine::{StrategyEngine, StrategyHandle, StrategyStatus};
+pub use backtest::{Backtest, BacktestResult, Frame, RecordedMarket, Recording};
+pub use bracket::{Bracket, BracketBook, BracketLeg, BracketStatus};
+pub use context::{MarketSnapshot, OrderSnapshot, PositionSnapshot, PricePoint, StrategyContext};
+pub use engine::{
+    EngineConfig, ParameterChange, ParameterField, SignalRecord, SignalResult, StrategyEngine,
+    StrategyHandle, StrategyStatus,
+};
+pub use execution::{
+    ChildOrder, ChildRequest, ExecutionAlgo, ExecutionBook, ExecutionStep, ParentOrder,
+    ParentStatus,
+};
+pub use lifecycle::{
+    LifecycleEvent, LifecycleTracker, LifecycleUpdate, OrderFill, OrderLifecycle, OrderStage,
+};
+pub use optimizer::{
+    OptimizationReport, Optimizer, OptimizerConfig, Score, SearchMethod, StrategyFactory, Trial,
+};
+pub use persistence::StateStore;
 pub use risk::{RiskConfig, RiskGuard, RiskViolation};
-pub use signal::{Signal, SignalStrength, SignalType};
-pub use strategies::{MeanReversionStrategy, MomentumStrategy, SpreadStrategy};
+pub use signal::{Signal, SignalAction, SignalStrength, SignalType};
+pub use sizing::{PositionSizer, Sizer, SizingConfig, SizingDecision, SizingLimit, SizingMethod};
+pub use strategies::{
+    BUILTIN_STRATEGIES, ExternalConfig, ExternalStrategy, MeanReversionStrategy, MomentumStrategy,
+    PROTOCOL_VERSION, ScriptLimits, ScriptStrategy, SpreadStrategy, builtin, create,
+};
 pub use traits::{
     ParameterDef, ParameterType, ParameterValue, Strategy, StrategyConfig, StrategyMetadata,
+    StrategyState,
 };
@@ -0,0 +1,788 @@
+//! Parameter search over recorded market data.
+//!
+//! The search space comes from each strategy's [`ParameterDef`]s: numeric
+//! parameters with both `min` and `max` are searched over that range,
+//! booleans over `false`/`true`, and parameters with `allowed_values` over
+//! those values. Every candidate is scored by replaying a [`Recording`]
+//! through a fresh strategy instance with [`Backtest`].
+
+use super::backtest::{Backtest, BacktestResult, Frame, Recording};
+use super::{ParameterDef, ParameterType, ParameterValue, Strategy, StrategyConfig};
+use crate::error::{Error, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+/// Largest grid the optimizer will evaluate.
+const MAX_GRID_SIZE: usize = 10_000;
+
+/// Candidates drawn per suggestion in Bayesian search.
+const BAYESIAN_CANDIDATES: usize = 24;
+
+/// Creates a fresh strategy instance for each backtest.
+pub type StrategyFactory = Arc<dyn Fn() -> Box<dyn Strategy> + Send + Sync>;
+
+/// How candidate parameter sets are chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMethod {
+    /// Every combination of evenly spaced values.
+    Grid,
+    /// Uniformly sampled values.
+    Random,
+    /// Tree-structured Parzen estimator: sample near the best trials so far.
+    Bayesian,
+}
+
+impl std::fmt::Display for SearchMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Grid => write!(f, "grid"),
+            Self::Random => write!(f, "random"),
+            Self::Bayesian => write!(f, "bayesian"),
+        }
+    }
+}
+
+/// Optimizer settings.
+#[derive(Debug, Clone)]
+pub struct OptimizerConfig {
+    /// Search method.
+    pub method: SearchMethod,
+    /// Number of candidates for random and Bayesian search.
+    pub trials: usize,
+    /// Values per numeric parameter in grid search.
+    pub grid_steps: usize,
+    /// Walk-forward folds; 0 scores the whole recording in-sample only.
+    pub folds: usize,
+    /// Backtests run at the same time.
+    pub parallelism: usize,
+    /// Seed for random and Bayesian search.
+    pub seed: Option<u64>,
+    /// Cash at the start of each backtest.
+    pub starting_balance: Decimal,
+    /// Parameters to search; empty searches every searchable parameter.
+    pub parameters: Vec<String>,
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> Self {
+        Self {
+            method: SearchMethod::Random,
+            trials: 50,
+            grid_steps: 5,
+            folds: 0,
+            parallelism: std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1),
+            seed: None,
+            starting_balance: Decimal::from(1000),
+            parameters: Vec::new(),
+        }
+    }
+}
+
+/// Backtest performance of a parameter set, averaged over walk-forward
+/// windows.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Score {
+    /// Mean return, in percent of the starting balance.
+    pub return_pct: Decimal,
+    /// Mean profit or loss.
+    pub pnl: Decimal,
+    /// Total filled signals.
+    pub trades: usize,
+    /// Worst drawdown of any window, as a fraction.
+    pub max_drawdown: Decimal,
+}
+
+impl Score {
+    fn from_results(results: &[BacktestResult]) -> Self {
+        let count = Decimal::from(results.len().max(1));
+        Self {
+            return_pct: results.iter().map(|r| r.return_pct).sum::<Decimal>() / count,
+            pnl: results.iter().map(|r| r.pnl).sum::<Decimal>() / count,
+            trades: results.iter().map(|r| r.trades).sum(),
+            max_drawdown: results
+                .iter()
+                .map(|r| r.max_drawdown)
+                .max()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// One evaluated parameter set.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Trial {
+    /// Position in the ranking, starting at 1.
+    pub rank: usize,
+    /// Searched parameters and their values.
+    pub parameters: BTreeMap<String, ParameterValue>,
+    /// Score on the training windows.
+    pub in_sample: Score,
+    /// Score on the test windows, with walk-forward folds.
+    pub out_of_sample: Option<Score>,
+}
+
+impl Trial {
+    /// The score trials are ranked by: out-of-sample when available.
+    pub fn score(&self) -> &Score {
+        self.out_of_sample.as_ref().unwrap_or(&self.in_sample)
+    }
+}
+
+/// Ranked results of an optimization run.
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimizationReport {
+    /// Strategy name.
+    pub strategy: String,
+    /// Search method used.
+    pub method: SearchMethod,
+    /// Number of frames replayed.
+    pub frames: usize,
+    /// Trials, best first.
+    pub trials: Vec<Trial>,
+}
+
+impl OptimizationReport {
+    /// Get the best trial.
+    pub fn best(&self) -> Option<&Trial> {
+        self.trials.first()
+    }
+
+    /// `base` with the best trial's parameters applied.
+    pub fn best_config(&self, base: &StrategyConfig) -> Option<StrategyConfig> {
+        let best = self.best()?;
+        let mut config = base.clone();
+        for (name, value) in &best.parameters {
+            config.parameters.insert(name.clone(), value.to_json());
+        }
+        Some(config)
+    }
+}
+
+/// Searches a strategy's parameter space against recorded data.
+#[derive(Debug, Clone, Default)]
+pub struct Optimizer {
+    config: OptimizerConfig,
+}
+
+impl Optimizer {
+    /// Create an optimizer.
+    pub fn new(config: OptimizerConfig) -> Self {
+        Self { config }
+    }
+
+    /// Search the parameters of the strategy built by `factory`.
+    ///
+    /// Each candidate starts from `base`, so parameters outside the search
+    /// keep their configured values.
+    pub async fn run(
+        &self,
+        factory: StrategyFactory,
+        base: &StrategyConfig,
+        recording: &Recording,
+    ) -> Result<OptimizationReport> {
+        let template = factory();
+        let space = self.search_space(template.as_ref())?;
+        let windows = windows(recording.len(), self.config.folds)?;
+        let evaluator = Evaluator {
+            factory,
+            base: base.clone(),
+            frames: Arc::new(recording.frames.clone()),
+            windows,
+            backtest: Backtest::new(self.config.starting_balance),
+        };
+        let mut rng = match self.config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let parallelism = self.config.parallelism.max(1);
+
+        let mut trials = match self.config.method {
+            SearchMethod::Grid => {
+                let points = grid(&space, self.config.grid_steps)?;
+                evaluator.evaluate_all(&space, points, parallelism).await?
+            }
+            SearchMethod::Random => {
+                let points = (0..self.config.trials)
+                    .map(|_| space.iter().map(|d| d.sample(&mut rng)).collect())
+                    .collect();
+                evaluator.evaluate_all(&space, points, parallelism).await?
+            }
+            SearchMethod::Bayesian => {
+                let startup = (self.config.trials / 4)
+                    .clamp(5, 20)
+                    .min(self.config.trials);
+                let mut history: Vec<(Vec<f64>, Trial)> = Vec::new();
+                while history.len() < self.config.trials {
+                    let batch = parallelism.min(self.config.trials - history.len());
+                    let points: Vec<Vec<f64>> = (0..batch)
+                        .map(|_| {
+                            if history.len() < startup {
+                                space.iter().map(|d| d.sample(&mut rng)).collect()
+                            } else {
+                                suggest(&space, &history, &mut rng)
+                            }
+                        })
+                        .collect();
+                    let trials = evaluator
+                        .evaluate_all(&space, points.clone(), parallelism)
+                        .await?;
+                    history.extend(points.into_iter().zip(trials));
+                }
+                history.into_iter().map(|(_, trial)| trial).collect()
+            }
+        };
+
+        trials.sort_by(|a, b| {
+            b.score()
+                .return_pct
+                .cmp(&a.score().return_pct)
+                .then_with(|| a.score().max_drawdown.cmp(&b.score().max_drawdown))
+        });
+        for (i, trial) in trials.iter_mut().enumerate() {
+            trial.rank = i + 1;
+        }
+
+        Ok(OptimizationReport {
+            strategy: template.name().to_string(),
+            method: self.config.method,
+            frames: recording.len(),
+            trials,
+        })
+    }
+
+    fn search_space(&self, strategy: &dyn Strategy) -> Result<Vec<Dimension>> {
+        let mut defs: Vec<ParameterDef> = strategy.parameters().into_values().collect();
+        defs.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for name in &self.config.parameters {
+            match defs.iter().find(|d| &d.name == name) {
+                None => {
+                    return Err(Error::invalid_input(format!(
+                        "Strategy '{}' has no parameter '{}'",
+                        strategy.name(),
+                        name
+                    )));
+                }
+                Some(def) if Dimension::from_def(def).is_none() => {
+                    return Err(Error::invalid_input(format!(
+                        "Parameter '{}' has no range or allowed values to search",
+                        name
+                    )));
+                }
+                Some(_) => {}
+            }
+        }
+
+        let space: Vec<Dimension> = defs
+            .iter()
+            .filter(|d| {
+                self.config.parameters.is_empty() || self.config.parameters.contains(&d.name)
+            })
+            .filter_map(Dimension::from_def)
+            .collect();
+        if space.is_empty() {
+            return Err(Error::invalid_input(format!(
+                "Strategy '{}' has no parameters with a range to search",
+                strategy.name()
+            )));
+        }
+        Ok(space)
+    }
+}
+
+/// A searchable parameter.
+///
+/// Points in the search space hold one coordinate per dimension: a position
+/// in `0.0..=1.0` for ranges, or an index into the choices.
+#[derive(Debug, Clone)]
+struct Dimension {
+    def: ParameterDef,
+    domain: Domain,
+}
+
+#[derive(Debug, Clone)]
+enum Domain {
+    Range { min: f64, max: f64 },
+    Choice(Vec<ParameterValue>),
+}
+
+impl Dimension {
+    fn from_def(def: &ParameterDef) -> Option<Self> {
+        let domain = if let Some(allowed) = &def.allowed_values {
+            Domain::Choice(allowed.clone())
+        } else {
+            match def.param_type {
+                ParameterType::Integer | ParameterType::Float | ParameterType::Decimal => {
+                    let min = def.min.as_ref()?.as_f64()?;
+                    let max = def.max.as_ref()?.as_f64()?;
+                    if min > max {
+                        return None;
+                    }
+                    Domain::Range { min, max }
+                }
+                ParameterType::Boolean => Domain::Choice(vec![
+                    ParameterValue::Boolean(false),
+                    ParameterValue::Boolean(true),
+                ]),
+                ParameterType::String | ParameterType::Enum => return None,
+            }
+        };
+        Some(Self {
+            def: def.clone(),
+            domain,
+        })
+    }
+
+    fn sample(&self, rng: &mut StdRng) -> f64 {
+        match &self.domain {
+            Domain::Range { .. } => rng.r#gen::<f64>(),
+            Domain::Choice(choices) => rng.gen_range(0..choices.len()) as f64,
+        }
+    }
+
+    /// Convert a coordinate to a parameter value.
+    fn value(&self, x: f64) -> ParameterValue {
+        match &self.domain {
+            Domain::Range { min, max } => {
+                let v = min + x.clamp(0.0, 1.0) * (max - min);
+                match self.def.param_type {
+                    ParameterType::Integer => ParameterValue::Integer(v.round() as i64),
+                    ParameterType::Decimal => Decimal::from_f64(v)
+                        .map(|d| ParameterValue::Decimal(d.round_dp(4)))
+                        .unwrap_or(ParameterValue::Float(v)),
+                    _ => ParameterValue::Float((v * 10_000.0).round() / 10_000.0),
+                }
+            }
+            Domain::Choice(choices) => choices[(x as usize).min(choices.len() - 1)].clone(),
+        }
+    }
+
+    /// Evenly spaced coordinates covering the dimension.
+    fn grid(&self, steps: usize) -> Vec<f64> {
+        match &self.domain {
+            Domain::Range { .. } if steps <= 1 => vec![0.5],
+            Domain::Range { .. } => (0..steps).map(|i| i as f64 / (steps - 1) as f64).collect(),
+            Domain::Choice(choices) => (0..choices.len()).map(|i| i as f64).collect(),
+        }
+    }
+
+    /// Parzen density of `x` given the coordinates of earlier trials.
+    fn density(&self, x: f64, observed: &[f64]) -> f64 {
+        match &self.domain {
+            Domain::Range { .. } => {
+                // Bandwidth shrinks slowly as trials accumulate
+                let bandwidth = (0.5 * (observed.len() as f64).powf(-0.2)).max(0.05);
+                let kernels: f64 = observed
+                    .iter()
+                    .map(|o| gaussian((x - o) / bandwidth) / bandwidth)
+                    .sum();
+                // Uniform prior keeps unexplored regions reachable
+                (kernels + 1.0) / (observed.len() as f64 + 1.0)
+            }
+            Domain::Choice(choices) => {
+                let count = observed.iter().filter(|o| **o == x).count();
+                (count as f64 + 1.0) / (observed.len() + choices.len()) as f64
+            }
+        }
+    }
+}
+
+fn gaussian(z: f64) -> f64 {
+    (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+/// Every combination of grid coordinates, without duplicate values.
+fn grid(space: &[Dimension], steps: usize) -> Result<Vec<Vec<f64>>> {
+    let axes: Vec<Vec<f64>> = space
+        .iter()
+        .map(|d| {
+            // Integer ranges narrower than the step count repeat values
+            let mut seen = HashSet::new();
+            d.grid(steps)
+                .into_iter()
+                .filter(|x| seen.insert(d.value(*x).to_string()))
+                .collect()
+        })
+        .collect();
+
+    let size = axes
+        .iter()
+        .try_fold(1usize, |size, axis| size.checked_mul(axis.len()))
+        .filter(|size| *size <= MAX_GRID_SIZE);
+    if size.is_none() {
+        return Err(Error::invalid_input(format!(
+            "Grid has more than {} points; search fewer parameters or use fewer steps",
+            MAX_GRID_SIZE
+        )));
+    }
+
+    let mut points = vec![Vec::new()];
+    for axis in axes {
+        points = points
+            .into_iter()
+            .flat_map(|point| {
+                axis.iter().map(move |x| {
+                    let mut point = point.clone();
+                    point.push(*x);
+                    point
+                })
+            })
+            .collect();
+    }
+    Ok(points)
+}
+
+/// Suggest the next point with a tree-structured Parzen estimator.
+///
+/// Earlier trials are split into the best quarter and the rest. Candidates
+/// are drawn around the good trials, and the one most likely under the good
+/// trials relative to the rest is chosen.
+fn suggest(space: &[Dimension], history: &[(Vec<f64>, Trial)], rng: &mut StdRng) -> Vec<f64> {
+    let mut sorted: Vec<&(Vec<f64>, Trial)> = history.iter().collect();
+    sorted.sort_by_key(|(_, trial)| std::cmp::Reverse(trial.in_sample.return_pct));
+    let good_len = history.len().div_ceil(4).max(1);
+    let (good, bad) = sorted.split_at(good_len);
+
+    let column = |trials: &[&(Vec<f64>, Trial)], i: usize| -> Vec<f64> {
+        trials.iter().map(|(point, _)| point[i]).collect()
+    };
+
+    let mut best: Option<(f64, Vec<f64>)> = None;
+    for _ in 0..BAYESIAN_CANDIDATES {
+        let parent = &good[rng.gen_range(0..good.len())].0;
+        let candidate: Vec<f64> = space
+            .iter()
+            .zip(parent)
+            .map(|(dimension, x)| match &dimension.domain {
+                Domain::Range { .. } => {
+                    let bandwidth = (0.5 * (good.len() as f64).powf(-0.2)).max(0.05);
+                    (x + bandwidth * standard_normal(rng)).clamp(0.0, 1.0)
+                }
+                Domain::Choice(_) if rng.gen_bool(0.8) => *x,
+                Domain::Choice(_) => dimension.sample(rng),
+            })
+            .collect();
+
+        let score: f64 = space
+            .iter()
+            .enumerate()
+            .map(|(i, dimension)| {
+                let x = candidate[i];
+                (dimension.density(x, &column(good, i)) / dimension.density(x, &column(bad, i)))
+                    .ln()
+            })
+            .sum();
+        if best.as_ref().is_none_or(|(s, _)| score > *s) {
+            best = Some((score, candidate));
+        }
+    }
+    best.map(|(_, point)| point)
+        .unwrap_or_else(|| space.iter().map(|d| d.sample(rng)).collect())
+}
+
+/// Box-Muller transform.
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.r#gen::<f64>().max(f64::MIN_POSITIVE);
+    let u2: f64 = rng.r#gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Anchored walk-forward windows as `(train_end, test_end)` frame indices.
+///
+/// The recording is cut into `folds + 1` segments. Fold `k` trains on the
+/// first `k + 1` segments and tests on the next one. Without folds there is
+/// one window covering the whole recording.
+fn windows(len: usize, folds: usize) -> Result<Vec<(usize, usize)>> {
+    if len == 0 {
+        return Err(Error::invalid_input("Recording has no frames"));
+    }
+    if folds == 0 {
+        return Ok(vec![(len, len)]);
+    }
+    let segment = len / (folds + 1);
+    if segment == 0 {
+        return Err(Error::invalid_input(format!(
+            "Recording has {} frames, too few for {} folds",
+            len, folds
+        )));
+    }
+    Ok((0..folds)
+        .map(|k| {
+            let train_end = segment * (k + 1);
+            let test_end = if k + 1 == folds {
+                len
+            } else {
+                segment * (k + 2)
+            };
+            (train_end, test_end)
+        })
+        .collect())
+}
+
+/// Scores parameter sets; cheap to clone into worker threads.
+#[derive(Clone)]
+struct Evaluator {
+    factory: StrategyFactory,
+    base: StrategyConfig,
+    frames: Arc<Vec<Frame>>,
+    windows: Vec<(usize, usize)>,
+    backtest: Backtest,
+}
+
+impl Evaluator {
+    /// Evaluate points, running up to `parallelism` backtests at once.
+    async fn evaluate_all(
+        &self,
+        space: &[Dimension],
+        points: Vec<Vec<f64>>,
+        parallelism: usize,
+    ) -> Result<Vec<Trial>> {
+        let mut trials = Vec::with_capacity(points.len());
+        for batch in points.chunks(parallelism) {
+            let handles: Vec<_> = batch
+                .iter()
+                .map(|point| {
+                    let parameters: BTreeMap<String, ParameterValue> = space
+                        .iter()
+                        .zip(point)
+                        .map(|(d, x)| (d.def.name.clone(), d.value(*x)))
+                        .collect();
+                    let evaluator = self.clone();
+                    // Backtests are CPU-bound, so each gets its own thread
+                    tokio::task::spawn_blocking(move || {
+                        futures::executor::block_on(evaluator.evaluate(parameters))
+                    })
+                })
+                .collect();
+            for handle in handles {
+                trials.push(
+                    handle
+                        .await
+                        .map_err(|e| Error::application(format!("Backtest failed: {}", e)))??,
+                );
+            }
+        }
+        Ok(trials)
+    }
+
+    async fn evaluate(&self, parameters: BTreeMap<String, ParameterValue>) -> Result<Trial> {
+        let mut config = self.base.clone();
+        for (name, value) in &parameters {
+            config.parameters.insert(name.clone(), value.to_json());
+        }
+
+        let mut in_sample = Vec::new();
+        let mut out_of_sample = Vec::new();
+        for &(train_end, test_end) in &self.windows {
+            let mut strategy = (self.factory)();
+            strategy.initialize(&config).await?;
+            in_sample.push(
+                self.backtest
+                    .run(strategy.as_mut(), &self.frames[..train_end]),
+            );
+
+            if test_end > train_end {
+                let mut strategy = (self.factory)();
+                strategy.initialize(&config).await?;
+                out_of_sample.push(self.backtest.run_from(
+                    strategy.as_mut(),
+                    &self.frames[..test_end],
+                    train_end,
+                ));
+            }
+        }
+
+        Ok(Trial {
+            rank: 0,
+            parameters,
+            in_sample: Score::from_results(&in_sample),
+            out_of_sample: (!out_of_sample.is_empty()).then(|| Score::from_results(&out_of_sample)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{MarketStatus, Quantity};
+    use crate::strategy::backtest::RecordedMarket;
+    use crate::strategy::{Signal, StrategyContext};
+    use async_trait::async_trait;
+    use rust_decimal_macros::dec;
+    use std::collections::HashMap;
+
+    /// Buys below `entry` and sells above `exit`.
+    #[derive(Debug)]
+    struct Threshold {
+        entry: Decimal,
+        exit: Decimal,
+    }
+
+    #[async_trait]
+    impl Strategy for Threshold {
+        fn name(&self) -> &str {
+            "threshold"
+        }
+
+        async fn initialize(&mut self, config: &StrategyConfig) -> Result<()> {
+            let get = |name: &str| {
+                config
+                    .parameters
+                    .get(name)
+                    .and_then(|v| v.as_f64())
+                    .and_then(Decimal::from_f64)
+            };
+            self.entry = get("entry").unwrap_or(self.entry);
+            self.exit = get("exit").unwrap_or(self.exit);
+            Ok(())
+        }
+
+        fn evaluate(&mut self, ctx: &StrategyContext) -> Vec<Signal> {
+            let price = ctx.markets["m"].token_prices[0];
+            match ctx.get_position("yes").map(|p| p.size) {
+                None if price <= self.entry => {
+                    vec![Signal::buy("m", "yes", Quantity::Shares(dec!(100)))]
+                }
+                Some(size) if price >= self.exit => {
+                    vec![Signal::sell("m", "yes", Quantity::Shares(size))]
+                }
+                _ => vec![],
+            }
+        }
+
+        fn parameters(&self) -> HashMap<String, ParameterDef> {
+            let def = |name: &str, min: f64, max: f64| ParameterDef {
+                name: name.to_string(),
+                description: String::new(),
+                param_type: ParameterType::Float,
+                default: ParameterValue::Float(min),
+                min: Some(ParameterValue::Float(min)),
+                max: Some(ParameterValue::Float(max)),
+                allowed_values: None,
+            };
+            [
+                ("entry".to_string(), def("entry", 0.2, 0.4)),
+                ("exit".to_string(), def("exit", 0.6, 0.8)),
+            ]
+            .into()
+        }
+    }
+
+    fn factory() -> StrategyFactory {
+        Arc::new(|| {
+            Box::new(Threshold {
+                entry: dec!(0.2),
+                exit: dec!(0.6),
+            })
+        })
+    }
+
+    /// Price oscillating between 0.2 and 0.8.
+    fn recording(len: usize) -> Recording {
+        let prices = [0.5, 0.4, 0.3, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.7, 0.6];
+        Recording {
+            frames: (0..len)
+                .map(|i| {
+                    let price = Decimal::from_f64(prices[i % prices.len()]).unwrap();
+                    Frame {
+                        timestamp: chrono::DateTime::from_timestamp(i as i64 * 60, 0).unwrap(),
+                        markets: vec![RecordedMarket {
+                            condition_id: "m".to_string(),
+                            question: String::new(),
+                            status: MarketStatus::Active,
+                            token_ids: vec!["yes".to_string(), "no".to_string()],
+                            token_names: vec![],
+                            token_prices: vec![price, Decimal::ONE - price],
+                            volume_24h: Decimal::ZERO,
+                            liquidity: Decimal::ZERO,
+                            spread: None,
+                        }],
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_walk_forward_windows() {
+        assert_eq!(windows(10, 0).unwrap(), vec![(10, 10)]);
+        assert_eq!(windows(10, 2).unwrap(), vec![(3, 6), (6, 10)]);
+        assert!(windows(2, 2).is_err());
+        assert!(windows(0, 0).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_grid_search_ranks_best_thresholds_first() {
+        let optimizer = Optimizer::new(OptimizerConfig {
+            method: SearchMethod::Grid,
+            grid_steps: 3,
+            parallelism: 2,
+            starting_balance: dec!(100),
+            ..Default::default()
+        });
+        let report = optimizer
+            .run(factory(), &StrategyConfig::default(), &recording(24))
+            .await
+            .unwrap();
+
+        assert_eq!(report.trials.len(), 9);
+        let best = report.best().unwrap();
+        assert_eq!(best.parameters["entry"], ParameterValue::Float(0.2));
+        assert_eq!(best.parameters["exit"], ParameterValue::Float(0.8));
+        assert!(
+            report
+                .trials
+                .windows(2)
+                .all(|w| w[0].score().return_pct >= w[1].score().return_pct)
+        );
+
+        let config = report.best_config(&StrategyConfig::default()).unwrap();
+        assert_eq!(config.parameters["exit"], serde_json::json!(0.8));
+    }
+
+    #[tokio::test]
+    async fn test_bayesian_search_with_folds_is_reproducible() {
+        let config = OptimizerConfig {
+            method: SearchMethod::Bayesian,
+            trials: 12,
+            folds: 2,
+            parallelism: 3,
+            seed: Some(7),
+            parameters: vec!["exit".to_string()],
+            ..Default::default()
+        };
+        let run = || async {
+            Optimizer::new(config.clone())
+                .run(factory(), &StrategyConfig::default(), &recording(36))
+                .await
+                .unwrap()
+        };
+        let first = run().await;
+        let second = run().await;
+
+        assert_eq!(first.trials.len(), 12);
+        assert_eq!(first.trials, second.trials);
+        assert!(first.trials.iter().all(|t| t.out_of_sample.is_some()));
+        assert!(first.trials.iter().all(|t| t.parameters.len() == 1));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_parameter_is_rejected() {
+        let optimizer = Optimizer::new(OptimizerConfig {
+            parameters: vec!["missing".to_string()],
+            ..Default::default()
+        });
+        let result = optimizer
+            .run(factory(), &StrategyConfig::default(), &recording(12))
+            .await;
+        assert!(matches!(result, Err(Error::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,133 @@
+//! Strategy state snapshots on disk.
+//!
+//! Each strategy's [`StrategyState`] is written to `{name}.json` in the state
+//! directory. JSON is used rather than TOML because snapshots are arbitrary
+//! serde values, which may contain nulls.
+//!
+//! The engine's open brackets are kept next to the snapshots in
+//! `_brackets.json`; the leading underscore keeps it apart from strategy
+//! names.
+
+use super::{Bracket, StrategyState};
+use std::path::{Path, PathBuf};
+
+/// File holding the engine's open brackets.
+const BRACKETS_FILE: &str = "_brackets.json";
+
+/// Directory of strategy state snapshots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateStore {
+    directory: PathBuf,
+}
+
+impl StateStore {
+    /// Create a store writing to `directory`.
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Get the default snapshot directory, with one subdirectory per profile.
+    pub fn default_directory(profile: Option<&str>) -> crate::Result<PathBuf> {
+        let directory = crate::config::data_dir()?.join("strategies");
+        Ok(match profile {
+            Some(profile) => directory.join(profile),
+            None => directory,
+        })
+    }
+
+    /// Get the snapshot directory.
+    pub fn directory(&self) -> &PathBuf {
+        &self.directory
+    }
+
+    /// Get the snapshot path of a strategy.
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.directory.join(format!("{}.json", name))
+    }
+
+    /// Load a strategy's snapshot, returning `None` if it has none.
+    pub fn load(&self, name: &str) -> crate::Result<Option<StrategyState>> {
+        let path = self.path(name);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Write a strategy's snapshot.
+    ///
+    /// Writes to a temporary file first so a crash mid-write never leaves a
+    /// truncated snapshot behind.
+    pub fn save(&self, name: &str, state: &StrategyState) -> crate::Result<()> {
+        self.write(&self.path(name), &serde_json::to_string_pretty(state)?)
+    }
+
+    /// Load the open brackets, returning none if they were never saved.
+    pub fn load_brackets(&self) -> crate::Result<Vec<Bracket>> {
+        let path = self.directory.join(BRACKETS_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the open brackets, the same way as snapshots.
+    pub fn save_brackets(&self, brackets: &[Bracket]) -> crate::Result<()> {
+        self.write(
+            &self.directory.join(BRACKETS_FILE),
+            &serde_json::to_string_pretty(brackets)?,
+        )
+    }
+
+    fn write(&self, path: &Path, content: &str) -> crate::Result<()> {
+        std::fs::create_dir_all(&self.directory)?;
+
+        let temp = path.with_extension("json.tmp");
+        std::fs::write(&temp, content)?;
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// Move a snapshot that cannot be restored out of the way, returning its
+    /// new path.
+    ///
+    /// The file is renamed to `{name}.v{version}.json` so the next checkpoint
+    /// does not overwrite it.
+    pub fn archive(&self, name: &str, version: u32) -> crate::Result<PathBuf> {
+        let archived = self.directory.join(format!("{}.v{}.json", name, version));
+        std::fs::rename(self.path(name), &archived)?;
+        Ok(archived)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_save_load_and_archive() {
+        let dir =
+            std::env::temp_dir().join(format!("clobster-strategies-{}", uuid::Uuid::new_v4()));
+        let store = StateStore::new(dir.clone());
+        assert_eq!(store.load("spread").unwrap(), None);
+
+        let inventory: HashMap<String, i64> = [("0xabc".to_string(), 5)].into();
+        let state = StrategyState::new(2, &inventory).unwrap();
+        store.save("spread", &state).unwrap();
+
+        let loaded = store.load("spread").unwrap().unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(loaded.decode::<HashMap<String, i64>>().unwrap(), inventory);
+
+        let archived = store.archive("spread", 2).unwrap();
+        assert!(archived.ends_with("spread.v2.json"));
+        assert_eq!(store.load("spread").unwrap(), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -18,6 +18,9 @@ impl RiskGuard {
     }
 
     /// Check if a signal passes all risk rules.
+    ///
+    /// Size and exposure limits are in USDC, so the signal is valued at its
+    /// limit price, or at the book's VWAP for market orders.
     pub fn check_signal(
         &self,
         signal: &Signal,
@@ -31,17 +34,23 @@ impl RiskGuard {
         // Check market whitelist/blacklist
         self.check_market_allowed(signal)?;
 
+        let signal_value = signal
+            .notional(ctx)
+            .ok_or_else(|| RiskViolation::PriceUnavailable {
+                token_id: signal.token_id.clone(),
+            })?;
+
         // Check position size limits
-        self.check_position_size(signal)?;
+        self.check_position_size(signal_value)?;
 
         // Check total exposure
-        self.check_total_exposure(signal, ctx)?;
+        self.check_total_exposure(signal, signal_value, ctx)?;
 
         // Check maximum positions
         self.check_max_positions(signal, ctx)?;
 
         // Check market-specific limits
-        self.check_market_exposure(signal, ctx)?;
+        self.check_market_exposure(signal, signal_value, ctx)?;
 
         // Check daily limits (not yet implemented)
         self.check_daily_limits()?;
@@ -73,20 +82,20 @@ impl RiskGuard {
     }
 
     #[allow(clippy::collapsible_if)] // Intentionally avoiding let-chains for stable Rust
-    fn check_position_size(&self, signal: &Signal) -> Result<(), RiskViolation> {
+    fn check_position_size(&self, signal_value: Decimal) -> Result<(), RiskViolation> {
         if let Some(max_size) = self.config.max_position_size {
-            if signal.size > max_size {
+            if signal_value > max_size {
                 return Err(RiskViolation::PositionSizeExceeded {
-                    requested: signal.size,
+                    requested: signal_value,
                     max: max_size,
                 });
             }
         }
 
         if let Some(min_size) = self.config.min_position_size {
-            if signal.size < min_size {
+            if signal_value < min_size {
                 return Err(RiskViolation::PositionSizeTooSmall {
-                    requested: signal.size,
+                    requested: signal_value,
                     min: min_size,
                 });
             }
@@ -98,11 +107,11 @@ impl RiskGuard {
     fn check_total_exposure(
         &self,
         signal: &Signal,
+        signal_value: Decimal,
         ctx: &StrategyContext,
     ) -> Result<(), RiskViolation> {
         if let Some(max_exposure) = self.config.max_total_exposure {
             let current_exposure = ctx.total_exposure();
-            let signal_value = signal.size * signal.price.unwrap_or(Decimal::ONE);
 
             // Sell signals reduce exposure, buy signals increase it
             let new_exposure = match signal.side {
@@ -161,6 +170,7 @@ impl RiskGuard {
     fn check_market_exposure(
         &self,
         signal: &Signal,
+        signal_value: Decimal,
         ctx: &StrategyContext,
     ) -> Result<(), RiskViolation> {
         if let Some(max_per_market) = self.config.max_exposure_per_market {
@@ -173,7 +183,6 @@ impl RiskGuard {
                 }
             }
 
-            let signal_value = signal.size * signal.price.unwrap_or(Decimal::ONE);
             let new_exposure = market_exposure + signal_value;
 
             if new_exposure > max_per_market {
@@ -219,25 +228,25 @@ impl RiskGuard {
 }
 
 /// Risk management configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RiskConfig {
     /// Whether risk checks are enabled.
     #[serde(default = "default_true")]
     pub enabled: bool,
 
-    /// Maximum size for a single position.
+    /// Maximum value of a single order, in USDC.
     pub max_position_size: Option<Decimal>,
 
-    /// Minimum size for a position.
+    /// Minimum value of a single order, in USDC.
     pub min_position_size: Option<Decimal>,
 
-    /// Maximum total exposure across all positions.
+    /// Maximum total exposure across all positions, in USDC.
     pub max_total_exposure: Option<Decimal>,
 
     /// Maximum number of open positions.
     pub max_positions: Option<usize>,
 
-    /// Maximum exposure per market.
+    /// Maximum exposure per market, in USDC.
     pub max_exposure_per_market: Option<Decimal>,
 
     /// Maximum daily trading volume.
@@ -333,6 +342,9 @@ pub enum RiskViolation {
         required: Decimal,
     },
 
+    /// The signal's value could not be priced.
+    PriceUnavailable { token_id: String },
+
     /// Market is blacklisted.
     MarketBlacklisted { market_id: String },
 
@@ -401,6 +413,9 @@ impl std::fmt::Display for RiskViolation {
                     available, required
                 )
             }
+            Self::PriceUnavailable { token_id } => {
+                write!(f, "No price to value order for token {}", token_id)
+            }
             Self::MarketBlacklisted { market_id } => {
                 write!(f, "Market {} is blacklisted", market_id)
             }
@@ -418,3 +433,56 @@ impl std::fmt::Display for RiskViolation {
 }
 
 impl std::error::Error for RiskViolation {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{OrderBookDepth, OrderType, PriceLevel, Quantity};
+    use rust_decimal_macros::dec;
+
+    fn guard() -> RiskGuard {
+        RiskGuard::new(RiskConfig {
+            max_position_size: Some(dec!(50)),
+            min_position_size: Some(dec!(1)),
+            ..RiskConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_limits_are_in_usdc() {
+        let ctx = StrategyContext::new();
+
+        // 100 shares at 0.40 are worth $40
+        let shares = Signal::buy("m1", "yes", Quantity::Shares(dec!(100))).with_price(dec!(0.40));
+        assert!(guard().check_signal(&shares, &ctx).is_ok());
+
+        let shares = shares.with_size(Quantity::Shares(dec!(200)));
+        assert!(matches!(
+            guard().check_signal(&shares, &ctx),
+            Err(RiskViolation::PositionSizeExceeded { requested, .. }) if requested == dec!(80)
+        ));
+    }
+
+    #[test]
+    fn test_market_orders_are_valued_from_the_book() {
+        let mut ctx = StrategyContext::new();
+        let market = Signal::buy("m1", "yes", Quantity::Shares(dec!(100)))
+            .with_order_type(OrderType::Market);
+        assert!(matches!(
+            guard().check_signal(&market, &ctx),
+            Err(RiskViolation::PriceUnavailable { .. })
+        ));
+
+        let mut book = OrderBookDepth::new("m1", "yes");
+        book.asks = vec![
+            PriceLevel::new(dec!(0.40), dec!(50)),
+            PriceLevel::new(dec!(0.80), dec!(50)),
+        ];
+        ctx.order_books.insert("yes".to_string(), book);
+        // $20 + $40 walking the asks
+        assert!(matches!(
+            guard().check_signal(&market, &ctx),
+            Err(RiskViolation::PositionSizeExceeded { requested, .. }) if requested == dec!(60)
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 //! Trading signals generated by strategies.
 
-use crate::state::{OrderSide, OrderType};
+use super::{ExecutionAlgo, SizingDecision, StrategyContext};
+use crate::state::{OrderSide, OrderType, Quantity};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -28,21 +29,28 @@ pub struct Signal {
     pub order_type: OrderType,
     /// Target price (limit price). Required for limit orders, optional for market orders.
     pub price: Option<Decimal>,
-    /// Suggested size.
-    pub size: Decimal,
+    /// Suggested size, in shares or USDC. The engine converts a notional
+    /// to shares before the signal is risk checked.
+    pub size: Quantity,
     /// Time-to-live in seconds (signal expires after this).
     pub ttl_secs: u64,
     /// When the signal was generated.
     pub created_at: DateTime<Utc>,
     /// Optional reason/rationale for the signal.
     pub reason: Option<String>,
+    /// Whether to place, cancel or amend an order.
+    #[serde(default)]
+    pub action: SignalAction,
+    /// How the order is worked.
+    #[serde(default)]
+    pub execution: ExecutionAlgo,
     /// Additional metadata.
     pub metadata: SignalMetadata,
 }
 
 impl Signal {
     /// Create a new buy signal.
-    pub fn buy(market_id: impl Into<String>, token_id: impl Into<String>, size: Decimal) -> Self {
+    pub fn buy(market_id: impl Into<String>, token_id: impl Into<String>, size: Quantity) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
             strategy_name: String::new(),
@@ -57,12 +65,14 @@ impl Signal {
             ttl_secs: 60,
             created_at: Utc::now(),
             reason: None,
+            action: SignalAction::Place,
+            execution: ExecutionAlgo::Immediate,
             metadata: SignalMetadata::default(),
         }
     }
 
     /// Create a new sell signal.
-    pub fn sell(market_id: impl Into<String>, token_id: impl Into<String>, size: Decimal) -> Self {
+    pub fn sell(market_id: impl Into<String>, token_id: impl Into<String>, size: Quantity) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
             strategy_name: String::new(),
@@ -77,10 +87,34 @@ impl Signal {
             ttl_secs: 60,
             created_at: Utc::now(),
             reason: None,
+            action: SignalAction::Place,
+            execution: ExecutionAlgo::Immediate,
             metadata: SignalMetadata::default(),
         }
     }
 
+    /// Create a signal to cancel an open order.
+    pub fn cancel(
+        market_id: impl Into<String>,
+        token_id: impl Into<String>,
+        order_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            action: SignalAction::Cancel {
+                order_id: order_id.into(),
+            },
+            ..Self::buy(market_id, token_id, Quantity::Shares(Decimal::ZERO))
+        }
+    }
+
+    /// Replace an open order with the order this signal describes.
+    pub fn amending(mut self, order_id: impl Into<String>) -> Self {
+        self.action = SignalAction::Amend {
+            order_id: order_id.into(),
+        };
+        self
+    }
+
     /// Set the strategy name.
     pub fn with_strategy(mut self, name: impl Into<String>) -> Self {
         self.strategy_name = name.into();
@@ -105,12 +139,25 @@ impl Signal {
         self
     }
 
+    /// Set the size.
+    pub fn with_size(mut self, size: Quantity) -> Self {
+        self.size = size;
+        self
+    }
+
     /// Set the limit price.
     pub fn with_price(mut self, price: Decimal) -> Self {
         self.price = Some(price);
         self
     }
 
+    /// Work the order with an execution algorithm instead of sending it
+    /// whole.
+    pub fn with_execution(mut self, execution: ExecutionAlgo) -> Self {
+        self.execution = execution;
+        self
+    }
+
     /// Set the time-to-live.
     pub fn with_ttl(mut self, ttl_secs: u64) -> Self {
         self.ttl_secs = ttl_secs;
@@ -147,18 +194,96 @@ impl Signal {
     pub fn expected_value(&self) -> Option<Decimal> {
         self.metadata.expected_value
     }
+
+    /// Price the signal is expected to trade at: its limit price, else the
+    /// best price on the side it takes, else the token's last price.
+    pub fn reference_price(&self, ctx: &StrategyContext) -> Option<Decimal> {
+        let price = self
+            .price
+            .or_else(|| match self.side {
+                OrderSide::Buy => ctx.best_ask(&self.token_id),
+                OrderSide::Sell => ctx.best_bid(&self.token_id),
+            })
+            .or_else(|| {
+                let market = ctx.get_market(&self.market_id)?;
+                let index = market.token_ids.iter().position(|t| *t == self.token_id)?;
+                market.token_prices.get(index).copied()
+            })?;
+        (price > Decimal::ZERO).then_some(price)
+    }
+
+    /// Shares the signal trades.
+    ///
+    /// A notional is converted at the limit price of a limit order, and at
+    /// the VWAP of the book for a market order, falling back to the
+    /// reference price.
+    pub fn shares(&self, ctx: &StrategyContext) -> Option<Decimal> {
+        if let Quantity::Shares(shares) = self.size {
+            return Some(shares);
+        }
+        if self.order_type == OrderType::Market
+            && let Some(book) = ctx.get_book(&self.token_id)
+            && let Some(shares) = self.size.shares_in(book, self.side)
+        {
+            return Some(shares);
+        }
+        self.size.shares_at(self.reference_price(ctx)?)
+    }
+
+    /// USDC value of the signal, converted like [`shares`](Self::shares).
+    pub fn notional(&self, ctx: &StrategyContext) -> Option<Decimal> {
+        if let Quantity::Notional(notional) = self.size {
+            return Some(notional);
+        }
+        if self.order_type == OrderType::Market
+            && let Some(book) = ctx.get_book(&self.token_id)
+            && let Some(notional) = self.size.notional_in(book, self.side)
+        {
+            return Some(notional);
+        }
+        Some(self.size.notional_at(self.reference_price(ctx)?))
+    }
 }
 
 impl fmt::Display for Signal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] ", self.strategy_name)?;
+        match &self.action {
+            SignalAction::Place => {}
+            SignalAction::Cancel { order_id } => return write!(f, "Cancel {}", order_id),
+            SignalAction::Amend { order_id } => write!(f, "Amend {}: ", order_id)?,
+        }
         write!(
             f,
-            "[{}] {:?} {:?} {} @ {:?} ({})",
-            self.strategy_name, self.side, self.signal_type, self.size, self.price, self.strength
+            "{:?} {:?} {} @ {:?} ({})",
+            self.side, self.signal_type, self.size, self.price, self.strength
         )
     }
 }
 
+/// What a signal asks the engine to do.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignalAction {
+    /// Place a new order.
+    #[default]
+    Place,
+    /// Cancel an open order. Size and price are ignored.
+    Cancel { order_id: String },
+    /// Cancel an open order and place the order described by the signal
+    /// in its place.
+    Amend { order_id: String },
+}
+
+impl SignalAction {
+    /// The open order cancelled by this action, if any.
+    pub fn cancels(&self) -> Option<&str> {
+        match self {
+            Self::Place => None,
+            Self::Cancel { order_id } | Self::Amend { order_id } => Some(order_id),
+        }
+    }
+}
+
 /// Type of trading signal.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SignalType {
@@ -231,4 +356,11 @@ pub struct SignalMetadata {
     pub indicators: std::collections::HashMap<String, f64>,
     /// Custom tags.
     pub tags: Vec<String>,
+    /// USDC notional the strategy asked for, when the engine converted it
+    /// to shares.
+    #[serde(default)]
+    pub notional: Option<Decimal>,
+    /// How the engine sized the signal, if its strategy has sizing configured.
+    #[serde(default)]
+    pub sizing: Option<SizingDecision>,
 }
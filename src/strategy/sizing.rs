@@ -0,0 +1,541 @@
+//! Position sizing.
+//!
+//! Strategies suggest a size with each signal. A strategy instance with
+//! `sizing` configured has the size of its entries replaced by a
+//! [`PositionSizer`], then limited by the available balance and the depth of
+//! the book, before the risk guard sees the signal. The decision is recorded
+//! in the signal's metadata so every order can be traced back to how it was
+//! sized.
+
+use super::{Signal, SignalAction, SignalType, StrategyContext};
+use crate::state::{OrderSide, Quantity, SIZE_DECIMALS};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+
+/// Chooses how much money to put behind a signal.
+pub trait PositionSizer: Send + Sync + std::fmt::Debug {
+    /// Name recorded with sizing decisions.
+    fn name(&self) -> &str;
+
+    /// Notional in USDC to trade for `signal` at `price`, or why the signal
+    /// cannot be sized.
+    fn notional(
+        &self,
+        signal: &Signal,
+        price: Decimal,
+        ctx: &StrategyContext,
+    ) -> std::result::Result<Decimal, String>;
+}
+
+/// Built-in sizing methods.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SizingMethod {
+    /// The same notional for every entry.
+    FixedNotional { notional: Decimal },
+    /// `notional` scaled by the signal's strength multiplier.
+    StrengthScaled { notional: Decimal },
+    /// `fraction` of the Kelly stake of the available balance, reading the
+    /// signal's confidence as the probability that its side wins.
+    Kelly { fraction: Decimal },
+    /// `notional` scaled so that positions carry `target_volatility`, the
+    /// standard deviation of price changes over the last `periods` prices.
+    /// Prices are only kept for each market's first outcome, so signals on
+    /// other outcomes cannot be sized.
+    VolatilityTarget {
+        notional: Decimal,
+        target_volatility: Decimal,
+        #[serde(default = "default_volatility_periods")]
+        periods: usize,
+    },
+}
+
+fn default_volatility_periods() -> usize {
+    20
+}
+
+impl PositionSizer for SizingMethod {
+    fn name(&self) -> &str {
+        match self {
+            Self::FixedNotional { .. } => "fixed_notional",
+            Self::StrengthScaled { .. } => "strength_scaled",
+            Self::Kelly { .. } => "kelly",
+            Self::VolatilityTarget { .. } => "volatility_target",
+        }
+    }
+
+    fn notional(
+        &self,
+        signal: &Signal,
+        price: Decimal,
+        ctx: &StrategyContext,
+    ) -> std::result::Result<Decimal, String> {
+        match self {
+            Self::FixedNotional { notional } => Ok(*notional),
+            Self::StrengthScaled { notional } => {
+                let multiplier = Decimal::try_from(signal.strength.as_multiplier())
+                    .map_err(|e| e.to_string())?;
+                Ok(*notional * multiplier)
+            }
+            Self::Kelly { fraction } => {
+                let confidence = signal
+                    .metadata
+                    .confidence
+                    .ok_or("signal has no confidence")?;
+                let p = Decimal::try_from(confidence).map_err(|e| e.to_string())?;
+                let stake = kelly_stake(signal.side, p, price)
+                    .ok_or_else(|| format!("no edge at confidence {} and price {}", p, price))?;
+                // The stake is what is at risk; shares are converted at the
+                // token's own price, so a sell's stake is restated at it
+                let at_risk = ctx.available_balance * stake * *fraction;
+                Ok(at_risk * price / cost_per_share(signal.side, price))
+            }
+            Self::VolatilityTarget {
+                notional,
+                target_volatility,
+                periods,
+            } => {
+                let market = ctx
+                    .get_market(&signal.market_id)
+                    .ok_or("market not in context")?;
+                if market.token_ids.first() != Some(&signal.token_id) {
+                    return Err("price history only covers the market's first outcome".to_string());
+                }
+                let realized = volatility(ctx, &signal.market_id, *periods)
+                    .ok_or("not enough price history")?;
+                if realized.is_zero() {
+                    return Ok(*notional);
+                }
+                Ok(*notional * *target_volatility / realized)
+            }
+        }
+    }
+}
+
+/// Kelly fraction of the bankroll for a binary token bought or sold at
+/// `price`, when the signal's side wins with probability `p`.
+///
+/// Buying at `q` wins `1 - q` per `q` staked; selling at `q` is taken as
+/// buying the other outcome at `1 - q`.
+fn kelly_stake(side: OrderSide, p: Decimal, price: Decimal) -> Option<Decimal> {
+    let cost = cost_per_share(side, price);
+    if cost <= Decimal::ZERO || cost >= Decimal::ONE {
+        return None;
+    }
+    let stake = (p - cost) / (Decimal::ONE - cost);
+    (stake > Decimal::ZERO).then_some(stake)
+}
+
+/// Money at risk per share of a binary token traded at `price`.
+fn cost_per_share(side: OrderSide, price: Decimal) -> Decimal {
+    match side {
+        OrderSide::Buy => price,
+        OrderSide::Sell => Decimal::ONE - price,
+    }
+}
+
+/// Standard deviation of the last `periods` price changes of a market's
+/// first outcome.
+fn volatility(ctx: &StrategyContext, market_id: &str, periods: usize) -> Option<Decimal> {
+    let history = ctx.get_price_history(market_id)?;
+    let start = history.len().saturating_sub(periods + 1);
+    let changes: Vec<Decimal> = history[start..]
+        .windows(2)
+        .filter(|w| !w[0].price.is_zero())
+        .map(|w| (w[1].price - w[0].price) / w[0].price)
+        .collect();
+    if changes.len() < 2 {
+        return None;
+    }
+    let n = Decimal::from(changes.len());
+    let mean = changes.iter().sum::<Decimal>() / n;
+    let variance = changes
+        .iter()
+        .map(|c| (c - mean) * (c - mean))
+        .sum::<Decimal>()
+        / n;
+    Decimal::try_from(variance.to_f64()?.sqrt()).ok()
+}
+
+/// Sizing configuration of a strategy instance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SizingConfig {
+    /// Sizing method and its settings.
+    #[serde(flatten)]
+    pub method: SizingMethod,
+    /// Largest share of the available balance one entry may use
+    /// (default: all of it).
+    #[serde(default)]
+    pub max_balance_fraction: Option<Decimal>,
+    /// Largest share of the visible book depth on the side taken one entry
+    /// may use (default: no limit).
+    #[serde(default)]
+    pub max_book_fraction: Option<Decimal>,
+}
+
+/// What held a sized signal back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizingLimit {
+    /// The available balance.
+    Balance,
+    /// The depth of the book.
+    BookLiquidity,
+}
+
+impl std::fmt::Display for SizingLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Balance => write!(f, "balance"),
+            Self::BookLiquidity => write!(f, "book liquidity"),
+        }
+    }
+}
+
+/// How a signal was sized, kept with the signal for audit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SizingDecision {
+    /// Sizer that chose the size.
+    pub method: String,
+    /// Size the strategy suggested.
+    pub requested: Quantity,
+    /// Shares after sizing and limits.
+    pub size: Decimal,
+    /// Price used to convert between notional and shares.
+    pub price: Decimal,
+    /// Notional chosen by the sizer, before limits.
+    pub notional: Decimal,
+    /// Limit that reduced the size, if any.
+    pub limited_by: Option<SizingLimit>,
+}
+
+impl std::fmt::Display for SizingDecision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {} sh (${:.2} at {})",
+            self.method, self.requested, self.size, self.notional, self.price
+        )?;
+        if let Some(limit) = self.limited_by {
+            write!(f, ", limited by {}", limit)?;
+        }
+        Ok(())
+    }
+}
+
+/// A sizer with the limits it is held to.
+#[derive(Debug)]
+pub struct Sizer {
+    sizer: Box<dyn PositionSizer>,
+    max_balance_fraction: Decimal,
+    max_book_fraction: Option<Decimal>,
+}
+
+impl Sizer {
+    /// Create a sizer using all of the available balance at most and any
+    /// depth of the book.
+    pub fn new(sizer: Box<dyn PositionSizer>) -> Self {
+        Self {
+            sizer,
+            max_balance_fraction: Decimal::ONE,
+            max_book_fraction: None,
+        }
+    }
+
+    /// Create the sizer described by a strategy instance's configuration.
+    pub fn from_config(config: &SizingConfig) -> Self {
+        Self {
+            sizer: Box::new(config.method.clone()),
+            max_balance_fraction: config.max_balance_fraction.unwrap_or(Decimal::ONE),
+            max_book_fraction: config.max_book_fraction,
+        }
+    }
+
+    /// Limit entries to `fraction` of the available balance.
+    pub fn with_max_balance_fraction(mut self, fraction: Decimal) -> Self {
+        self.max_balance_fraction = fraction;
+        self
+    }
+
+    /// Limit entries to `fraction` of the visible book depth.
+    pub fn with_max_book_fraction(mut self, fraction: Decimal) -> Self {
+        self.max_book_fraction = Some(fraction);
+        self
+    }
+
+    /// Size a signal, recording the decision in its metadata.
+    ///
+    /// Only new entries and additions are sized; exits, reductions and
+    /// cancels keep the size their strategy chose. Returns why the signal
+    /// should be dropped if it cannot be sized or comes to nothing.
+    pub fn apply(
+        &self,
+        signal: &mut Signal,
+        ctx: &StrategyContext,
+    ) -> std::result::Result<(), String> {
+        if matches!(signal.action, SignalAction::Cancel { .. })
+            || !matches!(
+                signal.signal_type,
+                SignalType::Entry | SignalType::AddToPosition
+            )
+        {
+            return Ok(());
+        }
+
+        let price = signal.reference_price(ctx).ok_or("no price to size at")?;
+        let notional = self.sizer.notional(signal, price, ctx)?.max(Decimal::ZERO);
+        let mut size = notional / price;
+        let mut limited_by = None;
+
+        if signal.side == OrderSide::Buy {
+            let affordable = ctx.available_balance * self.max_balance_fraction / price;
+            if size > affordable {
+                size = affordable;
+                limited_by = Some(SizingLimit::Balance);
+            }
+        }
+        if let Some(fraction) = self.max_book_fraction
+            && let Some(book) = ctx.get_book(&signal.token_id)
+        {
+            let levels = match signal.side {
+                OrderSide::Buy => &book.asks,
+                OrderSide::Sell => &book.bids,
+            };
+            let depth: Decimal = levels.iter().map(|l| l.size).sum::<Decimal>() * fraction;
+            if size > depth {
+                size = depth;
+                limited_by = Some(SizingLimit::BookLiquidity);
+            }
+        }
+
+        let size = size.round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero);
+        signal.metadata.sizing = Some(SizingDecision {
+            method: self.sizer.name().to_string(),
+            requested: signal.size,
+            size,
+            price,
+            notional,
+            limited_by,
+        });
+        if size <= Decimal::ZERO {
+            return Err(match limited_by {
+                Some(limit) => format!("no size left within {}", limit),
+                None => "sized to nothing".to_string(),
+            });
+        }
+        signal.size = Quantity::Shares(size);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{MarketStatus, OrderBookDepth, PriceLevel};
+    use crate::strategy::{MarketSnapshot, PricePoint, SignalStrength};
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn context() -> StrategyContext {
+        let mut ctx = StrategyContext::new();
+        ctx.available_balance = dec!(1000);
+        ctx
+    }
+
+    fn entry() -> Signal {
+        Signal::buy("m1", "yes", Quantity::Shares(dec!(10))).with_price(dec!(0.40))
+    }
+
+    #[test]
+    fn test_fixed_and_strength_scaled() {
+        let ctx = context();
+        let sizer = Sizer::new(Box::new(SizingMethod::FixedNotional { notional: dec!(50) }));
+        let mut signal = entry();
+        sizer.apply(&mut signal, &ctx).unwrap();
+        assert_eq!(signal.size, Quantity::Shares(dec!(125)));
+        let decision = signal.metadata.sizing.unwrap();
+        assert_eq!(decision.requested, Quantity::Shares(dec!(10)));
+        assert_eq!(decision.method, "fixed_notional");
+        assert_eq!(decision.limited_by, None);
+
+        let sizer = Sizer::new(Box::new(SizingMethod::StrengthScaled {
+            notional: dec!(50),
+        }));
+        let mut signal = entry().with_strength(SignalStrength::Weak);
+        sizer.apply(&mut signal, &ctx).unwrap();
+        assert_eq!(signal.size, Quantity::Shares(dec!(31.25)));
+
+        // Exits keep their size
+        let mut exit = Signal::sell("m1", "yes", Quantity::Shares(dec!(7))).with_price(dec!(0.40));
+        sizer.apply(&mut exit, &ctx).unwrap();
+        assert_eq!(exit.size, Quantity::Shares(dec!(7)));
+        assert!(exit.metadata.sizing.is_none());
+    }
+
+    #[test]
+    fn test_kelly_from_confidence_and_price() {
+        let ctx = context();
+        let sizer = Sizer::new(Box::new(SizingMethod::Kelly {
+            fraction: dec!(0.5),
+        }));
+
+        // f* = (0.55 - 0.40) / 0.60 = 0.25, half of it on a 1000 balance
+        let mut signal = entry();
+        signal.metadata.confidence = Some(0.55);
+        sizer.apply(&mut signal, &ctx).unwrap();
+        assert_eq!(signal.metadata.sizing.as_ref().unwrap().notional, dec!(125));
+        assert_eq!(signal.size, Quantity::Shares(dec!(312.5)));
+
+        // No edge, no trade
+        let mut signal = entry();
+        signal.metadata.confidence = Some(0.35);
+        assert!(sizer.apply(&mut signal, &ctx).is_err());
+
+        let mut signal = entry();
+        assert_eq!(
+            sizer.apply(&mut signal, &ctx),
+            Err("signal has no confidence".to_string())
+        );
+    }
+
+    #[test]
+    fn test_kelly_sell_risks_the_stake_at_one_minus_price() {
+        let ctx = context();
+        let sizer = Sizer::new(Box::new(SizingMethod::Kelly {
+            fraction: dec!(0.5),
+        }));
+
+        // Selling at 0.60 costs 0.40 a share: f* = (0.55 - 0.40) / 0.60 =
+        // 0.25, so half of it puts 125 at risk, or 312.5 shares
+        let mut signal = Signal::sell("m1", "yes", Quantity::Shares(dec!(10)))
+            .with_price(dec!(0.60))
+            .with_type(SignalType::Entry);
+        signal.metadata.confidence = Some(0.55);
+        sizer.apply(&mut signal, &ctx).unwrap();
+        assert_eq!(signal.size, Quantity::Shares(dec!(312.5)));
+        assert_eq!(
+            signal.metadata.sizing.as_ref().unwrap().notional,
+            dec!(187.5)
+        );
+    }
+
+    #[test]
+    fn test_limits_from_balance_and_book() {
+        let mut ctx = context();
+        ctx.available_balance = dec!(100);
+        let mut depth = OrderBookDepth::new("m1", "yes");
+        depth.asks = vec![
+            PriceLevel::new(dec!(0.40), dec!(100)),
+            PriceLevel::new(dec!(0.41), dec!(100)),
+        ];
+        ctx.order_books.insert("yes".to_string(), depth);
+
+        let sizer = Sizer::new(Box::new(SizingMethod::FixedNotional {
+            notional: dec!(500),
+        }))
+        .with_max_balance_fraction(dec!(0.5));
+        let mut signal = entry();
+        sizer.apply(&mut signal, &ctx).unwrap();
+        assert_eq!(signal.size, Quantity::Shares(dec!(125)));
+        assert_eq!(
+            signal.metadata.sizing.unwrap().limited_by,
+            Some(SizingLimit::Balance)
+        );
+
+        let sizer = sizer.with_max_book_fraction(dec!(0.25));
+        let mut signal = entry();
+        sizer.apply(&mut signal, &ctx).unwrap();
+        assert_eq!(signal.size, Quantity::Shares(dec!(50)));
+        assert_eq!(
+            signal.metadata.sizing.unwrap().limited_by,
+            Some(SizingLimit::BookLiquidity)
+        );
+    }
+
+    #[test]
+    fn test_volatility_target_scales_by_recent_changes() {
+        let mut ctx = context();
+        ctx.markets.insert(
+            "m1".to_string(),
+            MarketSnapshot {
+                condition_id: "m1".to_string(),
+                question: "m1?".to_string(),
+                status: MarketStatus::Active,
+                token_ids: vec!["yes".to_string(), "no".to_string()],
+                token_names: vec!["Yes".to_string(), "No".to_string()],
+                token_prices: vec![dec!(0.40), dec!(0.60)],
+                volume_24h: Decimal::ZERO,
+                liquidity: Decimal::ZERO,
+                spread: None,
+                end_date: None,
+            },
+        );
+        let sizer = Sizer::new(Box::new(SizingMethod::VolatilityTarget {
+            notional: dec!(100),
+            target_volatility: dec!(0.02),
+            periods: 4,
+        }));
+        assert_eq!(
+            sizer.apply(&mut entry(), &ctx),
+            Err("not enough price history".to_string())
+        );
+
+        // Only the last four changes count: +10%, -10%, +10%, -10%
+        let prices = [
+            dec!(0.10),
+            dec!(0.40),
+            dec!(0.44),
+            dec!(0.396),
+            dec!(0.4356),
+            dec!(0.39204),
+        ];
+        ctx.price_history.insert(
+            "m1".to_string(),
+            prices
+                .iter()
+                .map(|price| PricePoint {
+                    timestamp: Utc::now(),
+                    price: *price,
+                    volume: None,
+                })
+                .collect(),
+        );
+
+        // 10% volatility against a 2% target takes a fifth of the notional
+        let mut signal = entry();
+        sizer.apply(&mut signal, &ctx).unwrap();
+        assert_eq!(signal.metadata.sizing.as_ref().unwrap().notional, dec!(20));
+        assert_eq!(signal.size, Quantity::Shares(dec!(50)));
+
+        // The history is the first outcome's, so it cannot size the other
+        let mut signal = Signal::buy("m1", "no", Quantity::Shares(dec!(10))).with_price(dec!(0.60));
+        assert_eq!(
+            sizer.apply(&mut signal, &ctx),
+            Err("price history only covers the market's first outcome".to_string())
+        );
+    }
+
+    #[test]
+    fn test_config_parses_from_toml() {
+        let config: SizingConfig = toml::from_str(
+            r#"
+            method = "volatility_target"
+            notional = "100"
+            target_volatility = "0.02"
+            max_book_fraction = "0.5"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.method,
+            SizingMethod::VolatilityTarget {
+                notional: dec!(100),
+                target_volatility: dec!(0.02),
+                periods: 20,
+            }
+        );
+        assert_eq!(config.max_book_fraction, Some(dec!(0.5)));
+        assert_eq!(config.max_balance_fraction, None);
+    }
+}
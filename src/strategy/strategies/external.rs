@@ -0,0 +1,1237 @@
+//! External strategy.
+//!
+//! Runs a strategy in another process, written in any language, and talks to
+//! it over a JSON-lines protocol: the process is either launched as a child
+//! and spoken to over its stdin/stdout, or already running and reached
+//! through a Unix socket.
+//!
+//! # Protocol
+//!
+//! Every message is one JSON-RPC 2.0 object on its own line. CLOBster sends
+//! requests, which the strategy must answer with the same `id`, and
+//! notifications, which have no `id` and get no answer:
+//!
+//! | Method | Kind | Params | Result |
+//! |--------|------|--------|--------|
+//! | `initialize` | request | `protocol_version`, `name`, `config` | `protocol_version`, optional `metadata` and `parameters` |
+//! | `evaluate` | request | `context`, `parameters` | `signals` |
+//! | `on_signal_executed` | notification | `signal`, `success` | |
+//! | `on_order_placed` | notification | `signal_id`, `order_id` | |
+//! | `on_order_filled` | notification | `order_id`, `price`, `size` | |
+//! | `on_order_cancelled` | notification | `order_id` | |
+//! | `shutdown` | notification | | |
+//!
+//! The protocol version is [`PROTOCOL_VERSION`]; the strategy must echo it
+//! from `initialize`. Replies that arrive after their timeout are ignored.
+
+use crate::error::{Error, Result};
+use crate::state::{OrderSide, Quantity};
+use crate::strategy::{
+    ExecutionAlgo, ParameterDef, ParameterType, ParameterValue, Signal, SignalStrength, SignalType,
+    Strategy, StrategyConfig, StrategyContext, StrategyMetadata,
+};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::process::{ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Version of the JSON-lines protocol spoken with external strategies.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Evaluations in a row that may time out before the process is restarted.
+const MAX_CONSECUTIVE_TIMEOUTS: u32 = 3;
+
+/// How to reach an external strategy, set as `external` on an instance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalConfig {
+    /// Program and arguments to launch.
+    pub command: Vec<String>,
+    /// Unix socket of an already running strategy, instead of `command`.
+    pub socket: Option<PathBuf>,
+    /// Maximum time to wait for each reply, in milliseconds.
+    pub timeout_ms: u64,
+    /// Restarts attempted in a row before giving up.
+    pub max_restarts: u32,
+    /// Minimum time between restarts, in seconds.
+    pub restart_delay_secs: u64,
+}
+
+impl Default for ExternalConfig {
+    fn default() -> Self {
+        Self {
+            command: Vec::new(),
+            socket: None,
+            timeout_ms: 1000,
+            max_restarts: 5,
+            restart_delay_secs: 5,
+        }
+    }
+}
+
+/// Why a request got no result.
+#[derive(Debug)]
+enum CallError {
+    /// No reply within the timeout.
+    Timeout,
+    /// The process exited or the connection was closed.
+    Closed,
+    /// The strategy answered with an error, or with something unreadable.
+    Failed(String),
+}
+
+/// Open channel to a strategy process.
+///
+/// Lines are read and written by tasks on the runtime, so sending never
+/// blocks and replies are awaited with a timeout.
+struct Connection {
+    /// Launched process, if not reached through a socket. Killed on drop.
+    child: Option<Child>,
+    /// Lines for the writing task, `None` once closed.
+    outgoing: Option<mpsc::UnboundedSender<String>>,
+    /// Lines read from the strategy by the reading task.
+    lines: mpsc::UnboundedReceiver<String>,
+    /// Writing task; ends once the queued lines are written after closing.
+    writer: JoinHandle<()>,
+    /// Reading tasks, stopped on drop.
+    readers: Vec<JoinHandle<()>>,
+}
+
+impl Connection {
+    /// Launch `command`, logging its stderr.
+    fn spawn(name: &str, command: &[String]) -> Result<Self> {
+        let (program, args) = command
+            .split_first()
+            .ok_or_else(|| Error::config(format!("{}: external command is empty", name)))?;
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| Error::application(format!("{}: cannot run {}: {}", name, program, e)))?;
+
+        let writer = child.stdin.take().expect("stdin is piped");
+        let reader = child.stdout.take().expect("stdout is piped");
+        let mut readers = Vec::new();
+        if let Some(stderr) = child.stderr.take() {
+            let name = name.to_string();
+            readers.push(tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    tracing::info!(strategy = %name, "{}", line);
+                }
+            }));
+        }
+        Ok(Self::new(Some(child), reader, writer, readers))
+    }
+
+    /// Connect to a strategy listening on a Unix socket.
+    #[cfg(unix)]
+    async fn connect(name: &str, path: &std::path::Path) -> Result<Self> {
+        let stream = tokio::net::UnixStream::connect(path).await.map_err(|e| {
+            Error::application(format!(
+                "{}: cannot connect to {}: {}",
+                name,
+                path.display(),
+                e
+            ))
+        })?;
+        let (reader, writer) = stream.into_split();
+        Ok(Self::new(None, reader, writer, Vec::new()))
+    }
+
+    #[cfg(not(unix))]
+    async fn connect(name: &str, _path: &std::path::Path) -> Result<Self> {
+        Err(Error::config(format!(
+            "{}: external strategy sockets are only supported on Unix",
+            name
+        )))
+    }
+
+    /// Start the tasks reading lines from `reader` and writing queued lines
+    /// to `writer`.
+    fn new(
+        child: Option<Child>,
+        reader: impl AsyncRead + Unpin + Send + 'static,
+        mut writer: impl AsyncWrite + Unpin + Send + 'static,
+        mut readers: Vec<JoinHandle<()>>,
+    ) -> Self {
+        let (line_tx, lines) = mpsc::unbounded_channel();
+        readers.push(tokio::spawn(async move {
+            let mut reader = BufReader::new(reader).lines();
+            while let Ok(Some(line)) = reader.next_line().await {
+                if line_tx.send(line).is_err() {
+                    break;
+                }
+            }
+        }));
+
+        let (outgoing, mut queue) = mpsc::unbounded_channel::<String>();
+        let writer = tokio::spawn(async move {
+            while let Some(line) = queue.recv().await {
+                if writer.write_all(line.as_bytes()).await.is_err() || writer.flush().await.is_err()
+                {
+                    return;
+                }
+            }
+            let _ = writer.shutdown().await;
+        });
+
+        Self {
+            child,
+            outgoing: Some(outgoing),
+            lines,
+            writer,
+            readers,
+        }
+    }
+
+    /// Queue one message for writing.
+    fn send(&self, message: &Value) -> std::result::Result<(), CallError> {
+        let mut line = message.to_string();
+        line.push('\n');
+        self.outgoing
+            .as_ref()
+            .and_then(|outgoing| outgoing.send(line).ok())
+            .ok_or(CallError::Closed)
+    }
+
+    /// Wait for the reply to request `id`, skipping anything else.
+    async fn receive(
+        &mut self,
+        id: u64,
+        timeout: Duration,
+    ) -> std::result::Result<Value, CallError> {
+        let reply = async {
+            loop {
+                let line = self.lines.recv().await.ok_or(CallError::Closed)?;
+                let Ok(mut reply) = serde_json::from_str::<Value>(&line) else {
+                    tracing::debug!("Ignoring non-JSON line from external strategy: {}", line);
+                    continue;
+                };
+                if reply.get("id").and_then(Value::as_u64) != Some(id) {
+                    // A late reply to a request that already timed out
+                    continue;
+                }
+                if let Some(error) = reply.get("error") {
+                    let message = error
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| error.to_string());
+                    return Err(CallError::Failed(message));
+                }
+                return Ok(reply.get_mut("result").map(Value::take).unwrap_or_default());
+            }
+        };
+        tokio::time::timeout(timeout, reply)
+            .await
+            .unwrap_or(Err(CallError::Timeout))
+    }
+
+    /// Exit status of the process, if it has exited.
+    fn exit_status(&mut self) -> Option<ExitStatus> {
+        self.child
+            .as_mut()
+            .and_then(|c| c.try_wait().ok().flatten())
+    }
+
+    /// Write the queued messages, then wait up to `timeout` for the process
+    /// to exit on its own.
+    async fn close(mut self, timeout: Duration) {
+        self.outgoing = None;
+        let _ = tokio::time::timeout(timeout, async {
+            let _ = (&mut self.writer).await;
+            if let Some(child) = &mut self.child {
+                let _ = child.wait().await;
+            }
+        })
+        .await;
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        self.writer.abort();
+        for reader in &self.readers {
+            reader.abort();
+        }
+    }
+}
+
+/// Wait for `future` from the synchronous [`Strategy`] callbacks.
+///
+/// The worker thread is handed over to blocking first, so the runtime keeps
+/// running its other tasks while the strategy answers. This needs the
+/// multi-threaded runtime; on any other, `None` is returned without running
+/// `future`.
+fn block_on<F: Future>(future: F) -> Option<F::Output> {
+    let handle = tokio::runtime::Handle::current();
+    if handle.runtime_flavor() != tokio::runtime::RuntimeFlavor::MultiThread {
+        return None;
+    }
+    Some(tokio::task::block_in_place(|| handle.block_on(future)))
+}
+
+/// Strategy implemented by another process.
+///
+/// The process is started when the strategy is initialized. If it exits or
+/// stops answering, it is restarted before a later evaluation, at most
+/// `max_restarts` times in a row and no more often than every
+/// `restart_delay_secs`.
+pub struct ExternalStrategy {
+    /// Strategy name.
+    name: String,
+    /// How to reach the process.
+    settings: ExternalConfig,
+    /// Instance configuration, sent again on every restart.
+    config: StrategyConfig,
+    /// Open connection, if the process is running.
+    connection: Option<Connection>,
+    /// Id of the last request.
+    request_id: u64,
+    /// Metadata reported by the process.
+    metadata: StrategyMetadata,
+    /// Parameters declared by the process.
+    definitions: HashMap<String, ParameterDef>,
+    /// Current parameter values.
+    values: HashMap<String, ParameterValue>,
+    /// Restarts since the last successful evaluation.
+    restarts: u32,
+    /// When the process was last started.
+    started: Option<Instant>,
+    /// Evaluations in a row that timed out.
+    timeouts: u32,
+}
+
+impl std::fmt::Debug for ExternalStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalStrategy")
+            .field("name", &self.name)
+            .field("settings", &self.settings)
+            .field("running", &self.connection.is_some())
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ExternalStrategy {
+    /// Create an external strategy. Nothing is started until it is
+    /// initialized.
+    pub fn new(name: impl Into<String>, settings: ExternalConfig) -> Self {
+        let name = name.into();
+        Self {
+            metadata: default_metadata(&name),
+            name,
+            settings,
+            config: StrategyConfig::default(),
+            connection: None,
+            request_id: 0,
+            definitions: HashMap::new(),
+            values: HashMap::new(),
+            restarts: 0,
+            started: None,
+            timeouts: 0,
+        }
+    }
+
+    /// Whether the process is running.
+    pub fn is_running(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    /// Start the process and send it `initialize`.
+    async fn start(&mut self) -> Result<()> {
+        self.connection = None;
+        self.started = Some(Instant::now());
+        self.timeouts = 0;
+        let connection = match &self.settings.socket {
+            Some(path) => Connection::connect(&self.name, path).await?,
+            None => Connection::spawn(&self.name, &self.settings.command)?,
+        };
+        self.connection = Some(connection);
+        let result = self.handshake().await;
+        if result.is_err() {
+            self.connection = None;
+        }
+        result
+    }
+
+    /// Exchange `initialize` and read the declared parameters.
+    async fn handshake(&mut self) -> Result<()> {
+        let params = json!({
+            "protocol_version": PROTOCOL_VERSION,
+            "name": self.name,
+            "config": self.config,
+        });
+        let result = self
+            .request("initialize", params)
+            .await
+            .map_err(|e| self.call_error("initialize", e))?;
+        let reply: InitializeReply = serde_json::from_value(result)
+            .map_err(|e| Error::application(format!("{}: initialize: {}", self.name, e)))?;
+        if reply.protocol_version != PROTOCOL_VERSION {
+            return Err(Error::application(format!(
+                "{}: speaks protocol version {}, expected {}",
+                self.name, reply.protocol_version, PROTOCOL_VERSION
+            )));
+        }
+
+        if let Some(metadata) = reply.metadata {
+            self.metadata = StrategyMetadata {
+                name: self.name.clone(),
+                description: metadata.description,
+                version: metadata.version.unwrap_or_else(|| "1.0.0".to_string()),
+                author: metadata.author,
+                tags: metadata.tags,
+            };
+        }
+        self.definitions = reply
+            .parameters
+            .into_iter()
+            .map(|decl| {
+                let def = decl
+                    .into_def()
+                    .map_err(|e| Error::invalid_input(format!("{}: {}", self.name, e)))?;
+                Ok((def.name.clone(), def))
+            })
+            .collect::<Result<_>>()?;
+        self.apply_configured();
+        Ok(())
+    }
+
+    /// Fill in parameter values: the current value if still valid, else the
+    /// configured value, else the default.
+    fn apply_configured(&mut self) {
+        let current = std::mem::take(&mut self.values);
+        for (name, def) in &self.definitions {
+            let value = current
+                .get(name)
+                .and_then(|v| def.validate(v.clone()).ok())
+                .or_else(|| {
+                    self.config
+                        .parameters
+                        .get(name)
+                        .and_then(|v| def.from_json(v).ok())
+                })
+                .unwrap_or_else(|| def.default.clone());
+            self.values.insert(name.clone(), value);
+        }
+    }
+
+    /// Send a request and wait for its result.
+    async fn request(
+        &mut self,
+        method: &str,
+        params: Value,
+    ) -> std::result::Result<Value, CallError> {
+        let connection = self.connection.as_mut().ok_or(CallError::Closed)?;
+        self.request_id += 1;
+        let id = self.request_id;
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        connection.send(&message)?;
+        connection
+            .receive(id, Duration::from_millis(self.settings.timeout_ms))
+            .await
+    }
+
+    /// Send a notification, dropping the connection if it is closed.
+    fn notify(&mut self, method: &str, params: Value) {
+        let Some(connection) = &self.connection else {
+            return;
+        };
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        if connection.send(&message).is_err() {
+            self.lost();
+        }
+    }
+
+    fn call_error(&self, method: &str, error: CallError) -> Error {
+        match error {
+            CallError::Timeout => Error::application(format!(
+                "{}: no reply to {} within {} ms",
+                self.name, method, self.settings.timeout_ms
+            )),
+            CallError::Closed => {
+                Error::application(format!("{}: process exited during {}", self.name, method))
+            }
+            CallError::Failed(message) => {
+                Error::application(format!("{}: {}: {}", self.name, method, message))
+            }
+        }
+    }
+
+    /// Drop a connection that closed or stopped answering.
+    fn lost(&mut self) {
+        let Some(mut connection) = self.connection.take() else {
+            return;
+        };
+        match connection.exit_status() {
+            Some(status) => tracing::warn!("External strategy {} exited ({})", self.name, status),
+            None => tracing::warn!("External strategy {} disconnected", self.name),
+        }
+    }
+
+    /// Restart the process if it is not running and a restart is due.
+    async fn ensure_running(&mut self) -> bool {
+        if self.connection.is_some() {
+            return true;
+        }
+        if self.restarts >= self.settings.max_restarts {
+            return false;
+        }
+        let delay = Duration::from_secs(self.settings.restart_delay_secs);
+        if self.started.is_some_and(|t| t.elapsed() < delay) {
+            return false;
+        }
+
+        self.restarts += 1;
+        match self.start().await {
+            Ok(()) => {
+                tracing::info!(strategy = %self.name, "External strategy restarted");
+                true
+            }
+            Err(e) => {
+                tracing::warn!("{}", e);
+                if self.restarts >= self.settings.max_restarts {
+                    tracing::error!(
+                        "External strategy {} failed {} restarts, giving up",
+                        self.name,
+                        self.restarts
+                    );
+                }
+                false
+            }
+        }
+    }
+
+    /// Send `evaluate` and convert the signals in its result.
+    async fn evaluate_remote(&mut self, ctx: &StrategyContext) -> Vec<Signal> {
+        if !self.ensure_running().await {
+            return Vec::new();
+        }
+
+        let parameters: serde_json::Map<String, Value> = self
+            .values
+            .iter()
+            .map(|(name, value)| (name.clone(), value.to_json()))
+            .collect();
+        let params = json!({ "context": ctx, "parameters": parameters });
+        match self.request("evaluate", params).await {
+            Ok(result) => {
+                self.timeouts = 0;
+                self.restarts = 0;
+                self.signals(result)
+            }
+            Err(CallError::Timeout) => {
+                self.timeouts += 1;
+                tracing::warn!("{}", self.call_error("evaluate", CallError::Timeout));
+                if self.timeouts >= MAX_CONSECUTIVE_TIMEOUTS {
+                    tracing::warn!(
+                        "External strategy {} is not responding, restarting it",
+                        self.name
+                    );
+                    self.lost();
+                }
+                Vec::new()
+            }
+            Err(CallError::Closed) => {
+                self.lost();
+                Vec::new()
+            }
+            Err(e) => {
+                tracing::warn!("{}", self.call_error("evaluate", e));
+                Vec::new()
+            }
+        }
+    }
+
+    /// Convert the signals in an `evaluate` result, skipping invalid ones.
+    fn signals(&self, result: Value) -> Vec<Signal> {
+        let items = match result.get("signals") {
+            Some(Value::Array(items)) => items.clone(),
+            Some(Value::Null) | None => return Vec::new(),
+            Some(_) => {
+                tracing::warn!("{}: evaluate must return an array of signals", self.name);
+                return Vec::new();
+            }
+        };
+        items
+            .into_iter()
+            .filter_map(|item| match serde_json::from_value::<SignalReply>(item) {
+                Ok(reply) => Some(reply.into_signal().with_strategy(self.name.clone())),
+                Err(e) => {
+                    tracing::warn!("{}: ignoring an invalid signal: {}", self.name, e);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Strategy for ExternalStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn metadata(&self) -> StrategyMetadata {
+        self.metadata.clone()
+    }
+
+    async fn initialize(&mut self, config: &StrategyConfig) -> Result<()> {
+        if let Some(settings) = &config.external
+            && *settings != self.settings
+        {
+            self.settings = settings.clone();
+            self.connection = None;
+        }
+        self.config = config.clone();
+        self.values.clear();
+        self.restarts = 0;
+        if self.connection.is_some() {
+            let result = self.handshake().await;
+            if result.is_err() {
+                self.connection = None;
+            }
+            result
+        } else {
+            self.start().await
+        }
+    }
+
+    fn evaluate(&mut self, ctx: &StrategyContext) -> Vec<Signal> {
+        block_on(self.evaluate_remote(ctx)).unwrap_or_else(|| {
+            tracing::error!(
+                "External strategy {} needs the multi-threaded runtime to evaluate",
+                self.name
+            );
+            Vec::new()
+        })
+    }
+
+    fn on_signal_executed(&mut self, signal: &Signal, success: bool) {
+        let signal = json!({
+            "id": signal.id,
+            "market_id": signal.market_id,
+            "token_id": signal.token_id,
+            "side": match signal.side {
+                OrderSide::Buy => "buy",
+                OrderSide::Sell => "sell",
+            },
+            "size": signal.size.amount(),
+            "notional": matches!(signal.size, Quantity::Notional(_)),
+            "price": signal.price,
+        });
+        self.notify(
+            "on_signal_executed",
+            json!({ "signal": signal, "success": success }),
+        );
+    }
+
+    fn on_order_placed(&mut self, signal_id: &str, order_id: &str) {
+        self.notify(
+            "on_order_placed",
+            json!({ "signal_id": signal_id, "order_id": order_id }),
+        );
+    }
+
+    fn on_order_filled(&mut self, order_id: &str, filled_price: Decimal, filled_size: Decimal) {
+        self.notify(
+            "on_order_filled",
+            json!({ "order_id": order_id, "price": filled_price, "size": filled_size }),
+        );
+    }
+
+    fn on_order_cancelled(&mut self, order_id: &str) {
+        self.notify("on_order_cancelled", json!({ "order_id": order_id }));
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        self.notify("shutdown", Value::Null);
+        if let Some(connection) = self.connection.take() {
+            connection
+                .close(Duration::from_millis(self.settings.timeout_ms))
+                .await;
+        }
+        Ok(())
+    }
+
+    fn parameters(&self) -> HashMap<String, ParameterDef> {
+        self.definitions.clone()
+    }
+
+    fn set_parameter(&mut self, name: &str, value: ParameterValue) -> Result<()> {
+        let def = self
+            .definitions
+            .get(name)
+            .ok_or_else(|| Error::invalid_input(format!("Unknown parameter: {}", name)))?;
+        let value = def.validate(value)?;
+        self.values.insert(name.to_string(), value);
+        Ok(())
+    }
+}
+
+fn default_metadata(name: &str) -> StrategyMetadata {
+    StrategyMetadata {
+        name: name.to_string(),
+        description: String::new(),
+        version: "1.0.0".to_string(),
+        author: None,
+        tags: Vec::new(),
+    }
+}
+
+/// Result of `initialize`.
+#[derive(Debug, Deserialize)]
+struct InitializeReply {
+    protocol_version: u32,
+    #[serde(default)]
+    metadata: Option<MetadataReply>,
+    #[serde(default)]
+    parameters: Vec<ParameterDecl>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MetadataReply {
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    author: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// A parameter declared by the strategy, in the same shape as the
+/// declarations of script strategies.
+#[derive(Debug, Deserialize)]
+struct ParameterDecl {
+    name: String,
+    value: ParameterValue,
+    #[serde(default, rename = "type")]
+    param_type: Option<String>,
+    #[serde(default)]
+    min: Option<ParameterValue>,
+    #[serde(default)]
+    max: Option<ParameterValue>,
+    #[serde(default)]
+    allowed: Option<Vec<ParameterValue>>,
+    #[serde(default)]
+    description: String,
+}
+
+impl ParameterDecl {
+    /// Convert to a definition, checking the bounds and the default.
+    fn into_def(self) -> Result<ParameterDef> {
+        let param_type = match &self.param_type {
+            Some(t) => t.parse()?,
+            None => match self.value {
+                ParameterValue::Integer(_) => ParameterType::Integer,
+                ParameterValue::Float(_) => ParameterType::Float,
+                ParameterValue::Decimal(_) => ParameterType::Decimal,
+                ParameterValue::Boolean(_) => ParameterType::Boolean,
+                ParameterValue::String(_) => ParameterType::String,
+            },
+        };
+        let mut def = ParameterDef {
+            name: self.name,
+            description: self.description,
+            param_type,
+            default: self.value.clone(),
+            min: None,
+            max: None,
+            allowed_values: None,
+        };
+        let min = self.min.map(|v| def.validate(v)).transpose()?;
+        let max = self.max.map(|v| def.validate(v)).transpose()?;
+        let allowed = self
+            .allowed
+            .map(|values| values.into_iter().map(|v| def.validate(v)).collect())
+            .transpose()?;
+        def.min = min;
+        def.max = max;
+        def.allowed_values = allowed;
+        def.default = def.validate(self.value)?;
+        Ok(def)
+    }
+}
+
+/// A decimal sent either as a JSON number or, to keep its precision, as a
+/// string.
+#[derive(Debug, Clone, Copy)]
+struct Amount(Decimal);
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        use serde::de::Error as _;
+        let text = match Value::deserialize(deserializer)? {
+            Value::Number(n) => n.to_string(),
+            Value::String(s) => s,
+            other => {
+                return Err(D::Error::custom(format!(
+                    "expected a number, got {}",
+                    other
+                )));
+            }
+        };
+        text.parse()
+            .or_else(|_| Decimal::from_scientific(&text))
+            .map(Amount)
+            .map_err(|_| D::Error::custom(format!("'{}' is not a number", text)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SideReply {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(tag = "algo", rename_all = "snake_case")]
+enum ExecutionReply {
+    Immediate,
+    Twap {
+        duration_secs: u64,
+        slices: u32,
+    },
+    Iceberg {
+        clip_size: Amount,
+        timeout_secs: u64,
+    },
+    SlippageCapped {
+        max_slippage: Amount,
+        timeout_secs: u64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum KindReply {
+    Entry,
+    Exit,
+    Add,
+    Reduce,
+    Rebalance,
+    TakeProfit,
+    StopLoss,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum StrengthReply {
+    Weak,
+    Medium,
+    Strong,
+    VeryStrong,
+}
+
+/// A signal returned by `evaluate`, using the vocabulary of script
+/// strategies.
+#[derive(Debug, Deserialize)]
+struct SignalReply {
+    market_id: String,
+    token_id: String,
+    side: SideReply,
+    size: Amount,
+    #[serde(default)]
+    notional: bool,
+    #[serde(default)]
+    price: Option<Amount>,
+    #[serde(default)]
+    market_order: bool,
+    #[serde(default)]
+    kind: Option<KindReply>,
+    #[serde(default)]
+    strength: Option<StrengthReply>,
+    #[serde(default)]
+    ttl_secs: Option<u64>,
+    #[serde(default)]
+    reason: Option<String>,
+    #[serde(default)]
+    stop_loss: Option<Amount>,
+    #[serde(default)]
+    take_profit: Option<Amount>,
+    #[serde(default)]
+    confidence: Option<f64>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    execution: Option<ExecutionReply>,
+}
+
+impl SignalReply {
+    fn into_signal(self) -> Signal {
+        let size = match self.notional {
+            true => Quantity::Notional(self.size.0),
+            false => Quantity::Shares(self.size.0),
+        };
+        let mut signal = match self.side {
+            SideReply::Buy => Signal::buy(self.market_id, self.token_id, size),
+            SideReply::Sell => Signal::sell(self.market_id, self.token_id, size),
+        };
+        if let Some(Amount(price)) = self.price {
+            signal = signal.with_price(price);
+        }
+        if self.market_order {
+            signal = signal.with_order_type(crate::state::OrderType::Market);
+        }
+        if let Some(kind) = self.kind {
+            signal = signal.with_type(match kind {
+                KindReply::Entry => SignalType::Entry,
+                KindReply::Exit => SignalType::Exit,
+                KindReply::Add => SignalType::AddToPosition,
+                KindReply::Reduce => SignalType::ReducePosition,
+                KindReply::Rebalance => SignalType::Rebalance,
+                KindReply::TakeProfit => SignalType::TakeProfit,
+                KindReply::StopLoss => SignalType::StopLoss,
+            });
+        }
+        if let Some(strength) = self.strength {
+            signal = signal.with_strength(match strength {
+                StrengthReply::Weak => SignalStrength::Weak,
+                StrengthReply::Medium => SignalStrength::Medium,
+                StrengthReply::Strong => SignalStrength::Strong,
+                StrengthReply::VeryStrong => SignalStrength::VeryStrong,
+            });
+        }
+        if let Some(ttl) = self.ttl_secs {
+            signal = signal.with_ttl(ttl);
+        }
+        if let Some(reason) = self.reason {
+            signal = signal.with_reason(reason);
+        }
+        if let Some(Amount(price)) = self.stop_loss {
+            signal = signal.with_stop_loss(price);
+        }
+        if let Some(Amount(price)) = self.take_profit {
+            signal = signal.with_take_profit(price);
+        }
+        if let Some(execution) = self.execution {
+            signal = signal.with_execution(match execution {
+                ExecutionReply::Immediate => ExecutionAlgo::Immediate,
+                ExecutionReply::Twap {
+                    duration_secs,
+                    slices,
+                } => ExecutionAlgo::Twap {
+                    duration_secs,
+                    slices,
+                },
+                ExecutionReply::Iceberg {
+                    clip_size: Amount(clip_size),
+                    timeout_secs,
+                } => ExecutionAlgo::Iceberg {
+                    clip_size,
+                    timeout_secs,
+                },
+                ExecutionReply::SlippageCapped {
+                    max_slippage: Amount(max_slippage),
+                    timeout_secs,
+                } => ExecutionAlgo::SlippageCapped {
+                    max_slippage,
+                    timeout_secs,
+                },
+            });
+        }
+        signal.metadata.confidence = self.confidence;
+        signal.metadata.tags = self.tags;
+        signal
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+    use std::sync::{Arc, Mutex};
+
+    /// Listen on a fresh socket, returning its path and the listener.
+    fn listen() -> (PathBuf, UnixListener) {
+        let dir = std::env::temp_dir().join(format!("clobster-external-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("strategy.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+        (path, listener)
+    }
+
+    /// Accept one connection and answer each request with the fields
+    /// `respond` returns for its method, or not at all for `None`, until the
+    /// connection closes.
+    fn respond(listener: UnixListener, respond: impl Fn(&str) -> Option<Value> + Send + 'static) {
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut writer = stream.try_clone().unwrap();
+            for line in BufReader::new(stream).lines().map_while(|l| l.ok()) {
+                let message: Value = serde_json::from_str(&line).unwrap();
+                let (Some(id), Some(method)) = (message.get("id"), message["method"].as_str())
+                else {
+                    continue;
+                };
+                if let Some(Value::Object(mut reply)) = respond(method) {
+                    reply.insert("jsonrpc".to_string(), json!("2.0"));
+                    reply.insert("id".to_string(), id.clone());
+                    writeln!(writer, "{}", Value::Object(reply)).unwrap();
+                }
+            }
+        });
+    }
+
+    fn socket_strategy(
+        path: &std::path::Path,
+        timeout_ms: u64,
+    ) -> (ExternalStrategy, StrategyConfig) {
+        let settings = ExternalConfig {
+            socket: Some(path.to_path_buf()),
+            timeout_ms,
+            max_restarts: 0,
+            ..Default::default()
+        };
+        let config = StrategyConfig {
+            external: Some(settings.clone()),
+            ..Default::default()
+        };
+        (ExternalStrategy::new("remote", settings), config)
+    }
+
+    /// Answer requests on one connection until it closes or `evaluations`
+    /// evaluations were answered, returning the notifications received.
+    fn serve(listener: &UnixListener, evaluations: usize) -> Vec<String> {
+        let (stream, _) = listener.accept().unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut notifications = Vec::new();
+        let mut answered = 0;
+        for line in BufReader::new(stream).lines() {
+            let message: Value = serde_json::from_str(&line.unwrap()).unwrap();
+            let method = message["method"].as_str().unwrap().to_string();
+            let Some(id) = message.get("id") else {
+                notifications.push(method);
+                continue;
+            };
+            let result = match method.as_str() {
+                "initialize" => json!({
+                    "protocol_version": PROTOCOL_VERSION,
+                    "metadata": { "description": "Test strategy" },
+                    "parameters": [{ "name": "size", "value": 5, "min": 1, "max": 10 }],
+                }),
+                "evaluate" => {
+                    let market = message["params"]["context"]["markets"]["m"]["condition_id"]
+                        .as_str()
+                        .unwrap()
+                        .to_string();
+                    let size = message["params"]["parameters"]["size"].clone();
+                    json!({ "signals": [
+                        { "market_id": market, "token_id": "yes", "side": "buy",
+                          "size": size, "price": "0.4", "kind": "add" },
+                        { "market_id": market, "side": "sideways" },
+                    ]})
+                }
+                _ => unreachable!(),
+            };
+            let reply = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+            writeln!(writer, "{}", reply).unwrap();
+            if method == "evaluate" {
+                answered += 1;
+                if answered == evaluations {
+                    break;
+                }
+            }
+        }
+        notifications
+    }
+
+    fn context() -> StrategyContext {
+        let mut ctx = StrategyContext::new();
+        ctx.markets.insert(
+            "m".to_string(),
+            crate::strategy::MarketSnapshot {
+                condition_id: "m".to_string(),
+                question: "Will it rain?".to_string(),
+                status: crate::state::MarketStatus::Active,
+                token_ids: vec!["yes".to_string(), "no".to_string()],
+                token_names: vec!["Yes".to_string(), "No".to_string()],
+                token_prices: vec![Decimal::new(4, 1), Decimal::new(6, 1)],
+                volume_24h: Decimal::ZERO,
+                liquidity: Decimal::ZERO,
+                spread: None,
+                end_date: None,
+            },
+        );
+        ctx
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_external_strategy_round_trip_and_restart() {
+        let (path, listener) = listen();
+        let server = std::thread::spawn(move || {
+            // The first connection closes after one evaluation
+            let first = serve(&listener, 1);
+            let second = serve(&listener, usize::MAX);
+            (first, second)
+        });
+
+        let settings = ExternalConfig {
+            socket: Some(path.clone()),
+            restart_delay_secs: 0,
+            ..Default::default()
+        };
+        let config = StrategyConfig {
+            external: Some(settings.clone()),
+            parameters: HashMap::from([("size".to_string(), json!(3))]),
+            ..Default::default()
+        };
+        let mut strategy = ExternalStrategy::new("remote", settings);
+        strategy.initialize(&config).await.unwrap();
+        assert_eq!(strategy.metadata().description, "Test strategy");
+        assert_eq!(
+            strategy.parameters()["size"].max,
+            Some(ParameterValue::Integer(10))
+        );
+
+        let signals = strategy.evaluate(&context());
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].market_id, "m");
+        assert_eq!(signals[0].size, Quantity::Shares(Decimal::from(3)));
+        assert_eq!(signals[0].signal_type, SignalType::AddToPosition);
+        assert_eq!(signals[0].strategy_name, "remote");
+
+        // The connection closed: detected on the next evaluation, restarted
+        // on the one after
+        assert!(strategy.evaluate(&context()).is_empty());
+        assert!(!strategy.is_running());
+        assert!(
+            strategy
+                .set_parameter("size", ParameterValue::Integer(11))
+                .is_err()
+        );
+        strategy
+            .set_parameter("size", ParameterValue::Integer(7))
+            .unwrap();
+        let signals = strategy.evaluate(&context());
+        assert_eq!(signals[0].size, Quantity::Shares(Decimal::from(7)));
+
+        strategy.on_order_filled("o1", Decimal::ONE, Decimal::ONE);
+        strategy.shutdown().await.unwrap();
+        let (first, second) = server.join().unwrap();
+        assert!(first.is_empty());
+        assert_eq!(second, vec!["on_order_filled", "shutdown"]);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_external_strategy_rejects_other_protocol_version() {
+        let (path, listener) = listen();
+        respond(listener, |_| {
+            Some(json!({ "result": { "protocol_version": PROTOCOL_VERSION + 1 } }))
+        });
+
+        let (mut strategy, config) = socket_strategy(&path, 1000);
+        let error = strategy.initialize(&config).await.unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains(&format!("speaks protocol version {}", PROTOCOL_VERSION + 1)),
+            "{}",
+            error
+        );
+        assert!(!strategy.is_running());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_external_strategy_reports_error_replies() {
+        let (path, listener) = listen();
+        respond(listener, |method| match method {
+            "initialize" => Some(json!({ "result": { "protocol_version": PROTOCOL_VERSION } })),
+            _ => Some(json!({ "error": { "code": -32000, "message": "model not loaded" } })),
+        });
+
+        let (mut strategy, config) = socket_strategy(&path, 1000);
+        strategy.initialize(&config).await.unwrap();
+        // An error reply is logged; the connection stays up
+        assert!(strategy.evaluate(&context()).is_empty());
+        assert!(strategy.is_running());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_external_strategy_skips_evaluation_on_current_thread_runtime() {
+        let (path, listener) = listen();
+        respond(listener, |method| match method {
+            "initialize" => Some(json!({ "result": { "protocol_version": PROTOCOL_VERSION } })),
+            _ => Some(json!({ "result": { "signals": [] } })),
+        });
+
+        let (mut strategy, config) = socket_strategy(&path, 1000);
+        strategy.initialize(&config).await.unwrap();
+        // Blocking here would panic; the evaluation is skipped instead
+        assert!(strategy.evaluate(&context()).is_empty());
+        assert!(strategy.is_running());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_external_strategy_timeouts_do_not_block_the_runtime() {
+        let (path, listener) = listen();
+        respond(listener, |method| match method {
+            "initialize" => Some(json!({ "result": { "protocol_version": PROTOCOL_VERSION } })),
+            _ => None,
+        });
+
+        let (mut strategy, config) = socket_strategy(&path, 200);
+        strategy.initialize(&config).await.unwrap();
+
+        // Other tasks keep running while an evaluation waits for its reply
+        let ticked = Arc::new(Mutex::new(None));
+        let tick = ticked.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            *tick.lock().unwrap() = Some(Instant::now());
+        });
+        let started = Instant::now();
+        assert!(strategy.evaluate(&context()).is_empty());
+        let finished = Instant::now();
+        assert!(finished - started >= Duration::from_millis(200));
+        assert!(ticked.lock().unwrap().is_some_and(|t| t < finished));
+
+        // Unanswered evaluations eventually drop the connection
+        for _ in 1..MAX_CONSECUTIVE_TIMEOUTS {
+            assert!(strategy.is_running());
+            strategy.evaluate(&context());
+        }
+        assert!(!strategy.is_running());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_external_strategy_reports_crashed_process() {
+        let settings = ExternalConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()],
+            ..Default::default()
+        };
+        let config = StrategyConfig {
+            external: Some(settings.clone()),
+            ..Default::default()
+        };
+        let mut strategy = ExternalStrategy::new("crashing", settings);
+        let error = strategy.initialize(&config).await.unwrap_err();
+        assert!(error.to_string().contains("process exited"), "{}", error);
+        assert!(!strategy.is_running());
+    }
+}
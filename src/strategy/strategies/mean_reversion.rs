@@ -3,16 +3,20 @@
 //! Buys when price is below the moving average and sells when above.
 
 use crate::error::Result;
-use crate::state::OrderSide;
+use crate::state::{OrderSide, Quantity};
 use crate::strategy::{
     ParameterDef, ParameterType, ParameterValue, Signal, SignalStrength, SignalType, Strategy,
-    StrategyConfig, StrategyContext, StrategyMetadata,
+    StrategyConfig, StrategyContext, StrategyMetadata, StrategyState,
 };
 use async_trait::async_trait;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Version of the state snapshot layout (entered markets with the moving average at entry).
+const STATE_VERSION: u32 = 1;
+
 /// Mean reversion strategy.
 ///
 /// This strategy looks for markets where the current price has deviated
@@ -33,7 +37,7 @@ pub struct MeanReversionStrategy {
     entered_markets: HashMap<String, EntryInfo>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct EntryInfo {
     #[allow(dead_code)]
     entry_price: Decimal,
@@ -211,17 +215,18 @@ impl Strategy for MeanReversionStrategy {
                         OrderSide::Sell => OrderSide::Buy,
                     };
 
+                    // Close what is held rather than the entry notional
+                    let size = ctx
+                        .get_position(&token_id)
+                        .map(|p| Quantity::Shares(p.size))
+                        .unwrap_or(Quantity::Notional(self.position_size));
                     let signal = match exit_side {
-                        OrderSide::Buy => Signal::buy(
-                            market.condition_id.clone(),
-                            token_id.clone(),
-                            self.position_size,
-                        ),
-                        OrderSide::Sell => Signal::sell(
-                            market.condition_id.clone(),
-                            token_id.clone(),
-                            self.position_size,
-                        ),
+                        OrderSide::Buy => {
+                            Signal::buy(market.condition_id.clone(), token_id.clone(), size)
+                        }
+                        OrderSide::Sell => {
+                            Signal::sell(market.condition_id.clone(), token_id.clone(), size)
+                        }
                     }
                     .with_strategy(self.name())
                     .with_type(SignalType::Exit)
@@ -268,13 +273,13 @@ impl Strategy for MeanReversionStrategy {
                         Signal::buy(
                             market.condition_id.clone(),
                             token_id.clone(),
-                            self.position_size,
+                            Quantity::Notional(self.position_size),
                         )
                     } else {
                         Signal::sell(
                             market.condition_id.clone(),
                             token_id.clone(),
-                            self.position_size,
+                            Quantity::Notional(self.position_size),
                         )
                     };
 
@@ -330,6 +335,19 @@ impl Strategy for MeanReversionStrategy {
         }
     }
 
+    fn state_version(&self) -> u32 {
+        STATE_VERSION
+    }
+
+    fn state(&self) -> Result<Option<StrategyState>> {
+        StrategyState::new(STATE_VERSION, &self.entered_markets).map(Some)
+    }
+
+    fn restore_state(&mut self, state: StrategyState) -> Result<()> {
+        self.entered_markets = state.decode()?;
+        Ok(())
+    }
+
     fn parameters(&self) -> HashMap<String, ParameterDef> {
         let mut params = HashMap::new();
 
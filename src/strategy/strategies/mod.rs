@@ -1,9 +1,51 @@
 //! Built-in example strategies.
 
+mod external;
 mod mean_reversion;
 mod momentum;
+mod script;
 mod spread;
 
+pub use external::{ExternalConfig, ExternalStrategy, PROTOCOL_VERSION};
 pub use mean_reversion::MeanReversionStrategy;
 pub use momentum::MomentumStrategy;
+pub use script::{ScriptLimits, ScriptStrategy};
 pub use spread::SpreadStrategy;
+
+use super::{Strategy, StrategyConfig};
+
+/// Names of the built-in strategies, as accepted by [`builtin`].
+pub const BUILTIN_STRATEGIES: &[&str] = &["momentum", "mean_reversion", "spread"];
+
+/// Create a built-in strategy with default parameters by name.
+pub fn builtin(name: &str) -> Option<Box<dyn Strategy>> {
+    match name {
+        "momentum" => Some(Box::new(MomentumStrategy::new())),
+        "mean_reversion" => Some(Box::new(MeanReversionStrategy::new())),
+        "spread" => Some(Box::new(SpreadStrategy::new())),
+        _ => None,
+    }
+}
+
+/// Create the strategy for a configured instance: a [`ScriptStrategy`] when
+/// the instance names a script, an [`ExternalStrategy`] when it sets
+/// `external`, otherwise the built-in strategy `name`.
+pub fn create(
+    name: &str,
+    config: &StrategyConfig,
+    limits: &ScriptLimits,
+) -> crate::Result<Box<dyn Strategy>> {
+    if let Some(path) = &config.script {
+        return Ok(Box::new(ScriptStrategy::load(name, path, limits)?));
+    }
+    if let Some(external) = &config.external {
+        return Ok(Box::new(ExternalStrategy::new(name, external.clone())));
+    }
+    builtin(name).ok_or_else(|| {
+        crate::Error::invalid_input(format!(
+            "Unknown strategy '{}' (expected one of {}, a script or an external process)",
+            name,
+            BUILTIN_STRATEGIES.join(", ")
+        ))
+    })
+}
@@ -1,18 +1,25 @@
 //! Momentum strategy.
 //!
 //! Follows price trends, buying when price is rising and selling when falling.
+//! Entries carry stop-loss and take-profit levels, which the engine watches
+//! as brackets and exits on; the strategy forgets a position once its exit
+//! is executed.
 
 use crate::error::Result;
-use crate::state::OrderSide;
+use crate::state::{OrderSide, Quantity};
 use crate::strategy::{
     ParameterDef, ParameterType, ParameterValue, Signal, SignalStrength, SignalType, Strategy,
-    StrategyConfig, StrategyContext, StrategyMetadata,
+    StrategyConfig, StrategyContext, StrategyMetadata, StrategyState,
 };
 use async_trait::async_trait;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Version of the state snapshot layout (open positions with their stop-loss and take-profit levels).
+const STATE_VERSION: u32 = 1;
+
 /// Momentum strategy.
 ///
 /// This strategy identifies markets with strong price momentum and
@@ -37,7 +44,7 @@ pub struct MomentumStrategy {
     positions: HashMap<String, MomentumPosition>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct MomentumPosition {
     entry_price: Decimal,
     side: OrderSide,
@@ -106,34 +113,6 @@ impl MomentumStrategy {
 
         Some((short_ema - long_ema) / long_ema)
     }
-
-    fn check_stop_loss_take_profit(
-        &self,
-        position: &MomentumPosition,
-        current_price: Decimal,
-    ) -> Option<SignalType> {
-        match position.side {
-            OrderSide::Buy => {
-                if current_price <= position.stop_loss {
-                    Some(SignalType::StopLoss)
-                } else if current_price >= position.take_profit {
-                    Some(SignalType::TakeProfit)
-                } else {
-                    None
-                }
-            }
-            OrderSide::Sell => {
-                // For short positions, stop loss is above entry, take profit is below
-                if current_price >= position.stop_loss {
-                    Some(SignalType::StopLoss)
-                } else if current_price <= position.take_profit {
-                    Some(SignalType::TakeProfit)
-                } else {
-                    None
-                }
-            }
-        }
-    }
 }
 
 impl Default for MomentumStrategy {
@@ -225,56 +204,6 @@ impl Strategy for MomentumStrategy {
 
             let token_id = market.token_ids.first().cloned().unwrap_or_default();
 
-            // Check existing position for stop loss / take profit
-            if let Some(position) = self.positions.get(&market.condition_id) {
-                if let Some(exit_type) = self.check_stop_loss_take_profit(position, current_price) {
-                    let strength = match exit_type {
-                        SignalType::StopLoss => SignalStrength::VeryStrong,
-                        SignalType::TakeProfit => SignalStrength::Strong,
-                        _ => SignalStrength::Medium,
-                    };
-
-                    let reason = match exit_type {
-                        SignalType::StopLoss => format!(
-                            "Stop loss triggered at {:.4} (entry: {:.4})",
-                            current_price, position.entry_price
-                        ),
-                        SignalType::TakeProfit => format!(
-                            "Take profit triggered at {:.4} (entry: {:.4})",
-                            current_price, position.entry_price
-                        ),
-                        _ => String::new(),
-                    };
-
-                    // Exit with opposite side of entry position
-                    let exit_side = match position.side {
-                        OrderSide::Buy => OrderSide::Sell,
-                        OrderSide::Sell => OrderSide::Buy,
-                    };
-
-                    let signal = match exit_side {
-                        OrderSide::Buy => Signal::buy(
-                            market.condition_id.clone(),
-                            token_id.clone(),
-                            self.position_size,
-                        ),
-                        OrderSide::Sell => Signal::sell(
-                            market.condition_id.clone(),
-                            token_id.clone(),
-                            self.position_size,
-                        ),
-                    }
-                    .with_strategy(self.name())
-                    .with_type(exit_type)
-                    .with_strength(strength)
-                    .with_price(current_price)
-                    .with_reason(reason);
-
-                    signals.push(signal);
-                    continue;
-                }
-            }
-
             // Calculate momentum for new entries
             let Some(momentum) = self.calculate_momentum(ctx, &market.condition_id) else {
                 continue;
@@ -300,7 +229,7 @@ impl Strategy for MomentumStrategy {
                 let signal = Signal::buy(
                     market.condition_id.clone(),
                     token_id.clone(),
-                    self.position_size,
+                    Quantity::Notional(self.position_size),
                 )
                 .with_strategy(self.name())
                 .with_type(SignalType::Entry)
@@ -326,16 +255,17 @@ impl Strategy for MomentumStrategy {
 
                 // Only signal if we have a position in this market
                 if ctx.has_position_in_market(&market.condition_id) {
-                    let signal = Signal::sell(
-                        market.condition_id.clone(),
-                        token_id.clone(),
-                        self.position_size,
-                    )
-                    .with_strategy(self.name())
-                    .with_type(SignalType::Exit)
-                    .with_strength(strength)
-                    .with_price(current_price)
-                    .with_reason(format!("Bearish momentum: {:.2}%", momentum * dec!(100)));
+                    // Sell what is held rather than the entry notional
+                    let size = ctx
+                        .get_position(&token_id)
+                        .map(|p| Quantity::Shares(p.size))
+                        .unwrap_or(Quantity::Notional(self.position_size));
+                    let signal = Signal::sell(market.condition_id.clone(), token_id.clone(), size)
+                        .with_strategy(self.name())
+                        .with_type(SignalType::Exit)
+                        .with_strength(strength)
+                        .with_price(current_price)
+                        .with_reason(format!("Bearish momentum: {:.2}%", momentum * dec!(100)));
 
                     signals.push(signal);
                 }
@@ -381,6 +311,19 @@ impl Strategy for MomentumStrategy {
         }
     }
 
+    fn state_version(&self) -> u32 {
+        STATE_VERSION
+    }
+
+    fn state(&self) -> Result<Option<StrategyState>> {
+        StrategyState::new(STATE_VERSION, &self.positions).map(Some)
+    }
+
+    fn restore_state(&mut self, state: StrategyState) -> Result<()> {
+        self.positions = state.decode()?;
+        Ok(())
+    }
+
     fn parameters(&self) -> HashMap<String, ParameterDef> {
         let mut params = HashMap::new();
 
@@ -0,0 +1,1069 @@
+//! Script strategy.
+//!
+//! Runs a strategy written in [Rhai](https://rhai.rs), so it can be changed
+//! without recompiling. The script is reloaded whenever its file changes.
+
+use crate::error::{Error, Result};
+use crate::state::{OrderSide, OrderType, Quantity};
+use crate::strategy::{
+    ExecutionAlgo, MarketSnapshot, OrderSnapshot, ParameterDef, ParameterType, ParameterValue,
+    PositionSnapshot, Signal, SignalStrength, SignalType, Strategy, StrategyConfig,
+    StrategyContext, StrategyMetadata, StrategyState,
+};
+use async_trait::async_trait;
+use rhai::{AST, Array, CallFnOptions, Dynamic, Engine, EvalAltResult, FuncArgs, Map, Scope};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Version of the state snapshot layout (the script's `this` map).
+const STATE_VERSION: u32 = 1;
+
+/// Operations between checks of the time limit.
+const TIMEOUT_CHECK_INTERVAL: u64 = 1024;
+
+/// Resource limits applied to every script call.
+///
+/// Rhai has no overall memory cap, so memory is bounded through the size of
+/// individual strings, arrays and maps instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptLimits {
+    /// Maximum run time of one call, in milliseconds.
+    pub timeout_ms: u64,
+    /// Maximum operations per call.
+    pub max_operations: u64,
+    /// Maximum string length, in bytes.
+    pub max_string_size: usize,
+    /// Maximum number of array elements.
+    pub max_array_size: usize,
+    /// Maximum number of object map entries.
+    pub max_map_size: usize,
+    /// Maximum function call depth.
+    pub max_call_levels: usize,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 250,
+            max_operations: 5_000_000,
+            max_string_size: 64 * 1024,
+            max_array_size: 10_000,
+            max_map_size: 10_000,
+            max_call_levels: 32,
+        }
+    }
+}
+
+/// Strategy implemented by a Rhai script.
+///
+/// The script must define `evaluate(ctx, params)` returning an array of
+/// signals built with `buy()`/`sell()`. It may also define `parameters()`,
+/// `metadata()`, `on_signal_executed(signal, success)` and
+/// `on_order_filled(order_id, price, size)`. Inside these functions `this`
+/// is a map that persists between calls and across restarts.
+pub struct ScriptStrategy {
+    /// Strategy name.
+    name: String,
+    /// Script file, if loaded from one.
+    path: Option<PathBuf>,
+    /// Modification time of the loaded file.
+    modified: Option<SystemTime>,
+    /// Sandboxed script engine.
+    engine: Engine,
+    /// Compiled script.
+    ast: AST,
+    /// Metadata declared by the script.
+    metadata: StrategyMetadata,
+    /// Parameters declared by the script.
+    definitions: HashMap<String, ParameterDef>,
+    /// Current parameter values.
+    values: HashMap<String, ParameterValue>,
+    /// Configured parameters, reapplied when the script is reloaded.
+    configured: HashMap<String, serde_json::Value>,
+    /// State kept by the script in `this`.
+    state: Dynamic,
+    /// When the running call started, for the time limit.
+    call_started: Arc<Mutex<Instant>>,
+    /// Time limit of one call.
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for ScriptStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScriptStrategy")
+            .field("name", &self.name)
+            .field("path", &self.path)
+            .field("values", &self.values)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ScriptStrategy {
+    /// Load and compile the script at `path`.
+    pub fn load(name: impl Into<String>, path: &Path, limits: &ScriptLimits) -> Result<Self> {
+        let source = std::fs::read_to_string(path).map_err(|e| {
+            Error::invalid_input(format!("Cannot read script {}: {}", path.display(), e))
+        })?;
+        let mut strategy = Self::from_source(name, &source, limits)?;
+        strategy.modified = modified(path);
+        strategy.path = Some(path.to_path_buf());
+        Ok(strategy)
+    }
+
+    /// Compile a script from source.
+    pub fn from_source(
+        name: impl Into<String>,
+        source: &str,
+        limits: &ScriptLimits,
+    ) -> Result<Self> {
+        let name = name.into();
+        let call_started = Arc::new(Mutex::new(Instant::now()));
+        let timeout = Duration::from_millis(limits.timeout_ms);
+        let engine = script_engine(limits, call_started.clone(), timeout);
+        let mut strategy = Self {
+            metadata: default_metadata(&name),
+            name,
+            path: None,
+            modified: None,
+            ast: AST::empty(),
+            engine,
+            definitions: HashMap::new(),
+            values: HashMap::new(),
+            configured: HashMap::new(),
+            state: Dynamic::from_map(Map::new()),
+            call_started,
+            timeout,
+        };
+        strategy.compile(source)?;
+        Ok(strategy)
+    }
+
+    /// Script file, if loaded from one.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Compile `source` and read its parameter declarations.
+    ///
+    /// Nothing changes if the script does not compile.
+    fn compile(&mut self, source: &str) -> Result<()> {
+        let ast = self
+            .engine
+            .compile(source)
+            .map_err(|e| Error::invalid_input(format!("{}: {}", self.name, e)))?;
+        if !has_function(&ast, "evaluate", 2) {
+            return Err(Error::invalid_input(format!(
+                "{}: script must define evaluate(ctx, params)",
+                self.name
+            )));
+        }
+
+        let previous = std::mem::replace(&mut self.ast, ast);
+        let definitions = if has_function(&self.ast, "parameters", 0) {
+            self.call_map("parameters")
+                .and_then(|declared| parse_parameters(&self.name, declared))
+        } else {
+            Ok(HashMap::new())
+        };
+        let metadata = if has_function(&self.ast, "metadata", 0) {
+            self.call_map("metadata")
+                .map(|declared| parse_metadata(&self.name, declared))
+        } else {
+            Ok(default_metadata(&self.name))
+        };
+        match (definitions, metadata) {
+            (Ok(definitions), Ok(metadata)) => {
+                self.definitions = definitions;
+                self.metadata = metadata;
+                self.apply_configured();
+                Ok(())
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                self.ast = previous;
+                Err(e)
+            }
+        }
+    }
+
+    /// Fill in parameter values: the current value if still valid, else the
+    /// configured value, else the default.
+    fn apply_configured(&mut self) {
+        let current = std::mem::take(&mut self.values);
+        for (name, def) in &self.definitions {
+            let value = current
+                .get(name)
+                .and_then(|v| def.validate(v.clone()).ok())
+                .or_else(|| {
+                    self.configured
+                        .get(name)
+                        .and_then(|v| def.from_json(v).ok())
+                })
+                .unwrap_or_else(|| def.default.clone());
+            self.values.insert(name.clone(), value);
+        }
+    }
+
+    /// Recompile the script if its file changed, keeping the old version
+    /// when the new one does not compile.
+    fn reload_if_changed(&mut self) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        let modified = modified(&path);
+        if modified == self.modified {
+            return;
+        }
+        self.modified = modified;
+
+        match std::fs::read_to_string(&path) {
+            Ok(source) => match self.compile(&source) {
+                Ok(()) => tracing::info!(strategy = %self.name, "Script reloaded"),
+                Err(e) => tracing::warn!("Script reload failed, keeping previous version: {}", e),
+            },
+            Err(e) => tracing::warn!("Cannot read script {}: {}", path.display(), e),
+        }
+    }
+
+    /// Call a script function with the time limit and `this` bound to the
+    /// script's state.
+    fn call(&mut self, function: &str, args: impl FuncArgs) -> Result<Dynamic> {
+        if let Ok(mut started) = self.call_started.lock() {
+            *started = Instant::now();
+        }
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.state);
+        self.engine
+            .call_fn_with_options::<Dynamic>(options, &mut Scope::new(), &self.ast, function, args)
+            .map_err(|e| match *e {
+                EvalAltResult::ErrorTerminated(..) => Error::application(format!(
+                    "{}: {}() exceeded the {} ms time limit",
+                    self.name,
+                    function,
+                    self.timeout.as_millis()
+                )),
+                e => Error::application(format!("{}: {}", self.name, e)),
+            })
+    }
+
+    /// Call a script function without arguments that returns a map.
+    fn call_map(&mut self, function: &str) -> Result<Map> {
+        self.call(function, ())?.try_cast::<Map>().ok_or_else(|| {
+            Error::invalid_input(format!("{}: {}() must return a map", self.name, function))
+        })
+    }
+
+    /// Current parameter values as a script map.
+    fn params(&self) -> Map {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.into(), to_dynamic(value)))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Strategy for ScriptStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn metadata(&self) -> StrategyMetadata {
+        self.metadata.clone()
+    }
+
+    async fn initialize(&mut self, config: &StrategyConfig) -> Result<()> {
+        if let Some(path) = &config.script
+            && self.path.as_deref() != Some(path.as_path())
+        {
+            let source = std::fs::read_to_string(path).map_err(|e| {
+                Error::invalid_input(format!("Cannot read script {}: {}", path.display(), e))
+            })?;
+            self.compile(&source)?;
+            self.modified = modified(path);
+            self.path = Some(path.clone());
+        }
+        self.configured = config.parameters.clone();
+        self.values.clear();
+        self.apply_configured();
+        Ok(())
+    }
+
+    fn evaluate(&mut self, ctx: &StrategyContext) -> Vec<Signal> {
+        self.reload_if_changed();
+
+        let params = self.params();
+        let ctx = ScriptContext(Arc::new(ctx.clone()));
+        let result = match self.call("evaluate", (ctx, params)) {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::warn!("{}", e);
+                return Vec::new();
+            }
+        };
+        if result.is_unit() {
+            return Vec::new();
+        }
+        let Some(items) = result.try_cast::<Array>() else {
+            tracing::warn!("{}: evaluate() must return an array of signals", self.name);
+            return Vec::new();
+        };
+
+        items
+            .into_iter()
+            .filter_map(|item| match item.try_cast::<Signal>() {
+                Some(signal) => Some(signal.with_strategy(self.name.clone())),
+                None => {
+                    tracing::warn!(
+                        "{}: ignoring a non-signal returned by evaluate()",
+                        self.name
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn on_signal_executed(&mut self, signal: &Signal, success: bool) {
+        if has_function(&self.ast, "on_signal_executed", 2)
+            && let Err(e) = self.call("on_signal_executed", (signal.clone(), success))
+        {
+            tracing::warn!("{}", e);
+        }
+    }
+
+    fn on_order_filled(&mut self, order_id: &str, filled_price: Decimal, filled_size: Decimal) {
+        if has_function(&self.ast, "on_order_filled", 3)
+            && let Err(e) = self.call(
+                "on_order_filled",
+                (
+                    order_id.to_string(),
+                    float(filled_price),
+                    float(filled_size),
+                ),
+            )
+        {
+            tracing::warn!("{}", e);
+        }
+    }
+
+    fn on_order_placed(&mut self, signal_id: &str, order_id: &str) {
+        if has_function(&self.ast, "on_order_placed", 2)
+            && let Err(e) = self.call(
+                "on_order_placed",
+                (signal_id.to_string(), order_id.to_string()),
+            )
+        {
+            tracing::warn!("{}", e);
+        }
+    }
+
+    fn on_order_cancelled(&mut self, order_id: &str) {
+        if has_function(&self.ast, "on_order_cancelled", 1)
+            && let Err(e) = self.call("on_order_cancelled", (order_id.to_string(),))
+        {
+            tracing::warn!("{}", e);
+        }
+    }
+
+    fn state_version(&self) -> u32 {
+        STATE_VERSION
+    }
+
+    fn state(&self) -> Result<Option<StrategyState>> {
+        if self
+            .state
+            .clone()
+            .try_cast::<Map>()
+            .is_some_and(|m| m.is_empty())
+        {
+            return Ok(None);
+        }
+        let data: serde_json::Value = rhai::serde::from_dynamic(&self.state)
+            .map_err(|e| Error::application(format!("{}: {}", self.name, e)))?;
+        StrategyState::new(STATE_VERSION, &data).map(Some)
+    }
+
+    fn restore_state(&mut self, state: StrategyState) -> Result<()> {
+        self.state = rhai::serde::to_dynamic(&state.data)
+            .map_err(|e| Error::application(format!("{}: {}", self.name, e)))?;
+        Ok(())
+    }
+
+    fn parameters(&self) -> HashMap<String, ParameterDef> {
+        self.definitions.clone()
+    }
+
+    fn set_parameter(&mut self, name: &str, value: ParameterValue) -> Result<()> {
+        let def = self
+            .definitions
+            .get(name)
+            .ok_or_else(|| Error::invalid_input(format!("Unknown parameter: {}", name)))?;
+        let value = def.validate(value)?;
+        self.values.insert(name.to_string(), value);
+        Ok(())
+    }
+}
+
+fn default_metadata(name: &str) -> StrategyMetadata {
+    StrategyMetadata {
+        name: name.to_string(),
+        description: String::new(),
+        version: "1.0.0".to_string(),
+        author: None,
+        tags: vec!["script".to_string()],
+    }
+}
+
+/// Read the map returned by the script's `metadata()`: `description`,
+/// `version`, `author` and `tags`, all optional.
+fn parse_metadata(name: &str, declared: Map) -> StrategyMetadata {
+    let mut metadata = default_metadata(name);
+    let text = |key: &str| declared.get(key).and_then(|v| v.clone().into_string().ok());
+    if let Some(description) = text("description") {
+        metadata.description = description;
+    }
+    if let Some(version) = text("version") {
+        metadata.version = version;
+    }
+    metadata.author = text("author");
+    if let Some(tags) = declared
+        .get("tags")
+        .and_then(|v| v.clone().try_cast::<Array>())
+    {
+        metadata
+            .tags
+            .extend(tags.into_iter().filter_map(|t| t.into_string().ok()));
+    }
+    metadata
+}
+
+/// Modification time of a file, if it can be read.
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn has_function(ast: &AST, name: &str, params: usize) -> bool {
+    ast.iter_functions()
+        .any(|f| f.name == name && f.params.len() == params)
+}
+
+/// Parse the map returned by the script's `parameters()`.
+///
+/// Each entry is a map with the default `value` and optional `type`, `min`,
+/// `max`, `allowed` and `description`. Without a `type`, the type of the
+/// default is used. (`default` itself is a reserved word in Rhai.)
+fn parse_parameters(strategy: &str, declared: Map) -> Result<HashMap<String, ParameterDef>> {
+    let mut definitions = HashMap::new();
+    for (name, entry) in declared {
+        let invalid = |message: &str| {
+            Error::invalid_input(format!("{}: parameter '{}' {}", strategy, name, message))
+        };
+        let entry = entry
+            .try_cast::<Map>()
+            .ok_or_else(|| invalid("must be declared as a map"))?;
+        let field = |key: &str| entry.get(key).filter(|v| !v.is_unit()).map(from_dynamic);
+
+        let default = field("value")
+            .ok_or_else(|| invalid("has no default value"))?
+            .ok_or_else(|| invalid("has an unsupported default"))?;
+        let param_type = match field("type") {
+            Some(Some(ParameterValue::String(t))) => t
+                .parse()
+                .map_err(|_| invalid(&format!("has an unknown type '{}'", t)))?,
+            Some(_) => return Err(invalid("has a type that is not a string")),
+            None => match default {
+                ParameterValue::Integer(_) => ParameterType::Integer,
+                ParameterValue::Float(_) => ParameterType::Float,
+                ParameterValue::Decimal(_) => ParameterType::Decimal,
+                ParameterValue::Boolean(_) => ParameterType::Boolean,
+                ParameterValue::String(_) => ParameterType::String,
+            },
+        };
+
+        // Convert the bounds to the parameter's type before checking the default
+        let mut def = ParameterDef {
+            name: name.to_string(),
+            description: entry
+                .get("description")
+                .and_then(|v| v.clone().into_string().ok())
+                .unwrap_or_default(),
+            param_type,
+            default: default.clone(),
+            min: None,
+            max: None,
+            allowed_values: None,
+        };
+        let typed = |value: Option<ParameterValue>| -> Result<Option<ParameterValue>> {
+            value.map(|v| def.validate(v)).transpose()
+        };
+        let min = typed(field("min").flatten())?;
+        let max = typed(field("max").flatten())?;
+        let allowed = match entry.get("allowed").cloned() {
+            Some(list) => {
+                let list = list
+                    .try_cast::<Array>()
+                    .ok_or_else(|| invalid("has allowed values that are not an array"))?;
+                let values: Option<Vec<ParameterValue>> = list.iter().map(from_dynamic).collect();
+                let values = values.ok_or_else(|| invalid("has an unsupported allowed value"))?;
+                Some(
+                    values
+                        .into_iter()
+                        .map(|v| def.validate(v))
+                        .collect::<Result<Vec<_>>>()?,
+                )
+            }
+            None => None,
+        };
+        def.min = min;
+        def.max = max;
+        def.allowed_values = allowed;
+        def.default = def.validate(default)?;
+
+        definitions.insert(name.to_string(), def);
+    }
+    Ok(definitions)
+}
+
+fn from_dynamic(value: &Dynamic) -> Option<ParameterValue> {
+    if let Ok(v) = value.as_int() {
+        Some(ParameterValue::Integer(v))
+    } else if let Ok(v) = value.as_float() {
+        Some(ParameterValue::Float(v))
+    } else if let Ok(v) = value.as_bool() {
+        Some(ParameterValue::Boolean(v))
+    } else {
+        value.clone().into_string().ok().map(ParameterValue::String)
+    }
+}
+
+fn to_dynamic(value: &ParameterValue) -> Dynamic {
+    match value {
+        ParameterValue::Integer(v) => Dynamic::from_int(*v),
+        ParameterValue::Float(v) => Dynamic::from_float(*v),
+        ParameterValue::Decimal(v) => Dynamic::from_float(float(*v)),
+        ParameterValue::Boolean(v) => Dynamic::from_bool(*v),
+        ParameterValue::String(v) => Dynamic::from(v.clone()),
+    }
+}
+
+/// Decimals are exposed to scripts as floats.
+fn float(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+fn optional_float(value: Option<Decimal>) -> Dynamic {
+    value
+        .map(|v| Dynamic::from_float(float(v)))
+        .unwrap_or(Dynamic::UNIT)
+}
+
+/// Convert a script number to a decimal price or size.
+fn decimal(value: f64) -> std::result::Result<Decimal, Box<EvalAltResult>> {
+    Decimal::from_f64(value)
+        .map(|d| d.round_dp(6))
+        .ok_or_else(|| format!("{} is not a valid number", value).into())
+}
+
+fn periods(value: i64) -> usize {
+    value.max(0) as usize
+}
+
+/// Read-only view of the [`StrategyContext`] passed to scripts.
+#[derive(Debug, Clone)]
+struct ScriptContext(Arc<StrategyContext>);
+
+fn market_map(market: &MarketSnapshot) -> Dynamic {
+    let strings =
+        |values: &[String]| -> Array { values.iter().cloned().map(Dynamic::from).collect() };
+    let mut map = Map::new();
+    map.insert("id".into(), Dynamic::from(market.condition_id.clone()));
+    map.insert("question".into(), Dynamic::from(market.question.clone()));
+    map.insert("status".into(), Dynamic::from(market.status.to_string()));
+    map.insert(
+        "token_ids".into(),
+        Dynamic::from_array(strings(&market.token_ids)),
+    );
+    map.insert(
+        "token_names".into(),
+        Dynamic::from_array(strings(&market.token_names)),
+    );
+    map.insert(
+        "token_prices".into(),
+        Dynamic::from_array(
+            market
+                .token_prices
+                .iter()
+                .map(|p| Dynamic::from_float(float(*p)))
+                .collect(),
+        ),
+    );
+    map.insert("yes_price".into(), optional_float(market.yes_price()));
+    map.insert("no_price".into(), optional_float(market.no_price()));
+    map.insert(
+        "volume".into(),
+        Dynamic::from_float(float(market.volume_24h)),
+    );
+    map.insert(
+        "liquidity".into(),
+        Dynamic::from_float(float(market.liquidity)),
+    );
+    map.insert("spread".into(), optional_float(market.spread));
+    map.insert(
+        "tradeable".into(),
+        Dynamic::from_bool(market.is_tradeable()),
+    );
+    Dynamic::from_map(map)
+}
+
+fn position_map(position: &PositionSnapshot) -> Dynamic {
+    let mut map = Map::new();
+    map.insert(
+        "market_id".into(),
+        Dynamic::from(position.market_id.clone()),
+    );
+    map.insert("token_id".into(), Dynamic::from(position.token_id.clone()));
+    map.insert("size".into(), Dynamic::from_float(float(position.size)));
+    map.insert(
+        "avg_price".into(),
+        Dynamic::from_float(float(position.avg_price)),
+    );
+    map.insert(
+        "price".into(),
+        Dynamic::from_float(float(position.current_price)),
+    );
+    map.insert(
+        "value".into(),
+        Dynamic::from_float(float(position.current_value)),
+    );
+    map.insert(
+        "pnl".into(),
+        Dynamic::from_float(float(position.unrealized_pnl)),
+    );
+    map.insert(
+        "pnl_percent".into(),
+        Dynamic::from_float(float(position.pnl_percent)),
+    );
+    Dynamic::from_map(map)
+}
+
+fn order_map(order: &OrderSnapshot) -> Dynamic {
+    let mut map = Map::new();
+    map.insert("id".into(), Dynamic::from(order.order_id.clone()));
+    map.insert("market_id".into(), Dynamic::from(order.market_id.clone()));
+    map.insert("token_id".into(), Dynamic::from(order.token_id.clone()));
+    let side = match order.side {
+        OrderSide::Buy => "buy",
+        OrderSide::Sell => "sell",
+    };
+    map.insert("side".into(), Dynamic::from(side.to_string()));
+    map.insert("price".into(), Dynamic::from_float(float(order.price)));
+    map.insert(
+        "size".into(),
+        Dynamic::from_float(float(order.original_size)),
+    );
+    map.insert(
+        "remaining".into(),
+        Dynamic::from_float(float(order.remaining_size)),
+    );
+    map.insert(
+        "filled".into(),
+        Dynamic::from_float(float(order.filled_size)),
+    );
+    Dynamic::from_map(map)
+}
+
+/// Build an engine with the limits applied and the context and signal API
+/// registered.
+fn script_engine(
+    limits: &ScriptLimits,
+    call_started: Arc<Mutex<Instant>>,
+    timeout: Duration,
+) -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(limits.max_operations)
+        .set_max_string_size(limits.max_string_size)
+        .set_max_array_size(limits.max_array_size)
+        .set_max_map_size(limits.max_map_size)
+        .set_max_call_levels(limits.max_call_levels)
+        // Debug builds default to shallow nesting, too little for chained builders
+        .set_max_expr_depths(128, 64)
+        .disable_symbol("eval");
+    engine.on_progress(move |operations| {
+        if operations % TIMEOUT_CHECK_INTERVAL != 0 {
+            return None;
+        }
+        let elapsed = call_started.lock().map(|s| s.elapsed()).unwrap_or_default();
+        (elapsed > timeout).then_some(Dynamic::UNIT)
+    });
+    engine.on_print(|text| tracing::info!(target: "script", "{}", text));
+    engine.on_debug(
+        |text, _, position| tracing::debug!(target: "script", "{:?}: {}", position, text),
+    );
+
+    register_context(&mut engine);
+    register_signals(&mut engine);
+    engine
+}
+
+fn register_context(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<ScriptContext>("Context")
+        .register_get("balance", |c: &mut ScriptContext| {
+            float(c.0.available_balance)
+        })
+        .register_get("total_value", |c: &mut ScriptContext| {
+            float(c.0.total_value)
+        })
+        .register_get("exposure", |c: &mut ScriptContext| {
+            float(c.0.total_exposure())
+        })
+        .register_get("timestamp", |c: &mut ScriptContext| {
+            c.0.timestamp.timestamp()
+        })
+        .register_fn("markets", |c: &mut ScriptContext| -> Array {
+            let mut markets = c.0.markets();
+            markets.sort_by(|a, b| a.condition_id.cmp(&b.condition_id));
+            markets.into_iter().map(market_map).collect()
+        })
+        .register_fn("active_markets", |c: &mut ScriptContext| -> Array {
+            let mut markets = c.0.active_markets();
+            markets.sort_by(|a, b| a.condition_id.cmp(&b.condition_id));
+            markets.into_iter().map(market_map).collect()
+        })
+        .register_fn("market", |c: &mut ScriptContext, id: &str| {
+            c.0.get_market(id).map(market_map).unwrap_or(Dynamic::UNIT)
+        })
+        .register_fn("positions", |c: &mut ScriptContext| -> Array {
+            c.0.positions().into_iter().map(position_map).collect()
+        })
+        .register_fn("position", |c: &mut ScriptContext, token_id: &str| {
+            c.0.get_position(token_id)
+                .map(position_map)
+                .unwrap_or(Dynamic::UNIT)
+        })
+        .register_fn(
+            "has_position_in_market",
+            |c: &mut ScriptContext, id: &str| c.0.has_position_in_market(id),
+        )
+        .register_fn("open_orders", |c: &mut ScriptContext| -> Array {
+            c.0.open_orders().into_iter().map(order_map).collect()
+        })
+        .register_fn(
+            "orders_for_market",
+            |c: &mut ScriptContext, id: &str| -> Array {
+                c.0.orders_for_market(id)
+                    .into_iter()
+                    .map(order_map)
+                    .collect()
+            },
+        )
+        .register_fn(
+            "price_history",
+            |c: &mut ScriptContext, id: &str| -> Array {
+                c.0.get_price_history(id)
+                    .map(|history| {
+                        history
+                            .iter()
+                            .map(|p| Dynamic::from_float(float(p.price)))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            },
+        )
+        .register_fn(
+            "latest_price",
+            |c: &mut ScriptContext, id: &str, index: i64| {
+                optional_float(c.0.latest_price(id, periods(index)))
+            },
+        )
+        .register_fn("sma", |c: &mut ScriptContext, id: &str, n: i64| {
+            optional_float(c.0.sma(id, periods(n)))
+        })
+        .register_fn("ema", |c: &mut ScriptContext, id: &str, n: i64| {
+            optional_float(c.0.ema(id, periods(n)))
+        })
+        .register_fn("price_change", |c: &mut ScriptContext, id: &str, n: i64| {
+            optional_float(c.0.price_change(id, periods(n)))
+        })
+        .register_fn("best_bid", |c: &mut ScriptContext, token_id: &str| {
+            optional_float(c.0.best_bid(token_id))
+        })
+        .register_fn("best_ask", |c: &mut ScriptContext, token_id: &str| {
+            optional_float(c.0.best_ask(token_id))
+        })
+        .register_fn(
+            "liquidity",
+            |c: &mut ScriptContext, token_id: &str, depth: i64| {
+                optional_float(c.0.liquidity(token_id, periods(depth)))
+            },
+        )
+        .register_fn(
+            "imbalance",
+            |c: &mut ScriptContext, token_id: &str, depth: i64| {
+                optional_float(c.0.imbalance(token_id, periods(depth)))
+            },
+        )
+        .register_fn(
+            "vwap_buy",
+            |c: &mut ScriptContext, token_id: &str, size: f64| {
+                optional_float(decimal(size).ok().and_then(|s| c.0.vwap_buy(token_id, s)))
+            },
+        )
+        .register_fn(
+            "vwap_sell",
+            |c: &mut ScriptContext, token_id: &str, size: f64| {
+                optional_float(decimal(size).ok().and_then(|s| c.0.vwap_sell(token_id, s)))
+            },
+        );
+}
+
+type SignalResult = std::result::Result<Signal, Box<EvalAltResult>>;
+
+fn register_signals(engine: &mut Engine) {
+    engine
+        .register_type_with_name::<Signal>("Signal")
+        .register_fn(
+            "buy",
+            |market: &str, token: &str, size: f64| -> SignalResult {
+                Ok(Signal::buy(market, token, Quantity::Shares(decimal(size)?)))
+            },
+        )
+        .register_fn("buy", |market: &str, token: &str, size: i64| {
+            Signal::buy(market, token, Quantity::Shares(Decimal::from(size)))
+        })
+        .register_fn(
+            "sell",
+            |market: &str, token: &str, size: f64| -> SignalResult {
+                Ok(Signal::sell(
+                    market,
+                    token,
+                    Quantity::Shares(decimal(size)?),
+                ))
+            },
+        )
+        .register_fn("sell", |market: &str, token: &str, size: i64| {
+            Signal::sell(market, token, Quantity::Shares(Decimal::from(size)))
+        })
+        .register_fn("limit", |s: Signal, price: f64| -> SignalResult {
+            Ok(s.with_price(decimal(price)?))
+        })
+        .register_fn("notional", |s: Signal| {
+            let amount = s.size.amount();
+            s.with_size(Quantity::Notional(amount))
+        })
+        .register_fn("market_order", |s: Signal| {
+            s.with_order_type(OrderType::Market)
+        })
+        .register_fn("ttl", |s: Signal, secs: i64| s.with_ttl(secs.max(0) as u64))
+        .register_fn("reason", |s: Signal, reason: &str| s.with_reason(reason))
+        .register_fn("stop_loss", |s: Signal, price: f64| -> SignalResult {
+            Ok(s.with_stop_loss(decimal(price)?))
+        })
+        .register_fn("take_profit", |s: Signal, price: f64| -> SignalResult {
+            Ok(s.with_take_profit(decimal(price)?))
+        })
+        .register_fn("twap", |s: Signal, duration_secs: i64, slices: i64| {
+            s.with_execution(ExecutionAlgo::Twap {
+                duration_secs: duration_secs.max(0) as u64,
+                slices: slices.clamp(1, u32::MAX as i64) as u32,
+            })
+        })
+        .register_fn(
+            "iceberg",
+            |s: Signal, clip_size: f64, timeout_secs: i64| -> SignalResult {
+                Ok(s.with_execution(ExecutionAlgo::Iceberg {
+                    clip_size: decimal(clip_size)?,
+                    timeout_secs: timeout_secs.max(0) as u64,
+                }))
+            },
+        )
+        .register_fn(
+            "max_slippage",
+            |s: Signal, max_slippage: f64, timeout_secs: i64| -> SignalResult {
+                Ok(s.with_execution(ExecutionAlgo::SlippageCapped {
+                    max_slippage: decimal(max_slippage)?,
+                    timeout_secs: timeout_secs.max(0) as u64,
+                }))
+            },
+        )
+        .register_fn("confidence", |mut s: Signal, confidence: f64| {
+            s.metadata.confidence = Some(confidence);
+            s
+        })
+        .register_fn("tag", |mut s: Signal, tag: &str| {
+            s.metadata.tags.push(tag.to_string());
+            s
+        })
+        .register_fn("strength", |s: Signal, strength: &str| -> SignalResult {
+            let strength = match strength {
+                "weak" => SignalStrength::Weak,
+                "medium" => SignalStrength::Medium,
+                "strong" => SignalStrength::Strong,
+                "very_strong" => SignalStrength::VeryStrong,
+                other => return Err(format!("unknown signal strength '{}'", other).into()),
+            };
+            Ok(s.with_strength(strength))
+        })
+        .register_fn("kind", |s: Signal, kind: &str| -> SignalResult {
+            let kind = match kind {
+                "entry" => SignalType::Entry,
+                "exit" => SignalType::Exit,
+                "add" => SignalType::AddToPosition,
+                "reduce" => SignalType::ReducePosition,
+                "rebalance" => SignalType::Rebalance,
+                "take_profit" => SignalType::TakeProfit,
+                "stop_loss" => SignalType::StopLoss,
+                other => return Err(format!("unknown signal kind '{}'", other).into()),
+            };
+            Ok(s.with_type(kind))
+        })
+        .register_get("id", |s: &mut Signal| s.id.clone())
+        .register_get("market_id", |s: &mut Signal| s.market_id.clone())
+        .register_get("token_id", |s: &mut Signal| s.token_id.clone())
+        .register_get("side", |s: &mut Signal| match s.side {
+            OrderSide::Buy => "buy".to_string(),
+            OrderSide::Sell => "sell".to_string(),
+        })
+        .register_get("size", |s: &mut Signal| float(s.size.amount()))
+        .register_get("price", |s: &mut Signal| optional_float(s.price));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MarketStatus;
+    use rust_decimal_macros::dec;
+
+    const SCRIPT: &str = r#"
+        fn parameters() {
+            #{
+                entry: #{ type: "decimal", value: 0.3, min: 0.05, max: 0.5, description: "Buy below" },
+                size: #{ value: 10 },
+            }
+        }
+
+        fn evaluate(ctx, params) {
+            this.evaluations = (this.evaluations ?? 0) + 1;
+            let signals = [];
+            for market in ctx.markets() {
+                if market.yes_price < params.entry && !ctx.has_position_in_market(market.id) {
+                    signals.push(buy(market.id, market.token_ids[0], params.size)
+                        .limit(market.yes_price)
+                        .reason("cheap"));
+                }
+            }
+            signals
+        }
+    "#;
+
+    fn context(price: Decimal) -> StrategyContext {
+        let mut ctx = StrategyContext::new();
+        ctx.markets.insert(
+            "m".to_string(),
+            MarketSnapshot {
+                condition_id: "m".to_string(),
+                question: String::new(),
+                status: MarketStatus::Active,
+                token_ids: vec!["yes".to_string(), "no".to_string()],
+                token_names: vec![],
+                token_prices: vec![price, Decimal::ONE - price],
+                volume_24h: Decimal::ZERO,
+                liquidity: Decimal::ZERO,
+                spread: None,
+                end_date: None,
+            },
+        );
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_script_declares_parameters_and_builds_signals() {
+        let mut strategy =
+            ScriptStrategy::from_source("cheap", SCRIPT, &ScriptLimits::default()).unwrap();
+        let parameters = strategy.parameters();
+        assert_eq!(parameters["entry"].param_type, ParameterType::Decimal);
+        assert_eq!(
+            parameters["entry"].max,
+            Some(ParameterValue::Decimal(dec!(0.5)))
+        );
+        assert_eq!(parameters["size"].default, ParameterValue::Integer(10));
+
+        let config = StrategyConfig {
+            parameters: [("size".to_string(), serde_json::json!(25))].into(),
+            ..Default::default()
+        };
+        strategy.initialize(&config).await.unwrap();
+
+        let signals = strategy.evaluate(&context(dec!(0.2)));
+        assert_eq!(signals.len(), 1);
+        assert_eq!(signals[0].strategy_name, "cheap");
+        assert_eq!(signals[0].size, Quantity::Shares(dec!(25)));
+        assert_eq!(signals[0].price, Some(dec!(0.2)));
+        assert_eq!(signals[0].reason.as_deref(), Some("cheap"));
+
+        strategy
+            .set_parameter("entry", ParameterValue::Float(0.1))
+            .unwrap();
+        assert!(strategy.evaluate(&context(dec!(0.2))).is_empty());
+        assert!(
+            strategy
+                .set_parameter("entry", ParameterValue::Float(0.9))
+                .is_err()
+        );
+
+        let state = strategy.state().unwrap().unwrap();
+        assert_eq!(state.data, serde_json::json!({ "evaluations": 2 }));
+    }
+
+    #[test]
+    fn test_script_limits_are_enforced() {
+        let limits = ScriptLimits {
+            timeout_ms: 20,
+            max_operations: 0,
+            ..Default::default()
+        };
+        let source = "fn evaluate(ctx, params) { loop {} }";
+        let mut strategy = ScriptStrategy::from_source("spin", source, &limits).unwrap();
+        let ctx = ScriptContext(Arc::new(context(dec!(0.5))));
+        let error = strategy.call("evaluate", (ctx, Map::new())).unwrap_err();
+        assert!(error.to_string().contains("time limit"));
+
+        let source = "fn evaluate(ctx, params) { let a = []; loop { a.push(1); } }";
+        let mut strategy =
+            ScriptStrategy::from_source("grow", source, &ScriptLimits::default()).unwrap();
+        assert!(strategy.evaluate(&context(dec!(0.5))).is_empty());
+
+        assert!(ScriptStrategy::from_source("empty", "let x = 1;", &limits).is_err());
+    }
+
+    #[test]
+    fn test_script_reloads_when_file_changes() {
+        let path =
+            std::env::temp_dir().join(format!("clobster-script-{}.rhai", uuid::Uuid::new_v4()));
+        std::fs::write(&path, "fn evaluate(ctx, params) { [] }").unwrap();
+        let mut strategy = ScriptStrategy::load("file", &path, &ScriptLimits::default()).unwrap();
+        assert!(strategy.evaluate(&context(dec!(0.5))).is_empty());
+
+        std::fs::write(
+            &path,
+            "fn evaluate(ctx, params) { [sell(\"m\", \"yes\", 1)] }",
+        )
+        .unwrap();
+        // Make sure the modification time differs on coarse filesystems
+        strategy.modified = None;
+        assert_eq!(strategy.evaluate(&context(dec!(0.5))).len(), 1);
+
+        std::fs::write(&path, "fn evaluate(ctx, params) {").unwrap();
+        strategy.modified = None;
+        assert_eq!(strategy.evaluate(&context(dec!(0.5))).len(), 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,22 +1,38 @@
 //! Spread strategy.
 //!
-//! Market making strategy that places orders on both sides of the spread.
+//! Market making strategy that keeps a quote on both sides of the spread.
 
 use crate::error::Result;
-use crate::state::OrderSide;
+use crate::state::{OrderSide, Quantity, SIZE_DECIMALS};
 use crate::strategy::{
-    ParameterDef, ParameterType, ParameterValue, Signal, SignalStrength, SignalType, Strategy,
-    StrategyConfig, StrategyContext, StrategyMetadata,
+    MarketSnapshot, ParameterDef, ParameterType, ParameterValue, Signal, SignalStrength,
+    SignalType, Strategy, StrategyConfig, StrategyContext, StrategyMetadata, StrategyState,
 };
 use async_trait::async_trait;
-use rust_decimal::Decimal;
+use chrono::{DateTime, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
 use rust_decimal_macros::dec;
 use std::collections::HashMap;
 
+/// Version of the state snapshot layout (inventory per market).
+const STATE_VERSION: u32 = 1;
+
+/// Minimum price increment on Polymarket.
+const TICK_SIZE: Decimal = dec!(0.01);
+
+/// Time to live of quote signals, and how long a quote may wait for its
+/// order to be placed before it is given up.
+const QUOTE_TTL_SECS: i64 = 300;
+
 /// Spread/market-making strategy.
 ///
-/// This strategy provides liquidity by placing limit orders on both
-/// sides of the market, profiting from the bid-ask spread.
+/// This strategy provides liquidity by keeping one limit order on each
+/// side of every market it trades, profiting from the bid-ask spread.
+/// Quotes are cancelled and replaced when the mid-price moves, and skewed
+/// away from the side that would grow the inventory.
+///
+/// Quotes are not part of the saved state: orders left open by a previous
+/// session are not managed.
 #[derive(Debug)]
 pub struct SpreadStrategy {
     /// Minimum spread to participate (as decimal).
@@ -25,25 +41,74 @@ pub struct SpreadStrategy {
     bid_offset: Decimal,
     /// Offset from mid-price for asks.
     ask_offset: Decimal,
-    /// Order size.
+    /// Order size in USDC per side.
     order_size: Decimal,
     /// Minimum liquidity required.
     min_liquidity: Decimal,
     /// Maximum inventory imbalance.
     max_inventory_imbalance: Decimal,
-    /// Current inventory per market.
+    /// Mid-price move after which quotes are replaced.
+    requote_tolerance: Decimal,
+    /// Price shift of both quotes at the maximum inventory imbalance.
+    inventory_skew: Decimal,
+    /// Current inventory per market, from fills of quote orders.
     inventory: HashMap<String, Decimal>,
-    /// Active order pairs.
-    #[allow(dead_code)]
-    active_orders: HashMap<String, OrderPair>,
+    /// Live quotes per market.
+    quotes: HashMap<String, QuotePair>,
+    /// Orders placed for quotes by order ID, to attribute fills.
+    orders: HashMap<String, QuoteOrder>,
+}
+
+/// Quotes kept in one market.
+#[derive(Debug, Clone, Default)]
+struct QuotePair {
+    bid: Option<Quote>,
+    ask: Option<Quote>,
 }
 
+impl QuotePair {
+    fn side_mut(&mut self, side: OrderSide) -> &mut Option<Quote> {
+        match side {
+            OrderSide::Buy => &mut self.bid,
+            OrderSide::Sell => &mut self.ask,
+        }
+    }
+
+    /// Forget the quote matching `f`, returning whether there was one.
+    fn remove(&mut self, f: impl Fn(&Quote) -> bool) -> Option<Quote> {
+        for slot in [&mut self.bid, &mut self.ask] {
+            if slot.as_ref().is_some_and(&f) {
+                return slot.take();
+            }
+        }
+        None
+    }
+}
+
+/// One side of a market's quote.
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
-struct OrderPair {
-    bid_id: Option<String>,
-    ask_id: Option<String>,
+struct Quote {
+    /// Signal that requested the order.
+    signal_id: String,
+    /// Exchange order ID, once placed.
+    order_id: Option<String>,
+    /// Token quoted.
+    token_id: String,
+    /// Mid-price the quote was priced from.
     mid_price: Decimal,
+    /// Order size in shares.
+    size: Decimal,
+    /// When the order was requested.
+    requested_at: DateTime<Utc>,
+}
+
+/// An order placed for a quote.
+#[derive(Debug, Clone)]
+struct QuoteOrder {
+    market_id: String,
+    side: OrderSide,
+    /// Size not yet filled.
+    remaining: Decimal,
 }
 
 impl SpreadStrategy {
@@ -56,8 +121,11 @@ impl SpreadStrategy {
             order_size: dec!(5),               // 5 USDC per side
             min_liquidity: dec!(1000),         // Minimum 1000 liquidity
             max_inventory_imbalance: dec!(50), // Max 50 units imbalance
+            requote_tolerance: dec!(0.01),     // Requote after a 1 cent move
+            inventory_skew: dec!(0.01),        // Shift up to 1 cent against inventory
             inventory: HashMap::new(),
-            active_orders: HashMap::new(),
+            quotes: HashMap::new(),
+            orders: HashMap::new(),
         }
     }
 
@@ -85,6 +153,19 @@ impl SpreadStrategy {
         self
     }
 
+    /// Set the mid-price move after which quotes are replaced.
+    pub fn with_requote_tolerance(mut self, tolerance: Decimal) -> Self {
+        self.requote_tolerance = tolerance;
+        self
+    }
+
+    /// Set how far quotes shift against the inventory at the maximum
+    /// imbalance.
+    pub fn with_inventory_skew(mut self, skew: Decimal) -> Self {
+        self.inventory_skew = skew;
+        self
+    }
+
     fn calculate_mid_price(&self, yes_price: Decimal) -> Decimal {
         // For binary markets, mid = yes_price (since no = 1 - yes)
         yes_price
@@ -123,6 +204,203 @@ impl SpreadStrategy {
             _ => base_size,
         }
     }
+
+    /// Stop tracking an order that can no longer fill, freeing its quote.
+    fn forget_order(&mut self, order_id: &str) {
+        if let Some(order) = self.orders.remove(order_id)
+            && let Some(pair) = self.quotes.get_mut(&order.market_id)
+        {
+            pair.remove(|q| q.order_id.as_deref() == Some(order_id));
+        }
+    }
+
+    /// Price shift of both quotes for the inventory held: down when long so
+    /// the ask is more likely to fill, up when short.
+    fn skew_for_inventory(&self, inventory: Decimal) -> Decimal {
+        if self.max_inventory_imbalance.is_zero() {
+            return Decimal::ZERO;
+        }
+        let ratio = (inventory / self.max_inventory_imbalance).clamp(-Decimal::ONE, Decimal::ONE);
+        ratio * self.inventory_skew
+    }
+
+    /// Price and size to quote on each side of a market, or `None` for a
+    /// market that should not be quoted at all.
+    fn target_quotes(&self, ctx: &StrategyContext, market: &MarketSnapshot) -> Option<Targets> {
+        // Skip low liquidity markets
+        if market.liquidity < self.min_liquidity {
+            return None;
+        }
+
+        let yes_price = market.yes_price()?;
+        let token_id = market.token_ids.first().cloned().unwrap_or_default();
+
+        // Prefer the live book; fall back to the outcome mid when no book is loaded
+        let book_quote = ctx.best_bid(&token_id).zip(ctx.best_ask(&token_id));
+        let mid_price = match book_quote {
+            Some((bid, ask)) => (bid + ask) / Decimal::TWO,
+            None => self.calculate_mid_price(yes_price),
+        };
+
+        // Calculate spread
+        let spread = if let Some((bid, ask)) = book_quote {
+            ask - bid
+        } else if let Some(spread) = market.spread {
+            spread
+        } else {
+            // Estimate spread from price proximity to extremes
+            let dist_from_half = (mid_price - dec!(0.5)).abs();
+            dec!(0.02) + dist_from_half * dec!(0.1) // Wider spread near extremes
+        };
+
+        // Skip if spread is too tight
+        if spread < self.min_spread {
+            return None;
+        }
+
+        // Calculate bid and ask prices, skewed by inventory and rounded away
+        // from the mid onto the tick, never crossing the book
+        let inventory = self.get_inventory(&market.condition_id);
+        let skew = self.skew_for_inventory(inventory);
+        let mut bid_price = ((mid_price - self.bid_offset - skew) / TICK_SIZE).floor() * TICK_SIZE;
+        let mut ask_price = ((mid_price + self.ask_offset - skew) / TICK_SIZE).ceil() * TICK_SIZE;
+        if let Some((best_bid, best_ask)) = book_quote {
+            bid_price = bid_price.min(best_ask - TICK_SIZE);
+            ask_price = ask_price.max(best_bid + TICK_SIZE);
+        }
+
+        // Validate prices are in valid range
+        if bid_price <= Decimal::ZERO || ask_price >= Decimal::ONE {
+            return None;
+        }
+
+        // At the maximum imbalance only quote the side that reduces it
+        let at_limit = inventory.abs() >= self.max_inventory_imbalance;
+        let quote = |side: OrderSide, price: Decimal| {
+            let grows = match side {
+                OrderSide::Buy => inventory > Decimal::ZERO,
+                OrderSide::Sell => inventory < Decimal::ZERO,
+            };
+            let notional = self.adjust_size_for_inventory(self.order_size, inventory, side);
+            let shares = Quantity::Notional(notional)
+                .shares_at(price)?
+                .round_dp_with_strategy(SIZE_DECIMALS, RoundingStrategy::ToZero);
+            (!(at_limit && grows) && notional > dec!(0.1)).then_some((price, shares))
+        };
+
+        Some(Targets {
+            token_id,
+            mid_price,
+            spread,
+            bid: quote(OrderSide::Buy, bid_price),
+            ask: quote(OrderSide::Sell, ask_price),
+        })
+    }
+
+    /// Bring one side of a market's quote in line with its target, returning
+    /// the signal needed to do so, if any.
+    fn manage_quote(
+        &mut self,
+        market_id: &str,
+        side: OrderSide,
+        targets: Option<&Targets>,
+        now: DateTime<Utc>,
+    ) -> Option<Signal> {
+        let target = targets.and_then(|t| match side {
+            OrderSide::Buy => t.bid,
+            OrderSide::Sell => t.ask,
+        });
+        let tolerance = self.requote_tolerance;
+        let name = self.name().to_string();
+        let slot = self
+            .quotes
+            .entry(market_id.to_string())
+            .or_default()
+            .side_mut(side);
+
+        let replaces = match slot.as_ref() {
+            // Waiting for the exchange; give up once the order cannot be coming
+            Some(quote) if quote.order_id.is_none() => {
+                if (now - quote.requested_at).num_seconds() < QUOTE_TTL_SECS {
+                    return None;
+                }
+                *slot = None;
+                None
+            }
+            Some(quote) => {
+                let order_id = quote.order_id.clone().unwrap_or_default();
+                match (targets, target) {
+                    // Still priced from a close enough mid
+                    (Some(t), Some(_)) if (t.mid_price - quote.mid_price).abs() <= tolerance => {
+                        return None;
+                    }
+                    (Some(_), Some(_)) => Some(order_id),
+                    _ => {
+                        let token_id = quote.token_id.clone();
+                        *slot = None;
+                        return Some(
+                            Signal::cancel(market_id, token_id, order_id)
+                                .with_strategy(name)
+                                .with_reason("Spread quote withdrawn"),
+                        );
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let targets = targets?;
+        let (price, size) = target?;
+        let signal = match side {
+            OrderSide::Buy => {
+                Signal::buy(market_id, targets.token_id.clone(), Quantity::Shares(size))
+            }
+            OrderSide::Sell => {
+                Signal::sell(market_id, targets.token_id.clone(), Quantity::Shares(size))
+            }
+        }
+        .with_strategy(name)
+        .with_type(SignalType::Entry)
+        .with_strength(SignalStrength::Weak)
+        .with_price(price)
+        .with_ttl(QUOTE_TTL_SECS as u64)
+        .with_reason(format!(
+            "Spread {}: {:.4} (mid: {:.4}, spread: {:.2}%)",
+            match side {
+                OrderSide::Buy => "bid",
+                OrderSide::Sell => "ask",
+            },
+            price,
+            targets.mid_price,
+            targets.spread * dec!(100)
+        ));
+        let signal = match replaces {
+            Some(order_id) => signal.amending(order_id),
+            None => signal,
+        };
+
+        *slot = Some(Quote {
+            signal_id: signal.id.clone(),
+            order_id: None,
+            token_id: targets.token_id.clone(),
+            mid_price: targets.mid_price,
+            size,
+            requested_at: now,
+        });
+        Some(signal)
+    }
+}
+
+/// What a market should be quoted at.
+#[derive(Debug, Clone)]
+struct Targets {
+    token_id: String,
+    mid_price: Decimal,
+    spread: Decimal,
+    /// Bid price and size, if bidding.
+    bid: Option<(Decimal, Decimal)>,
+    /// Ask price and size, if offering.
+    ask: Option<(Decimal, Decimal)>,
 }
 
 impl Default for SpreadStrategy {
@@ -207,6 +485,34 @@ impl Strategy for SpreadStrategy {
                 ),
             }
         }
+        if let Some(n) = config
+            .parameters
+            .get("requote_tolerance")
+            .and_then(|v| v.as_f64())
+        {
+            match Decimal::try_from(n) {
+                Ok(d) => self.requote_tolerance = d,
+                Err(e) => tracing::warn!(
+                    "Failed to convert requote_tolerance {} to Decimal: {}, using default",
+                    n,
+                    e
+                ),
+            }
+        }
+        if let Some(n) = config
+            .parameters
+            .get("inventory_skew")
+            .and_then(|v| v.as_f64())
+        {
+            match Decimal::try_from(n) {
+                Ok(d) => self.inventory_skew = d,
+                Err(e) => tracing::warn!(
+                    "Failed to convert inventory_skew {} to Decimal: {}, using default",
+                    n,
+                    e
+                ),
+            }
+        }
 
         Ok(())
     }
@@ -214,128 +520,118 @@ impl Strategy for SpreadStrategy {
     fn evaluate(&mut self, ctx: &StrategyContext) -> Vec<Signal> {
         let mut signals = Vec::new();
 
-        for market in ctx.active_markets() {
-            // Skip low liquidity markets
-            if market.liquidity < self.min_liquidity {
-                continue;
-            }
-
-            let Some(yes_price) = market.yes_price() else {
-                continue;
-            };
-
-            let token_id = market.token_ids.first().cloned().unwrap_or_default();
-            let mid_price = self.calculate_mid_price(yes_price);
-
-            // Calculate spread
-            let implied_spread = if let Some(spread) = market.spread {
-                spread
-            } else {
-                // Estimate spread from price proximity to extremes
-                let dist_from_half = (mid_price - dec!(0.5)).abs();
-                dec!(0.02) + dist_from_half * dec!(0.1) // Wider spread near extremes
-            };
-
-            // Skip if spread is too tight
-            if implied_spread < self.min_spread {
-                continue;
-            }
-
-            // Calculate bid and ask prices
-            let bid_price = mid_price - self.bid_offset;
-            let ask_price = mid_price + self.ask_offset;
-
-            // Validate prices are in valid range
-            if bid_price <= Decimal::ZERO || ask_price >= Decimal::ONE {
-                continue;
-            }
-
-            // Check inventory
-            let inventory = self.get_inventory(&market.condition_id);
-
-            // Skip if at max inventory
-            if inventory.abs() >= self.max_inventory_imbalance {
-                continue;
+        let mut markets: Vec<&MarketSnapshot> = ctx.active_markets();
+        markets.sort_by(|a, b| a.condition_id.cmp(&b.condition_id));
+        for market in &markets {
+            let targets = self.target_quotes(ctx, market);
+            for side in [OrderSide::Buy, OrderSide::Sell] {
+                signals.extend(self.manage_quote(
+                    &market.condition_id,
+                    side,
+                    targets.as_ref(),
+                    ctx.timestamp,
+                ));
             }
+        }
 
-            // Generate bid signal (buy order below mid)
-            let bid_size =
-                self.adjust_size_for_inventory(self.order_size, inventory, OrderSide::Buy);
-            if bid_size > dec!(0.1) {
-                let signal = Signal::buy(market.condition_id.clone(), token_id.clone(), bid_size)
-                    .with_strategy(self.name())
-                    .with_type(SignalType::Entry)
-                    .with_strength(SignalStrength::Weak)
-                    .with_price(bid_price)
-                    .with_ttl(300) // 5 minute TTL for limit orders
-                    .with_reason(format!(
-                        "Spread bid: {:.4} (mid: {:.4}, spread: {:.2}%)",
-                        bid_price,
-                        mid_price,
-                        implied_spread * dec!(100)
-                    ));
-
-                signals.push(signal);
-            }
-
-            // Generate ask signal (sell order above mid)
-            let ask_size =
-                self.adjust_size_for_inventory(self.order_size, inventory, OrderSide::Sell);
-            if ask_size > dec!(0.1) {
-                let signal = Signal::sell(market.condition_id.clone(), token_id.clone(), ask_size)
-                    .with_strategy(self.name())
-                    .with_type(SignalType::Entry)
-                    .with_strength(SignalStrength::Weak)
-                    .with_price(ask_price)
-                    .with_ttl(300)
-                    .with_reason(format!(
-                        "Spread ask: {:.4} (mid: {:.4}, spread: {:.2}%)",
-                        ask_price,
-                        mid_price,
-                        implied_spread * dec!(100)
-                    ));
-
-                signals.push(signal);
+        // Withdraw quotes from markets that are no longer active
+        let mut gone: Vec<String> = self
+            .quotes
+            .keys()
+            .filter(|id| !markets.iter().any(|m| &m.condition_id == *id))
+            .cloned()
+            .collect();
+        gone.sort();
+        for market_id in gone {
+            for side in [OrderSide::Buy, OrderSide::Sell] {
+                signals.extend(self.manage_quote(&market_id, side, None, ctx.timestamp));
             }
         }
+        self.quotes
+            .retain(|_, pair| pair.bid.is_some() || pair.ask.is_some());
 
         signals
     }
 
-    fn on_order_filled(&mut self, _order_id: &str, filled_price: Decimal, filled_size: Decimal) {
-        // This would need the market_id to properly track inventory
-        // For now, this is a simplified implementation
-        // In production, you'd track which market/token the order belongs to
-        tracing::debug!(
-            "Spread order filled: price={}, size={}",
-            filled_price,
-            filled_size
-        );
-    }
-
     fn on_signal_executed(&mut self, signal: &Signal, success: bool) {
-        if !success {
+        if success {
             return;
         }
+        // The order was never placed; quote again next time
+        if let Some(pair) = self.quotes.get_mut(&signal.market_id) {
+            pair.remove(|q| q.signal_id == signal.id && q.order_id.is_none());
+        }
+    }
 
-        // Update inventory tracking
-        let delta = match signal.side {
-            OrderSide::Buy => signal.size,
-            OrderSide::Sell => -signal.size,
+    fn on_order_placed(&mut self, signal_id: &str, order_id: &str) {
+        for (market_id, pair) in &mut self.quotes {
+            for (side, slot) in [
+                (OrderSide::Buy, &mut pair.bid),
+                (OrderSide::Sell, &mut pair.ask),
+            ] {
+                if let Some(quote) = slot.as_mut().filter(|q| q.signal_id == signal_id) {
+                    quote.order_id = Some(order_id.to_string());
+                    self.orders.insert(
+                        order_id.to_string(),
+                        QuoteOrder {
+                            market_id: market_id.clone(),
+                            side,
+                            remaining: quote.size,
+                        },
+                    );
+                    return;
+                }
+            }
+        }
+    }
+
+    fn on_order_filled(&mut self, order_id: &str, filled_price: Decimal, filled_size: Decimal) {
+        let Some(order) = self.orders.get_mut(order_id) else {
+            tracing::debug!("Fill of unknown order {} ignored", order_id);
+            return;
         };
 
+        // Update inventory tracking
+        let delta = match order.side {
+            OrderSide::Buy => filled_size,
+            OrderSide::Sell => -filled_size,
+        };
         let inventory = self
             .inventory
-            .entry(signal.market_id.clone())
+            .entry(order.market_id.clone())
             .or_insert(Decimal::ZERO);
         *inventory += delta;
 
         tracing::debug!(
-            "Spread inventory updated for {}: {} (delta: {})",
-            signal.market_id,
+            "Spread inventory updated for {}: {} (filled {} at {})",
+            order.market_id,
             inventory,
-            delta
+            filled_size,
+            filled_price
         );
+
+        // A completely filled quote is replaced on the next evaluation
+        order.remaining -= filled_size;
+        if order.remaining <= Decimal::ZERO {
+            self.forget_order(order_id);
+        }
+    }
+
+    fn on_order_cancelled(&mut self, order_id: &str) {
+        self.forget_order(order_id);
+    }
+
+    fn state_version(&self) -> u32 {
+        STATE_VERSION
+    }
+
+    fn state(&self) -> Result<Option<StrategyState>> {
+        StrategyState::new(STATE_VERSION, &self.inventory).map(Some)
+    }
+
+    fn restore_state(&mut self, state: StrategyState) -> Result<()> {
+        self.inventory = state.decode()?;
+        Ok(())
     }
 
     fn parameters(&self) -> HashMap<String, ParameterDef> {
@@ -419,6 +715,35 @@ impl Strategy for SpreadStrategy {
             },
         );
 
+        params.insert(
+            "requote_tolerance".to_string(),
+            ParameterDef {
+                name: "requote_tolerance".to_string(),
+                description: "Mid-price move after which quotes are cancelled and replaced"
+                    .to_string(),
+                param_type: ParameterType::Float,
+                default: ParameterValue::Float(0.01),
+                min: Some(ParameterValue::Float(0.0)),
+                max: Some(ParameterValue::Float(0.20)),
+                allowed_values: None,
+            },
+        );
+
+        params.insert(
+            "inventory_skew".to_string(),
+            ParameterDef {
+                name: "inventory_skew".to_string(),
+                description:
+                    "Price shift of both quotes against the inventory at the maximum imbalance"
+                        .to_string(),
+                param_type: ParameterType::Float,
+                default: ParameterValue::Float(0.01),
+                min: Some(ParameterValue::Float(0.0)),
+                max: Some(ParameterValue::Float(0.10)),
+                allowed_values: None,
+            },
+        );
+
         params
     }
 
@@ -460,8 +785,154 @@ impl Strategy for SpreadStrategy {
                     .as_decimal()
                     .ok_or_else(|| crate::Error::invalid_input("Expected decimal"))?;
             }
+            "requote_tolerance" => {
+                self.requote_tolerance = value
+                    .as_decimal()
+                    .ok_or_else(|| crate::Error::invalid_input("Expected decimal"))?;
+            }
+            "inventory_skew" => {
+                self.inventory_skew = value
+                    .as_decimal()
+                    .ok_or_else(|| crate::Error::invalid_input("Expected decimal"))?;
+            }
             _ => return Err(crate::Error::invalid_input("Unknown parameter")),
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MarketStatus;
+    use crate::strategy::SignalAction;
+
+    fn context(price: Decimal) -> StrategyContext {
+        let mut ctx = StrategyContext::new();
+        ctx.markets.insert(
+            "m".to_string(),
+            MarketSnapshot {
+                condition_id: "m".to_string(),
+                question: String::new(),
+                status: MarketStatus::Active,
+                token_ids: vec!["yes".to_string(), "no".to_string()],
+                token_names: vec![],
+                token_prices: vec![price, Decimal::ONE - price],
+                volume_24h: Decimal::ZERO,
+                liquidity: dec!(5000),
+                spread: Some(dec!(0.04)),
+                end_date: None,
+            },
+        );
+        ctx
+    }
+
+    /// Evaluate and acknowledge every order placed, as the engine would.
+    fn quote(strategy: &mut SpreadStrategy, ctx: &StrategyContext) -> Vec<Signal> {
+        let signals = strategy.evaluate(ctx);
+        for signal in &signals {
+            if !matches!(signal.action, SignalAction::Cancel { .. }) {
+                strategy.on_order_placed(&signal.id, &format!("order-{}", signal.id));
+            }
+        }
+        signals
+    }
+
+    fn order_id(signal: &Signal) -> String {
+        format!("order-{}", signal.id)
+    }
+
+    #[test]
+    fn test_keeps_quotes_until_mid_moves() {
+        let mut strategy = SpreadStrategy::new();
+        let first = quote(&mut strategy, &context(dec!(0.50)));
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].price, Some(dec!(0.49)));
+        assert_eq!(first[1].price, Some(dec!(0.51)));
+
+        // Within tolerance: the live quotes stay
+        assert!(quote(&mut strategy, &context(dec!(0.505))).is_empty());
+
+        // Beyond it: both are replaced
+        let moved = quote(&mut strategy, &context(dec!(0.55)));
+        assert_eq!(moved.len(), 2);
+        assert_eq!(
+            moved[0].action,
+            SignalAction::Amend {
+                order_id: order_id(&first[0])
+            }
+        );
+        assert_eq!(moved[0].price, Some(dec!(0.54)));
+
+        // Inactive market: the quotes are withdrawn
+        let withdrawn = strategy.evaluate(&StrategyContext::new());
+        assert_eq!(withdrawn.len(), 2);
+        assert!(
+            withdrawn
+                .iter()
+                .all(|s| matches!(s.action, SignalAction::Cancel { .. }))
+        );
+        assert!(strategy.evaluate(&StrategyContext::new()).is_empty());
+    }
+
+    #[test]
+    fn test_fills_update_inventory_and_skew_quotes() {
+        // $2.45 buys 5 shares at the 0.49 bid
+        let mut strategy = SpreadStrategy::new().with_order_size(dec!(2.45));
+        let ctx = context(dec!(0.50));
+        let first = quote(&mut strategy, &ctx);
+
+        // A partial fill of the bid leaves it resting
+        strategy.on_order_filled(&order_id(&first[0]), dec!(0.49), dec!(2));
+        assert_eq!(strategy.get_inventory("m"), dec!(2));
+        assert!(quote(&mut strategy, &ctx).is_empty());
+
+        // Once completely filled it is replaced, lower since we are long and
+        // rounded down onto the tick
+        strategy.on_order_filled(&order_id(&first[0]), dec!(0.49), dec!(3));
+        assert_eq!(strategy.get_inventory("m"), dec!(5));
+        let requoted = quote(&mut strategy, &ctx);
+        assert_eq!(requoted.len(), 1);
+        assert_eq!(requoted[0].side, OrderSide::Buy);
+        assert_eq!(requoted[0].action, SignalAction::Place);
+        assert_eq!(requoted[0].price, Some(dec!(0.48)));
+
+        // Fills of unknown orders are not attributed
+        strategy.on_order_filled("elsewhere", dec!(0.5), dec!(1));
+        assert_eq!(strategy.get_inventory("m"), dec!(5));
+    }
+
+    #[test]
+    fn test_only_reduces_inventory_at_the_limit() {
+        let mut strategy = SpreadStrategy::new();
+        let ctx = context(dec!(0.50));
+        let first = quote(&mut strategy, &ctx);
+        strategy.on_order_filled(&order_id(&first[0]), dec!(0.49), dec!(50));
+
+        // The bid is gone and the ask stays; no new bid at the limit
+        assert!(quote(&mut strategy, &ctx).is_empty());
+        strategy.on_order_cancelled(&order_id(&first[1]));
+        let requoted = quote(&mut strategy, &ctx);
+        assert_eq!(requoted.len(), 1);
+        assert_eq!(requoted[0].side, OrderSide::Sell);
+        assert_eq!(requoted[0].price, Some(dec!(0.50)));
+    }
+
+    #[test]
+    fn test_unplaced_quotes_are_retried() {
+        let mut strategy = SpreadStrategy::new();
+        let ctx = context(dec!(0.50));
+        let first = strategy.evaluate(&ctx);
+        assert!(strategy.evaluate(&ctx).is_empty());
+
+        strategy.on_signal_executed(&first[0], false);
+        let retried = strategy.evaluate(&ctx);
+        assert_eq!(retried.len(), 1);
+        assert_eq!(retried[0].side, OrderSide::Buy);
+
+        // Never acknowledged: given up after the quote TTL
+        let mut later = ctx.clone();
+        later.timestamp += chrono::Duration::seconds(QUOTE_TTL_SECS);
+        assert_eq!(strategy.evaluate(&later).len(), 2);
+    }
+}
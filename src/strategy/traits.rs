@@ -3,11 +3,14 @@
 use super::{Signal, StrategyContext};
 use crate::error::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::path::PathBuf;
 
 /// Core trait that all trading strategies must implement.
 ///
@@ -49,7 +52,9 @@ pub trait Strategy: Send + Sync + Debug {
     /// A vector of signals (can be empty if no action needed)
     fn evaluate(&mut self, ctx: &StrategyContext) -> Vec<Signal>;
 
-    /// Called when a signal from this strategy was executed.
+    /// Called when a signal from this strategy was executed, or with
+    /// `success` false when it was dropped by sizing, rejected by the risk
+    /// guard, or its order was refused.
     ///
     /// Use this to update internal state, track performance, etc.
     fn on_signal_executed(&mut self, _signal: &Signal, _success: bool) {}
@@ -63,6 +68,11 @@ pub trait Strategy: Send + Sync + Debug {
     /// and update internal indicators or state as needed.
     fn on_market_update(&mut self, _ctx: &StrategyContext) {}
 
+    /// Called when the exchange accepted the order placed for a signal from
+    /// this strategy. `order_id` is what later fills, cancellations and
+    /// cancel or amend signals refer to.
+    fn on_order_placed(&mut self, _signal_id: &str, _order_id: &str) {}
+
     /// Called when an order from this strategy is filled.
     fn on_order_filled(&mut self, _order_id: &str, _filled_price: Decimal, _filled_size: Decimal) {}
 
@@ -74,16 +84,40 @@ pub trait Strategy: Send + Sync + Debug {
         Ok(())
     }
 
-    /// Returns the current state for persistence/debugging.
-    fn state(&self) -> Option<Box<dyn Any + Send + Sync>> {
-        None
+    /// Version of the snapshot layout written by [`Strategy::state`].
+    ///
+    /// Bump it whenever the layout changes, and convert older snapshots in
+    /// [`Strategy::migrate_state`].
+    fn state_version(&self) -> u32 {
+        1
+    }
+
+    /// Snapshot the state that should survive a restart, e.g. open positions.
+    ///
+    /// Returns `None` for stateless strategies.
+    fn state(&self) -> Result<Option<StrategyState>> {
+        Ok(None)
     }
 
-    /// Restore state from a previous session.
-    fn restore_state(&mut self, _state: Box<dyn Any + Send + Sync>) -> Result<()> {
+    /// Restore a snapshot from a previous session.
+    ///
+    /// Only called with snapshots of the current [`Strategy::state_version`].
+    fn restore_state(&mut self, _state: StrategyState) -> Result<()> {
         Ok(())
     }
 
+    /// Convert a snapshot written by an older state version.
+    ///
+    /// The default rejects it, and the engine starts the strategy without
+    /// its previous state.
+    fn migrate_state(&self, state: StrategyState) -> Result<StrategyState> {
+        Err(crate::Error::invalid_input(format!(
+            "No migration from state version {} to {}",
+            state.version,
+            self.state_version()
+        )))
+    }
+
     /// Validate that the strategy is properly configured.
     fn validate(&self) -> Result<()> {
         Ok(())
@@ -100,6 +134,33 @@ pub trait Strategy: Send + Sync + Debug {
     }
 }
 
+/// Serializable snapshot of a strategy's runtime state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrategyState {
+    /// Layout version, see [`Strategy::state_version`].
+    pub version: u32,
+    /// When the snapshot was taken.
+    pub saved_at: DateTime<Utc>,
+    /// Strategy-specific data.
+    pub data: serde_json::Value,
+}
+
+impl StrategyState {
+    /// Snapshot `data` with the given layout version.
+    pub fn new<T: Serialize>(version: u32, data: &T) -> Result<Self> {
+        Ok(Self {
+            version,
+            saved_at: Utc::now(),
+            data: serde_json::to_value(data)?,
+        })
+    }
+
+    /// Decode the strategy-specific data.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_value(self.data.clone())?)
+    }
+}
+
 /// Metadata about a strategy.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StrategyMetadata {
@@ -116,7 +177,7 @@ pub struct StrategyMetadata {
 }
 
 /// Configuration for a strategy instance.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct StrategyConfig {
     /// Whether the strategy is enabled.
     #[serde(default = "default_true")]
@@ -146,9 +207,29 @@ pub struct StrategyConfig {
     #[serde(default)]
     pub exclude_markets: Vec<String>,
 
+    /// Named watchlist whose markets form the strategy's universe,
+    /// in addition to `include_markets`.
+    #[serde(default)]
+    pub watchlist: Option<String>,
+
     /// Custom parameters for the strategy.
     #[serde(default)]
     pub parameters: HashMap<String, serde_json::Value>,
+
+    /// Rhai script implementing the strategy, for instances that are not
+    /// built-in strategies.
+    #[serde(default)]
+    pub script: Option<PathBuf>,
+
+    /// Process implementing the strategy over the external strategy
+    /// protocol, for instances that are neither built-in nor scripts.
+    #[serde(default)]
+    pub external: Option<super::ExternalConfig>,
+
+    /// Position sizing applied to the strategy's entries. Without it,
+    /// entries keep the size their strategy chose.
+    #[serde(default)]
+    pub sizing: Option<super::SizingConfig>,
 }
 
 fn default_true() -> bool {
@@ -156,7 +237,7 @@ fn default_true() -> bool {
 }
 
 /// Definition of a tunable parameter.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ParameterDef {
     /// Parameter name.
     pub name: String,
@@ -174,6 +255,120 @@ pub struct ParameterDef {
     pub allowed_values: Option<Vec<ParameterValue>>,
 }
 
+impl ParameterDef {
+    /// Check `value` against this definition, converting it to the
+    /// parameter's type.
+    ///
+    /// Numbers are accepted for any numeric type as long as the conversion
+    /// is exact, then checked against `min`, `max` and `allowed_values`.
+    pub fn validate(&self, value: ParameterValue) -> Result<ParameterValue> {
+        let converted = match self.param_type {
+            ParameterType::Integer => match &value {
+                ParameterValue::Float(v) if v.fract() != 0.0 => None,
+                ParameterValue::Decimal(v) if !v.fract().is_zero() => None,
+                ParameterValue::Decimal(v) => v.to_i64().map(ParameterValue::Integer),
+                v => v.as_i64().map(ParameterValue::Integer),
+            },
+            ParameterType::Float => value.as_f64().map(ParameterValue::Float),
+            ParameterType::Decimal => value.as_decimal().map(ParameterValue::Decimal),
+            ParameterType::Boolean => value.as_bool().map(ParameterValue::Boolean),
+            ParameterType::String | ParameterType::Enum => value
+                .as_str()
+                .map(|v| ParameterValue::String(v.to_string())),
+        };
+        let Some(value) = converted else {
+            return Err(crate::Error::invalid_input(format!(
+                "{}: expected {}, got {}",
+                self.name, self.param_type, value
+            )));
+        };
+
+        if let Some(amount) = value.as_decimal() {
+            if let Some(min) = self.min.as_ref().and_then(ParameterValue::as_decimal)
+                && amount < min
+            {
+                return Err(crate::Error::invalid_input(format!(
+                    "{}: must be at least {}",
+                    self.name, min
+                )));
+            }
+            if let Some(max) = self.max.as_ref().and_then(ParameterValue::as_decimal)
+                && amount > max
+            {
+                return Err(crate::Error::invalid_input(format!(
+                    "{}: must be at most {}",
+                    self.name, max
+                )));
+            }
+        }
+
+        if let Some(allowed) = &self.allowed_values
+            && !allowed.iter().any(|a| a.to_string() == value.to_string())
+        {
+            let allowed: Vec<String> = allowed.iter().map(|a| a.to_string()).collect();
+            return Err(crate::Error::invalid_input(format!(
+                "{}: must be one of {}",
+                self.name,
+                allowed.join(", ")
+            )));
+        }
+
+        Ok(value)
+    }
+
+    /// Parse and validate a value typed by the user.
+    pub fn parse(&self, input: &str) -> Result<ParameterValue> {
+        let input = input.trim();
+        let invalid = || {
+            crate::Error::invalid_input(format!(
+                "{}: '{}' is not a valid {}",
+                self.name, input, self.param_type
+            ))
+        };
+        let value = match self.param_type {
+            ParameterType::Integer => {
+                ParameterValue::Integer(input.parse().map_err(|_| invalid())?)
+            }
+            ParameterType::Float => ParameterValue::Float(input.parse().map_err(|_| invalid())?),
+            ParameterType::Decimal => {
+                ParameterValue::Decimal(input.parse().map_err(|_| invalid())?)
+            }
+            ParameterType::Boolean => match input.to_lowercase().as_str() {
+                "true" | "yes" | "on" | "1" => ParameterValue::Boolean(true),
+                "false" | "no" | "off" | "0" => ParameterValue::Boolean(false),
+                _ => return Err(invalid()),
+            },
+            ParameterType::String | ParameterType::Enum => {
+                ParameterValue::String(input.to_string())
+            }
+        };
+        self.validate(value)
+    }
+
+    /// Read and validate a value from `StrategyConfig::parameters`.
+    pub fn from_json(&self, value: &serde_json::Value) -> Result<ParameterValue> {
+        let value = match value {
+            serde_json::Value::Bool(v) => ParameterValue::Boolean(*v),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(v) => ParameterValue::Integer(v),
+                None => ParameterValue::Float(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            serde_json::Value::String(v) => match self.param_type {
+                // Decimals may be written as strings to keep their precision
+                ParameterType::Decimal => return self.parse(v),
+                _ => ParameterValue::String(v.clone()),
+            },
+            other => {
+                return Err(crate::Error::invalid_input(format!(
+                    "{}: expected {}, got {}",
+                    self.name, self.param_type, other
+                )));
+            }
+        };
+        self.validate(value)
+    }
+}
+
 /// Types of strategy parameters.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ParameterType {
@@ -185,8 +380,40 @@ pub enum ParameterType {
     Enum,
 }
 
+impl std::str::FromStr for ParameterType {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "integer" | "int" => Ok(Self::Integer),
+            "float" => Ok(Self::Float),
+            "decimal" => Ok(Self::Decimal),
+            "boolean" | "bool" => Ok(Self::Boolean),
+            "string" => Ok(Self::String),
+            "enum" => Ok(Self::Enum),
+            _ => Err(crate::Error::invalid_input(format!(
+                "unknown parameter type '{}'",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for ParameterType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer => write!(f, "integer"),
+            Self::Float => write!(f, "float"),
+            Self::Decimal => write!(f, "decimal"),
+            Self::Boolean => write!(f, "boolean"),
+            Self::String => write!(f, "string"),
+            Self::Enum => write!(f, "enum"),
+        }
+    }
+}
+
 /// A parameter value.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ParameterValue {
     Integer(i64),
@@ -236,4 +463,103 @@ impl ParameterValue {
             _ => None,
         }
     }
+
+    /// Convert to the form stored in `StrategyConfig::parameters`.
+    ///
+    /// Decimals become JSON numbers, which is how strategies read them
+    /// in `initialize`.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Integer(v) => serde_json::Value::from(*v),
+            Self::Float(v) => serde_json::Value::from(*v),
+            Self::Decimal(v) => v
+                .to_f64()
+                .map(serde_json::Value::from)
+                .unwrap_or_else(|| serde_json::Value::String(v.to_string())),
+            Self::Boolean(v) => serde_json::Value::from(*v),
+            Self::String(v) => serde_json::Value::from(v.as_str()),
+        }
+    }
+}
+
+impl std::fmt::Display for ParameterValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(v) => write!(f, "{}", v),
+            Self::Float(v) => write!(f, "{}", v),
+            Self::Decimal(v) => write!(f, "{}", v),
+            Self::Boolean(v) => write!(f, "{}", v),
+            Self::String(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn def(param_type: ParameterType, min: i64, max: i64) -> ParameterDef {
+        ParameterDef {
+            name: "periods".to_string(),
+            description: String::new(),
+            param_type,
+            default: ParameterValue::Integer(min),
+            min: Some(ParameterValue::Integer(min)),
+            max: Some(ParameterValue::Integer(max)),
+            allowed_values: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_converts_and_checks_bounds() {
+        let periods = def(ParameterType::Integer, 3, 50);
+        assert_eq!(
+            periods.validate(ParameterValue::Float(12.0)).unwrap(),
+            ParameterValue::Integer(12)
+        );
+        assert!(periods.validate(ParameterValue::Float(12.5)).is_err());
+        assert!(periods.validate(ParameterValue::Boolean(true)).is_err());
+        assert!(
+            periods
+                .validate(ParameterValue::Integer(2))
+                .unwrap_err()
+                .to_string()
+                .contains("must be at least 3")
+        );
+        assert!(periods.parse("51").is_err());
+        assert!(periods.parse("ten").is_err());
+
+        let size = def(ParameterType::Decimal, 1, 1000);
+        assert_eq!(
+            size.parse("12.50").unwrap(),
+            ParameterValue::Decimal(Decimal::new(1250, 2))
+        );
+        assert_eq!(
+            size.from_json(&serde_json::json!(7.5)).unwrap(),
+            ParameterValue::Decimal(Decimal::new(75, 1))
+        );
+    }
+
+    #[test]
+    fn test_validate_allowed_values() {
+        let mode = ParameterDef {
+            name: "mode".to_string(),
+            description: String::new(),
+            param_type: ParameterType::Enum,
+            default: ParameterValue::String("fast".to_string()),
+            min: None,
+            max: None,
+            allowed_values: Some(vec![
+                ParameterValue::String("fast".to_string()),
+                ParameterValue::String("slow".to_string()),
+            ]),
+        };
+        assert!(mode.parse("slow").is_ok());
+        assert!(
+            mode.parse("medium")
+                .unwrap_err()
+                .to_string()
+                .contains("must be one of fast, slow")
+        );
+    }
 }
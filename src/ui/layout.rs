@@ -10,7 +10,7 @@ pub struct Layout {
     pub tab_area: Rect,
     /// Main content area.
     pub main_area: Rect,
-    /// Notification area (overlaid).
+    /// Notification toast area (overlaid, bottom right).
     pub notification_area: Rect,
 }
 
@@ -26,12 +26,14 @@ impl Layout {
             ])
             .split(area);
 
-        // Notification area is centered in the main area
+        // Toasts stack in the bottom-right corner of the main area
+        let main_area = chunks[2];
+        let width = (area.width / 2).clamp(main_area.width.min(40), main_area.width);
         let notification_area = Rect {
-            x: area.width / 4,
-            y: area.height / 2 - 2,
-            width: area.width / 2,
-            height: 4,
+            x: main_area.x + main_area.width.saturating_sub(width),
+            y: main_area.y,
+            width,
+            height: main_area.height,
         };
 
         Self {
@@ -6,7 +6,10 @@ mod layout;
 mod widgets;
 
 pub use layout::Layout;
-pub use widgets::{HelpPanel, MarketList, OrderList, PositionList, StatusBar, TabBar};
+pub use widgets::{
+    AlertList, HelpPanel, LogViewer, MarketList, NotificationLog, OrderList, PortfolioDashboard,
+    PositionList, SignalList, StatusBar, StrategyEditor, TabBar, TradeHistory, WatchlistView,
+};
 
 use crate::state::Store;
 use ratatui::Frame;
@@ -33,9 +36,33 @@ impl Ui {
             crate::state::View::Orders | crate::state::View::OrderEntry => {
                 OrderList::render(frame, layout.main_area, store);
             }
-            crate::state::View::Positions | crate::state::View::Portfolio => {
+            crate::state::View::Positions => {
                 PositionList::render(frame, layout.main_area, store);
             }
+            crate::state::View::Portfolio => {
+                PortfolioDashboard::render(frame, layout.main_area, store);
+            }
+            crate::state::View::Watchlist => {
+                WatchlistView::render(frame, layout.main_area, store);
+            }
+            crate::state::View::Alerts => {
+                AlertList::render(frame, layout.main_area, store);
+            }
+            crate::state::View::Trades => {
+                TradeHistory::render(frame, layout.main_area, store);
+            }
+            crate::state::View::Notifications => {
+                NotificationLog::render(frame, layout.main_area, store);
+            }
+            crate::state::View::Logs => {
+                LogViewer::render(frame, layout.main_area, store);
+            }
+            crate::state::View::Strategies => {
+                StrategyEditor::render(frame, layout.main_area, store);
+            }
+            crate::state::View::Signals => {
+                SignalList::render(frame, layout.main_area, store);
+            }
             crate::state::View::Settings => {
                 // TODO: Settings view - render placeholder for now
                 let block = ratatui::widgets::Block::default()
@@ -51,14 +78,7 @@ impl Ui {
             HelpPanel::render(frame, frame.area());
         }
 
-        // Render notification if present
-        if let Some(notification) = &store.app.notification {
-            widgets::render_notification(frame, layout.notification_area, notification);
-        }
-
-        // Render error if present
-        if let Some(error) = &store.app.error {
-            widgets::render_error(frame, layout.notification_area, error);
-        }
+        // Render notification toasts (errors included)
+        widgets::render_notifications(frame, layout.notification_area, store);
     }
 }
@@ -0,0 +1,109 @@
+//! Alert rule list widget.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+};
+
+use crate::state::{InputMode, Store};
+
+/// Alert rule list widget.
+pub struct AlertList;
+
+impl AlertList {
+    /// Render the alert rules.
+    pub fn render(frame: &mut Frame, area: Rect, store: &Store) {
+        let rules = &store.alerts.rules;
+
+        let header_cells = ["Rule", "Status", "Hooks", "Last Fired"].iter().map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+        let rows = rules.iter().enumerate().map(|(i, rule)| {
+            let selected = store.alerts.selected_index == Some(i);
+            let style = if selected {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            let (status, status_style) = if !rule.enabled {
+                ("Disabled", Style::default().fg(Color::DarkGray))
+            } else if rule.active {
+                ("Triggered", Style::default().fg(Color::Red))
+            } else {
+                ("Armed", Style::default().fg(Color::Green))
+            };
+
+            let mut hooks = Vec::new();
+            if rule.bell {
+                hooks.push("bell");
+            }
+            if rule.desktop {
+                hooks.push("desktop");
+            }
+            if rule.command.is_some() {
+                hooks.push("cmd");
+            }
+            if rule.webhook.is_some() {
+                hooks.push("webhook");
+            }
+
+            let last_fired = rule
+                .last_fired
+                .map(|t| t.format("%H:%M:%S").to_string())
+                .unwrap_or_else(|| "-".to_string());
+
+            let cells = vec![
+                Cell::from(rule.condition.to_string()),
+                Cell::from(status).style(status_style),
+                Cell::from(hooks.join(",")),
+                Cell::from(last_fired),
+            ];
+
+            Row::new(cells).style(style).height(1)
+        });
+
+        let title = if store.app.input_mode == InputMode::Insert {
+            format!(" New alert: {}_ ", store.app.input_buffer)
+        } else {
+            format!(
+                " Alerts ({}) | n new  e enable/disable  x delete ",
+                rules.len()
+            )
+        };
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(55),
+                Constraint::Length(10),
+                Constraint::Length(20),
+                Constraint::Length(12),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+
+        let mut state = TableState::default();
+        state.select(store.alerts.selected_index);
+
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
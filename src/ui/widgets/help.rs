@@ -75,7 +75,35 @@ impl HelpPanel {
             ]),
             Line::from(vec![
                 Span::styled("  4    ", Style::default().fg(Color::Cyan)),
-                Span::raw("Settings"),
+                Span::raw("Portfolio dashboard"),
+            ]),
+            Line::from(vec![
+                Span::styled("  5    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Watchlist view"),
+            ]),
+            Line::from(vec![
+                Span::styled("  6    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Alerts view"),
+            ]),
+            Line::from(vec![
+                Span::styled("  7    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Notification log"),
+            ]),
+            Line::from(vec![
+                Span::styled("  8    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Trade history"),
+            ]),
+            Line::from(vec![
+                Span::styled("  9    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Application logs"),
+            ]),
+            Line::from(vec![
+                Span::styled("  0    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Strategy parameters"),
+            ]),
+            Line::from(vec![
+                Span::styled("  s    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Signal history (from strategies)"),
             ]),
             Line::from(""),
             Line::from(vec![Span::styled(
@@ -97,6 +125,18 @@ impl HelpPanel {
                 Span::styled("  c    ", Style::default().fg(Color::Cyan)),
                 Span::raw("Cancel order"),
             ]),
+            Line::from(vec![
+                Span::styled("  w    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Add/remove market from watchlist"),
+            ]),
+            Line::from(vec![
+                Span::styled("  p    ", Style::default().fg(Color::Cyan)),
+                Span::raw("Switch to next profile"),
+            ]),
+            Line::from(vec![
+                Span::styled("  ^d   ", Style::default().fg(Color::Cyan)),
+                Span::raw("Dismiss notification"),
+            ]),
             Line::from(vec![
                 Span::styled("  ?    ", Style::default().fg(Color::Cyan)),
                 Span::raw("Toggle help"),
@@ -0,0 +1,112 @@
+//! Application log viewer widget.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+};
+use tracing::Level;
+
+use crate::state::{InputMode, Store};
+
+/// Application log viewer widget.
+pub struct LogViewer;
+
+impl LogViewer {
+    /// Render captured log entries, newest first.
+    pub fn render(frame: &mut Frame, area: Rect, store: &Store) {
+        let logs = &store.logs;
+        let entries = logs.filtered_entries();
+
+        let header_cells = ["Time", "Level", "Target", "Message"].iter().map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+        let rows = entries.iter().map(|entry| {
+            let cells = vec![
+                Cell::from(
+                    entry
+                        .timestamp
+                        .with_timezone(&chrono::Local)
+                        .format("%H:%M:%S%.3f")
+                        .to_string(),
+                )
+                .style(Style::default().fg(Color::DarkGray)),
+                Cell::from(entry.level.to_string())
+                    .style(Style::default().fg(level_color(entry.level))),
+                Cell::from(short_target(&entry.target)).style(Style::default().fg(Color::DarkGray)),
+                Cell::from(entry.message.clone()),
+            ];
+            Row::new(cells).height(1)
+        });
+
+        let title = if store.app.input_mode == InputMode::Insert {
+            format!(" Target filter: {}_ ", store.app.input_buffer)
+        } else {
+            let level = logs
+                .max_level
+                .map(|l| format!("≤ {}", l))
+                .unwrap_or_else(|| "all".to_string());
+            let target = logs
+                .target_filter
+                .as_ref()
+                .map(|t| format!(" | target: {}", t))
+                .unwrap_or_default();
+            format!(
+                " Logs ({}/{}) | level: {}{} | ←/→ level  f target  x clear ",
+                entries.len(),
+                logs.buffer.len(),
+                level,
+                target
+            )
+        };
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Length(5),
+                Constraint::Length(24),
+                Constraint::Min(20),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+
+        let mut state = TableState::default();
+        state.select(logs.selected_index);
+
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
+
+fn level_color(level: Level) -> Color {
+    match level {
+        Level::ERROR => Color::Red,
+        Level::WARN => Color::Yellow,
+        Level::INFO => Color::Green,
+        Level::DEBUG => Color::Cyan,
+        Level::TRACE => Color::DarkGray,
+    }
+}
+
+/// Drop the crate prefix so module paths fit the column.
+fn short_target(target: &str) -> String {
+    target
+        .strip_prefix("clobster::")
+        .unwrap_or(target)
+        .to_string()
+}
@@ -56,8 +56,18 @@ impl MarketList {
                 .map(|o| format!("{:.2}¢", o.mid_price() * rust_decimal::Decimal::ONE_HUNDRED))
                 .unwrap_or_default();
 
+            let watched = if store.watchlists.is_watched(&market.id) {
+                "★ "
+            } else {
+                ""
+            };
+
             let cells = vec![
-                Cell::from(truncate_string(&market.question, 50)),
+                Cell::from(format!(
+                    "{}{}",
+                    watched,
+                    truncate_string(&market.question, 50)
+                )),
                 Cell::from(format!("{}", market.status)).style(status_style),
                 Cell::from(yes_price).style(Style::default().fg(Color::Green)),
                 Cell::from(no_price).style(Style::default().fg(Color::Red)),
@@ -1,21 +1,59 @@
 //! TUI widgets.
 
+mod alert_list;
 mod help;
+mod log_viewer;
 mod market_list;
 mod notifications;
 mod order_list;
 mod orderbook;
+mod portfolio;
 mod position_list;
+mod signal_list;
 mod status_bar;
+mod strategy_editor;
 mod tab_bar;
+mod trade_history;
+mod watchlist;
 
+pub use alert_list::AlertList;
 pub use help::HelpPanel;
+pub use log_viewer::LogViewer;
 pub use market_list::MarketList;
-pub use notifications::{render_error, render_notification};
+pub use notifications::{NotificationLog, render_notifications};
 pub use order_list::OrderList;
 // Allow unused until orderbook widgets are integrated with UI layout
 #[allow(unused_imports)]
 pub use orderbook::{OrderBook, OrderBookChart, OrderBookCompact};
+pub use portfolio::PortfolioDashboard;
 pub use position_list::PositionList;
+pub use signal_list::SignalList;
 pub use status_bar::StatusBar;
+pub use strategy_editor::StrategyEditor;
 pub use tab_bar::TabBar;
+pub use trade_history::TradeHistory;
+pub use watchlist::WatchlistView;
+
+/// Shorten `s` to at most `max_len` characters, ending in "..." when cut.
+pub(crate) fn truncate_string(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else {
+        let kept: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", kept)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_string() {
+        assert_eq!(truncate_string("short", 10), "short");
+        assert_eq!(truncate_string("a longer title", 10), "a longe...");
+        // Cuts on character boundaries, not bytes
+        assert_eq!(truncate_string("Will Zürich beat Köln?", 10), "Will Zü...");
+        assert_eq!(truncate_string("€€€€€", 4), "€...");
+    }
+}
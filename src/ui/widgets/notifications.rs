@@ -2,24 +2,22 @@
 
 use ratatui::{
     Frame,
-    layout::Rect,
+    layout::{Constraint, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
 };
 
-use crate::state::{Notification, NotificationLevel};
+use crate::state::{Notification, NotificationLevel, Store};
+
+/// Height of a single toast, including borders.
+const TOAST_HEIGHT: u16 = 3;
 
 /// Render a notification popup.
 pub fn render_notification(frame: &mut Frame, area: Rect, notification: &Notification) {
     frame.render_widget(Clear, area);
 
-    let (border_color, icon) = match notification.level {
-        NotificationLevel::Info => (Color::Cyan, "ℹ"),
-        NotificationLevel::Success => (Color::Green, "✓"),
-        NotificationLevel::Warning => (Color::Yellow, "⚠"),
-        NotificationLevel::Error => (Color::Red, "✗"),
-    };
+    let (border_color, icon) = level_style(notification.level);
 
     let content = Line::from(vec![
         Span::styled(format!("{} ", icon), Style::default().fg(border_color)),
@@ -37,25 +35,97 @@ pub fn render_notification(frame: &mut Frame, area: Rect, notification: &Notific
     frame.render_widget(paragraph, area);
 }
 
-/// Render an error popup.
-pub fn render_error(frame: &mut Frame, area: Rect, error: &str) {
-    frame.render_widget(Clear, area);
+/// Render all active toasts stacked upwards from the bottom of `area`,
+/// newest at the bottom.
+pub fn render_notifications(frame: &mut Frame, area: Rect, store: &Store) {
+    let mut bottom = area.y + area.height;
 
-    let content = Line::from(vec![
-        Span::styled(
-            "✗ Error: ",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-        ),
-        Span::raw(error),
-    ]);
+    for notification in store.notifications.active.iter().rev() {
+        if bottom < area.y + TOAST_HEIGHT {
+            break;
+        }
+        bottom -= TOAST_HEIGHT;
 
-    let paragraph = Paragraph::new(content)
+        let toast_area = Rect {
+            x: area.x,
+            y: bottom,
+            width: area.width,
+            height: TOAST_HEIGHT,
+        };
+        render_notification(frame, toast_area, notification);
+    }
+}
+
+fn level_style(level: NotificationLevel) -> (Color, &'static str) {
+    match level {
+        NotificationLevel::Info => (Color::Cyan, "ℹ"),
+        NotificationLevel::Success => (Color::Green, "✓"),
+        NotificationLevel::Warning => (Color::Yellow, "⚠"),
+        NotificationLevel::Error => (Color::Red, "✗"),
+    }
+}
+
+/// Notification history log widget.
+pub struct NotificationLog;
+
+impl NotificationLog {
+    /// Render the notification history, newest first.
+    pub fn render(frame: &mut Frame, area: Rect, store: &Store) {
+        let history = &store.notifications.history;
+
+        let header_cells = ["Time", "Level", "Message"].iter().map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+        let rows = history.iter().rev().map(|notification| {
+            let (color, icon) = level_style(notification.level);
+            let cells = vec![
+                Cell::from(
+                    notification
+                        .created_at
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string(),
+                )
+                .style(Style::default().fg(Color::DarkGray)),
+                Cell::from(format!("{} {}", icon, notification.level))
+                    .style(Style::default().fg(color)),
+                Cell::from(notification.message.clone()),
+            ];
+            Row::new(cells).height(1)
+        });
+
+        let errors = store.notifications.count(NotificationLevel::Error);
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(20),
+                Constraint::Length(10),
+                Constraint::Min(20),
+            ],
+        )
+        .header(header)
         .block(
             Block::default()
+                .title(format!(
+                    " Notifications ({}) | {} errors | x clear ",
+                    history.len(),
+                    errors
+                ))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red)),
+                .border_style(Style::default().fg(Color::Cyan)),
         )
-        .style(Style::default().fg(Color::White));
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
 
-    frame.render_widget(paragraph, area);
+        let mut state = TableState::default();
+        state.select(store.notifications.selected_index);
+
+        frame.render_stateful_widget(table, area, &mut state);
+    }
 }
@@ -187,7 +187,7 @@ impl OrderBook {
             };
 
             // Create a visual bar based on cumulative size
-            let bar_width = ((area.width as f64 * 0.3 * fill_pct) as usize).max(0);
+            let bar_width = (area.width as f64 * 0.3 * fill_pct) as usize;
             let bar = "█".repeat(bar_width);
 
             let cells = vec![
@@ -251,7 +251,7 @@ impl OrderBook {
             };
 
             // Create a visual bar based on cumulative size
-            let bar_width = ((area.width as f64 * 0.3 * fill_pct) as usize).max(0);
+            let bar_width = (area.width as f64 * 0.3 * fill_pct) as usize;
             let bar = "█".repeat(bar_width);
 
             let cells = vec![
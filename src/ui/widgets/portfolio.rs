@@ -0,0 +1,326 @@
+//! Portfolio dashboard widget.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table},
+};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::state::{Exposure, Store};
+
+/// Number of exposure rows shown per breakdown.
+const MAX_EXPOSURE_ROWS: usize = 8;
+
+/// Portfolio dashboard with equity history, exposure and resolution calendar.
+pub struct PortfolioDashboard;
+
+impl PortfolioDashboard {
+    /// Render the portfolio dashboard.
+    pub fn render(frame: &mut Frame, area: Rect, store: &Store) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(40), // Summary + equity chart
+                Constraint::Percentage(35), // Exposure
+                Constraint::Min(5),         // Resolution calendar
+            ])
+            .split(area);
+
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(36), Constraint::Min(20)])
+            .split(chunks[0]);
+        Self::render_summary(frame, top[0], store);
+        Self::render_equity_chart(frame, top[1], store);
+
+        let exposure = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(45),
+                Constraint::Percentage(30),
+                Constraint::Percentage(25),
+            ])
+            .split(chunks[1]);
+        let portfolio = &store.portfolio;
+        render_exposure(
+            frame,
+            exposure[0],
+            " By Market ",
+            &portfolio.exposure_by_market(),
+        );
+        render_exposure(
+            frame,
+            exposure[1],
+            " By Tag ",
+            &portfolio.exposure_by_tag(&store.markets.markets),
+        );
+        render_exposure(
+            frame,
+            exposure[2],
+            " By Outcome ",
+            &portfolio.exposure_by_outcome(),
+        );
+
+        Self::render_calendar(frame, chunks[2], store);
+    }
+
+    fn render_summary(frame: &mut Frame, area: Rect, store: &Store) {
+        let portfolio = &store.portfolio;
+        let markets = &store.markets.markets;
+
+        let largest = portfolio
+            .exposure_by_market()
+            .first()
+            .map(|e| format!("{:.1}%", e.percent))
+            .unwrap_or_else(|| "-".to_string());
+
+        let lines = vec![
+            summary_line(
+                "Equity",
+                format!("${:.2}", portfolio.total_value),
+                Color::White,
+            ),
+            summary_line(
+                "Unrealized",
+                signed_usd(portfolio.total_unrealized_pnl),
+                pnl_color(portfolio.total_unrealized_pnl),
+            ),
+            summary_line(
+                "Realized",
+                signed_usd(portfolio.total_realized_pnl),
+                pnl_color(portfolio.total_realized_pnl),
+            ),
+            summary_line(
+                "Available",
+                format!("${:.2}", portfolio.available_usdc()),
+                Color::White,
+            ),
+            summary_line(
+                "In orders",
+                format!("${:.2}", store.orders.locked_capital()),
+                Color::Yellow,
+            ),
+            summary_line(
+                "Worst case",
+                format!("-${:.2}", portfolio.worst_case_loss(markets)),
+                Color::Red,
+            ),
+            summary_line("Top market", largest, Color::Yellow),
+        ];
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(format!(
+                    " Portfolio ({} positions) ",
+                    portfolio.positions.len()
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_equity_chart(frame: &mut Frame, area: Rect, store: &Store) {
+        let history = &store.portfolio.equity_history;
+        let block = Block::default()
+            .title(" Equity ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan));
+
+        let Some(first) = history.front() else {
+            let empty = Paragraph::new(Span::styled(
+                "No snapshots yet - equity is sampled every minute once the portfolio loads",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .block(block);
+            frame.render_widget(empty, area);
+            return;
+        };
+
+        let points: Vec<(f64, f64)> = history
+            .iter()
+            .map(|s| {
+                let minutes = (s.timestamp - first.timestamp).num_seconds() as f64 / 60.0;
+                (minutes, s.equity.to_f64().unwrap_or(0.0))
+            })
+            .collect();
+
+        let x_max = points.last().map(|p| p.0).unwrap_or(0.0).max(1.0);
+        let (y_min, y_max) = points.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| {
+            (lo.min(p.1), hi.max(p.1))
+        });
+        let padding = ((y_max - y_min) * 0.1).max(1.0);
+        let (y_min, y_max) = (y_min - padding, y_max + padding);
+
+        let last = history.back().unwrap_or(first);
+        let change = last.equity - first.equity;
+        let dataset = Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(pnl_color(change)))
+            .data(&points);
+
+        let chart = Chart::new(vec![dataset])
+            .block(block.title(format!(" Equity {} ", signed_usd(change))))
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::DarkGray))
+                    .bounds([0.0, x_max])
+                    .labels([
+                        first
+                            .timestamp
+                            .with_timezone(&chrono::Local)
+                            .format("%H:%M")
+                            .to_string(),
+                        last.timestamp
+                            .with_timezone(&chrono::Local)
+                            .format("%H:%M")
+                            .to_string(),
+                    ]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::DarkGray))
+                    .bounds([y_min, y_max])
+                    .labels([format!("{:.0}", y_min), format!("{:.0}", y_max)]),
+            );
+
+        frame.render_widget(chart, area);
+    }
+
+    fn render_calendar(frame: &mut Frame, area: Rect, store: &Store) {
+        let now = chrono::Utc::now();
+        let entries = store.portfolio.resolution_calendar(&store.markets.markets);
+
+        let header = header_row(&["Ends", "In", "Market", "Value"]);
+
+        let rows = entries.iter().map(|entry| {
+            let remaining = entry.end_date - now;
+            let (countdown, style) = if remaining.num_seconds() <= 0 {
+                ("ended".to_string(), Style::default().fg(Color::DarkGray))
+            } else if remaining.num_days() < 1 {
+                (
+                    format!("{}h", remaining.num_hours()),
+                    Style::default().fg(Color::Red),
+                )
+            } else if remaining.num_days() < 7 {
+                (
+                    format!("{}d", remaining.num_days()),
+                    Style::default().fg(Color::Yellow),
+                )
+            } else {
+                (format!("{}d", remaining.num_days()), Style::default())
+            };
+
+            Row::new(vec![
+                Cell::from(
+                    entry
+                        .end_date
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string(),
+                ),
+                Cell::from(countdown).style(style),
+                Cell::from(entry.question.clone()),
+                Cell::from(format!("${:.2}", entry.value)),
+            ])
+            .height(1)
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(17),
+                Constraint::Length(6),
+                Constraint::Min(20),
+                Constraint::Length(12),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(" Resolution Calendar ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+
+        frame.render_widget(table, area);
+    }
+}
+
+fn render_exposure(frame: &mut Frame, area: Rect, title: &str, exposures: &[Exposure]) {
+    let header = header_row(&["Name", "Value", "%"]);
+
+    let rows = exposures.iter().take(MAX_EXPOSURE_ROWS).map(|exposure| {
+        // Flag anything above a quarter of the book
+        let style = if exposure.percent > Decimal::from(25) {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(exposure.label.clone()),
+            Cell::from(format!("${:.2}", exposure.value)),
+            Cell::from(format!("{:.1}", exposure.percent)).style(style),
+        ])
+        .height(1)
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(10),
+            Constraint::Length(11),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(title.to_string())
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+
+    frame.render_widget(table, area);
+}
+
+fn header_row(titles: &[&'static str]) -> Row<'static> {
+    let cells = titles.iter().map(|h| {
+        Cell::from(*h).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+    Row::new(cells).height(1).bottom_margin(1)
+}
+
+fn summary_line(label: &'static str, value: String, color: Color) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!(" {:<12}", label), Style::default().fg(Color::Gray)),
+        Span::styled(value, Style::default().fg(color)),
+    ])
+}
+
+fn signed_usd(value: Decimal) -> String {
+    if value >= Decimal::ZERO {
+        format!("+${:.2}", value)
+    } else {
+        format!("-${:.2}", value.abs())
+    }
+}
+
+fn pnl_color(value: Decimal) -> Color {
+    if value >= Decimal::ZERO {
+        Color::Green
+    } else {
+        Color::Red
+    }
+}
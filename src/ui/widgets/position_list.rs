@@ -9,6 +9,7 @@ use ratatui::{
 };
 
 use crate::state::Store;
+use crate::strategy::{Bracket, BracketStatus};
 use rust_decimal::Decimal;
 
 /// Position list widget.
@@ -27,6 +28,8 @@ impl PositionList {
             "Current",
             "P&L",
             "P&L %",
+            "Stop",
+            "Target",
         ]
         .iter()
         .map(|h| {
@@ -60,6 +63,8 @@ impl PositionList {
                 ""
             };
 
+            let bracket = store.portfolio.bracket_for(&position.token_id);
+
             let cells = vec![
                 Cell::from(truncate_string(&position.market_question, 25)),
                 Cell::from(position.outcome_name.clone()),
@@ -75,6 +80,8 @@ impl PositionList {
                     pnl_sign, position.unrealized_pnl_percent
                 ))
                 .style(pnl_style),
+                bracket_cell(bracket.and_then(|b| b.stop_loss), bracket),
+                bracket_cell(bracket.and_then(|b| b.take_profit), bracket),
             ];
 
             Row::new(cells).style(style).height(1)
@@ -90,6 +97,8 @@ impl PositionList {
                 Constraint::Length(12),
                 Constraint::Length(12),
                 Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
             ],
         )
         .header(header)
@@ -119,6 +128,18 @@ impl PositionList {
     }
 }
 
+/// Bracket level, dimmed unless its bracket is watching it.
+fn bracket_cell(level: Option<Decimal>, bracket: Option<&Bracket>) -> Cell<'static> {
+    let Some(level) = level else {
+        return Cell::from("-").style(Style::default().fg(Color::DarkGray));
+    };
+    let style = match bracket.map(|b| b.status) {
+        Some(BracketStatus::Active) => Style::default().fg(Color::Cyan),
+        _ => Style::default().fg(Color::DarkGray),
+    };
+    Cell::from(format!("{:.2}¢", level * Decimal::ONE_HUNDRED)).style(style)
+}
+
 fn truncate_string(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
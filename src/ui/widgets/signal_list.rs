@@ -0,0 +1,391 @@
+//! Signal history widget.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table, TableState},
+};
+use rust_decimal::Decimal;
+
+use super::truncate_string;
+use crate::state::{OrderSide, SignalEntry, Store};
+use crate::strategy::{OrderStage, ParentOrder, ParentStatus, SignalAction, SignalResult};
+
+/// Executed signals, with the selected signal's order below.
+pub struct SignalList;
+
+impl SignalList {
+    /// Render the signal table and the detail pane.
+    pub fn render(frame: &mut Frame, area: Rect, store: &Store) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(8), Constraint::Length(12)])
+            .split(area);
+
+        Self::render_signals(frame, chunks[0], store);
+
+        let detail = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        Self::render_order(frame, detail[0], store);
+        Self::render_events(frame, detail[1], store);
+    }
+
+    fn render_signals(frame: &mut Frame, area: Rect, store: &Store) {
+        let state = &store.signals;
+
+        let header_cells = [
+            "Time", "Strategy", "Market", "Side", "Price", "Size", "Order", "Result",
+        ]
+        .iter()
+        .map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+        let rows = state.entries.iter().map(|entry| {
+            let signal = &entry.record.signal;
+            let (side, side_style) = match (&signal.action, signal.side) {
+                (SignalAction::Cancel { .. }, _) => {
+                    ("Cancel".to_string(), Style::default().fg(Color::DarkGray))
+                }
+                (_, OrderSide::Buy) => ("Buy".to_string(), Style::default().fg(Color::Green)),
+                (_, OrderSide::Sell) => ("Sell".to_string(), Style::default().fg(Color::Red)),
+            };
+            let stage = entry.lifecycle.as_ref().map(|l| l.stage);
+            let order = match (&entry.execution, stage) {
+                (Some(parent), _) => Cell::from(format!(
+                    "{:.0}% {}",
+                    parent.progress() * Decimal::ONE_HUNDRED,
+                    parent.status
+                ))
+                .style(parent_style(parent.status)),
+                (None, Some(stage)) => {
+                    Cell::from(stage.to_string()).style(stage_style(Some(stage)))
+                }
+                (None, None) => Cell::from("-").style(stage_style(None)),
+            };
+            let cells = vec![
+                Cell::from(
+                    entry
+                        .record
+                        .executed_at
+                        .unwrap_or(signal.created_at)
+                        .with_timezone(&chrono::Local)
+                        .format("%m-%d %H:%M:%S")
+                        .to_string(),
+                ),
+                Cell::from(signal.strategy_name.clone()),
+                Cell::from(truncate_string(&market_label(store, &signal.market_id), 30)),
+                Cell::from(side).style(side_style),
+                Cell::from(price(signal.price)),
+                Cell::from(format!("{:.2}", signal.size)),
+                order,
+                Cell::from(outcome(entry.record.result.as_ref())),
+            ];
+            Row::new(cells).height(1)
+        });
+
+        let title = format!(
+            " Signals ({}) | Esc back to strategies ",
+            state.entries.len()
+        );
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(16),
+                Constraint::Percentage(30),
+                Constraint::Length(6),
+                Constraint::Length(10),
+                Constraint::Length(12),
+                Constraint::Length(14),
+                Constraint::Min(16),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+
+        let mut table_state = TableState::default();
+        table_state.select(state.selected_index);
+
+        frame.render_stateful_widget(table, area, &mut table_state);
+    }
+
+    fn render_order(frame: &mut Frame, area: Rect, store: &Store) {
+        let lines = match store.signals.selected() {
+            Some(entry) => order_lines(entry),
+            None => vec![Line::from(Span::styled(
+                "No signals executed yet",
+                Style::default().fg(Color::DarkGray),
+            ))],
+        };
+
+        let paragraph = Paragraph::new(lines).block(
+            Block::default()
+                .title(" Order ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_events(frame: &mut Frame, area: Rect, store: &Store) {
+        if let Some(parent) = store.signals.selected().and_then(|e| e.execution.as_ref()) {
+            Self::render_children(frame, area, parent);
+            return;
+        }
+
+        let items: Vec<ListItem> = store
+            .signals
+            .selected()
+            .and_then(|e| e.lifecycle.as_ref())
+            .map(|l| l.events.as_slice())
+            .unwrap_or(&[])
+            .iter()
+            .rev()
+            .map(|event| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        event
+                            .at
+                            .with_timezone(&chrono::Local)
+                            .format("%H:%M:%S ")
+                            .to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        format!("{:<10}", event.stage.to_string()),
+                        stage_style(Some(event.stage)),
+                    ),
+                    Span::raw(event.detail.clone()),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(" Timeline ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+
+        frame.render_widget(list, area);
+    }
+
+    fn render_children(frame: &mut Frame, area: Rect, parent: &ParentOrder) {
+        let items: Vec<ListItem> = parent
+            .children
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, child)| {
+                let (state, style) = if !child.done {
+                    ("working", Style::default().fg(Color::Cyan))
+                } else if child.filled >= child.size {
+                    ("filled", Style::default().fg(Color::Green))
+                } else {
+                    ("ended", Style::default().fg(Color::DarkGray))
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        child
+                            .sent_at
+                            .with_timezone(&chrono::Local)
+                            .format("%H:%M:%S ")
+                            .to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(format!(
+                        "#{:<3} {:.2} @ {}, filled {:.2} ",
+                        i + 1,
+                        child.size,
+                        price(child.price),
+                        child.filled
+                    )),
+                    Span::styled(state, style),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(format!(" Child orders ({}) ", parent.children.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+
+        frame.render_widget(list, area);
+    }
+}
+
+/// Details of the order placed for a signal.
+fn order_lines(entry: &SignalEntry) -> Vec<Line<'static>> {
+    let signal = &entry.record.signal;
+    let mut lines = vec![
+        field("Signal", signal.id.clone()),
+        field(
+            "Cancels",
+            signal.action.cancels().unwrap_or("-").to_string(),
+        ),
+        field(
+            "Reason",
+            signal.reason.clone().unwrap_or_else(|| "-".into()),
+        ),
+        field(
+            "Client ID",
+            entry
+                .record
+                .client_order_id
+                .clone()
+                .unwrap_or_else(|| "-".into()),
+        ),
+    ];
+    if let Some(notional) = signal.metadata.notional {
+        lines.push(field("Notional", format!("${:.2}", notional)));
+    }
+    if let Some(sizing) = &signal.metadata.sizing {
+        lines.push(field("Sizing", sizing.to_string()));
+    }
+
+    if let Some(parent) = &entry.execution {
+        lines.push(field("Algorithm", parent.algo().to_string()));
+        lines.push(field("Status", parent.status.to_string()));
+        lines.push(field(
+            "Filled",
+            format!(
+                "{:.2} of {:.2} ({:.0}%), avg {}",
+                parent.filled_size(),
+                parent.size,
+                parent.progress() * Decimal::ONE_HUNDRED,
+                parent
+                    .average_price()
+                    .map(|p| price(Some(p)))
+                    .unwrap_or_else(|| "-".to_string())
+            ),
+        ));
+        return lines;
+    }
+
+    let Some(lifecycle) = &entry.lifecycle else {
+        lines.push(field("Order", "no longer tracked".into()));
+        return lines;
+    };
+
+    let request = &lifecycle.request;
+    lines.push(field(
+        "Exchange ID",
+        lifecycle.order_id().unwrap_or("-").to_string(),
+    ));
+    lines.push(field(
+        "Request",
+        format!(
+            "{:?} {:?} {:.2} sh @ {}",
+            request.order_type,
+            request.side,
+            request.size,
+            price(request.price)
+        ),
+    ));
+    lines.push(field(
+        "Filled",
+        format!(
+            "{:.2} of {:.2}, avg {}",
+            lifecycle.filled_size(),
+            request.size,
+            lifecycle
+                .average_price()
+                .map(|p| price(Some(p)))
+                .unwrap_or_else(|| "-".to_string())
+        ),
+    ));
+    for fill in &lifecycle.fills {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {} {:.2} @ {}",
+                fill.at.with_timezone(&chrono::Local).format("%H:%M:%S"),
+                fill.size,
+                price(Some(fill.price))
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    lines
+}
+
+fn field(label: &'static str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!("{:<12}", label),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::raw(value),
+    ])
+}
+
+/// Short description of a signal's result.
+fn outcome(result: Option<&SignalResult>) -> String {
+    match result {
+        None => "Pending".to_string(),
+        Some(SignalResult::OrderPlaced { .. }) => "Placed".to_string(),
+        Some(SignalResult::Filled { filled_price, .. }) => {
+            format!("Filled @ {}", price(Some(*filled_price)))
+        }
+        Some(SignalResult::Rejected { reason }) => format!("Rejected: {}", reason),
+        Some(SignalResult::Cancelled) => "Cancelled".to_string(),
+    }
+}
+
+fn parent_style(status: ParentStatus) -> Style {
+    match status {
+        ParentStatus::Working => Style::default().fg(Color::Cyan),
+        ParentStatus::Completed => Style::default().fg(Color::Green),
+        ParentStatus::Expired => Style::default().fg(Color::DarkGray),
+    }
+}
+
+fn stage_style(stage: Option<OrderStage>) -> Style {
+    match stage {
+        Some(OrderStage::Filled) => Style::default().fg(Color::Green),
+        Some(OrderStage::PartiallyFilled) => Style::default().fg(Color::Yellow),
+        Some(OrderStage::Rejected) => Style::default().fg(Color::Red),
+        Some(OrderStage::Cancelled | OrderStage::Expired) | None => {
+            Style::default().fg(Color::DarkGray)
+        }
+        Some(OrderStage::Submitted | OrderStage::Open) => Style::default().fg(Color::Cyan),
+    }
+}
+
+fn price(price: Option<Decimal>) -> String {
+    match price {
+        Some(price) => format!("{:.2}¢", price * Decimal::ONE_HUNDRED),
+        None => "market".to_string(),
+    }
+}
+
+fn market_label(store: &Store, market_id: &str) -> String {
+    store
+        .markets
+        .markets
+        .iter()
+        .find(|m| m.id == market_id)
+        .map(|m| m.question.clone())
+        .unwrap_or_else(|| market_id.to_string())
+}
@@ -8,7 +8,7 @@ use ratatui::{
     widgets::Paragraph,
 };
 
-use crate::state::Store;
+use crate::state::{DAEMON_STALE_SECS, Store};
 
 /// Status bar widget.
 pub struct StatusBar;
@@ -38,6 +38,29 @@ impl StatusBar {
             Span::raw("")
         };
 
+        // Headless daemon attached to the same data directory
+        let daemon = match &store.daemon {
+            Some(status) if status.is_alive(chrono::Utc::now(), DAEMON_STALE_SECS) => Span::styled(
+                format!(
+                    " ⚙ Daemon: {}/{} strategies ",
+                    status.running_strategies(),
+                    status.strategies.len()
+                ),
+                Style::default().fg(Color::Magenta),
+            ),
+            _ => Span::raw(""),
+        };
+
+        let profile = match &store.app.profile {
+            Some(name) => Span::styled(
+                format!(" Profile: {} ", name),
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            None => Span::raw(""),
+        };
+
         let help_hint = Span::styled(" Press ? for help ", Style::default().fg(Color::DarkGray));
 
         // Create the status line
@@ -51,8 +74,10 @@ impl StatusBar {
             Span::raw(" | "),
             connection_status,
             Span::raw(" | "),
+            profile,
             mode,
             loading,
+            daemon,
         ];
 
         let status_line = Line::from(left_content);
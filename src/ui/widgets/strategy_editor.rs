@@ -0,0 +1,164 @@
+//! Strategy parameter editor widget.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, List, ListItem, Row, Table, TableState},
+};
+
+use crate::state::{InputMode, Store};
+use crate::strategy::ParameterDef;
+
+/// Form of the active strategy's parameters, with the change log below.
+pub struct StrategyEditor;
+
+impl StrategyEditor {
+    /// Render the parameter form and change log.
+    pub fn render(frame: &mut Frame, area: Rect, store: &Store) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(8), Constraint::Length(8)])
+            .split(area);
+
+        Self::render_parameters(frame, chunks[0], store);
+        Self::render_changes(frame, chunks[1], store);
+    }
+
+    fn render_parameters(frame: &mut Frame, area: Rect, store: &Store) {
+        let state = &store.strategies;
+
+        let header_cells = [
+            "Parameter",
+            "Type",
+            "Value",
+            "Default",
+            "Range",
+            "Description",
+        ]
+        .iter()
+        .map(|h| {
+            Cell::from(*h).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )
+        });
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+        let fields = state
+            .active()
+            .map(|s| s.parameters.as_slice())
+            .unwrap_or(&[]);
+        let rows = fields.iter().map(|field| {
+            let def = &field.def;
+            // Highlight values changed from their default
+            let value_style = if field.current == def.default {
+                Style::default()
+            } else {
+                Style::default().fg(Color::Yellow)
+            };
+            let cells = vec![
+                Cell::from(def.name.clone()),
+                Cell::from(def.param_type.to_string()).style(Style::default().fg(Color::DarkGray)),
+                Cell::from(field.current.to_string()).style(value_style),
+                Cell::from(def.default.to_string()).style(Style::default().fg(Color::DarkGray)),
+                Cell::from(range(def)).style(Style::default().fg(Color::DarkGray)),
+                Cell::from(def.description.clone()),
+            ];
+            Row::new(cells).height(1)
+        });
+
+        let title = match (state.active(), state.selected_field()) {
+            (_, Some(field)) if store.app.input_mode == InputMode::Insert => format!(
+                " Set {} ({}, {}): {}_ ",
+                field.def.name,
+                field.def.param_type,
+                range(&field.def),
+                store.app.input_buffer
+            ),
+            (Some(strategy), _) => format!(
+                " {} ({}/{}) {} | ←/→ strategy  Enter edit  d default  w save to config  s signals ",
+                strategy.name,
+                state.active_index + 1,
+                state.strategies.len(),
+                strategy.status
+            ),
+            (None, _) => " Strategies (none registered) ".to_string(),
+        };
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(24),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(16),
+                Constraint::Min(20),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+
+        let mut table_state = TableState::default();
+        table_state.select(state.selected_index);
+
+        frame.render_stateful_widget(table, area, &mut table_state);
+    }
+
+    fn render_changes(frame: &mut Frame, area: Rect, store: &Store) {
+        let items: Vec<ListItem> = store
+            .strategies
+            .active_changes()
+            .into_iter()
+            .map(|change| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        change
+                            .changed_at
+                            .with_timezone(&chrono::Local)
+                            .format("%H:%M:%S ")
+                            .to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(format!("{}: ", change.parameter)),
+                    Span::styled(change.old.to_string(), Style::default().fg(Color::DarkGray)),
+                    Span::raw(" → "),
+                    Span::styled(change.new.to_string(), Style::default().fg(Color::Yellow)),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(" Changes ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+
+        frame.render_widget(list, area);
+    }
+}
+
+/// Bounds or allowed values of a parameter.
+fn range(def: &ParameterDef) -> String {
+    if let Some(allowed) = &def.allowed_values {
+        let allowed: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+        return allowed.join("|");
+    }
+    match (&def.min, &def.max) {
+        (Some(min), Some(max)) => format!("{}..{}", min, max),
+        (Some(min), None) => format!("≥ {}", min),
+        (None, Some(max)) => format!("≤ {}", max),
+        (None, None) => "-".to_string(),
+    }
+}
@@ -20,7 +20,13 @@ impl TabBar {
             ("1", "Markets", View::Markets),
             ("2", "Orders", View::Orders),
             ("3", "Positions", View::Positions),
-            ("4", "Settings", View::Settings),
+            ("4", "Portfolio", View::Portfolio),
+            ("5", "Watchlist", View::Watchlist),
+            ("6", "Alerts", View::Alerts),
+            ("7", "Log", View::Notifications),
+            ("8", "Trades", View::Trades),
+            ("9", "Logs", View::Logs),
+            ("0", "Strategies", View::Strategies),
         ];
 
         let mut spans = vec![Span::raw(" ")];
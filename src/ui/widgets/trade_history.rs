@@ -0,0 +1,286 @@
+//! Trade history widget.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+};
+
+use super::truncate_string;
+use crate::state::{OrderSide, Store, TradeSummary};
+use rust_decimal::Decimal;
+
+/// Trade history widget with per-market and per-day summaries.
+pub struct TradeHistory;
+
+impl TradeHistory {
+    /// Render the fill list above the market and daily summaries.
+    pub fn render(frame: &mut Frame, area: Rect, store: &Store) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(area);
+        let summary_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(chunks[1]);
+
+        Self::render_fills(frame, chunks[0], store);
+        Self::render_market_summaries(frame, summary_chunks[0], store);
+        Self::render_daily_summaries(frame, summary_chunks[1], store);
+    }
+
+    fn render_fills(frame: &mut Frame, area: Rect, store: &Store) {
+        let trades = &store.trades;
+
+        let header = header_row(&[
+            "Time", "Market", "Outcome", "Side", "Price", "Size", "Fee", "Realized",
+        ]);
+
+        let rows = trades
+            .fills
+            .iter()
+            .zip(&trades.fill_pnl)
+            .rev()
+            .enumerate()
+            .map(|(i, (fill, pnl))| {
+                let selected = trades.selected_index == Some(i);
+                let style = if selected {
+                    Style::default()
+                        .bg(Color::DarkGray)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+
+                let side_style = match fill.side {
+                    OrderSide::Buy => Style::default().fg(Color::Green),
+                    OrderSide::Sell => Style::default().fg(Color::Red),
+                };
+                let realized = if fill.side == OrderSide::Sell {
+                    pnl_cell(*pnl)
+                } else {
+                    Cell::from("-")
+                };
+
+                let cells = vec![
+                    Cell::from(
+                        fill.timestamp
+                            .with_timezone(&chrono::Local)
+                            .format("%m-%d %H:%M:%S")
+                            .to_string(),
+                    ),
+                    Cell::from(truncate_string(&market_label(store, &fill.market_id), 30)),
+                    Cell::from(fill.outcome_name.clone()),
+                    Cell::from(format!("{:?}", fill.side)).style(side_style),
+                    Cell::from(format!("{:.2}¢", fill.price * Decimal::ONE_HUNDRED)),
+                    Cell::from(format!("{:.2}", fill.size)),
+                    Cell::from(format!("${:.2}", fill.fee)),
+                    realized,
+                ];
+
+                Row::new(cells).style(style).height(1)
+            });
+
+        let title = format!(
+            " Trades ({}) | {} | Realized {}${:.2} | Fees ${:.2} ",
+            trades.fills.len(),
+            trades.method,
+            sign(trades.total_realized_pnl()),
+            trades.total_realized_pnl(),
+            trades.total_fees()
+        );
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Percentage(30),
+                Constraint::Length(10),
+                Constraint::Length(6),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(10),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+
+        let mut state = TableState::default();
+        state.select(trades.selected_index);
+
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+
+    fn render_market_summaries(frame: &mut Frame, area: Rect, store: &Store) {
+        let header = header_row(&[
+            "Market",
+            "Trades",
+            "Volume",
+            "Fees",
+            "Realized",
+            "Unrealized",
+        ]);
+
+        let rows: Vec<Row> = store
+            .trades
+            .market_summaries()
+            .into_iter()
+            .map(|summary| {
+                let unrealized = match market_unrealized_pnl(store, &summary.key) {
+                    Some(pnl) => pnl_cell(pnl),
+                    None => Cell::from("-"),
+                };
+
+                let mut cells = vec![Cell::from(truncate_string(
+                    &market_label(store, &summary.key),
+                    30,
+                ))];
+                cells.extend(summary_cells(&summary));
+                cells.push(unrealized);
+                Row::new(cells).height(1)
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(20),
+                Constraint::Length(7),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(11),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(" By Market ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+
+        frame.render_widget(table, area);
+    }
+
+    fn render_daily_summaries(frame: &mut Frame, area: Rect, store: &Store) {
+        let header = header_row(&["Day", "Trades", "Volume", "Fees", "Realized"]);
+
+        let rows: Vec<Row> = store
+            .trades
+            .daily_summaries()
+            .into_iter()
+            .map(|summary| {
+                let mut cells = vec![Cell::from(summary.key.clone())];
+                cells.extend(summary_cells(&summary));
+                Row::new(cells).height(1)
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(11),
+                Constraint::Length(7),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(10),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(" By Day (UTC) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+
+        frame.render_widget(table, area);
+    }
+}
+
+fn header_row(titles: &[&'static str]) -> Row<'static> {
+    let cells = titles.iter().map(|h| {
+        Cell::from(*h).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    });
+    Row::new(cells).height(1).bottom_margin(1)
+}
+
+fn summary_cells(summary: &TradeSummary) -> Vec<Cell<'static>> {
+    vec![
+        Cell::from(summary.trades.to_string()),
+        Cell::from(format!("${:.2}", summary.volume)),
+        Cell::from(format!("${:.2}", summary.fees)),
+        pnl_cell(summary.realized_pnl),
+    ]
+}
+
+fn pnl_cell(pnl: Decimal) -> Cell<'static> {
+    let style = if pnl >= Decimal::ZERO {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default().fg(Color::Red)
+    };
+    Cell::from(format!("{}${:.2}", sign(pnl), pnl)).style(style)
+}
+
+fn sign(value: Decimal) -> &'static str {
+    if value >= Decimal::ZERO { "+" } else { "" }
+}
+
+/// Market question if the market is loaded, otherwise its ID.
+fn market_label(store: &Store, market_id: &str) -> String {
+    store
+        .markets
+        .markets
+        .iter()
+        .find(|m| m.id == market_id)
+        .map(|m| m.question.clone())
+        .unwrap_or_else(|| market_id.to_string())
+}
+
+/// Unrealized PnL of the shares still held in a market, if every held token has a price.
+fn market_unrealized_pnl(store: &Store, market_id: &str) -> Option<Decimal> {
+    store
+        .trades
+        .pnl
+        .values()
+        .filter(|p| p.market_id == market_id && !p.size.is_zero())
+        .map(|p| token_mark(store, &p.token_id).map(|mark| p.unrealized_pnl(mark)))
+        .sum()
+}
+
+/// Current mark price for a token: position price first, then the market mid.
+fn token_mark(store: &Store, token_id: &str) -> Option<Decimal> {
+    if let Some(position) = store
+        .portfolio
+        .positions
+        .iter()
+        .find(|p| p.token_id == token_id && !p.current_price.is_zero())
+    {
+        return Some(position.current_price);
+    }
+
+    store
+        .markets
+        .markets
+        .iter()
+        .flat_map(|m| &m.outcomes)
+        .find(|o| o.token_id == token_id)
+        .map(|o| o.mid_price())
+        .filter(|mid| !mid.is_zero())
+}
@@ -0,0 +1,138 @@
+//! Watchlist widget.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+};
+
+use super::truncate_string;
+use crate::state::{InputMode, Store};
+use rust_decimal::Decimal;
+
+/// Watchlist widget showing live prices for pinned markets.
+pub struct WatchlistView;
+
+impl WatchlistView {
+    /// Render the active watchlist.
+    pub fn render(frame: &mut Frame, area: Rect, store: &Store) {
+        let watchlists = &store.watchlists;
+        let entries: &[String] = watchlists
+            .active()
+            .map(|w| w.market_ids.as_slice())
+            .unwrap_or_default();
+
+        let header_cells = ["Market", "Outcome", "Bid", "Ask", "Mid", "24h"]
+            .iter()
+            .map(|h| {
+                Cell::from(*h).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            });
+        let header = Row::new(header_cells).height(1).bottom_margin(1);
+
+        let rows = entries.iter().enumerate().map(|(i, market_id)| {
+            let selected = watchlists.selected_index == Some(i);
+            let style = if selected {
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            let market = store.markets.markets.iter().find(|m| &m.id == market_id);
+            let Some(outcome) = market.and_then(|m| m.outcomes.first()) else {
+                // Market not loaded (yet) - show the ID so the entry can still be removed
+                let cells = vec![
+                    Cell::from(truncate_string(market_id, 40))
+                        .style(Style::default().fg(Color::DarkGray)),
+                    Cell::from("-"),
+                    Cell::from("-"),
+                    Cell::from("-"),
+                    Cell::from("-"),
+                    Cell::from("-"),
+                ];
+                return Row::new(cells).style(style).height(1);
+            };
+
+            let change_style = if outcome.price_change_24h >= Decimal::ZERO {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Red)
+            };
+            let change_sign = if outcome.price_change_24h >= Decimal::ZERO {
+                "+"
+            } else {
+                ""
+            };
+
+            let question = market.map(|m| m.question.as_str()).unwrap_or_default();
+            let cells = vec![
+                Cell::from(truncate_string(question, 40)),
+                Cell::from(outcome.name.clone()),
+                Cell::from(format!("{:.2}¢", outcome.bid * Decimal::ONE_HUNDRED))
+                    .style(Style::default().fg(Color::Green)),
+                Cell::from(format!("{:.2}¢", outcome.ask * Decimal::ONE_HUNDRED))
+                    .style(Style::default().fg(Color::Red)),
+                Cell::from(format!(
+                    "{:.2}¢",
+                    outcome.mid_price() * Decimal::ONE_HUNDRED
+                )),
+                Cell::from(format!(
+                    "{}{:.2}¢",
+                    change_sign,
+                    outcome.price_change_24h * Decimal::ONE_HUNDRED
+                ))
+                .style(change_style),
+            ];
+
+            Row::new(cells).style(style).height(1)
+        });
+
+        let title = if store.app.input_mode == InputMode::Insert {
+            format!(" New watchlist: {}_ ", store.app.input_buffer)
+        } else {
+            let name = watchlists
+                .active()
+                .map(|w| w.name.as_str())
+                .unwrap_or_default();
+            format!(
+                " Watchlist: {} ({}/{}) | {} markets | h/l switch  n new  D delete ",
+                name,
+                watchlists.active_index + 1,
+                watchlists.watchlists.len(),
+                entries.len()
+            )
+        };
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(40),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+
+        let mut state = TableState::default();
+        state.select(watchlists.selected_index);
+
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}